@@ -21,6 +21,7 @@ mod io_detection;
 mod rdf_generation;
 mod telemetry_validation;
 mod validation;
+mod verb_options;
 
 // Frontier: Meta-Framework for self-introspection
 // Note: proc-macro crates cannot export modules, only proc_macro functions
@@ -374,18 +375,27 @@ pub fn verb(args: TokenStream, input: TokenStream) -> TokenStream {
                     None,
                     None,
                     arg_relationships,
+                    &verb_options::VerbOptions::default(),
                 );
             }
         };
 
-    let verb_name = if args_vec.is_empty() {
+    // Leading string literals are positional (verb, noun); the rest are named options
+    let positional: Vec<&syn::Expr> =
+        args_vec.iter().take_while(|arg| !verb_options::is_option_expr(arg)).collect();
+    let options = match verb_options::parse_verb_options(args_vec.iter().skip(positional.len())) {
+        Ok(options) => options,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let verb_name = if positional.is_empty() {
         extract_verb_name_from_fn_name(&input_fn)
     } else {
-        match &args_vec[0] {
+        match positional[0] {
             syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => s.value(),
             _ => {
                 return syn::Error::new_spanned(
-                    &args_vec[0],
+                    positional[0],
                     "First argument must be a string literal",
                 )
                 .to_compile_error()
@@ -394,12 +404,28 @@ pub fn verb(args: TokenStream, input: TokenStream) -> TokenStream {
         }
     };
 
-    // Extract noun name if provided as second arg, or auto-detect from #[noun] attribute or file context
-    let noun_name = if args_vec.len() > 1 {
-        match &args_vec[1] {
-            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Some(s.value()),
+    // Extract noun name if provided as second arg or `noun = "..."`, or auto-detect from
+    // #[noun] attribute or file context. Dotted names ("cloud.vm") denote nested nouns.
+    let noun_name = if positional.len() > 1 {
+        if options.noun.is_some() {
+            return syn::Error::new_spanned(
+                positional[1],
+                "Noun given both positionally and as `noun = ...`; use only one",
+            )
+            .to_compile_error()
+            .into();
+        }
+        match positional[1] {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => {
+                if let Err(e) = verb_options::validate_noun_path(&s.value(), s.span()) {
+                    return e.to_compile_error().into();
+                }
+                Some(s.value())
+            }
             _ => None,
         }
+    } else if options.noun.is_some() {
+        options.noun.clone()
     } else {
         // Try to auto-detect noun name:
         // 1. First check for #[noun] attribute on same function
@@ -411,8 +437,9 @@ pub fn verb(args: TokenStream, input: TokenStream) -> TokenStream {
     // If verb name was auto-inferred and noun name was auto-detected,
     // strip the noun name from the verb name if it appears in the function name
     // Example: show_collector_status() with noun="collector" -> verb="status" (not "collector_status")
-    let verb_name = if args_vec.is_empty() {
-        if let Some(noun) = noun_name.as_ref() {
+    let verb_name = if positional.is_empty() {
+        // For nested nouns only the innermost segment can prefix the function name
+        if let Some(noun) = noun_name.as_deref().map(|n| n.rsplit('.').next().unwrap_or(n)) {
             // Check if verb_name starts with noun_name (e.g., "collector_status" starts with "collector")
             if verb_name.starts_with(noun) && verb_name.len() > noun.len() {
                 // Check if there's a separator (underscore) after the noun
@@ -440,7 +467,14 @@ pub fn verb(args: TokenStream, input: TokenStream) -> TokenStream {
 
     // Clean docstring for about - remove # Arguments section and relationship tags
     let clean_about = clean_docstring_for_about(&docstring);
-    generate_verb_registration(input_fn, verb_name, noun_name, Some(clean_about), arg_relationships)
+    generate_verb_registration(
+        input_fn,
+        verb_name,
+        noun_name,
+        Some(clean_about),
+        arg_relationships,
+        &options,
+    )
 }
 
 /// Extract verb name from function name (remove common prefixes)
@@ -935,10 +969,33 @@ fn generate_verb_registration(
     noun_name: Option<String>,
     about: Option<String>,
     arg_relationships: std::collections::HashMap<String, DocArgRelationships>,
+    options: &verb_options::VerbOptions,
 ) -> TokenStream {
     let fn_name = &input_fn.sig.ident;
     let wrapper_name = quote::format_ident!("__{}_wrapper", fn_name);
     let init_fn_name = quote::format_ident!("__init_{}", fn_name);
+    let is_async = input_fn.sig.asyncness.is_some();
+
+    // Async-only options must not be silently ignored on sync verbs
+    if !is_async {
+        let async_only = if options.timeout_ms.is_some() {
+            Some("timeout")
+        } else if options.runtime.is_some() {
+            Some("runtime")
+        } else if options.on_exceed.as_deref() == Some("cancel") {
+            Some("on_exceed = \"cancel\"")
+        } else {
+            None
+        };
+        if let Some(option) = async_only {
+            return syn::Error::new_spanned(
+                input_fn.sig.fn_token,
+                format!("#[verb] option `{}` requires an `async fn`", option),
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
 
     // Analyze function signature for arguments
     let mut arg_extractions = Vec::new();
@@ -953,6 +1010,29 @@ fn generate_verb_registration(
 
             let arg_name_str = arg_name.to_string();
 
+            // Cancellation tokens are injected by the async runtime, not parsed from the CLI
+            if is_cancellation_token_type(&pat_type.ty) {
+                if !is_async {
+                    return syn::Error::new_spanned(
+                        &pat_type.ty,
+                        "CancellationToken parameters require an `async fn` verb",
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+                arg_calls.push(quote! { __cancel_token.clone() });
+                continue;
+            }
+
+            // The invocation context is injected by the runner, not parsed from the CLI
+            if is_invocation_context_type(&pat_type.ty) {
+                arg_extractions.push(quote! {
+                    let #arg_name = __handler_input.context.invocation.clone();
+                });
+                arg_calls.push(quote! { #arg_name });
+                continue;
+            }
+
             // Determine if optional (Option<T>) or required
             let is_option = is_option_type(&pat_type.ty);
             let inner_type = extract_inner_type(&pat_type.ty);
@@ -976,36 +1056,30 @@ fn generate_verb_registration(
             };
 
             if is_count_action {
-                // Count action - extract count from __handler_input
+                // Count action - extract count from the typed values of __handler_input
                 arg_extractions.push(quote! {
-                    let #arg_name: usize = __handler_input.args.get(#arg_name_str)
-                        .and_then(|v| v.parse::<usize>().ok())
-                        .unwrap_or(0);
+                    let #arg_name: usize = __handler_input.get::<usize>(#arg_name_str)?.unwrap_or(0);
                 });
                 arg_calls.push(quote! { #arg_name });
             } else if is_flag {
                 // Boolean flags
                 arg_extractions.push(quote! {
-                    let #arg_name = __handler_input.opts.get(#arg_name_str)
-                        .map(|v| v.parse::<bool>().unwrap_or(false))
-                        .unwrap_or(false);
+                    let #arg_name = __handler_input.get::<bool>(#arg_name_str)?.unwrap_or(false);
                 });
                 arg_calls.push(quote! { #arg_name });
             } else if is_vec {
-                // Vec<T> types - extract from __handler_input.args as comma-separated string, then parse
-                // The registry extracts multiple values and joins them
+                // Vec<T> types - the registry keeps each value separately, so parse them one by one
+                let vec_type = &pat_type.ty;
                 arg_extractions.push(quote! {
-                    let #arg_name: #pat_type.ty = if let Some(value_str) = __handler_input.args.get(#arg_name_str) {
-                        // Parse comma-separated values
-                        value_str.split(',')
-                            .map(|s| s.trim().parse::<#vec_inner_type>())
-                            .collect::<Result<Vec<_>, _>>()
-                            .map_err(|_| ::clap_noun_verb::error::NounVerbError::argument_error(
-                                format!("Invalid value for argument '{}'", #arg_name_str)
-                            ))?
-                    } else {
-                        Vec::new()
-                    };
+                    let #arg_name: #vec_type = __handler_input
+                        .get::<Vec<String>>(#arg_name_str)?
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|s| s.trim().parse::<#vec_inner_type>())
+                        .collect::<::std::result::Result<Vec<_>, _>>()
+                        .map_err(|_| ::clap_noun_verb::error::NounVerbError::argument_error(
+                            format!("Invalid value for argument '{}'", #arg_name_str)
+                        ))?;
                 });
                 arg_calls.push(quote! { #arg_name });
             } else if is_option {
@@ -1039,6 +1113,11 @@ fn generate_verb_registration(
                 _ => continue,
             };
 
+            if is_cancellation_token_type(&pat_type.ty) || is_invocation_context_type(&pat_type.ty)
+            {
+                continue;
+            }
+
             let is_option = is_option_type(&pat_type.ty);
             let is_flag = is_bool_type(&pat_type.ty);
 
@@ -1176,6 +1255,11 @@ fn generate_verb_registration(
                 quote! { None }
             };
 
+            let long_token = match arg_config.as_ref().and_then(|c| c.long.as_ref()) {
+                Some(long) => quote! { Some(#long.to_string()) },
+                None => quote! { None },
+            };
+
             let default_value_token = if let Some(config) = &arg_config {
                 if let Some(ref dv) = config.default_value {
                     quote! { Some(#dv.to_string()) }
@@ -1512,6 +1596,17 @@ fn generate_verb_registration(
                 quote! { None }
             };
 
+            // Runtime completion function from #[arg(complete = fn_name)]
+            let complete_token =
+                if let Some(path) = arg_config.as_ref().and_then(|c| c.complete.as_ref()) {
+                    quote! { Some(#path as ::clap_noun_verb::cli::complete::CompleteFn) }
+                } else {
+                    quote! { None }
+                };
+
+            // Record the value type for grammar export (Option unwrapped, whitespace removed)
+            let value_type_str = quote!(#inner_ty).to_string().replace(' ', "");
+
             arg_metadata.push(quote! {
                 ::clap_noun_verb::cli::registry::ArgMetadata {
                     name: #arg_name.to_string(),
@@ -1523,6 +1618,7 @@ fn generate_verb_registration(
                     min_length: #min_length_token,
                     max_length: #max_length_token,
                     short: #short_token,
+                    long: #long_token,
                     default_value: #final_default_token,
                     env: #final_env_token,
                     multiple: #multiple_values,
@@ -1544,6 +1640,8 @@ fn generate_verb_registration(
                     allow_negative_numbers: #allow_negative_numbers_token,
                     value_hint: #value_hint_token,
                     global: #global_token,
+                    value_type: Some(#value_type_str.to_string()),
+                    complete: #complete_token,
                 }
             });
         }
@@ -1564,8 +1662,7 @@ fn generate_verb_registration(
     let about_str = about.as_deref().unwrap_or("");
 
     // Remove #[noun] attribute from output (it's been processed)
-    // Keep #[arg] attributes - they're handled by the #[arg] proc macro (pass-through)
-    // The #[verb] macro parses them from pat_type.attrs before output
+    // #[arg] attributes on parameters are stripped below after being parsed
     let mut output_fn = input_fn.clone();
     output_fn.attrs.retain(|attr| {
         let is_noun = attr.path().is_ident("noun")
@@ -1573,8 +1670,111 @@ fn generate_verb_registration(
         !is_noun
     });
 
-    // Keep #[arg] attributes on parameters - the #[arg] proc macro will handle them
-    // and the #[verb] macro has already parsed them for metadata generation
+    // Strip #[arg] attributes from parameters - the #[verb] macro has already parsed
+    // them for metadata generation, and attribute macros are not allowed on parameters
+    for input in output_fn.sig.inputs.iter_mut() {
+        if let syn::FnArg::Typed(pat_type) = input {
+            pat_type.attrs.retain(|attr| !attr.path().is_ident("arg"));
+        }
+    }
+
+    // Async verbs are driven to completion on a Tokio runtime with Ctrl-C and timeout handling
+    let handler_call = if is_async {
+        // `on_exceed = "cancel"` cancels the handler once the latency budget elapses
+        let cancel_after =
+            options.max_latency_ms.filter(|_| options.on_exceed.as_deref() == Some("cancel"));
+        let timeout_ms = match (options.timeout_ms, cancel_after) {
+            (Some(timeout), Some(budget)) => Some(timeout.min(budget)),
+            (timeout, budget) => timeout.or(budget),
+        };
+        let timeout = timeout_ms.map(|ms| {
+            quote! { .with_timeout(::std::time::Duration::from_millis(#ms)) }
+        });
+        let runtime = options.runtime.as_deref().map(|flavor| {
+            let variant = if flavor == "multi_thread" {
+                quote! { MultiThread }
+            } else {
+                quote! { CurrentThread }
+            };
+            quote! { .with_runtime(::clap_noun_verb::async_verb::RuntimeFlavor::#variant) }
+        });
+        quote! {
+            ::clap_noun_verb::async_verb::block_on_verb(
+                ::clap_noun_verb::async_verb::AsyncVerbOptions::default() #timeout #runtime,
+                move |__cancel_token| async move { #fn_name(#(#arg_calls),*).await },
+            )
+        }
+    } else {
+        quote! { #fn_name(#(#arg_calls),*) }
+    };
+
+    // Side effects from `effect = "..."` and `dry_run` are recorded after registration
+    let safety_registration = if options.effect.is_some() || options.dry_run {
+        let effect = match options.effect.as_deref() {
+            Some("destructive") => quote! { Destructive },
+            Some("mutating") => quote! { Mutating },
+            _ => quote! { ReadOnly },
+        };
+        let dry_run = options.dry_run;
+        quote! {
+            ::clap_noun_verb::cli::registry::CommandRegistry::set_verb_safety(
+                noun_name_static,
+                verb_name_final,
+                ::clap_noun_verb::cli::safety::VerbSafety {
+                    effect: ::clap_noun_verb::cli::safety::VerbEffect::#effect,
+                    dry_run: #dry_run,
+                },
+            );
+        }
+    } else {
+        quote! {}
+    };
+
+    // Latency, CPU and memory budgets are enforced by the registry around the handler
+    let budget_registration = if options.has_budget() {
+        let optional = |value: Option<u64>| match value {
+            Some(value) => quote! { ::std::option::Option::Some(#value) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let (max_latency_ms, max_cpu_ms, max_memory_kb) = (
+            optional(options.max_latency_ms),
+            optional(options.max_cpu_ms),
+            optional(options.max_memory_kb),
+        );
+        let on_exceed = match options.on_exceed.as_deref() {
+            Some("fail") => quote! { Fail },
+            Some("cancel") => quote! { Cancel },
+            _ => quote! { Warn },
+        };
+        quote! {
+            ::clap_noun_verb::cli::registry::CommandRegistry::set_verb_budget(
+                noun_name_static,
+                verb_name_final,
+                ::clap_noun_verb::cli::budget::VerbBudget {
+                    max_latency_ms: #max_latency_ms,
+                    max_cpu_ms: #max_cpu_ms,
+                    max_memory_kb: #max_memory_kb,
+                    on_exceed: ::clap_noun_verb::cli::budget::BudgetAction::#on_exceed,
+                },
+            );
+        }
+    } else {
+        quote! {}
+    };
+
+    // Example command lines shown in help and the interactive explorer
+    let examples_registration = if options.examples.is_empty() {
+        quote! {}
+    } else {
+        let examples = &options.examples;
+        quote! {
+            ::clap_noun_verb::cli::registry::CommandRegistry::set_verb_examples(
+                noun_name_static,
+                verb_name_final,
+                &[#(#examples),*],
+            );
+        }
+    };
 
     let expanded = quote! {
         #output_fn
@@ -1587,7 +1787,7 @@ fn generate_verb_registration(
         fn #wrapper_name(__handler_input: ::clap_noun_verb::logic::HandlerInput) -> ::clap_noun_verb::error::Result<::clap_noun_verb::logic::HandlerOutput> {
             // Execute handler with argument extraction
             #(#arg_extractions)*
-            let result = #fn_name(#(#arg_calls),*)?;
+            let result = #handler_call?;
 
             ::clap_noun_verb::logic::HandlerOutput::from_data(result)
         }
@@ -1676,6 +1876,9 @@ fn generate_verb_registration(
                     args,
                     #wrapper_name,
                 );
+                #safety_registration
+                #budget_registration
+                #examples_registration
             }
             __register_impl  // Return function pointer (not a call!)
         };
@@ -1684,6 +1887,24 @@ fn generate_verb_registration(
     expanded.into()
 }
 
+/// Check if type is a `CancellationToken` (injected into async verbs)
+fn is_cancellation_token_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(type_path) = ty {
+        type_path.path.segments.last().map(|s| s.ident == "CancellationToken").unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+/// Check if type is an `InvocationContext` (injected from the handler context)
+fn is_invocation_context_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(type_path) = ty {
+        type_path.path.segments.last().map(|s| s.ident == "InvocationContext").unwrap_or(false)
+    } else {
+        false
+    }
+}
+
 /// Check if type is Option<T>
 fn is_option_type(ty: &syn::Type) -> bool {
     if let syn::Type::Path(type_path) = ty {
@@ -1734,6 +1955,7 @@ struct ValidationConstraints {
 /// Argument configuration parsed from #[arg(...)] attributes
 struct ArgConfig {
     short: Option<char>,
+    long: Option<String>,
     default_value: Option<String>,
     env: Option<String>,
     multiple: bool,
@@ -1752,6 +1974,7 @@ struct ArgConfig {
     exclusive: Option<bool>,                        // Exclusive group flag
     trailing_vararg: bool,                          // Trailing varargs support
     allow_negative_numbers: bool,                   // Allow negative numbers
+    complete: Option<syn::Path>,                    // Runtime completion function
 }
 
 /// Parse argument attributes from parameter attributes
@@ -1764,6 +1987,7 @@ fn parse_arg_attributes(attrs: &[syn::Attribute]) -> Option<ArgConfig> {
                 // Parse tokens manually to handle both flags (just names) and key-value pairs
                 let mut config = ArgConfig {
                     short: None,
+                    long: None,
                     default_value: None,
                     env: None,
                     multiple: false,
@@ -1782,6 +2006,7 @@ fn parse_arg_attributes(attrs: &[syn::Attribute]) -> Option<ArgConfig> {
                     exclusive: None,
                     trailing_vararg: false,
                     allow_negative_numbers: false,
+                    complete: None,
                 };
 
                 // Try parsing as MetaList first (handles key=value pairs)
@@ -1812,6 +2037,15 @@ fn parse_arg_attributes(attrs: &[syn::Attribute]) -> Option<ArgConfig> {
                                             }
                                         }
                                     }
+                                    "long" => {
+                                        if let syn::Expr::Lit(syn::ExprLit {
+                                            lit: syn::Lit::Str(s),
+                                            ..
+                                        }) = &nv.value
+                                        {
+                                            config.long = Some(s.value());
+                                        }
+                                    }
                                     "default_value" => {
                                         if let syn::Expr::Lit(syn::ExprLit {
                                             lit: syn::Lit::Str(s),
@@ -2015,6 +2249,12 @@ fn parse_arg_attributes(attrs: &[syn::Attribute]) -> Option<ArgConfig> {
                                             config.allow_negative_numbers = b.value;
                                         }
                                     }
+                                    "complete" => {
+                                        // Parse complete = fn_name (path to a CompleteFn)
+                                        if let syn::Expr::Path(p) = &nv.value {
+                                            config.complete = Some(p.path.clone());
+                                        }
+                                    }
                                     _ => {}
                                 }
                             }
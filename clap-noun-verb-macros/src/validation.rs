@@ -128,6 +128,7 @@ fn validate_type_is_serializable(ty: &Type, fn_name: &syn::Ident) -> syn::Result
 /// - #[verb]
 /// - #[verb("name")]
 /// - #[verb("name", "noun")]
+/// - #[verb("name", noun = "parent.child")]
 pub fn validate_verb_attribute_syntax(args: &TokenStream, input_fn: &ItemFn) -> syn::Result<()> {
     let fn_name = &input_fn.sig.ident;
 
@@ -160,8 +161,26 @@ pub fn validate_verb_attribute_syntax(args: &TokenStream, input_fn: &ItemFn) ->
         }
     };
 
-    // Validate number of arguments (0, 1, or 2)
-    if args_vec.len() > 2 {
+    // Positional literals come first; named options are validated separately
+    let positional: Vec<&syn::Expr> =
+        args_vec.iter().take_while(|arg| !crate::verb_options::is_option_expr(arg)).collect();
+    if let Some(misplaced) =
+        args_vec.iter().skip(positional.len()).find(|arg| !crate::verb_options::is_option_expr(arg))
+    {
+        return Err(syn::Error::new(
+            misplaced.span(),
+            format!(
+                "Positional arguments in #[verb] must come before named options for function '{}'\n\
+                 \n\
+                 Example: #[verb(\"create\", noun = \"cloud.vm\")]",
+                fn_name
+            ),
+        ));
+    }
+    crate::verb_options::parse_verb_options(args_vec.iter().skip(positional.len()))?;
+
+    // Validate number of positional arguments (0, 1, or 2)
+    if positional.len() > 2 {
         return Err(syn::Error::new(
             args.span(),
             format!(
@@ -182,8 +201,8 @@ pub fn validate_verb_attribute_syntax(args: &TokenStream, input_fn: &ItemFn) ->
         ));
     }
 
-    // Validate that all arguments are string literals
-    for (idx, arg) in args_vec.iter().enumerate() {
+    // Validate that all positional arguments are string literals
+    for (idx, arg) in positional.iter().enumerate() {
         match arg {
             syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(_), .. }) => {
                 // Valid string literal
@@ -279,15 +298,13 @@ pub fn generate_duplicate_detection(
     // Generate a const that will conflict if the same verb is registered twice
     quote! {
         // Compile-time duplicate detection: this const will conflict if
-        // another function tries to register the same noun+verb combination
+        // another function tries to register the same noun+verb combination.
+        //
+        // The empty tuple is only a marker; a second registration of the same
+        // noun+verb fails with "duplicate definitions".
         #[doc(hidden)]
         #[allow(non_upper_case_globals)]
-        const #duplicate_check_ident: () = {
-            // This empty tuple serves as a marker that this noun+verb combination
-            // has been registered. If another function tries to register the same
-            // combination, the compiler will error with "duplicate definitions"
-            ()
-        };
+        const #duplicate_check_ident: () = ();
     }
 }
 
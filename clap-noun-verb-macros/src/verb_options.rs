@@ -0,0 +1,389 @@
+//! Named options for the `#[verb]` attribute
+//!
+//! After the optional positional verb and noun literals, `#[verb]` accepts
+//! `key = value` options:
+//!
+//! ```rust,ignore
+//! #[verb("create", noun = "cloud.vm.snapshot")]
+//! fn create_snapshot(vm: String) -> Result<Snapshot> { ... }
+//!
+//! #[verb("delete", "bucket", effect = "destructive", dry_run)]
+//! fn delete_bucket(name: String, dry_run: bool) -> Result<Deleted> { ... }
+//!
+//! #[verb("sync", "mirror", max_latency_ms = 500, on_exceed = "fail")]
+//! fn sync_mirror(name: String) -> Result<Synced> { ... }
+//!
+//! #[verb("list", "bucket", example = "app bucket list", example = "app bucket list --all")]
+//! fn list_buckets(all: bool) -> Result<Vec<Bucket>> { ... }
+//! ```
+//!
+//! `example` may be repeated to document several command lines.
+
+use syn::spanned::Spanned;
+use syn::Expr;
+
+/// Options accepted as `key = value` pairs
+const NAMED_OPTIONS: &[&str] = &[
+    "effect",
+    "example",
+    "max_cpu_ms",
+    "max_latency_ms",
+    "max_memory_kb",
+    "noun",
+    "on_exceed",
+    "runtime",
+    "timeout",
+];
+
+/// Tokio runtime flavors accepted by `runtime = "..."`
+const RUNTIME_FLAVORS: &[&str] = &["current_thread", "multi_thread"];
+
+/// Side-effect classes accepted by `effect = "..."`
+const EFFECTS: &[&str] = &["read_only", "mutating", "destructive"];
+
+/// Budget actions accepted by `on_exceed = "..."`
+const BUDGET_ACTIONS: &[&str] = &["warn", "fail", "cancel"];
+
+/// Options accepted as bare flags (e.g. `#[verb("rm", some_flag)]`)
+const FLAG_OPTIONS: &[&str] = &["dry_run"];
+
+/// Parsed named options of a `#[verb]` attribute
+#[derive(Debug, Default)]
+pub struct VerbOptions {
+    /// Noun path; dot-separated segments express nested nouns (`cloud.vm.snapshot`)
+    pub noun: Option<String>,
+    /// Per-verb timeout in milliseconds (async verbs only), from `timeout = "30s"`
+    pub timeout_ms: Option<u64>,
+    /// Tokio runtime flavor override (async verbs only): `current_thread` or `multi_thread`
+    pub runtime: Option<String>,
+    /// Side-effect class: `read_only`, `mutating` or `destructive`
+    pub effect: Option<String>,
+    /// Whether the verb accepts `--dry-run`
+    pub dry_run: bool,
+    /// Wall time budget in milliseconds
+    pub max_latency_ms: Option<u64>,
+    /// CPU time budget in milliseconds
+    pub max_cpu_ms: Option<u64>,
+    /// Budget for growth of the peak resident set size during the handler, in kilobytes
+    pub max_memory_kb: Option<u64>,
+    /// What happens when a budget is exceeded: `warn`, `fail` or `cancel`
+    pub on_exceed: Option<String>,
+    /// Example command lines, in declaration order
+    pub examples: Vec<String>,
+}
+
+impl VerbOptions {
+    /// Whether any budget option is set
+    pub fn has_budget(&self) -> bool {
+        self.max_latency_ms.is_some() || self.max_cpu_ms.is_some() || self.max_memory_kb.is_some()
+    }
+}
+
+/// Check whether an attribute argument is a named option rather than a positional literal
+pub fn is_option_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Assign(_) => true,
+        Expr::Path(path) => path
+            .path
+            .get_ident()
+            .map(|i| FLAG_OPTIONS.contains(&i.to_string().as_str()))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Parse the named options of a `#[verb]` attribute
+pub fn parse_verb_options<'a>(exprs: impl Iterator<Item = &'a Expr>) -> syn::Result<VerbOptions> {
+    let mut options = VerbOptions::default();
+
+    for expr in exprs {
+        match expr {
+            Expr::Assign(assign) => {
+                let key = option_key(&assign.left)?;
+                match key.as_str() {
+                    "noun" => {
+                        let path = string_value(&assign.right, &key)?;
+                        validate_noun_path(&path, assign.right.span())?;
+                        options.noun = Some(path);
+                    }
+                    "timeout" => {
+                        let duration = string_value(&assign.right, &key)?;
+                        options.timeout_ms =
+                            Some(parse_duration_ms(&duration, assign.right.span())?);
+                    }
+                    "runtime" => {
+                        let flavor = string_value(&assign.right, &key)?;
+                        if !RUNTIME_FLAVORS.contains(&flavor.as_str()) {
+                            return Err(syn::Error::new(
+                                assign.right.span(),
+                                format!(
+                                    "Unknown runtime '{}'\n\nSupported runtimes: {}",
+                                    flavor,
+                                    RUNTIME_FLAVORS.join(", ")
+                                ),
+                            ));
+                        }
+                        options.runtime = Some(flavor);
+                    }
+                    "effect" => {
+                        let effect = string_value(&assign.right, &key)?;
+                        if !EFFECTS.contains(&effect.as_str()) {
+                            return Err(syn::Error::new(
+                                assign.right.span(),
+                                format!(
+                                    "Unknown effect '{}'\n\nSupported effects: {}",
+                                    effect,
+                                    EFFECTS.join(", ")
+                                ),
+                            ));
+                        }
+                        options.effect = Some(effect);
+                    }
+                    "example" => {
+                        let example = string_value(&assign.right, &key)?;
+                        if example.trim().is_empty() {
+                            return Err(syn::Error::new(
+                                assign.right.span(),
+                                "#[verb] option `example` expects a non-empty command line",
+                            ));
+                        }
+                        options.examples.push(example);
+                    }
+                    "max_latency_ms" => {
+                        options.max_latency_ms = Some(int_value(&assign.right, &key)?);
+                    }
+                    "max_cpu_ms" => options.max_cpu_ms = Some(int_value(&assign.right, &key)?),
+                    "max_memory_kb" => {
+                        options.max_memory_kb = Some(int_value(&assign.right, &key)?);
+                    }
+                    "on_exceed" => {
+                        let action = string_value(&assign.right, &key)?;
+                        if !BUDGET_ACTIONS.contains(&action.as_str()) {
+                            return Err(syn::Error::new(
+                                assign.right.span(),
+                                format!(
+                                    "Unknown budget action '{}'\n\nSupported actions: {}",
+                                    action,
+                                    BUDGET_ACTIONS.join(", ")
+                                ),
+                            ));
+                        }
+                        options.on_exceed = Some(action);
+                    }
+                    _ => return Err(unknown_option(&key, assign.left.span())),
+                }
+            }
+            Expr::Path(path) => {
+                let key = path.path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+                match key.as_str() {
+                    "dry_run" => options.dry_run = true,
+                    _ => return Err(unknown_option(&key, path.span())),
+                }
+            }
+            other => {
+                return Err(syn::Error::new(
+                    other.span(),
+                    "Expected a string literal or a `key = value` option in #[verb]",
+                ));
+            }
+        }
+    }
+
+    if options.on_exceed.is_some() && !options.has_budget() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "#[verb] option `on_exceed` needs a budget: max_latency_ms, max_cpu_ms or max_memory_kb",
+        ));
+    }
+    if options.on_exceed.as_deref() == Some("cancel") && options.max_latency_ms.is_none() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "#[verb] option `on_exceed = \"cancel\"` needs `max_latency_ms`",
+        ));
+    }
+
+    Ok(options)
+}
+
+/// Parse a duration such as `"500ms"`, `"30s"`, `"5m"` or `"1h"` into milliseconds
+pub fn parse_duration_ms(value: &str, span: proc_macro2::Span) -> syn::Result<u64> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let multiplier = match unit {
+        "ms" => Some(1),
+        "s" => Some(1_000),
+        "m" => Some(60_000),
+        "h" => Some(3_600_000),
+        _ => None,
+    };
+    match (amount.parse::<u64>().ok(), multiplier) {
+        (Some(amount), Some(multiplier)) if amount > 0 => {
+            amount.checked_mul(multiplier).ok_or_else(|| {
+                syn::Error::new(span, format!("Duration '{}' is too large", value))
+            })
+        }
+        _ => Err(syn::Error::new(
+            span,
+            format!(
+                "Invalid duration '{}'\n\nExpected a positive whole number with a unit: ms, s, m or h (e.g. \"30s\")",
+                value
+            ),
+        )),
+    }
+}
+
+/// Validate a dot-separated noun path such as `cloud.vm.snapshot`
+pub fn validate_noun_path(path: &str, span: proc_macro2::Span) -> syn::Result<()> {
+    if path.split('.').any(|segment| segment.is_empty() || segment.contains(char::is_whitespace)) {
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "Invalid noun path '{}'\n\
+                 \n\
+                 Expected dot-separated segments without whitespace, e.g. \"cloud.vm.snapshot\"",
+                path
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn option_key(expr: &Expr) -> syn::Result<String> {
+    match expr {
+        Expr::Path(path) if path.path.get_ident().is_some() => {
+            Ok(path.path.get_ident().map(|i| i.to_string()).unwrap_or_default())
+        }
+        other => Err(syn::Error::new(other.span(), "Option name must be a plain identifier")),
+    }
+}
+
+fn string_value(expr: &Expr, key: &str) -> syn::Result<String> {
+    match expr {
+        Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Ok(s.value()),
+        other => Err(syn::Error::new(
+            other.span(),
+            format!("Option `{}` in #[verb] expects a string literal", key),
+        )),
+    }
+}
+
+fn int_value(expr: &Expr, key: &str) -> syn::Result<u64> {
+    match expr {
+        Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(i), .. }) => match i.base10_parse::<u64>() {
+            Ok(value) if value > 0 => Ok(value),
+            _ => Err(syn::Error::new(
+                i.span(),
+                format!("Option `{}` in #[verb] expects a positive integer", key),
+            )),
+        },
+        other => Err(syn::Error::new(
+            other.span(),
+            format!("Option `{}` in #[verb] expects an integer literal", key),
+        )),
+    }
+}
+
+fn unknown_option(key: &str, span: proc_macro2::Span) -> syn::Error {
+    let mut known: Vec<&str> = NAMED_OPTIONS.iter().chain(FLAG_OPTIONS.iter()).copied().collect();
+    known.sort_unstable();
+    syn::Error::new(
+        span,
+        format!("Unknown #[verb] option `{}`\n\nSupported options: {}", key, known.join(", ")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse::Parser;
+
+    fn parse_args(tokens: proc_macro2::TokenStream) -> Vec<Expr> {
+        let parser = syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated;
+        parser.parse2(tokens).map(|p| p.into_iter().collect()).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_parse_noun_option() {
+        let args = parse_args(quote::quote!("create", noun = "cloud.vm.snapshot"));
+        let options = parse_verb_options(args.iter().filter(|e| is_option_expr(e)));
+        assert_eq!(options.ok().and_then(|o| o.noun), Some("cloud.vm.snapshot".to_string()));
+    }
+
+    #[test]
+    fn test_reject_unknown_option() {
+        let args = parse_args(quote::quote!("create", colour = "red"));
+        assert!(parse_verb_options(args.iter().filter(|e| is_option_expr(e))).is_err());
+    }
+
+    #[test]
+    fn test_parse_async_options() {
+        let args = parse_args(quote::quote!(timeout = "2m", runtime = "multi_thread"));
+        let options = parse_verb_options(args.iter()).ok();
+        assert_eq!(options.as_ref().and_then(|o| o.timeout_ms), Some(120_000));
+        assert_eq!(options.and_then(|o| o.runtime).as_deref(), Some("multi_thread"));
+    }
+
+    #[test]
+    fn test_parse_effect_options() {
+        let args = parse_args(quote::quote!("delete", effect = "destructive", dry_run));
+        let options = parse_verb_options(args.iter().filter(|e| is_option_expr(e))).ok();
+        assert_eq!(options.as_ref().and_then(|o| o.effect.as_deref()), Some("destructive"));
+        assert!(options.is_some_and(|o| o.dry_run));
+
+        let args = parse_args(quote::quote!(effect = "explosive"));
+        assert!(parse_verb_options(args.iter()).is_err());
+    }
+
+    #[test]
+    fn test_parse_budget_options() {
+        let args = parse_args(quote::quote!(
+            max_latency_ms = 500,
+            max_memory_kb = 65536,
+            on_exceed = "fail"
+        ));
+        let options = parse_verb_options(args.iter()).ok();
+        assert_eq!(options.as_ref().and_then(|o| o.max_latency_ms), Some(500));
+        assert_eq!(options.as_ref().and_then(|o| o.max_memory_kb), Some(65536));
+        assert_eq!(options.and_then(|o| o.on_exceed).as_deref(), Some("fail"));
+
+        for args in [
+            quote::quote!(max_latency_ms = "500"),
+            quote::quote!(on_exceed = "fail"),
+            quote::quote!(max_cpu_ms = 10, on_exceed = "cancel"),
+            quote::quote!(max_latency_ms = 10, on_exceed = "panic"),
+        ] {
+            assert!(parse_verb_options(parse_args(args).iter()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_repeated_examples() {
+        let args = parse_args(quote::quote!(
+            "list",
+            example = "app bucket list",
+            example = "app bucket list --all"
+        ));
+        let options = parse_verb_options(args.iter().filter(|e| is_option_expr(e))).ok();
+        assert_eq!(
+            options.map(|o| o.examples).unwrap_or_default(),
+            vec!["app bucket list", "app bucket list --all"]
+        );
+
+        let args = parse_args(quote::quote!(example = " "));
+        assert!(parse_verb_options(args.iter()).is_err());
+    }
+
+    #[test]
+    fn test_reject_invalid_duration() {
+        for duration in ["30", "0s", "1.5s", "10 days"] {
+            assert!(parse_duration_ms(duration, proc_macro2::Span::call_site()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_reject_empty_noun_segment() {
+        let args = parse_args(quote::quote!(noun = "cloud..vm"));
+        assert!(parse_verb_options(args.iter()).is_err());
+    }
+}
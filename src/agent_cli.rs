@@ -330,6 +330,17 @@ pub mod agent_builder {
             self.commands.get(name).map(|cmd| cmd.metadata.clone())
         }
 
+        /// Get every command's metadata and handler, sorted by name
+        pub fn command_handlers(&self) -> Vec<(CommandMetadata, Arc<dyn CommandHandler>)> {
+            let mut handlers: Vec<_> = self
+                .commands
+                .values()
+                .map(|cmd| (cmd.metadata.clone(), Arc::clone(&cmd.handler)))
+                .collect();
+            handlers.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+            handlers
+        }
+
         /// Execute a command by name with arguments
         pub fn execute(&self, name: &str, args: CommandArgs) -> AgentResult<serde_json::Value> {
             let command = self.commands.get(name).ok_or_else(|| {
@@ -1,33 +1,289 @@
 //! Async handler support for verbs
 //!
-//! This module provides utilities for using async operations within verb handlers.
+//! `#[verb]` accepts `async fn` handlers when the `async` feature is enabled.
+//! Each invocation runs on a fresh Tokio runtime (current-thread by default).
+//! Ctrl-C cancels a [`CancellationToken`] that handlers may take as a
+//! parameter; a second Ctrl-C aborts the handler. Ctrl-C is only intercepted
+//! while the handler runs. A per-verb `timeout` bounds how long the handler
+//! may run.
 //!
 //! # Example
 //!
 //! ```rust,ignore
-//! use clap_noun_verb::async_verb::run_async;
-//! use clap_noun_verb::VerbArgs;
-//! use serde::Serialize;
-//! use std::time::Duration;
+//! use clap_noun_verb::async_verb::CancellationToken;
+//! use clap_noun_verb_macros::verb;
 //!
-//! #[derive(Serialize)]
-//! struct Output {
-//!     message: String,
-//! }
-//!
-//! fn my_handler(args: &VerbArgs) -> clap_noun_verb::Result<Output> {
-//!     // Run async code from sync handler
-//!     run_async(async {
-//!         // Async operations here
-//!         tokio::time::sleep(Duration::from_millis(100)).await;
-//!         Ok(Output {
-//!             message: "Done!".to_string(),
-//!         })
-//!     })
+//! /// Sync remote state
+//! #[verb("sync", timeout = "30s", runtime = "multi_thread")]
+//! async fn sync_remote(remote: String, cancel: CancellationToken) -> Result<SyncReport> {
+//!     tokio::select! {
+//!         report = fetch(remote) => report,
+//!         _ = cancel.cancelled() => Ok(SyncReport::aborted()),
+//!     }
 //! }
 //! ```
+//!
+//! Sync handlers can still run async code with [`run_async`].
 
 use crate::Result;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub use tokio_util::sync::CancellationToken;
+
+/// How long a timed-out handler may run after its token is cancelled
+pub const CANCEL_GRACE_PERIOD: Duration = Duration::from_millis(500);
+
+/// Tokio runtime flavor used to drive async verbs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RuntimeFlavor {
+    /// Single-threaded runtime (default)
+    #[default]
+    CurrentThread,
+    /// Work-stealing runtime with one worker per core
+    MultiThread,
+}
+
+static DEFAULT_RUNTIME: Mutex<RuntimeFlavor> = Mutex::new(RuntimeFlavor::CurrentThread);
+
+/// Set the runtime flavor for async verbs that do not specify `runtime = "..."`
+pub fn set_default_runtime(flavor: RuntimeFlavor) {
+    *DEFAULT_RUNTIME.lock().unwrap_or_else(|e| e.into_inner()) = flavor;
+}
+
+/// Get the runtime flavor used by async verbs that do not specify one
+pub fn default_runtime() -> RuntimeFlavor {
+    *DEFAULT_RUNTIME.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Per-invocation options for an async verb (generated by `#[verb]`)
+#[derive(Debug, Clone, Default)]
+pub struct AsyncVerbOptions {
+    /// Maximum run time before the handler is cancelled
+    pub timeout: Option<Duration>,
+    /// Runtime flavor override; `None` uses [`default_runtime`]
+    pub runtime: Option<RuntimeFlavor>,
+}
+
+impl AsyncVerbOptions {
+    /// Set the timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the runtime flavor
+    pub fn with_runtime(mut self, runtime: RuntimeFlavor) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+}
+
+/// Run an async verb handler to completion
+///
+/// Builds a runtime of the requested flavor, hands the handler a
+/// [`CancellationToken`] that is cancelled on the first Ctrl-C or on timeout,
+/// and aborts the handler on a second Ctrl-C. A timed-out handler keeps
+/// running for up to [`CANCEL_GRACE_PERIOD`] after the cancellation so it can
+/// observe it and clean up; then it is dropped.
+///
+/// When called from inside a multi-thread Tokio runtime, the handler runs on
+/// that runtime (via [`tokio::task::block_in_place`]) and the requested flavor
+/// is ignored.
+///
+/// # Errors
+///
+/// Returns the handler's error, an execution error if the runtime cannot be
+/// created or the caller is inside a current-thread runtime,
+/// [`NounVerbError::Timeout`](crate::error::NounVerbError::Timeout) if the
+/// timeout elapses, or [`NounVerbError::Interrupted`](crate::error::NounVerbError::Interrupted)
+/// on a second Ctrl-C.
+pub fn block_on_verb<F, Fut, T>(options: AsyncVerbOptions, handler: F) -> Result<T>
+where
+    F: FnOnce(CancellationToken) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let token = CancellationToken::new();
+    let future = handler(token.clone());
+
+    let run = async move {
+        let run = async {
+            tokio::pin!(future);
+            let Some(limit) = options.timeout else {
+                return future.await;
+            };
+            if let Ok(result) = tokio::time::timeout(limit, &mut future).await {
+                return result;
+            }
+            token.cancel();
+            let _ = tokio::time::timeout(CANCEL_GRACE_PERIOD, &mut future).await;
+            Err(crate::error::NounVerbError::timeout(format!(
+                "command exceeded its {:?} limit",
+                limit
+            )))
+        };
+
+        tokio::select! {
+            result = run => result,
+            () = interrupt_signal(token.clone()) => {
+                Err(crate::error::NounVerbError::interrupted("aborted by a second Ctrl-C"))
+            }
+        }
+    };
+
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| handle.block_on(run))
+        }
+        Ok(_) => Err(crate::error::NounVerbError::execution_error(
+            "Cannot run an async verb inside a current-thread Tokio runtime; \
+             call it from a multi-thread runtime or outside any runtime",
+        )),
+        Err(_) => build_runtime(options.runtime.unwrap_or_else(default_runtime))?.block_on(run),
+    }
+}
+
+/// Resolve on the second Ctrl-C, cancelling `token` on the first
+///
+/// SIGINT is only intercepted while this future is alive; dropping it
+/// restores the previous disposition, so Ctrl-C terminates a host process
+/// again once no async verb is running.
+#[cfg(unix)]
+async fn interrupt_signal(token: CancellationToken) {
+    interrupt::Listener::install(token).aborted().await
+}
+
+/// Resolve on the second Ctrl-C, cancelling `token` on the first
+#[cfg(not(unix))]
+async fn interrupt_signal(token: CancellationToken) {
+    for _ in 0..2 {
+        if tokio::signal::ctrl_c().await.is_err() {
+            // No signal support: never interrupt
+            std::future::pending::<()>().await;
+        }
+        token.cancel();
+    }
+}
+
+/// SIGINT handling scoped to running async verbs
+#[cfg(unix)]
+mod interrupt {
+    use super::CancellationToken;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// How often a listener checks for new signals
+    const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+    /// SIGINTs received while any listener was installed
+    static RECEIVED: AtomicUsize = AtomicUsize::new(0);
+
+    /// Installed listeners and the disposition the first one replaced
+    static INSTALLED: Mutex<(usize, Option<libc::sigaction>)> = Mutex::new((0, None));
+
+    extern "C" fn on_sigint(_: libc::c_int) {
+        RECEIVED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Counts SIGINTs for one invocation until dropped
+    pub(super) struct Listener {
+        stopped: Arc<AtomicBool>,
+        aborted: Arc<tokio::sync::Notify>,
+    }
+
+    impl Listener {
+        /// Intercept SIGINT, cancelling `token` on the first signal
+        pub(super) fn install(token: CancellationToken) -> Self {
+            swap_handler(true);
+            let start = RECEIVED.load(Ordering::SeqCst);
+            let stopped = Arc::new(AtomicBool::new(false));
+            let aborted = Arc::new(tokio::sync::Notify::new());
+            let (watch_stopped, watch_aborted) = (stopped.clone(), aborted.clone());
+            std::thread::spawn(move || {
+                while !watch_stopped.load(Ordering::SeqCst) {
+                    std::thread::sleep(POLL_INTERVAL);
+                    let received = RECEIVED.load(Ordering::SeqCst).wrapping_sub(start);
+                    if received >= 1 {
+                        token.cancel();
+                    }
+                    if received >= 2 {
+                        watch_aborted.notify_one();
+                        break;
+                    }
+                }
+            });
+            Self { stopped, aborted }
+        }
+
+        /// Resolve on the second SIGINT
+        pub(super) async fn aborted(&self) {
+            self.aborted.notified().await
+        }
+    }
+
+    impl Drop for Listener {
+        fn drop(&mut self) {
+            self.stopped.store(true, Ordering::SeqCst);
+            swap_handler(false);
+        }
+    }
+
+    /// Install the handler for the first listener, restore the previous
+    /// disposition after the last one
+    #[allow(unsafe_code)]
+    fn swap_handler(install: bool) {
+        let mut installed = INSTALLED.lock().unwrap_or_else(|e| e.into_inner());
+        let (listeners, previous) = &mut *installed;
+        if install {
+            *listeners += 1;
+            if *listeners > 1 {
+                return;
+            }
+            // SAFETY: the handler only touches an atomic, which is
+            // async-signal-safe, and both sigaction structs are valid for the
+            // duration of the call.
+            unsafe {
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = on_sigint as extern "C" fn(libc::c_int) as libc::sighandler_t;
+                action.sa_flags = libc::SA_RESTART;
+                libc::sigemptyset(&mut action.sa_mask);
+                let mut replaced: libc::sigaction = std::mem::zeroed();
+                if libc::sigaction(libc::SIGINT, &action, &mut replaced) == 0 {
+                    *previous = Some(replaced);
+                }
+            }
+        } else {
+            *listeners = listeners.saturating_sub(1);
+            if *listeners > 0 {
+                return;
+            }
+            if let Some(replaced) = previous.take() {
+                // SAFETY: `replaced` is the disposition sigaction reported
+                // when the handler was installed.
+                unsafe {
+                    libc::sigaction(libc::SIGINT, &replaced, std::ptr::null_mut());
+                }
+            }
+        }
+    }
+}
+
+/// Build a Tokio runtime of the given flavor
+///
+/// # Errors
+///
+/// Returns an execution error if the runtime cannot be created.
+pub fn build_runtime(flavor: RuntimeFlavor) -> Result<tokio::runtime::Runtime> {
+    let mut builder = match flavor {
+        RuntimeFlavor::CurrentThread => tokio::runtime::Builder::new_current_thread(),
+        RuntimeFlavor::MultiThread => tokio::runtime::Builder::new_multi_thread(),
+    };
+    builder.enable_all().build().map_err(|e| {
+        crate::error::NounVerbError::execution_error(format!("Failed to create runtime: {}", e))
+    })
+}
 
 /// Helper for running async code from sync contexts using tokio runtime
 ///
@@ -45,11 +301,7 @@ pub fn run_async<F, T>(future: F) -> Result<T>
 where
     F: std::future::Future<Output = Result<T>>,
 {
-    let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().map_err(|e| {
-        crate::error::NounVerbError::execution_error(format!("Failed to create runtime: {}", e))
-    })?;
-
-    rt.block_on(future)
+    build_runtime(RuntimeFlavor::CurrentThread)?.block_on(future)
 }
 
 /// Create a tokio runtime that can be reused for multiple async operations
@@ -62,9 +314,7 @@ where
 /// let result2 = runtime.block_on(async_op2());
 /// ```
 pub fn create_runtime() -> Result<tokio::runtime::Runtime> {
-    tokio::runtime::Builder::new_current_thread().enable_all().build().map_err(|e| {
-        crate::error::NounVerbError::execution_error(format!("Failed to create runtime: {}", e))
-    })
+    build_runtime(RuntimeFlavor::CurrentThread)
 }
 
 #[cfg(test)]
@@ -77,6 +327,94 @@ mod tests {
         assert!(rt.is_ok());
     }
 
+    #[test]
+    fn test_block_on_verb_times_out_and_cancels_token() {
+        let options = AsyncVerbOptions::default().with_timeout(Duration::from_millis(10));
+        let observed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let handler_observed = observed.clone();
+        let result: Result<()> = block_on_verb(options, |token| async move {
+            tokio::select! {
+                () = tokio::time::sleep(Duration::from_secs(5)) => {}
+                () = token.cancelled() => {
+                    handler_observed.store(true, std::sync::atomic::Ordering::SeqCst);
+                }
+            }
+            Ok(())
+        });
+        assert!(matches!(result, Err(crate::error::NounVerbError::Timeout { .. })));
+        assert!(observed.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_block_on_verb_drops_handler_after_grace_period() {
+        let options = AsyncVerbOptions::default().with_timeout(Duration::from_millis(10));
+        let started = std::time::Instant::now();
+        let result: Result<()> = block_on_verb(options, |_| async {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(())
+        });
+        assert!(matches!(result, Err(crate::error::NounVerbError::Timeout { .. })));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    /// Set in the child process of [`test_sigint_terminates_after_an_async_verb`]
+    #[cfg(unix)]
+    const SIGINT_CHILD_ENV: &str = "CNV_TEST_SIGINT_CHILD";
+
+    #[cfg(unix)]
+    #[test]
+    #[allow(unsafe_code)]
+    fn test_sigint_terminates_after_an_async_verb() {
+        use std::os::unix::process::ExitStatusExt;
+
+        if std::env::var_os(SIGINT_CHILD_ENV).is_some() {
+            // A Ctrl-C during the verb only cancels its token
+            let cancelled = block_on_verb(AsyncVerbOptions::default(), |token| async move {
+                // SAFETY: raise only delivers a signal to this process.
+                unsafe { libc::raise(libc::SIGINT) };
+                token.cancelled().await;
+                Ok(())
+            });
+            assert!(cancelled.is_ok());
+            // Afterwards Ctrl-C terminates the process again
+            // SAFETY: as above.
+            unsafe { libc::raise(libc::SIGINT) };
+            std::thread::sleep(Duration::from_secs(5));
+            std::process::exit(0);
+        }
+
+        let status = std::env::current_exe().and_then(|test_binary| {
+            std::process::Command::new(test_binary)
+                .args(["--exact", "async_verb::tests::test_sigint_terminates_after_an_async_verb"])
+                .env(SIGINT_CHILD_ENV, "1")
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .status()
+        });
+        assert_eq!(status.ok().and_then(|status| status.signal()), Some(libc::SIGINT));
+    }
+
+    #[test]
+    fn test_block_on_verb_uses_requested_runtime() {
+        let options = AsyncVerbOptions::default().with_runtime(RuntimeFlavor::MultiThread);
+        let flavor = block_on_verb(options, |_| async {
+            Ok(tokio::runtime::Handle::current().runtime_flavor())
+        });
+        assert_eq!(flavor.ok(), Some(tokio::runtime::RuntimeFlavor::MultiThread));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_block_on_verb_inside_multi_thread_runtime() {
+        let result = block_on_verb(AsyncVerbOptions::default(), |_| async { Ok(7) });
+        assert_eq!(result.ok(), Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_block_on_verb_inside_current_thread_runtime_errors() {
+        let result = block_on_verb(AsyncVerbOptions::default(), |_| async { Ok(7) });
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_async_execution() {
         let value = 42;
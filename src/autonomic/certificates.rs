@@ -11,6 +11,8 @@
 //! 3. **Verifiability**: Every certificate can be independently verified
 //! 4. **Replayability**: Certificates can be serialized and replayed
 
+#[cfg(feature = "signing")]
+use super::signing::{signing_payload, IssuerKey, SignatureError, TrustStore};
 use super::{
     capability_id::CapabilityId,
     effects::EffectMetadata,
@@ -86,8 +88,8 @@ pub struct Certificate<State = Unchecked> {
     /// Correlation ID linking related invocations
     pub correlation_id: String,
 
-    /// Digital signature (for future crypto verification)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Issuer's signature over the certificate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub signature: Option<CertificateSignature>,
 
     /// Phantom state marker (zero-sized)
@@ -187,6 +189,22 @@ pub struct CertificateSignature {
     pub signature: String,
 }
 
+#[cfg(feature = "signing")]
+impl<State> Certificate<State> {
+    /// Sign the certificate with an issuer key, replacing any previous signature
+    pub fn sign(mut self, key: &IssuerKey) -> Result<Self, CertificateError> {
+        self.signature = Some(key.sign(&signing_payload(&self, &[])?));
+        Ok(self)
+    }
+
+    /// Verify the certificate was signed with a key in `trust_store`
+    pub fn verify_signature(&self, trust_store: &TrustStore) -> Result<(), CertificateError> {
+        let signature = self.signature.as_ref().ok_or(SignatureError::Missing)?;
+        trust_store.verify(&signing_payload(self, &[])?, signature, None)?;
+        Ok(())
+    }
+}
+
 // State transition implementations
 
 impl Certificate<Unchecked> {
@@ -364,6 +382,14 @@ impl Certificate<Verified> {
 
         Ok(cert)
     }
+
+    /// Import a certificate and verify its signature against `trust_store`
+    #[cfg(feature = "signing")]
+    pub fn import_signed(data: &str, trust_store: &TrustStore) -> Result<Self, CertificateError> {
+        let cert = Self::import(data)?;
+        cert.verify_signature(trust_store)?;
+        Ok(cert)
+    }
 }
 
 /// Certificate-related errors
@@ -383,6 +409,10 @@ pub enum CertificateError {
 
     #[error("Deserialization failed: {0}")]
     DeserializationFailed(String),
+
+    #[cfg(feature = "signing")]
+    #[error("Invalid signature: {0}")]
+    Signature(#[from] SignatureError),
 }
 
 /// Wrapper for verified arguments with certificate
@@ -4,8 +4,8 @@
 //! and the clap CLI building infrastructure.
 
 use super::introspection::{
-    AppMetadata, CommandCapabilities, CommandMetadata, IntrospectionResponse, NounMetadata,
-    VerbMetadata,
+    AppMetadata, ArgumentMetadata, CommandCapabilities, CommandMetadata, IntrospectionResponse,
+    NounMetadata, VerbMetadata,
 };
 use crate::error::Result;
 use crate::noun::NounCommand;
@@ -16,6 +16,8 @@ pub struct AutonomicCli {
     registry: CommandRegistry,
     cli_version: String,
     app_metadata: AppMetadata,
+    /// Nouns of a macro command registry, see [`AutonomicCli::with_command_registry`]
+    command_nouns: Vec<NounMetadata>,
 }
 
 impl AutonomicCli {
@@ -25,13 +27,29 @@ impl AutonomicCli {
         cli_version: impl Into<String>,
         app_metadata: AppMetadata,
     ) -> Self {
-        Self { registry, cli_version: cli_version.into(), app_metadata }
+        Self { registry, cli_version: cli_version.into(), app_metadata, command_nouns: Vec::new() }
     }
 
     /// Create from registry with default app metadata
     pub fn from_registry(registry: CommandRegistry, cli_version: impl Into<String>) -> Self {
-        let app_metadata = AppMetadata::new("cli");
-        Self { registry, cli_version: cli_version.into(), app_metadata }
+        Self::new(registry, cli_version, AppMetadata::new("cli"))
+    }
+
+    /// Also introspect the nouns of a macro [`CommandRegistry`](crate::cli::registry::CommandRegistry)
+    ///
+    /// Covers `#[verb]` functions and the builder nouns and agent commands
+    /// added to it, with their arguments and declared effects. Root verbs have
+    /// no noun and are not listed.
+    pub fn with_command_registry(
+        mut self,
+        registry: &crate::cli::registry::CommandRegistry,
+    ) -> Self {
+        self.command_nouns = registry
+            .get_sub_nouns("")
+            .into_iter()
+            .map(|noun_path| command_noun(registry, noun_path))
+            .collect();
+        self
     }
 
     /// Get command capabilities
@@ -47,6 +65,9 @@ impl AutonomicCli {
         for noun in self.registry.nouns() {
             response = response.with_noun(self.introspect_noun(noun));
         }
+        for noun in &self.command_nouns {
+            response = response.with_noun(noun.clone());
+        }
 
         response
     }
@@ -77,16 +98,21 @@ impl AutonomicCli {
 
     /// Handle introspection request for a specific noun
     pub fn introspect_noun_by_name(&self, noun_name: &str) -> Option<NounMetadata> {
-        self.registry.get_noun(noun_name).map(|noun| self.introspect_noun(noun))
+        match self.registry.get_noun(noun_name) {
+            Some(noun) => Some(self.introspect_noun(noun)),
+            None => self.command_nouns.iter().find(|noun| noun.name == noun_name).cloned(),
+        }
     }
 
     /// Handle introspection request for a specific verb
     pub fn introspect_verb(&self, noun_name: &str, verb_name: &str) -> Option<VerbMetadata> {
-        self.registry.get_noun(noun_name).and_then(|noun| {
-            noun.verbs().into_iter().find(|v| v.name() == verb_name).map(|verb| {
-                VerbMetadata::new(verb.name(), verb.about())
-                    .with_command(self.introspect_verb_command(verb.as_ref()))
-            })
+        let Some(noun) = self.registry.get_noun(noun_name) else {
+            let noun = self.command_nouns.iter().find(|noun| noun.name == noun_name)?;
+            return noun.verbs.iter().find(|verb| verb.name == verb_name).cloned();
+        };
+        noun.verbs().into_iter().find(|v| v.name() == verb_name).map(|verb| {
+            VerbMetadata::new(verb.name(), verb.about())
+                .with_command(self.introspect_verb_command(verb.as_ref()))
         })
     }
 
@@ -108,6 +134,64 @@ impl AutonomicCli {
     }
 }
 
+/// Introspection metadata for a noun of the macro command registry
+fn command_noun(registry: &crate::cli::registry::CommandRegistry, noun_path: &str) -> NounMetadata {
+    use crate::cli::registry::NOUN_PATH_SEPARATOR;
+
+    let name = noun_path.rsplit(NOUN_PATH_SEPARATOR).next().unwrap_or(noun_path);
+    let about = registry
+        .get_nouns()
+        .into_iter()
+        .find(|(path, _)| *path == noun_path)
+        .map(|(_, about)| about)
+        .unwrap_or_default();
+    let mut noun_meta = NounMetadata::new(name, about);
+
+    let mut verbs = registry.get_verbs_with_metadata(noun_path);
+    verbs.sort_by_key(|(verb_name, _, _)| *verb_name);
+    for (verb_name, verb_about, args) in verbs {
+        let mut command = CommandMetadata::new();
+        for arg in args {
+            command = command.with_argument(command_argument(arg));
+        }
+        if let Some(safety) = registry.verb_safety(noun_path, verb_name) {
+            command = command.with_effects(safety.effect_metadata());
+        }
+        noun_meta =
+            noun_meta.with_verb(VerbMetadata::new(verb_name, verb_about).with_command(command));
+    }
+
+    for sub_noun in registry.get_sub_nouns(noun_path) {
+        noun_meta = noun_meta.with_sub_noun(command_noun(registry, sub_noun));
+    }
+    noun_meta
+}
+
+/// Introspection metadata for an argument of the macro command registry
+fn command_argument(arg: &crate::cli::registry::ArgMetadata) -> ArgumentMetadata {
+    let arg_type = arg.value_type.as_deref().unwrap_or(if arg.is_flag { "bool" } else { "String" });
+    let mut meta = ArgumentMetadata::new(&arg.name, arg_type);
+    if arg.required {
+        meta = meta.required();
+    }
+    if let Some(default) = &arg.default_value {
+        meta = meta.with_default(default);
+    }
+    if let Some(help) = &arg.help {
+        meta = meta.with_help(help);
+    }
+    if let Some(short) = arg.short {
+        meta = meta.with_short(short);
+    }
+    if let Some(env) = &arg.env {
+        meta = meta.with_env(env);
+    }
+    match arg.positional {
+        Some(index) => meta.with_index(index),
+        None => meta.with_long(arg.long.as_deref().unwrap_or(&arg.name)),
+    }
+}
+
 /// Trait for verbs that provide autonomic metadata
 pub trait AutonomicVerbCommand: crate::verb::VerbCommand {
     /// Get command metadata for introspection
@@ -10,8 +10,11 @@
 //! 3. **Complete Chains**: Full delegation history from origin to executor
 //! 4. **Policy Integration**: Policies can match on delegation chains
 
+#[cfg(feature = "signing")]
+use super::signing::{signing_payload, IssuerKey, SignatureError, TrustStore};
 use super::{
     capability_id::CapabilityId,
+    certificates::CertificateSignature,
     tenancy::{AgentIdentity, TenantIdentity},
 };
 use serde::{Deserialize, Serialize};
@@ -258,6 +261,14 @@ pub struct DelegationToken {
     /// Metadata
     pub metadata: DelegationMetadata,
 
+    /// Delegator's signature over the token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<CertificateSignature>,
+
+    /// Signature of the parent token, covered by this token's signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_signature: Option<String>,
+
     /// Number of times this token has been used
     #[serde(skip)]
     #[allow(dead_code)]
@@ -280,11 +291,16 @@ impl DelegationToken {
             temporal,
             parent_token_id: None,
             metadata: DelegationMetadata::default(),
+            signature: None,
+            parent_signature: None,
             uses: std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0)),
         }
     }
 
     /// Create a sub-delegation (delegation of a delegation)
+    ///
+    /// When this token is signed, the sub-delegation records its signature
+    /// and signing it chains the two signatures.
     pub fn sub_delegate(
         &self,
         delegate: Principal,
@@ -314,10 +330,55 @@ impl DelegationToken {
             temporal: combined_temporal,
             parent_token_id: Some(self.token_id.clone()),
             metadata: DelegationMetadata::default(),
+            signature: None,
+            parent_signature: self.signature.as_ref().map(|s| s.signature.clone()),
             uses: std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0)),
         })
     }
 
+    /// Sign this token with the delegator's key
+    ///
+    /// The key must belong to the delegator's agent. Any previous signature
+    /// is replaced.
+    #[cfg(feature = "signing")]
+    pub fn sign(mut self, key: &IssuerKey) -> Result<Self, DelegationError> {
+        if key.agent_id() != self.delegator.agent.agent_id {
+            return Err(SignatureError::WrongSigner {
+                key_id: key.key_id(),
+                expected: self.delegator.agent.agent_id.clone(),
+            }
+            .into());
+        }
+        self.signature = Some(key.sign(&self.signing_payload()?));
+        Ok(self)
+    }
+
+    /// Verify the delegator signed this token with a key in `trust_store`
+    #[cfg(feature = "signing")]
+    pub fn verify_signature(&self, trust_store: &TrustStore) -> Result<(), DelegationError> {
+        let signature = self.signature.as_ref().ok_or(SignatureError::Missing)?;
+        trust_store.verify(
+            &self.signing_payload()?,
+            signature,
+            Some(&self.delegator.agent.agent_id),
+        )?;
+        Ok(())
+    }
+
+    /// Bytes covered by the signature
+    #[cfg(feature = "signing")]
+    fn signing_payload(&self) -> Result<Vec<u8>, SignatureError> {
+        signing_payload(
+            self,
+            &[
+                &["constraints", "allowed_capabilities"],
+                &["constraints", "forbidden_capabilities"],
+                &["constraints", "allowed_nouns"],
+                &["constraints", "allowed_verbs"],
+            ],
+        )
+    }
+
     /// Verify this token is valid
     pub fn verify(&self) -> Result<(), DelegationError> {
         // Check temporal validity
@@ -450,6 +511,32 @@ impl DelegationChain {
         Ok(())
     }
 
+    /// Verify the chain and the signature of every hop against `trust_store`
+    ///
+    /// Each token must be signed by its delegator with a trusted key, and
+    /// each sub-delegation must name the previous token as its parent and
+    /// carry its signature.
+    #[cfg(feature = "signing")]
+    pub fn verify_against(&self, trust_store: &TrustStore) -> Result<(), DelegationError> {
+        self.verify()?;
+
+        for (i, token) in self.tokens.iter().enumerate() {
+            token.verify_signature(trust_store)?;
+
+            if i > 0 {
+                let parent = &self.tokens[i - 1];
+                let parent_signature = parent.signature.as_ref().map(|s| &s.signature);
+                if token.parent_token_id.as_ref() != Some(&parent.token_id)
+                    || token.parent_signature.as_ref() != parent_signature
+                {
+                    return Err(DelegationError::BrokenChain);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get effective constraints (intersection of all tokens)
     pub fn effective_constraints(&self) -> CapabilityConstraint {
         self.tokens.iter().fold(CapabilityConstraint::unrestricted(), |acc, token| {
@@ -493,6 +580,10 @@ pub enum DelegationError {
 
     #[error("Lock poisoned: {0}")]
     LockPoisoned(String),
+
+    #[cfg(feature = "signing")]
+    #[error("Invalid signature: {0}")]
+    Signature(#[from] SignatureError),
 }
 
 /// Delegation registry for managing active delegations
@@ -55,7 +55,7 @@ pub struct StructuredError {
     /// Human-readable message
     pub message: String,
     /// Additional structured details
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub details: HashMap<String, serde_json::Value>,
 }
 
@@ -149,6 +149,22 @@ impl StructuredError {
             crate::error::NounVerbError::Generic(message) => {
                 Self::new(ErrorKind::InternalError, message)
             }
+            crate::error::NounVerbError::Timeout { message } => {
+                Self::new(ErrorKind::DeadlineExceeded, message)
+            }
+            crate::error::NounVerbError::Interrupted { message } => Self::execution_error(message),
+            crate::error::NounVerbError::PolicyDenied { command, reason, .. } => {
+                Self::new(ErrorKind::PermissionDenied, reason.clone())
+                    .with_detail("command", command)
+            }
+            crate::error::NounVerbError::BudgetExceeded { command, usage } => {
+                Self::guard_exceeded(error.to_string())
+                    .with_detail("command", command)
+                    .with_detail("guard", usage.guard_result())
+            }
+            crate::error::NounVerbError::Exit { code, message } => {
+                Self::execution_error(message).with_detail("exit_code", code)
+            }
         }
     }
 }
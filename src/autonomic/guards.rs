@@ -95,6 +95,18 @@ pub struct GuardResult {
     pub latency_ms: Option<u64>,
     /// Maximum allowed latency in milliseconds
     pub max_latency_ms: Option<u64>,
+    /// Actual CPU time in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_ms: Option<u64>,
+    /// Maximum allowed CPU time in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cpu_ms: Option<u64>,
+    /// Actual peak memory in kilobytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_kb: Option<u64>,
+    /// Maximum allowed memory in kilobytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_kb: Option<u64>,
     /// Guard evaluation status
     pub status: GuardStatus,
     /// Additional details about guard violations
@@ -107,6 +119,10 @@ impl Default for GuardResult {
             enforced: false,
             latency_ms: None,
             max_latency_ms: None,
+            cpu_ms: None,
+            max_cpu_ms: None,
+            memory_kb: None,
+            max_memory_kb: None,
             status: GuardStatus::NoGuards,
             details: None,
         }
@@ -136,7 +152,7 @@ impl GuardResult {
             latency_ms: Some(latency_ms),
             max_latency_ms: Some(max_latency_ms),
             status: GuardStatus::WithinBudget,
-            details: None,
+            ..Default::default()
         }
     }
 
@@ -148,6 +164,7 @@ impl GuardResult {
             max_latency_ms: Some(max_latency_ms),
             status: GuardStatus::ExceededBudget,
             details: Some(format!("Latency {}ms exceeded budget {}ms", latency_ms, max_latency_ms)),
+            ..Default::default()
         }
     }
 
@@ -35,8 +35,11 @@ pub mod phases;
 pub mod planes;
 pub mod policy;
 pub mod protocol;
+pub mod receipt_log;
 pub mod receipts;
 pub mod schema;
+#[cfg(feature = "signing")]
+pub mod signing;
 pub mod simd;
 pub mod streaming;
 pub mod telemetry;
@@ -49,8 +52,8 @@ pub use capability_id::{
     DeprecationInfo,
 };
 pub use certificates::{
-    Certificate, CertificateBuilder, CertificateError, CertificateId, CertifiedInvocation,
-    PolicyTrace, SchemaHash,
+    Certificate, CertificateBuilder, CertificateError, CertificateId, CertificateSignature,
+    CertifiedInvocation, PolicyTrace, SchemaHash,
 };
 pub use cli::{AutonomicCli, AutonomicNounCommand, AutonomicVerbCommand};
 pub use contracts::{
@@ -86,11 +89,14 @@ pub use planes::{InteractionType, Plane, PlaneInteraction};
 pub use policy::{
     PolicyDecision, PolicyEngine, PolicyRequest, PolicyResult, RuleBasedPolicyEngine,
 };
+pub use receipt_log::{ReceiptLog, ReceiptRecord, ReceiptVerification};
 pub use receipts::{ExecutionReceipt, ReceiptConfig, ReceiptWithOutput};
 pub use schema::{
     CommandReference, CompositionMetadata, EquivalenceClass, EquivalenceRelationship, InputSchema,
     OutputSchema, PrimitiveType, Resource, TypeSchema,
 };
+#[cfg(feature = "signing")]
+pub use signing::{IssuerKey, PublicKey, SignatureError, TrustStore, SIGNATURE_ALGORITHM};
 pub use streaming::{
     IncrementalReceipt, SessionContext, SessionId, SessionManager, SessionState, StreamEvent,
     StreamEventType,
@@ -5,6 +5,23 @@
 //! - Rewrite arguments within allowed transformations
 //! - Redirect to alternative noun/verb
 //! - Enforce global governance rules
+//!
+//! [`CommandRegistry::set_policy_engine`](crate::cli::registry::CommandRegistry::set_policy_engine)
+//! evaluates an engine before every verb. Rules for [`RuleBasedPolicyEngine`]
+//! can be kept in a YAML, TOML or JSON policy file:
+//!
+//! ```yaml
+//! name: production
+//! rules:
+//!   - name: no-bucket-delete
+//!     conditions:
+//!       - { type: environment, environment: production }
+//!       - { type: command, pattern: bucket.delete }
+//!     action: { type: deny, reason: "Buckets are managed by terraform", suggestion: "bucket archive" }
+//!   - name: cap-replicas
+//!     conditions: [{ type: command, pattern: deployment.scale }]
+//!     action: { type: rewrite, args: { replicas: 3 } }
+//! ```
 
 use super::tenancy::InvocationContext;
 use crate::autonomic::EffectMetadata;
@@ -58,6 +75,8 @@ impl PolicyDecision {
     }
 
     /// Create a rewrite decision
+    ///
+    /// The listed arguments replace the invocation's values; `null` unsets one.
     pub fn rewrite(new_args: HashMap<String, serde_json::Value>) -> Self {
         PolicyDecision::Rewrite { new_args }
     }
@@ -184,8 +203,10 @@ pub struct PolicyRule {
     /// Rule name
     pub name: String,
     /// Rule description
+    #[serde(default)]
     pub description: String,
     /// Conditions that must be met
+    #[serde(default)]
     pub conditions: Vec<PolicyCondition>,
     /// Action to take when conditions are met
     pub action: PolicyAction,
@@ -262,6 +283,8 @@ pub enum PolicyCondition {
     Tenant { tenant_id: String },
     /// Match noun/verb pattern
     Command { pattern: String },
+    /// Match the tenant environment (e.g. "production")
+    Environment { environment: String },
     /// Check if command handles sensitive data
     HandlesSensitiveData,
     /// Check if command requires isolation
@@ -295,6 +318,9 @@ impl PolicyCondition {
                 request.context.agent.agent_type == *agent_type
             }
             PolicyCondition::Tenant { tenant_id } => request.context.tenant.tenant_id == *tenant_id,
+            PolicyCondition::Environment { environment } => {
+                request.context.tenant.environment.as_deref() == Some(environment.as_str())
+            }
             PolicyCondition::Command { pattern } => {
                 let command = format!("{}.{}", request.noun, request.verb);
                 // Simple pattern matching (could be enhanced with regex)
@@ -313,11 +339,37 @@ pub enum PolicyAction {
     /// Allow the command
     Allow,
     /// Deny the command
-    Deny { reason: String },
+    Deny {
+        reason: String,
+        #[serde(default)]
+        suggestion: Option<String>,
+    },
+    /// Replace some arguments and continue evaluating
+    Rewrite { args: HashMap<String, serde_json::Value> },
+    /// Run a different command instead
+    Redirect {
+        noun: String,
+        verb: String,
+        #[serde(default)]
+        args: HashMap<String, serde_json::Value>,
+    },
     /// Require approval (audit mode)
     RequireApproval { approver: String },
 }
 
+/// Rules of a [`RuleBasedPolicyEngine`] as stored in a policy file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PolicyFile {
+    #[serde(default = "default_policy_name")]
+    name: String,
+    #[serde(default)]
+    rules: Vec<PolicyRule>,
+}
+
+fn default_policy_name() -> String {
+    "policy".to_string()
+}
+
 /// Simple rule-based policy engine
 pub struct RuleBasedPolicyEngine {
     name: String,
@@ -337,15 +389,30 @@ impl RuleBasedPolicyEngine {
         self
     }
 
+    /// Build an engine from a policy document (`name` and `rules`)
+    pub fn from_value(value: serde_json::Value) -> Result<Self> {
+        let file: PolicyFile = serde_json::from_value(value)
+            .map_err(|e| NounVerbError::execution_error(format!("Invalid policy: {}", e)))?;
+        Ok(file.rules.into_iter().fold(Self::new(file.name), Self::add_rule))
+    }
+
+    /// Load an engine from a YAML, TOML or JSON policy file
+    #[cfg(feature = "config-formats")]
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Self::from_value(crate::config::Config::from_file(path)?.value().clone())
+    }
+
     /// Sort rules by priority (higher first)
     fn sort_rules(&mut self) {
-        self.rules.sort_by(|a, b| b.priority.cmp(&a.priority));
+        // Stable, so rules of equal priority keep their file order
+        self.rules.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
     }
 }
 
 impl PolicyEngine for RuleBasedPolicyEngine {
     fn evaluate(&self, request: &PolicyRequest) -> Result<PolicyResult> {
         let mut result = PolicyResult::new(PolicyDecision::Allow);
+        let mut rewrites = HashMap::new();
 
         for rule in &self.rules {
             if rule.matches(request) {
@@ -355,10 +422,19 @@ impl PolicyEngine for RuleBasedPolicyEngine {
                     PolicyAction::Allow => {
                         // Continue evaluating other rules
                     }
-                    PolicyAction::Deny { reason } => {
-                        return Ok(
-                            PolicyResult::new(PolicyDecision::deny(reason)).with_rule(&rule.name)
-                        );
+                    PolicyAction::Deny { reason, suggestion } => {
+                        let decision = PolicyDecision::Deny {
+                            reason: reason.clone(),
+                            suggestion: suggestion.clone(),
+                        };
+                        return Ok(PolicyResult::new(decision).with_rule(&rule.name));
+                    }
+                    PolicyAction::Rewrite { args } => {
+                        rewrites.extend(args.clone());
+                    }
+                    PolicyAction::Redirect { noun, verb, args } => {
+                        let decision = PolicyDecision::redirect(noun, verb, args.clone());
+                        return Ok(PolicyResult::new(decision).with_rule(&rule.name));
                     }
                     PolicyAction::RequireApproval { approver } => {
                         result = result.with_metadata(
@@ -373,6 +449,9 @@ impl PolicyEngine for RuleBasedPolicyEngine {
             }
         }
 
+        if !rewrites.is_empty() {
+            result.decision = PolicyDecision::rewrite(rewrites);
+        }
         Ok(result)
     }
 
@@ -409,6 +488,7 @@ mod tests {
                 .with_condition(PolicyCondition::EffectType { effect: "privileged".to_string() })
                 .with_action(PolicyAction::Deny {
                     reason: "Privileged operations not allowed".to_string(),
+                    suggestion: None,
                 }),
         );
 
@@ -424,4 +504,33 @@ mod tests {
         let result = engine.evaluate(&request).unwrap();
         assert!(!result.is_allowed());
     }
+
+    #[test]
+    fn test_policy_from_value_rewrites_and_redirects() {
+        let engine = RuleBasedPolicyEngine::from_value(serde_json::json!({
+            "name": "prod",
+            "rules": [
+                {"name": "cap", "conditions": [{"type": "command", "pattern": "vm.scale"}],
+                 "action": {"type": "rewrite", "args": {"replicas": 3}}},
+                {"name": "archive", "conditions": [{"type": "command", "pattern": "vm.delete"}],
+                 "action": {"type": "redirect", "noun": "vm", "verb": "archive"}}
+            ]
+        }))
+        .unwrap();
+        let request = |verb: &str| {
+            let effects =
+                crate::autonomic::EffectMetadata::new(crate::autonomic::EffectType::ReadOnly);
+            PolicyRequest::new(InvocationContext::anonymous(), "vm", verb, HashMap::new(), effects)
+        };
+
+        let scale = engine.evaluate(&request("scale")).unwrap();
+        let delete = engine.evaluate(&request("delete")).unwrap();
+
+        assert_eq!(engine.name(), "prod");
+        assert_eq!(
+            scale.decision,
+            PolicyDecision::rewrite(HashMap::from([("replicas".to_string(), 3.into())]))
+        );
+        assert_eq!(delete.decision, PolicyDecision::redirect("vm", "archive", HashMap::new()));
+    }
 }
@@ -0,0 +1,375 @@
+//! Append-only JSONL log of execution receipts
+//!
+//! Each line of a [`ReceiptLog`] is a [`ReceiptRecord`]: a sequence number and
+//! an [`ExecutionReceipt`]. A chained log also stores the blake3 hash of every
+//! record and of the record before it, so [`ReceiptLog::verify`] detects edited,
+//! removed or reordered lines. Truncating the tail is only detectable against
+//! a copy of the last hash ([`ReceiptVerification::head`]) kept elsewhere.
+//!
+//! On Unix an append holds an exclusive `flock` on the file from reading the
+//! last record to writing the new one, so concurrent writers never reuse a
+//! sequence number. Elsewhere the log assumes a single writer.
+
+use super::receipts::{ExecutionReceipt, ReceiptConfig};
+use crate::error::{NounVerbError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Bytes read per step when looking for the last record of a log
+const TAIL_CHUNK: u64 = 4096;
+
+/// One line of a receipt log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptRecord {
+    /// Position in the log, starting at 1
+    pub seq: u64,
+    /// Hash of the previous record (chained logs only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+    /// The receipt
+    pub receipt: ExecutionReceipt,
+    /// Hash of this record (chained logs only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+impl ReceiptRecord {
+    /// blake3 hash over the sequence number, previous hash and receipt
+    ///
+    /// The record is hashed as JSON with sorted keys, so the hash does not
+    /// depend on the field order of the stored line.
+    pub fn compute_hash(&self) -> Result<String> {
+        let canonical = serde_json::to_value(serde_json::json!({
+            "seq": self.seq,
+            "prev_hash": self.prev_hash,
+            "receipt": self.receipt,
+        }))
+        .and_then(|value| serde_json::to_vec(&value))
+        .map_err(|e| NounVerbError::execution_error(format!("Failed to encode receipt: {}", e)))?;
+        Ok(format!("blake3:{}", blake3::hash(&canonical).to_hex()))
+    }
+}
+
+/// Outcome of [`ReceiptLog::verify`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReceiptVerification {
+    /// Records read before the first problem (all of them when intact)
+    pub records: u64,
+    /// Whether every record carries a hash chained to its predecessor
+    pub chained: bool,
+    /// Hash of the last verified record
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+    /// Sequence number expected at the first broken record
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken_at: Option<u64>,
+    /// What is wrong with the first broken record
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub problem: Option<String>,
+}
+
+impl ReceiptVerification {
+    /// Whether no record was found altered, missing or out of order
+    pub fn is_intact(&self) -> bool {
+        self.problem.is_none()
+    }
+}
+
+/// Append-only receipt log stored as JSON lines
+#[derive(Debug, Clone)]
+pub struct ReceiptLog {
+    path: PathBuf,
+    chained: bool,
+    config: ReceiptConfig,
+}
+
+impl ReceiptLog {
+    /// Log appending to `path`, created on first write
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), chained: false, config: ReceiptConfig::default() }
+    }
+
+    /// Chain records with blake3 hashes so tampering can be detected
+    pub fn chained(mut self) -> Self {
+        self.chained = true;
+        self
+    }
+
+    /// Choose what receipts record (`include_args`, `hash_result`)
+    pub fn with_config(mut self, config: ReceiptConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Receipt settings
+    pub fn config(&self) -> &ReceiptConfig {
+        &self.config
+    }
+
+    /// Append a receipt, syncing it to disk before returning its record
+    pub fn append(&self, receipt: ExecutionReceipt) -> Result<ReceiptRecord> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| self.io_error("open", e))?;
+        // Released when `file` is closed at the end of the append
+        lock_exclusive(&file).map_err(|e| self.io_error("lock", e))?;
+
+        let last = match last_line(&mut file).map_err(|e| self.io_error("read", e))? {
+            Some(line) => Some(parse_record(&line).map_err(|problem| {
+                NounVerbError::execution_error(format!(
+                    "Receipt log {} ends with a corrupt record: {}",
+                    self.path.display(),
+                    problem
+                ))
+            })?),
+            None => None,
+        };
+
+        let mut record = ReceiptRecord {
+            seq: last.as_ref().map_or(1, |last| last.seq + 1),
+            prev_hash: None,
+            receipt,
+            hash: None,
+        };
+        if self.chained {
+            record.prev_hash = last.and_then(|last| last.hash);
+            record.hash = Some(record.compute_hash()?);
+        }
+
+        let line = serde_json::to_string(&record).map_err(|e| {
+            NounVerbError::execution_error(format!("Failed to encode receipt: {}", e))
+        })?;
+        writeln!(file, "{}", line)
+            .and_then(|()| file.sync_data())
+            .map_err(|e| self.io_error("write", e))?;
+        Ok(record)
+    }
+
+    /// All records in order; empty when the log does not exist yet
+    pub fn records(&self) -> Result<Vec<ReceiptRecord>> {
+        self.lines()?
+            .into_iter()
+            .map(|(number, line)| {
+                parse_record(&line).map_err(|problem| {
+                    NounVerbError::execution_error(format!(
+                        "{}:{}: {}",
+                        self.path.display(),
+                        number,
+                        problem
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// The record with sequence number `seq`
+    pub fn get(&self, seq: u64) -> Result<Option<ReceiptRecord>> {
+        Ok(self.records()?.into_iter().find(|record| record.seq == seq))
+    }
+
+    /// Check sequence numbers and, for hashed records, the hash chain
+    ///
+    /// Once a record carries a hash, every later record must as well.
+    pub fn verify(&self) -> Result<ReceiptVerification> {
+        let mut verification = ReceiptVerification {
+            records: 0,
+            chained: true,
+            head: None,
+            broken_at: None,
+            problem: None,
+        };
+        let mut hashed = false;
+
+        for (_, line) in self.lines()? {
+            let seq = verification.records + 1;
+            match check_record(&line, seq, verification.head.as_deref(), hashed) {
+                Ok(hash) => {
+                    verification.records = seq;
+                    hashed |= hash.is_some();
+                    verification.chained &= hash.is_some();
+                    verification.head = hash;
+                }
+                Err(problem) => {
+                    verification.broken_at = Some(seq);
+                    verification.problem = Some(problem);
+                    break;
+                }
+            }
+        }
+
+        verification.chained &= verification.records > 0;
+        Ok(verification)
+    }
+
+    /// Non-empty lines with their 1-based line numbers
+    fn lines(&self) -> Result<Vec<(usize, String)>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(self.io_error("open", e)),
+        };
+        let mut lines = Vec::new();
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| self.io_error("read", e))?;
+            if !line.trim().is_empty() {
+                lines.push((index + 1, line));
+            }
+        }
+        Ok(lines)
+    }
+
+    fn io_error(&self, action: &str, error: std::io::Error) -> NounVerbError {
+        NounVerbError::execution_error(format!(
+            "Failed to {} receipt log {}: {}",
+            action,
+            self.path.display(),
+            error
+        ))
+    }
+}
+
+/// Parse one log line
+fn parse_record(line: &str) -> std::result::Result<ReceiptRecord, String> {
+    serde_json::from_str(line).map_err(|e| format!("unreadable record ({})", e))
+}
+
+/// Check one line against the expected sequence number and previous hash,
+/// returning the record's hash
+fn check_record(
+    line: &str,
+    seq: u64,
+    prev_hash: Option<&str>,
+    hashed: bool,
+) -> std::result::Result<Option<String>, String> {
+    let record = parse_record(line)?;
+    if record.seq != seq {
+        return Err(format!("expected sequence number {}, found {}", seq, record.seq));
+    }
+    let Some(hash) = &record.hash else {
+        return if hashed { Err("record has no hash".to_string()) } else { Ok(None) };
+    };
+    if record.prev_hash.as_deref() != prev_hash {
+        return Err("previous hash does not match".to_string());
+    }
+    if *hash != record.compute_hash().map_err(|e| e.to_string())? {
+        return Err("hash does not match record".to_string());
+    }
+    Ok(record.hash)
+}
+
+/// Block until this process holds an exclusive advisory lock on `file`
+#[cfg(unix)]
+#[allow(unsafe_code)]
+fn lock_exclusive(file: &File) -> std::io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: flock only acts on the descriptor, which `file` keeps open for
+    // the duration of the call.
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn lock_exclusive(_file: &File) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Last non-empty line of a file, read backwards from the end
+fn last_line(file: &mut File) -> std::io::Result<Option<String>> {
+    let len = file.seek(SeekFrom::End(0))?;
+    let mut start = len;
+    let mut tail = Vec::new();
+    loop {
+        let trimmed = tail.iter().rposition(|b| !b"\r\n".contains(b)).map_or(0, |end| end + 1);
+        if let Some(newline) = tail[..trimmed].iter().rposition(|b| *b == b'\n') {
+            let line = String::from_utf8_lossy(&tail[newline + 1..trimmed]).into_owned();
+            return Ok(Some(line));
+        }
+        if start == 0 {
+            let line = String::from_utf8_lossy(&tail[..trimmed]).into_owned();
+            return Ok(Some(line).filter(|line| !line.is_empty()));
+        }
+        let step = start.min(TAIL_CHUNK);
+        start -= step;
+        let mut chunk = vec![0; step as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_in(dir: &tempfile::TempDir) -> ReceiptLog {
+        ReceiptLog::new(dir.path().join("receipts.jsonl")).chained()
+    }
+
+    #[test]
+    fn test_chained_log_detects_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = log_in(&dir);
+        for command in ["bucket list", "bucket delete", "bucket list"] {
+            log.append(ExecutionReceipt::new(command)).unwrap();
+        }
+
+        let intact = log.verify().unwrap();
+        let content = std::fs::read_to_string(log.path()).unwrap();
+        std::fs::write(log.path(), content.replacen("bucket delete", "bucket create", 1)).unwrap();
+        let edited = log.verify().unwrap();
+
+        assert!(intact.is_intact() && intact.chained);
+        assert_eq!(intact.records, 3);
+        assert_eq!(edited.broken_at, Some(2));
+        assert_eq!(edited.records, 1);
+    }
+
+    #[test]
+    fn test_append_continues_sequence_after_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        log_in(&dir).append(ExecutionReceipt::new("a")).unwrap();
+
+        let second = log_in(&dir).append(ExecutionReceipt::new("b")).unwrap();
+
+        assert_eq!(second.seq, 2);
+        assert!(second.prev_hash.is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_concurrent_appends_keep_the_chain_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                let log = log_in(&dir);
+                std::thread::spawn(move || {
+                    for n in 0..10 {
+                        log.append(ExecutionReceipt::new(format!("w{} {}", writer, n))).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let verification = log_in(&dir).verify().unwrap();
+
+        assert!(verification.is_intact(), "{:?}", verification.problem);
+        assert_eq!(verification.records, 40);
+    }
+}
@@ -69,6 +69,15 @@ pub struct ExecutionReceipt {
     pub result_hash: Option<String>,
     /// Correlation ID for tracing
     pub correlation_id: String,
+    /// Agent that invoked the command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    /// Tenant the command ran for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// Agent that delegated the invocation (for delegated calls)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_behalf_of: Option<String>,
     /// Success status
     pub success: bool,
     /// Error information (if failed)
@@ -98,6 +107,9 @@ impl ExecutionReceipt {
             planes: HashMap::new(),
             result_hash: None,
             correlation_id: uuid::Uuid::new_v4().to_string(),
+            agent_id: None,
+            tenant_id: None,
+            on_behalf_of: None,
             success: true,
             error: None,
         }
@@ -144,6 +156,15 @@ impl ExecutionReceipt {
         self
     }
 
+    /// Attribute the receipt to an invocation's agent, tenant and correlation ID
+    pub fn with_invocation(mut self, context: &super::tenancy::InvocationContext) -> Self {
+        self.correlation_id = context.correlation_id.clone();
+        self.agent_id = Some(context.agent.agent_id.clone());
+        self.tenant_id = Some(context.tenant.tenant_id.clone());
+        self.on_behalf_of = context.on_behalf_of().map(|agent| agent.agent_id.clone());
+        self
+    }
+
     /// Mark as failed with error
     pub fn with_error(mut self, error: super::errors::StructuredError) -> Self {
         self.success = false;
@@ -0,0 +1,277 @@
+//! # Ed25519 Signing for Delegation Tokens and Certificates
+//!
+//! Issuers sign [`DelegationToken`](super::DelegationToken)s and
+//! [`Certificate`](super::Certificate)s with an [`IssuerKey`]; verifiers check
+//! them against a [`TrustStore`] of public keys.
+//!
+//! ## Design Principles
+//!
+//! 1. **Bound Keys**: Every key belongs to one agent; a token must be signed by its delegator
+//! 2. **Canonical Content**: Signatures cover the serialized value without its signature
+//! 3. **Explicit Trust**: Only keys added to the trust store are accepted
+
+use super::certificates::CertificateSignature;
+use ed25519_dalek::{Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Algorithm name recorded in every signature
+pub const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+/// Private Ed25519 key of an agent issuing tokens or certificates
+#[derive(Clone)]
+pub struct IssuerKey {
+    agent_id: String,
+    signing_key: ed25519_dalek::SigningKey,
+}
+
+impl IssuerKey {
+    /// Generate a new random key for `agent_id`
+    pub fn generate(agent_id: impl Into<String>) -> Self {
+        let signing_key = ed25519_dalek::SigningKey::generate(&mut rand_core::OsRng);
+        Self { agent_id: agent_id.into(), signing_key }
+    }
+
+    /// Create the key for `agent_id` from a 32-byte secret seed
+    pub fn from_seed(agent_id: impl Into<String>, seed: [u8; 32]) -> Self {
+        Self {
+            agent_id: agent_id.into(),
+            signing_key: ed25519_dalek::SigningKey::from_bytes(&seed),
+        }
+    }
+
+    /// Agent owning this key
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+
+    /// Key ID recorded in signatures
+    pub fn key_id(&self) -> String {
+        self.public_key().key_id()
+    }
+
+    /// Public half of the key, to add to trust stores
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey { agent_id: self.agent_id.clone(), key: self.signing_key.verifying_key() }
+    }
+
+    /// Sign `message`
+    pub fn sign(&self, message: &[u8]) -> CertificateSignature {
+        CertificateSignature {
+            algorithm: SIGNATURE_ALGORITHM.to_string(),
+            key_id: self.key_id(),
+            signature: hex::encode(self.signing_key.sign(message).to_bytes()),
+        }
+    }
+}
+
+impl std::fmt::Debug for IssuerKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IssuerKey")
+            .field("agent_id", &self.agent_id)
+            .field("key_id", &self.key_id())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Public Ed25519 key of an agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKey {
+    /// Agent owning the key
+    pub agent_id: String,
+
+    /// Verifying key
+    pub key: ed25519_dalek::VerifyingKey,
+}
+
+impl PublicKey {
+    /// Create a public key for `agent_id` from its 32 bytes
+    pub fn from_bytes(
+        agent_id: impl Into<String>,
+        bytes: &[u8; 32],
+    ) -> Result<Self, SignatureError> {
+        let key = ed25519_dalek::VerifyingKey::from_bytes(bytes)
+            .map_err(|e| SignatureError::Malformed(e.to_string()))?;
+        Ok(Self { agent_id: agent_id.into(), key })
+    }
+
+    /// The 32 key bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.key.to_bytes()
+    }
+
+    /// Key ID: the algorithm and the first 8 bytes of the key's SHA-256, in hex
+    pub fn key_id(&self) -> String {
+        use sha2::{Digest, Sha256};
+        let hash = Sha256::digest(self.key.as_bytes());
+        format!("{}:{}", SIGNATURE_ALGORITHM, hex::encode(&hash[..8]))
+    }
+
+    /// Verify `signature` over `message`
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &CertificateSignature,
+    ) -> Result<(), SignatureError> {
+        if signature.algorithm != SIGNATURE_ALGORITHM {
+            return Err(SignatureError::UnsupportedAlgorithm(signature.algorithm.clone()));
+        }
+        let bytes: [u8; 64] = hex::decode(&signature.signature)
+            .map_err(|e| SignatureError::Malformed(e.to_string()))?
+            .try_into()
+            .map_err(|bytes: Vec<u8>| {
+                SignatureError::Malformed(format!("expected 64 bytes, got {}", bytes.len()))
+            })?;
+        self.key
+            .verify(message, &ed25519_dalek::Signature::from_bytes(&bytes))
+            .map_err(|_| SignatureError::Invalid)
+    }
+}
+
+/// Public keys whose signatures are accepted, by key ID
+#[derive(Debug, Clone, Default)]
+pub struct TrustStore {
+    keys: HashMap<String, PublicKey>,
+}
+
+impl TrustStore {
+    /// Create an empty trust store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust `key`
+    pub fn trust(&mut self, key: PublicKey) {
+        self.keys.insert(key.key_id(), key);
+    }
+
+    /// Trust `key` (builder form)
+    pub fn with_key(mut self, key: PublicKey) -> Self {
+        self.trust(key);
+        self
+    }
+
+    /// Stop trusting the key with `key_id`
+    pub fn revoke(&mut self, key_id: &str) -> Option<PublicKey> {
+        self.keys.remove(key_id)
+    }
+
+    /// Trusted key with `key_id`
+    pub fn get(&self, key_id: &str) -> Option<&PublicKey> {
+        self.keys.get(key_id)
+    }
+
+    /// Number of trusted keys
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether no key is trusted
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Verify `signature` over `message` with a trusted key
+    ///
+    /// With `signer`, the key must also belong to that agent.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &CertificateSignature,
+        signer: Option<&str>,
+    ) -> Result<(), SignatureError> {
+        let key = self
+            .get(&signature.key_id)
+            .ok_or_else(|| SignatureError::UntrustedKey(signature.key_id.clone()))?;
+        if let Some(signer) = signer {
+            if key.agent_id != signer {
+                return Err(SignatureError::WrongSigner {
+                    key_id: signature.key_id.clone(),
+                    expected: signer.to_string(),
+                });
+            }
+        }
+        key.verify(message, signature)
+    }
+}
+
+/// Bytes covered by the signature of `value`: its JSON without the `signature` field
+///
+/// `serde_json` maps keep their keys sorted, so the encoding does not depend
+/// on `HashMap` iteration order; `sets` lists the object paths holding
+/// `HashSet`s, whose arrays are sorted too.
+pub(crate) fn signing_payload<T: Serialize>(
+    value: &T,
+    sets: &[&[&str]],
+) -> Result<Vec<u8>, SignatureError> {
+    let mut value =
+        serde_json::to_value(value).map_err(|e| SignatureError::Malformed(e.to_string()))?;
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("signature");
+    }
+    for path in sets {
+        let array = path.iter().try_fold(&mut value, |value, field| value.get_mut(*field));
+        if let Some(serde_json::Value::Array(items)) = array {
+            items.sort_by_cached_key(|item| item.to_string());
+        }
+    }
+    serde_json::to_vec(&value).map_err(|e| SignatureError::Malformed(e.to_string()))
+}
+
+/// Signature errors
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SignatureError {
+    #[error("Not signed")]
+    Missing,
+
+    #[error("Unsupported signature algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Malformed signature: {0}")]
+    Malformed(String),
+
+    #[error("Key {0} is not trusted")]
+    UntrustedKey(String),
+
+    #[error("Key {key_id} does not belong to {expected}")]
+    WrongSigner { key_id: String, expected: String },
+
+    #[error("Signature does not match the signed content")]
+    Invalid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_round_trip() {
+        let key = IssuerKey::from_seed("issuer", [7; 32]);
+        let trust = TrustStore::new().with_key(key.public_key());
+
+        let signature = key.sign(b"payload");
+
+        assert_eq!(trust.verify(b"payload", &signature, Some("issuer")), Ok(()));
+        assert_eq!(trust.verify(b"tampered", &signature, None), Err(SignatureError::Invalid));
+        assert!(matches!(
+            trust.verify(b"payload", &signature, Some("other")),
+            Err(SignatureError::WrongSigner { .. })
+        ));
+        assert!(matches!(
+            TrustStore::new().verify(b"payload", &signature, None),
+            Err(SignatureError::UntrustedKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_signing_payload_sorts_sets_and_drops_signature() {
+        let first = serde_json::json!({ "signature": "x", "set": { "items": ["b", "a"] } });
+        let second = serde_json::json!({ "set": { "items": ["a", "b"] } });
+
+        let first = signing_payload(&first, &[&["set", "items"]]);
+        let second = signing_payload(&second, &[&["set", "items"]]);
+
+        assert!(first.is_ok());
+        assert_eq!(first, second);
+    }
+}
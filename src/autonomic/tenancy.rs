@@ -6,6 +6,8 @@
 //! - Policy context (what rules apply)
 //! - Priority and QoS hints (how important is this)
 
+use super::delegation::DelegationChain;
+use super::telemetry::TraceSpan;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -201,6 +203,35 @@ impl Default for PriorityClass {
     }
 }
 
+impl PriorityClass {
+    /// Name of the priority class as accepted by `from_str`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriorityClass::BestEffort => "best_effort",
+            PriorityClass::Normal => "normal",
+            PriorityClass::High => "high",
+            PriorityClass::Critical => "critical",
+        }
+    }
+}
+
+impl std::str::FromStr for PriorityClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "best_effort" => Ok(PriorityClass::BestEffort),
+            "normal" => Ok(PriorityClass::Normal),
+            "high" => Ok(PriorityClass::High),
+            "critical" => Ok(PriorityClass::Critical),
+            _ => Err(format!(
+                "Unknown priority '{}' (expected best_effort, normal, high or critical)",
+                s
+            )),
+        }
+    }
+}
+
 /// Quality of Service (QoS) hints for scheduling
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QoSHints {
@@ -296,6 +327,9 @@ pub struct InvocationContext {
     /// Parent invocation ID (for nested calls)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_invocation_id: Option<String>,
+    /// Delegation chain the agent acts under (for delegated calls)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegation: Option<DelegationChain>,
 }
 
 impl InvocationContext {
@@ -308,6 +342,7 @@ impl InvocationContext {
             qos: QoSHints::default(),
             correlation_id: uuid::Uuid::new_v4().to_string(),
             parent_invocation_id: None,
+            delegation: None,
         }
     }
 
@@ -335,6 +370,40 @@ impl InvocationContext {
         self
     }
 
+    /// Act under a delegation chain: the agent and tenant become its executor's
+    pub fn with_delegation(mut self, chain: DelegationChain) -> Self {
+        self.agent = chain.executor.agent.clone();
+        self.tenant = chain.executor.tenant.clone();
+        self.delegation = Some(chain);
+        self
+    }
+
+    /// Agent that originally granted the delegation, if the call is delegated
+    pub fn on_behalf_of(&self) -> Option<&AgentIdentity> {
+        self.delegation.as_ref().filter(|chain| !chain.is_direct()).map(|chain| &chain.origin.agent)
+    }
+
+    /// Start a span for `operation` in this invocation's trace
+    ///
+    /// The correlation ID is the trace ID, the parent invocation (if any) the
+    /// parent span, and the agent, tenant and priority become attributes.
+    pub fn span(&self, operation: impl Into<String>) -> TraceSpan {
+        let mut span = TraceSpan::new_root(operation);
+        span.trace_id = self.correlation_id.clone();
+        span.parent_id = self.parent_invocation_id.clone();
+        span.set_attribute("agent.id", self.agent.agent_id.as_str());
+        span.set_attribute("agent.type", self.agent.agent_type.as_str());
+        span.set_attribute("tenant.id", self.tenant.tenant_id.as_str());
+        if let Some(environment) = &self.tenant.environment {
+            span.set_attribute("tenant.environment", environment.as_str());
+        }
+        span.set_attribute("priority", self.qos.priority.as_str());
+        if let Some(origin) = self.on_behalf_of() {
+            span.set_attribute("on_behalf_of", origin.agent_id.as_str());
+        }
+        span
+    }
+
     /// Create a default context for anonymous execution
     pub fn anonymous() -> Self {
         Self::new(AgentIdentity::anonymous(), TenantIdentity::default_tenant())
@@ -404,4 +473,12 @@ mod tests {
         assert_eq!(ctx.qos.priority, PriorityClass::Critical);
         assert!(ctx.qos.must_succeed);
     }
+
+    #[test]
+    fn test_priority_class_from_str() {
+        assert_eq!("best-effort".parse(), Ok(PriorityClass::BestEffort));
+        assert_eq!(" High ".parse(), Ok(PriorityClass::High));
+        assert_eq!(PriorityClass::Critical.as_str().parse(), Ok(PriorityClass::Critical));
+        assert!("urgent".parse::<PriorityClass>().is_err());
+    }
 }
@@ -0,0 +1,341 @@
+//! Resource budgets for verbs, enforced around every handler run
+//!
+//! ```rust,ignore
+//! #[verb("sync", "mirror", max_latency_ms = 500, max_memory_kb = 65536, on_exceed = "fail")]
+//! fn sync_mirror(name: String) -> Result<Synced> { ... }
+//! ```
+//!
+//! The registry measures wall time, CPU time (user + system, from `getrusage`)
+//! and how far the handler raises the peak resident set size of the process
+//! above the peak it had when the handler started. Memory the handler
+//! allocates below an earlier high-water mark does not count against the
+//! budget. CPU time and memory are only measured on Unix.
+//!
+//! Both come from `RUSAGE_SELF` and so cover the whole process, not just the
+//! handler: in a multi-threaded host, CPU time and allocations of other
+//! threads during the run count against the verb's budget. Per-thread figures
+//! would miss the work of async handlers on runtime worker threads.
+//!
+//! An exceeded budget prints a warning (`warn`, the default), fails the
+//! invocation with
+//! [`NounVerbError::BudgetExceeded`](crate::error::NounVerbError::BudgetExceeded)
+//! (`fail`), or, for async verbs, cancels the handler once the latency budget
+//! elapses (`cancel`).
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// What happens when a verb exceeds its budget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetAction {
+    /// Print a warning to stderr and keep the result (the default)
+    #[default]
+    Warn,
+    /// Fail the invocation after the handler returns
+    Fail,
+    /// Cancel an async handler when the latency budget elapses, then fail
+    Cancel,
+}
+
+impl BudgetAction {
+    /// Name as written in `#[verb(on_exceed = "...")]`
+    pub fn name(self) -> &'static str {
+        match self {
+            BudgetAction::Warn => "warn",
+            BudgetAction::Fail => "fail",
+            BudgetAction::Cancel => "cancel",
+        }
+    }
+}
+
+impl fmt::Display for BudgetAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for BudgetAction {
+    type Err = crate::error::NounVerbError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "warn" => Ok(BudgetAction::Warn),
+            "fail" => Ok(BudgetAction::Fail),
+            "cancel" => Ok(BudgetAction::Cancel),
+            other => Err(crate::error::NounVerbError::argument_error(format!(
+                "Unknown budget action '{}' (expected warn, fail or cancel)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Latency, CPU and memory limits of a verb
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerbBudget {
+    /// Maximum wall time in milliseconds
+    pub max_latency_ms: Option<u64>,
+    /// Maximum CPU time (user + system) in milliseconds
+    pub max_cpu_ms: Option<u64>,
+    /// Maximum growth of the process's peak resident set size in kilobytes
+    pub max_memory_kb: Option<u64>,
+    /// What happens when a limit is exceeded
+    pub on_exceed: BudgetAction,
+}
+
+impl VerbBudget {
+    /// Budget without limits that warns when exceeded
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set maximum wall time in milliseconds
+    pub fn with_max_latency_ms(mut self, ms: u64) -> Self {
+        self.max_latency_ms = Some(ms);
+        self
+    }
+
+    /// Set maximum CPU time in milliseconds
+    pub fn with_max_cpu_ms(mut self, ms: u64) -> Self {
+        self.max_cpu_ms = Some(ms);
+        self
+    }
+
+    /// Set maximum growth of the peak resident set size in kilobytes
+    pub fn with_max_memory_kb(mut self, kb: u64) -> Self {
+        self.max_memory_kb = Some(kb);
+        self
+    }
+
+    /// Set what happens when a limit is exceeded
+    pub fn on_exceed(mut self, action: BudgetAction) -> Self {
+        self.on_exceed = action;
+        self
+    }
+
+    /// Whether any limit is set
+    pub fn has_limits(&self) -> bool {
+        self.max_latency_ms.is_some() || self.max_cpu_ms.is_some() || self.max_memory_kb.is_some()
+    }
+
+    /// Latency budget as a duration
+    pub fn max_latency(&self) -> Option<Duration> {
+        self.max_latency_ms.map(Duration::from_millis)
+    }
+
+    /// Guard configuration as used by the autonomic layer
+    #[cfg(feature = "autonomic")]
+    pub fn guard_config(&self) -> crate::autonomic::GuardConfig {
+        crate::autonomic::GuardConfig {
+            max_latency_ms: self.max_latency_ms,
+            max_latency_ns: None,
+            max_memory_kb: self.max_memory_kb,
+            max_cpu_ms: self.max_cpu_ms,
+        }
+    }
+}
+
+/// Resources used by one handler run, with the limits they were checked against
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    /// Wall time in milliseconds
+    pub latency_ms: u64,
+    /// CPU time (user + system) in milliseconds, where measurable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_ms: Option<u64>,
+    /// Growth of the process's peak resident set size in kilobytes, where measurable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_growth_kb: Option<u64>,
+    /// Limits in effect
+    pub budget: VerbBudget,
+}
+
+impl BudgetUsage {
+    /// Descriptions of every exceeded limit, e.g. `latency 812ms > 500ms`
+    pub fn violations(&self) -> Vec<String> {
+        let checks = [
+            ("latency", Some(self.latency_ms), self.budget.max_latency_ms, "ms"),
+            ("cpu", self.cpu_ms, self.budget.max_cpu_ms, "ms"),
+            ("memory", self.memory_growth_kb, self.budget.max_memory_kb, "KB"),
+        ];
+        checks
+            .into_iter()
+            .filter_map(|(name, used, limit, unit)| match (used, limit) {
+                (Some(used), Some(limit)) if used > limit => {
+                    Some(format!("{} {}{} > {}{}", name, used, unit, limit, unit))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether any limit was exceeded
+    pub fn is_exceeded(&self) -> bool {
+        !self.violations().is_empty()
+    }
+
+    /// Apply the budget's [`BudgetAction`] to the result of running `command`
+    ///
+    /// Within budget, or with `warn` (which prints the violations to stderr),
+    /// the result is kept. Otherwise a successful result becomes
+    /// [`NounVerbError::BudgetExceeded`](crate::error::NounVerbError::BudgetExceeded);
+    /// handler errors are kept, except the timeout of a cancelled async
+    /// handler, which becomes the budget error too.
+    pub fn enforce<T>(&self, command: &str, result: crate::Result<T>) -> crate::Result<T> {
+        if !self.is_exceeded() {
+            return result;
+        }
+        match (self.budget.on_exceed, result) {
+            (BudgetAction::Warn, result) => {
+                eprintln!(
+                    "warning: '{}' exceeded its budget: {}",
+                    command,
+                    self.violations().join(", ")
+                );
+                result
+            }
+            (_, Ok(_) | Err(crate::error::NounVerbError::Timeout { .. })) => {
+                Err(crate::error::NounVerbError::budget_exceeded(command, self.clone()))
+            }
+            (_, Err(error)) => Err(error),
+        }
+    }
+
+    /// Guard result as recorded in execution receipts
+    #[cfg(feature = "autonomic")]
+    pub fn guard_result(&self) -> crate::autonomic::GuardResult {
+        use crate::autonomic::{GuardResult, GuardStatus};
+
+        let violations = self.violations();
+        GuardResult {
+            enforced: true,
+            latency_ms: Some(self.latency_ms),
+            max_latency_ms: self.budget.max_latency_ms,
+            cpu_ms: self.cpu_ms,
+            max_cpu_ms: self.budget.max_cpu_ms,
+            memory_kb: self.memory_growth_kb,
+            max_memory_kb: self.budget.max_memory_kb,
+            status: if violations.is_empty() {
+                GuardStatus::WithinBudget
+            } else {
+                GuardStatus::ExceededBudget
+            },
+            details: (!violations.is_empty()).then(|| violations.join(", ")),
+        }
+    }
+}
+
+/// Measures a handler run from [`start`](Self::start) to [`finish`](Self::finish)
+#[derive(Debug)]
+pub struct UsageMeter {
+    started: Instant,
+    start: Option<(Duration, u64)>,
+}
+
+impl UsageMeter {
+    /// Start measuring
+    pub fn start() -> Self {
+        Self { started: Instant::now(), start: process_usage() }
+    }
+
+    /// Stop measuring and compare against `budget`
+    pub fn finish(self, budget: VerbBudget) -> BudgetUsage {
+        let latency = self.started.elapsed();
+        let usage = self.start.zip(process_usage());
+        BudgetUsage {
+            latency_ms: millis(latency),
+            cpu_ms: usage
+                .map(|((cpu_start, _), (cpu_end, _))| millis(cpu_end.saturating_sub(cpu_start))),
+            memory_growth_kb: usage
+                .map(|((_, peak_start), (_, peak_end))| peak_end.saturating_sub(peak_start)),
+            budget,
+        }
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// CPU time and peak RSS (KB) of the current process
+#[cfg(unix)]
+#[allow(unsafe_code)]
+fn process_usage() -> Option<(Duration, u64)> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: getrusage only writes to the rusage struct it is given and
+    // reports failure through its return value.
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+    let time = |tv: libc::timeval| {
+        Duration::from_secs(u64::try_from(tv.tv_sec).unwrap_or(0))
+            + Duration::from_micros(u64::try_from(tv.tv_usec).unwrap_or(0))
+    };
+    let max_rss = u64::try_from(usage.ru_maxrss).unwrap_or(0);
+    // ru_maxrss is in bytes on macOS and kilobytes elsewhere
+    let peak_kb = if cfg!(target_os = "macos") { max_rss / 1024 } else { max_rss };
+    Some((time(usage.ru_utime) + time(usage.ru_stime), peak_kb))
+}
+
+#[cfg(not(unix))]
+fn process_usage() -> Option<(Duration, u64)> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_violations_compare_measured_limits_only() {
+        let usage = BudgetUsage {
+            latency_ms: 812,
+            cpu_ms: None,
+            memory_growth_kb: Some(1024),
+            budget: VerbBudget::new()
+                .with_max_latency_ms(500)
+                .with_max_cpu_ms(10)
+                .with_max_memory_kb(2048),
+        };
+
+        assert_eq!(usage.violations(), vec!["latency 812ms > 500ms"]);
+    }
+
+    #[test]
+    fn test_exceeded_memory_budget_is_enforced() {
+        let usage = |on_exceed| BudgetUsage {
+            latency_ms: 1,
+            cpu_ms: Some(1),
+            memory_growth_kb: Some(4096),
+            budget: VerbBudget::new().with_max_memory_kb(1024).on_exceed(on_exceed),
+        };
+
+        let failed = usage(BudgetAction::Fail).enforce("cache warm", Ok(()));
+        let warned = usage(BudgetAction::Warn).enforce("cache warm", Ok(()));
+        let within = BudgetUsage { memory_growth_kb: Some(512), ..usage(BudgetAction::Fail) }
+            .enforce("cache warm", Ok(()));
+
+        assert!(matches!(
+            failed,
+            Err(crate::error::NounVerbError::BudgetExceeded { ref command, ref usage })
+                if command == "cache warm" && usage.violations() == ["memory 4096KB > 1024KB"]
+        ));
+        assert!(warned.is_ok());
+        assert!(within.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_meter_measures_cpu_and_memory() {
+        let usage = UsageMeter::start().finish(VerbBudget::new());
+
+        assert!(usage.cpu_ms.is_some());
+        assert!(usage.memory_growth_kb.is_some());
+    }
+}
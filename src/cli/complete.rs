@@ -0,0 +1,255 @@
+//! Dynamic (runtime) shell completions
+//!
+//! Static completion scripts only know the command tree. For argument values
+//! that depend on runtime state (resource names, profiles, hosts) a verb
+//! parameter can name a completion function:
+//!
+//! ```rust,ignore
+//! use clap_noun_verb::cli::complete::{CompletionCandidate, CompletionRequest};
+//!
+//! fn complete_service(request: &CompletionRequest) -> Vec<CompletionCandidate> {
+//!     list_services(request.arg("namespace"))
+//!         .into_iter()
+//!         .map(|s| CompletionCandidate::new(s.name).with_description(s.status))
+//!         .collect()
+//! }
+//!
+//! #[verb("restart", "service")]
+//! fn restart(#[arg(complete = complete_service)] name: String, namespace: Option<String>) -> Result<()> {
+//!     // ...
+//! }
+//! ```
+//!
+//! The scripts from [`dynamic_script`] call back into the binary as
+//! `app __complete <shell> -- <words...>`; the registry works out which noun,
+//! verb or argument the cursor is on and prints one candidate per line.
+//! `app __complete <shell>` without words prints the script itself, so
+//! `eval "$(app __complete bash)"` installs it.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Hidden first argument routing an invocation to the completion engine
+pub const COMPLETE_COMMAND: &str = "__complete";
+
+/// Completion function named by `#[arg(complete = fn_name)]`
+pub type CompleteFn = fn(&CompletionRequest) -> Vec<CompletionCandidate>;
+
+/// A single completion candidate with an optional description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionCandidate {
+    /// Value inserted on the command line
+    pub value: String,
+    /// Description shown next to the value by shells that support it
+    pub description: Option<String>,
+}
+
+impl CompletionCandidate {
+    /// Create a candidate without a description
+    pub fn new(value: impl Into<String>) -> Self {
+        Self { value: value.into(), description: None }
+    }
+
+    /// Attach a description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        let description = description.into();
+        self.description = if description.is_empty() { None } else { Some(description) };
+        self
+    }
+}
+
+impl From<&str> for CompletionCandidate {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for CompletionCandidate {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+/// What is being completed, passed to a [`CompleteFn`]
+#[derive(Debug, Clone, Default)]
+pub struct CompletionRequest {
+    /// Partial value under the cursor
+    pub current: String,
+    /// Noun path of the verb (`None` for root-level verbs)
+    pub noun: Option<String>,
+    /// Verb being completed
+    pub verb: String,
+    /// Argument whose value is being completed
+    pub arg: String,
+    /// Values already given for the verb's other arguments, by argument name
+    pub args: HashMap<String, Vec<String>>,
+}
+
+impl CompletionRequest {
+    /// Partial value under the cursor
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    /// Last value already given for another argument
+    pub fn arg(&self, name: &str) -> Option<&str> {
+        self.args.get(name).and_then(|values| values.last()).map(String::as_str)
+    }
+
+    /// All values already given for another argument
+    pub fn arg_values(&self, name: &str) -> &[String] {
+        self.args.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+/// Shells supported by dynamic completion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionShell {
+    /// Bash (values only)
+    Bash,
+    /// Zsh (values with descriptions)
+    Zsh,
+    /// Fish (values with descriptions)
+    Fish,
+}
+
+impl CompletionShell {
+    /// Shell name as used on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            CompletionShell::Bash => "bash",
+            CompletionShell::Zsh => "zsh",
+            CompletionShell::Fish => "fish",
+        }
+    }
+
+    /// Render candidates in the line format the shell's script expects
+    pub fn render(self, candidates: &[CompletionCandidate]) -> String {
+        let mut out = String::new();
+        for candidate in candidates {
+            match (self, &candidate.description) {
+                (CompletionShell::Zsh, Some(description)) => {
+                    out.push_str(&candidate.value.replace(':', "\\:"));
+                    out.push(':');
+                    out.push_str(&single_line(description));
+                }
+                (CompletionShell::Zsh, None) => {
+                    out.push_str(&candidate.value.replace(':', "\\:"));
+                }
+                (CompletionShell::Fish, Some(description)) => {
+                    out.push_str(&candidate.value);
+                    out.push('\t');
+                    out.push_str(&single_line(description));
+                }
+                _ => out.push_str(&candidate.value),
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl fmt::Display for CompletionShell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for CompletionShell {
+    type Err = crate::error::NounVerbError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bash" => Ok(CompletionShell::Bash),
+            "zsh" => Ok(CompletionShell::Zsh),
+            "fish" => Ok(CompletionShell::Fish),
+            other => Err(crate::error::NounVerbError::argument_error(format!(
+                "Unsupported completion shell '{}' (expected bash, zsh or fish)",
+                other
+            ))),
+        }
+    }
+}
+
+/// First line of a description, tabs replaced, so it fits the line protocol
+fn single_line(description: &str) -> String {
+    description.lines().next().unwrap_or_default().replace('\t', " ")
+}
+
+/// Shell function name derived from the application name
+fn function_name(app_name: &str) -> String {
+    let ident: String =
+        app_name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    format!("__{}_complete", ident)
+}
+
+/// Completion script calling back into `app_name __complete` for candidates
+pub fn dynamic_script(shell: CompletionShell, app_name: &str) -> String {
+    let func = function_name(app_name);
+    match shell {
+        CompletionShell::Bash => format!(
+            "{func}() {{\n    \
+                 local IFS=$'\\n'\n    \
+                 COMPREPLY=($(\"{app}\" {cmd} bash -- \"${{COMP_WORDS[@]:0:COMP_CWORD+1}}\" 2>/dev/null))\n\
+             }}\n\
+             complete -o default -F {func} {app}\n",
+            func = func,
+            app = app_name,
+            cmd = COMPLETE_COMMAND,
+        ),
+        CompletionShell::Zsh => format!(
+            "#compdef {app}\n\
+             {func}() {{\n    \
+                 local -a candidates\n    \
+                 candidates=(${{(f)\"$(\"{app}\" {cmd} zsh -- \"${{(@)words[1,CURRENT]}}\" 2>/dev/null)\"}})\n    \
+                 _describe 'values' candidates\n\
+             }}\n\
+             compdef {func} {app}\n",
+            func = func,
+            app = app_name,
+            cmd = COMPLETE_COMMAND,
+        ),
+        CompletionShell::Fish => format!(
+            "function {func}\n    \
+                 set -l tokens (commandline -opc)\n    \
+                 set -l current (commandline -ct)\n    \
+                 {app} {cmd} fish -- $tokens \"$current\" 2>/dev/null\n\
+             end\n\
+             complete -c {app} -f -a '({func})'\n",
+            func = func,
+            app = app_name,
+            cmd = COMPLETE_COMMAND,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_uses_shell_line_format() {
+        let candidates = vec![
+            CompletionCandidate::new("web:1").with_description("running"),
+            CompletionCandidate::new("db"),
+        ];
+
+        assert_eq!(CompletionShell::Bash.render(&candidates), "web:1\ndb\n");
+        assert_eq!(CompletionShell::Zsh.render(&candidates), "web\\:1:running\ndb\n");
+        assert_eq!(CompletionShell::Fish.render(&candidates), "web:1\trunning\ndb\n");
+    }
+
+    #[test]
+    fn test_scripts_call_back_into_binary() {
+        for shell in [CompletionShell::Bash, CompletionShell::Zsh, CompletionShell::Fish] {
+            let script = dynamic_script(shell, "my-app");
+            assert!(
+                script.contains(&format!("my-app {} {} --", COMPLETE_COMMAND, shell))
+                    || script.contains(&format!("\"my-app\" {} {} --", COMPLETE_COMMAND, shell))
+            );
+            assert!(script.contains("__my_app_complete"));
+        }
+        assert!("powershell".parse::<CompletionShell>().is_err());
+    }
+}
@@ -182,18 +182,33 @@ impl CommandDiscovery {
     }
 
     /// Suggest commands for unknown input
+    ///
+    /// Close misspellings (by edit distance) come first, followed by
+    /// keyword search results.
     pub fn suggest(&self, unknown_command: &str) -> Vec<Suggestion> {
-        let search_results = self.search(unknown_command);
+        let typos =
+            did_you_mean(unknown_command, self.commands.iter().map(|cmd| cmd.name.as_str()))
+                .into_iter()
+                .map(|name| Suggestion {
+                    score: 100.0 - 10.0 * edit_distance(unknown_command, &name) as f32,
+                    command: name,
+                    reason: "Similar to your input (typo)".to_string(),
+                });
 
-        search_results
-            .into_iter()
-            .take(5) // Top 5 suggestions
-            .map(|result| Suggestion {
-                command: result.name,
-                reason: format!("Similar to your input ({})", result.match_type.description()),
-                score: result.score,
-            })
-            .collect()
+        let search_results = self.search(unknown_command).into_iter().map(|result| Suggestion {
+            command: result.name,
+            reason: format!("Similar to your input ({})", result.match_type.description()),
+            score: result.score,
+        });
+
+        let mut suggestions: Vec<Suggestion> = Vec::new();
+        for suggestion in typos.chain(search_results) {
+            if !suggestions.iter().any(|s| s.command == suggestion.command) {
+                suggestions.push(suggestion);
+            }
+        }
+        suggestions.truncate(5); // Top 5 suggestions
+        suggestions
     }
 
     /// Get all categories with command counts
@@ -275,6 +290,68 @@ impl MatchType {
     }
 }
 
+/// Optimal string alignment distance between two strings
+///
+/// Counts insertions, deletions, substitutions and transpositions of adjacent
+/// characters, so `stauts` is one edit away from `status`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best =
+                (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+/// Candidates close enough to `input` to be a likely typo, closest first
+///
+/// A candidate matches when its edit distance is at most a third of the
+/// input's length (minimum 1), or when it extends a prefix of at least three
+/// characters. Comparison is case-insensitive; ties sort alphabetically.
+pub fn did_you_mean<'a, I>(input: &str, candidates: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let input = input.to_lowercase();
+    let max_distance = (input.chars().count() / 3).max(1);
+
+    let mut matches: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter(|candidate| !candidate.is_empty())
+        .filter_map(|candidate| {
+            let lowered = candidate.to_lowercase();
+            if lowered == input {
+                return None;
+            }
+            let distance = edit_distance(&input, &lowered);
+            let is_prefix = input.chars().count() >= 3 && lowered.starts_with(&input);
+            (distance <= max_distance || is_prefix).then_some((distance, candidate))
+        })
+        .collect();
+
+    matches.sort_unstable();
+    matches.dedup_by(|a, b| a.1 == b.1);
+    matches.into_iter().map(|(_, candidate)| candidate.to_string()).collect()
+}
+
 /// Command suggestion
 #[derive(Debug, Clone, Serialize)]
 pub struct Suggestion {
@@ -445,6 +522,34 @@ mod tests {
         assert!(suggestions.iter().any(|s| s.command == "pack list"));
     }
 
+    #[test]
+    fn test_suggest_ranks_typos_first() {
+        let discovery = create_test_discovery();
+        let suggestions = discovery.suggest("pack lsit");
+
+        assert_eq!(suggestions.first().map(|s| s.command.as_str()), Some("pack list"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("list", "list"), 0);
+        assert_eq!(edit_distance("lst", "list"), 1);
+        assert_eq!(edit_distance("stauts", "status"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        let candidates = ["list", "lint", "create", "status"];
+
+        assert_eq!(did_you_mean("lst", candidates), vec!["list".to_string()]);
+        assert_eq!(did_you_mean("lit", candidates), vec!["lint".to_string(), "list".to_string()]);
+        assert_eq!(did_you_mean("cre", candidates), vec!["create".to_string()]);
+        assert!(did_you_mean("xyz", candidates).is_empty());
+        assert!(did_you_mean("list", candidates).iter().all(|c| c != "list"));
+    }
+
     #[test]
     fn test_categories_summary() {
         let discovery = create_test_discovery();
@@ -3,6 +3,7 @@
 //! This module provides working examples with expected output
 //! for the most-used commands to accelerate learning.
 
+use crate::cli::registry::{CommandRegistry, NOUN_PATH_SEPARATOR};
 use crate::error::Result;
 use serde::Serialize;
 
@@ -75,6 +76,51 @@ impl ExamplesRegistry {
         Self { examples: Vec::new() }
     }
 
+    /// Examples declared with `#[verb(example = "...")]` on registered verbs
+    ///
+    /// Each verb with examples yields one [`Example`]: the first command line,
+    /// with the others as variations. Examples are sorted by title and tagged
+    /// with the noun path segments and the verb name.
+    pub fn from_registry(registry: &CommandRegistry) -> Self {
+        let mut targets: Vec<(String, &str, &str)> = registry
+            .get_root_verbs_with_metadata()
+            .into_iter()
+            .map(|(verb, about, _)| (String::new(), verb, about))
+            .collect();
+        for noun in registry.get_all_noun_names() {
+            targets.extend(
+                registry
+                    .get_verbs_with_metadata(noun)
+                    .into_iter()
+                    .map(|(verb, about, _)| (noun.to_string(), verb, about)),
+            );
+        }
+
+        let mut examples = Vec::new();
+        for (noun, verb, about) in targets {
+            let Some((first, rest)) =
+                registry.verb_examples(&noun, verb).and_then(|examples| examples.split_first())
+            else {
+                continue;
+            };
+            let mut tags: Vec<String> = noun
+                .split(NOUN_PATH_SEPARATOR)
+                .filter(|segment| !segment.is_empty())
+                .map(String::from)
+                .collect();
+            tags.push(verb.to_string());
+            let title = tags.join(" ");
+            let example = rest
+                .iter()
+                .fold(Example::new(title, about, first.as_str(), ""), |example, command| {
+                    example.with_variation("", command.as_str())
+                });
+            examples.push(example.with_tags(tags));
+        }
+        examples.sort_by(|a, b| a.title.cmp(&b.title));
+        Self { examples }
+    }
+
     /// Register an example
     pub fn register(&mut self, example: Example) {
         self.examples.push(example);
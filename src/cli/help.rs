@@ -137,7 +137,7 @@ impl HelpSystem {
     /// Get top N most popular commands
     pub fn popular_commands(&self, n: usize) -> Vec<&CommandInfo> {
         let mut sorted = self.commands.iter().collect::<Vec<_>>();
-        sorted.sort_by(|a, b| b.popularity.cmp(&a.popularity));
+        sorted.sort_by_key(|c| std::cmp::Reverse(c.popularity));
         sorted.into_iter().take(n).collect()
     }
 
@@ -2,11 +2,17 @@
 //!
 //! This module provides a guided, menu-driven interface
 //! for common tasks to help new users get started quickly.
+//!
+//! [`InteractiveHelp`] is a fixed menu for ggen's commands. [`RegistryExplorer`]
+//! works for any app: it is built from the live [`CommandRegistry`], lets the
+//! user browse nouns, pick a verb and answer a prompt per argument, then shows
+//! the equivalent command line and runs it.
 
 use crate::cli::help::CommandCategory;
+use crate::cli::registry::{ArgMetadata, CommandRegistry, NOUN_PATH_SEPARATOR};
 use crate::error::Result;
 use serde::Serialize;
-use std::io::{self, Write};
+use std::io::{self, BufRead, Write};
 
 /// Interactive help menu
 pub struct InteractiveHelp {
@@ -339,6 +345,354 @@ pub fn generate_interactive_output() -> Result<InteractiveOutput> {
     help.display_menu()
 }
 
+/// Interactive explorer for the commands of a [`CommandRegistry`]
+///
+/// ```rust,no_run
+/// use clap_noun_verb::cli::interactive::RegistryExplorer;
+/// use clap_noun_verb::cli::registry::CommandRegistry;
+///
+/// let registry = CommandRegistry::get();
+/// let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+/// RegistryExplorer::new(&registry, "myapp").run()?;
+/// # Ok::<(), clap_noun_verb::NounVerbError>(())
+/// ```
+///
+/// Answers are read line by line: a number picks an entry, `b` goes back and
+/// `q` (or end of input) quits. Verbs run through
+/// [`CommandRegistry::call_tool`], so validation, policy, confirmation and
+/// receipts apply as for any other invocation.
+pub struct RegistryExplorer<'a> {
+    registry: &'a CommandRegistry,
+    app_name: String,
+}
+
+/// Entry of an explorer menu
+enum ExplorerEntry<'a> {
+    /// Noun path
+    Noun(&'a str),
+    /// Verb name and description
+    Verb(&'a str, &'a str),
+}
+
+impl<'a> RegistryExplorer<'a> {
+    /// Explorer over `registry`, showing command lines as `app_name ...`
+    pub fn new(registry: &'a CommandRegistry, app_name: impl Into<String>) -> Self {
+        Self { registry, app_name: app_name.into() }
+    }
+
+    /// Run the explorer on stdin and stdout
+    pub fn run(&self) -> Result<()> {
+        let stdin = io::stdin();
+        self.run_with(stdin.lock(), io::stdout())
+    }
+
+    /// Run the explorer reading answers from `input` and writing to `output`
+    pub fn run_with<R: BufRead, W: Write>(&self, mut input: R, mut output: W) -> Result<()> {
+        let mut session = ExplorerSession { input: &mut input, output: &mut output };
+        let mut noun_path = String::new();
+
+        loop {
+            let entries = self.entries(&noun_path);
+            session.line("")?;
+            session.line(&self.menu_title(&noun_path))?;
+            for (index, entry) in entries.iter().enumerate() {
+                let text = match entry {
+                    ExplorerEntry::Noun(path) => {
+                        format!("{}/  {}", leaf(path), self.noun_about(path))
+                    }
+                    ExplorerEntry::Verb(verb, about) => format!("{}  {}", verb, about),
+                };
+                session.line(&format!("  {}) {}", index + 1, text.trim_end()))?;
+            }
+            if !noun_path.is_empty() {
+                session.line("  b) back")?;
+            }
+            session.line("  q) quit")?;
+
+            let Some(answer) = session.ask("> ")? else {
+                return Ok(());
+            };
+            match answer.as_str() {
+                "q" => return Ok(()),
+                "b" if !noun_path.is_empty() => {
+                    noun_path = noun_path
+                        .rsplit_once(NOUN_PATH_SEPARATOR)
+                        .map_or("", |(parent, _)| parent)
+                        .into();
+                }
+                _ => {
+                    match answer.parse::<usize>().ok().and_then(|n| entries.get(n.checked_sub(1)?))
+                    {
+                        Some(ExplorerEntry::Noun(path)) => noun_path = path.to_string(),
+                        Some(ExplorerEntry::Verb(verb, _)) => {
+                            if !self.explore_verb(&mut session, &noun_path, verb)? {
+                                return Ok(());
+                            }
+                        }
+                        None => session.line(&format!("Unknown choice '{}'", answer))?,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sub-nouns, then verbs, of a noun path (`""` for the top level)
+    fn entries(&self, noun_path: &str) -> Vec<ExplorerEntry<'a>> {
+        let registry = self.registry;
+        let mut verbs = if noun_path.is_empty() {
+            registry.get_root_verbs_with_metadata()
+        } else {
+            registry.get_verbs_with_metadata(noun_path)
+        };
+        verbs.sort_by_key(|(verb, _, _)| *verb);
+        registry
+            .get_sub_nouns(noun_path)
+            .into_iter()
+            .map(ExplorerEntry::Noun)
+            .chain(verbs.into_iter().map(|(verb, about, _)| ExplorerEntry::Verb(verb, about)))
+            .collect()
+    }
+
+    fn menu_title(&self, noun_path: &str) -> String {
+        if noun_path.is_empty() {
+            format!("{} commands:", self.app_name)
+        } else {
+            format!("{} {}:", self.app_name, noun_path.replace(NOUN_PATH_SEPARATOR, " "))
+        }
+    }
+
+    fn noun_about(&self, noun_path: &str) -> &'a str {
+        self.registry
+            .get_nouns()
+            .into_iter()
+            .find(|(name, _)| *name == noun_path)
+            .map_or("", |(_, about)| about)
+    }
+
+    /// Prompt for a verb's arguments, then show and run the command
+    ///
+    /// Returns `false` when the input ended.
+    fn explore_verb<R: BufRead, W: Write>(
+        &self,
+        session: &mut ExplorerSession<'_, R, W>,
+        noun_path: &str,
+        verb: &str,
+    ) -> Result<bool> {
+        let registry = self.registry;
+        let noun = (!noun_path.is_empty()).then_some(noun_path);
+        let verbs = match noun {
+            Some(noun) => registry.get_verbs_with_metadata(noun),
+            None => registry.get_root_verbs_with_metadata(),
+        };
+        let Some((_, about, args)) = verbs.into_iter().find(|(name, _, _)| *name == verb) else {
+            return Ok(true);
+        };
+
+        session.line("")?;
+        session.line(&format!("{} {}", self.menu_title(noun_path).trim_end_matches(':'), verb))?;
+        if !about.is_empty() {
+            session.line(&format!("  {}", about))?;
+        }
+        let examples = registry.verb_examples(noun_path, verb).unwrap_or_default();
+        if !examples.is_empty() {
+            session.line("Examples:")?;
+            for example in examples {
+                session.line(&format!("  $ {}", example))?;
+            }
+        }
+
+        let mut arguments = serde_json::Map::new();
+        for arg_meta in args.iter().filter(|arg_meta| !arg_meta.hide) {
+            match self.prompt_argument(session, arg_meta)? {
+                Prompted::Value(value) => {
+                    arguments.insert(arg_meta.name.clone(), value);
+                }
+                Prompted::Skipped => {}
+                Prompted::Eof => return Ok(false),
+            }
+        }
+
+        let destructive = registry
+            .verb_safety(noun_path, verb)
+            .is_some_and(|safety| safety.effect.requires_confirmation());
+        if destructive && !arguments.contains_key("yes") {
+            match session.ask("This command is destructive. Continue? [y/N] ")? {
+                None => return Ok(false),
+                Some(answer) if is_yes(&answer) => {
+                    arguments.insert("yes".to_string(), serde_json::Value::Bool(true));
+                }
+                Some(_) => {
+                    session.line("Cancelled")?;
+                    return Ok(true);
+                }
+            }
+        }
+
+        let arguments = serde_json::Value::Object(arguments);
+        let tokens = registry.command_line(noun, verb, &arguments)?;
+        let command_line = std::iter::once(self.app_name.as_str())
+            .chain(tokens.iter().map(String::as_str))
+            .map(shell_quote)
+            .collect::<Vec<_>>()
+            .join(" ");
+        session.line(&format!("$ {}", command_line))?;
+
+        match session.ask("Run it? [Y/n] ")? {
+            None => return Ok(false),
+            Some(answer) if answer.is_empty() || is_yes(&answer) => {}
+            Some(_) => return Ok(true),
+        }
+        match registry.call_tool(&crate::mcp::tool_name(noun, verb), &arguments) {
+            Ok(output) => {
+                let rendered = serde_json::to_string_pretty(&output.data)
+                    .unwrap_or_else(|_| output.data.to_string());
+                session.line(&rendered)?;
+            }
+            Err(error) => session.line(&format!("error: {}", error))?,
+        }
+        Ok(true)
+    }
+
+    /// Ask for one argument until the answer is usable
+    fn prompt_argument<R: BufRead, W: Write>(
+        &self,
+        session: &mut ExplorerSession<'_, R, W>,
+        arg_meta: &ArgMetadata,
+    ) -> Result<Prompted> {
+        let is_count = matches!(arg_meta.action, Some(clap::ArgAction::Count));
+        let is_flag = !is_count
+            && (arg_meta.is_flag
+                || matches!(
+                    arg_meta.action,
+                    Some(clap::ArgAction::SetTrue) | Some(clap::ArgAction::SetFalse)
+                ));
+
+        let mut details = Vec::new();
+        if let Some(value_type) = &arg_meta.value_type {
+            details.push(value_type.clone());
+        }
+        if let Some(default) = &arg_meta.default_value {
+            details.push(format!("default: {}", default));
+        }
+        let possible_values = self.registry.possible_values(arg_meta);
+        if !possible_values.is_empty() {
+            details.push(format!("one of: {}", possible_values.join(", ")));
+        }
+        if arg_meta.multiple {
+            details.push("comma-separated".to_string());
+        }
+        let required = arg_meta.required && arg_meta.default_value.is_none();
+        if required {
+            details.push("required".to_string());
+        }
+        if let Some(help) = &arg_meta.help {
+            session.line(&format!("  {}", help))?;
+        }
+        let details =
+            if details.is_empty() { String::new() } else { format!(" ({})", details.join(", ")) };
+        let question = if is_flag {
+            format!("{}{} [y/N] ", arg_meta.name, details)
+        } else {
+            format!("{}{}: ", arg_meta.name, details)
+        };
+
+        loop {
+            let Some(answer) = session.ask(&question)? else {
+                return Ok(Prompted::Eof);
+            };
+            if answer.is_empty() {
+                if required && !is_flag {
+                    session.line(&format!("{} is required", arg_meta.name))?;
+                    continue;
+                }
+                return Ok(Prompted::Skipped);
+            }
+            if is_flag {
+                return Ok(if is_yes(&answer) {
+                    Prompted::Value(serde_json::Value::Bool(true))
+                } else {
+                    Prompted::Skipped
+                });
+            }
+            if is_count {
+                match answer.parse::<u64>() {
+                    Ok(count) => return Ok(Prompted::Value(count.into())),
+                    Err(_) => {
+                        session.line("Enter a number")?;
+                        continue;
+                    }
+                }
+            }
+            if arg_meta.multiple {
+                let values: Vec<serde_json::Value> = answer
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| value.to_string().into())
+                    .collect();
+                return Ok(Prompted::Value(values.into()));
+            }
+            return Ok(Prompted::Value(answer.into()));
+        }
+    }
+}
+
+/// Answer to an argument prompt
+enum Prompted {
+    Value(serde_json::Value),
+    Skipped,
+    Eof,
+}
+
+/// Line-based input and output of an explorer run
+struct ExplorerSession<'io, R, W> {
+    input: &'io mut R,
+    output: &'io mut W,
+}
+
+impl<R: BufRead, W: Write> ExplorerSession<'_, R, W> {
+    fn line(&mut self, text: &str) -> Result<()> {
+        writeln!(self.output, "{}", text).map_err(explorer_io_error)
+    }
+
+    /// Print `prompt` and read a trimmed answer, `None` at end of input
+    fn ask(&mut self, prompt: &str) -> Result<Option<String>> {
+        write!(self.output, "{}", prompt)
+            .and_then(|()| self.output.flush())
+            .map_err(explorer_io_error)?;
+        let mut answer = String::new();
+        let read = self.input.read_line(&mut answer).map_err(explorer_io_error)?;
+        Ok((read > 0).then(|| answer.trim().to_string()))
+    }
+}
+
+fn explorer_io_error(error: io::Error) -> crate::error::NounVerbError {
+    crate::error::NounVerbError::execution_error(format!(
+        "Interactive explorer I/O failed: {}",
+        error
+    ))
+}
+
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+/// Last segment of a noun path
+fn leaf(noun_path: &str) -> &str {
+    noun_path.rsplit(NOUN_PATH_SEPARATOR).next().unwrap_or(noun_path)
+}
+
+/// Quote a command-line token for POSIX shells when needed
+fn shell_quote(token: &str) -> String {
+    let plain = !token.is_empty()
+        && token.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,@%+".contains(c));
+    if plain {
+        token.to_string()
+    } else {
+        format!("'{}'", token.replace('\'', "'\\''"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -409,6 +763,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_shell_quote_only_quotes_when_needed() {
+        assert_eq!(shell_quote("--name=web-1"), "--name=web-1");
+        assert_eq!(shell_quote("two words"), "'two words'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
     #[test]
     fn test_menu_action_serialization() {
         let action = MenuAction::ShowExample("test".to_string());
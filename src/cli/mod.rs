@@ -8,9 +8,12 @@
 //! CLI code ONLY validates arguments and options, then delegates to
 //! business logic functions. No business logic is allowed in this layer.
 
+pub mod budget;
 pub mod builder;
+pub mod complete;
 pub mod registry;
 pub mod router;
+pub mod safety;
 pub mod validator;
 pub(crate) mod value_parser;
 
@@ -20,16 +23,19 @@ pub mod examples;
 pub mod help;
 pub mod interactive;
 
+pub use budget::{BudgetAction, VerbBudget};
 pub use builder::CliBuilder;
+pub use complete::{CompleteFn, CompletionCandidate, CompletionRequest, CompletionShell};
 pub use registry::CommandRegistry;
 pub use router::CommandRouter;
+pub use safety::{VerbEffect, VerbSafety};
 pub use validator::ArgValidator;
 
 // Re-export help system components
 pub use discovery::{CommandDiscovery, SearchResult};
 pub use examples::{Example, ExamplesRegistry};
 pub use help::{CommandCategory, CommandInfo, HelpSystem};
-pub use interactive::{InteractiveHelp, InteractiveOutput};
+pub use interactive::{InteractiveHelp, InteractiveOutput, RegistryExplorer};
 
 /// Auto-run CLI with all registered commands
 ///
@@ -45,3 +51,37 @@ pub fn run() -> crate::error::Result<()> {
     let args: Vec<String> = std::env::args().collect();
     registry.run(args)
 }
+
+/// Explore all registered commands interactively on stdin and stdout
+///
+/// See [`RegistryExplorer`]; `app_name` is shown in front of command lines.
+pub fn run_interactive(app_name: &str) -> crate::error::Result<()> {
+    let registry = registry::CommandRegistry::get();
+    let registry = registry.lock().map_err(|e| {
+        crate::error::NounVerbError::execution_error(format!("Failed to lock registry: {}", e))
+    })?;
+    RegistryExplorer::new(&registry, app_name).run()
+}
+
+/// Auto-run CLI and return a semantic process exit code
+///
+/// Like [`run`], but errors are printed to stderr and mapped to an exit code
+/// via [`NounVerbError::exit_code`](crate::error::NounVerbError::exit_code),
+/// so `main` can return it directly:
+///
+/// ```rust,no_run
+/// fn main() -> std::process::ExitCode {
+///     clap_noun_verb::run_with_exit_code()
+/// }
+/// ```
+pub fn run_with_exit_code() -> std::process::ExitCode {
+    let registry = registry::CommandRegistry::get();
+    let registry = match registry.lock() {
+        Ok(registry) => registry,
+        Err(e) => {
+            eprintln!("Error: Failed to lock registry: {}", e);
+            return crate::error::ExitCodeClass::GeneralError.into();
+        }
+    };
+    registry.run_to_exit_code(std::env::args().collect())
+}
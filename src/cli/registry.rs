@@ -52,9 +52,20 @@
 //! For library use cases or long-running services, alternatives should be
 //! investigated. For typical CLI applications, Box::leak is the idiomatic solution.
 
+use crate::cli::budget::{BudgetUsage, UsageMeter, VerbBudget};
+use crate::cli::complete::{
+    dynamic_script, CompletionCandidate, CompletionRequest, CompletionShell, COMPLETE_COMMAND,
+};
+use crate::cli::safety::{
+    prompt_confirmation, ConfirmPrompt, VerbSafety, CONFIRM_ARG_ID, DRY_RUN_ARG, DRY_RUN_FLAG,
+};
 use crate::cli::value_parser;
+use crate::envelope::{render_error_envelope, ErrorFormat, StructuredError};
 use crate::error::Result;
+use crate::format::OutputFormat;
 use crate::logic::{HandlerInput, HandlerOutput};
+use crate::middleware::{Middleware, MiddlewarePipeline, MiddlewareRequest, MiddlewareResponse};
+use crate::plugin::{ExternalCommand, ExternalPluginDiscovery};
 use linkme::distributed_slice;
 use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
@@ -160,23 +171,671 @@ pub static __NOUN_REGISTRY: [fn()] = [..];
 #[distributed_slice]
 pub static __VERB_REGISTRY: [fn()] = [..];
 
+/// Argument id of the framework-injected `--format`/`-o` option
+///
+/// The id is namespaced so it never clashes with a verb parameter named `format`.
+pub const FORMAT_ARG_ID: &str = "__cnv_format";
+
+/// Environment variable that selects the output format when `--format` is not given
+pub const FORMAT_ENV_VAR: &str = "CNV_FORMAT";
+
+/// Build the framework-injected `--format` option
+///
+/// `with_short` is false when the verb already claims `-o` for one of its own arguments.
+fn build_format_arg(with_short: bool) -> clap::Arg {
+    let mut arg = clap::Arg::new(FORMAT_ARG_ID)
+        .long("format")
+        .value_name("FORMAT")
+        .value_parser(clap::builder::PossibleValuesParser::new(OutputFormat::available_formats()))
+        .help("Output format [env: CNV_FORMAT] (json, json-pretty, yaml, table, plain, tsv)")
+        .hide_possible_values(true)
+        .display_order(usize::MAX);
+    if with_short {
+        arg = arg.short('o');
+    }
+    arg
+}
+
+/// Resolve the output format for an invocation
+///
+/// Precedence: `--format` on the verb, then `--format` before the noun, then
+/// the `CNV_FORMAT` environment variable, then compact JSON.
+/// `matches` must be ordered from most to least specific command level.
+pub fn resolve_output_format(matches: &[&clap::ArgMatches]) -> Result<OutputFormat> {
+    for level in matches {
+        if let Ok(Some(value)) = level.try_get_one::<String>(FORMAT_ARG_ID) {
+            return value.parse().map_err(crate::error::NounVerbError::argument_error);
+        }
+    }
+
+    match std::env::var(FORMAT_ENV_VAR) {
+        Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|e: String| {
+            crate::error::NounVerbError::argument_error(format!("{}: {}", FORMAT_ENV_VAR, e))
+        }),
+        _ => Ok(OutputFormat::Json),
+    }
+}
+
+/// Environment variable selecting how the runner reports errors (`text`, `json`, `auto`)
+pub const ERROR_FORMAT_ENV_VAR: &str = "CNV_ERROR_FORMAT";
+
+/// Whether an invocation explicitly asked for JSON output
+///
+/// Scans the raw arguments (so it also works when clap rejects them) for
+/// `--format json|json-pretty` / `-o json`, falling back to `CNV_FORMAT`.
+pub fn json_output_requested(args: &[String]) -> bool {
+    let is_json = |value: &str| {
+        matches!(value.parse::<OutputFormat>(), Ok(OutputFormat::Json | OutputFormat::JsonPretty))
+    };
+
+    let mut args = args.iter().skip(1).take_while(|arg| arg.as_str() != "--");
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "--format" | "-o" => args.next().map(String::as_str),
+            other => other.strip_prefix("--format=").or_else(|| other.strip_prefix("-o")),
+        };
+        if let Some(value) = value {
+            return is_json(value.trim_start_matches('='));
+        }
+    }
+
+    std::env::var(FORMAT_ENV_VAR).map(|value| is_json(value.trim())).unwrap_or(false)
+}
+
+/// Hidden entry points that print the command grammar as JSON
+///
+/// `app __grammar` and `app --grammar` write
+/// [`GrammarModel::from_registry`](crate::kernel::grammar::GrammarModel::from_registry)
+/// to stdout, so agents can discover the whole CLI surface in one call.
+#[cfg(feature = "kernel")]
+pub const GRAMMAR_COMMANDS: [&str; 2] = ["__grammar", "--grammar"];
+
+/// Whether an invocation asks for the grammar export
+#[cfg(feature = "kernel")]
+pub fn grammar_requested(args: &[String]) -> bool {
+    args.get(1).is_some_and(|arg| GRAMMAR_COMMANDS.contains(&arg.as_str()))
+}
+
+/// Leading flag recording an invocation: `app --record calls.jsonl <noun> <verb> ...`
+///
+/// See [`crate::kernel::recording`].
+#[cfg(feature = "kernel")]
+pub const RECORD_FLAG: &str = "--record";
+
+/// Flag after the recording file that also records stdin:
+/// `app --record calls.jsonl --record-stdin <noun> <verb> ...`
+///
+/// Without it the recorded command reads the caller's stdin directly and
+/// stdin is not recorded.
+#[cfg(feature = "kernel")]
+pub const RECORD_STDIN_FLAG: &str = "--record-stdin";
+
+/// Leading flag replaying a recording: `app --replay calls.jsonl`
+#[cfg(feature = "kernel")]
+pub const REPLAY_FLAG: &str = "--replay";
+
+/// Invocation to record, from `app --record <file> [--record-stdin] ...`
+#[cfg(feature = "kernel")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordRequest {
+    /// Recording file
+    pub recording: std::path::PathBuf,
+    /// Command line to run, with the program name and without the record flags
+    pub argv: Vec<String>,
+    /// Whether stdin is read and recorded ([`RECORD_STDIN_FLAG`])
+    pub record_stdin: bool,
+}
+
+/// Recording request of `app --record <file> [--record-stdin] ...`
+#[cfg(feature = "kernel")]
+pub fn record_requested(args: &[String]) -> Option<RecordRequest> {
+    let (recording, rest) = leading_file_flag(args, RECORD_FLAG)?;
+    let (record_stdin, rest) = match rest.split_first() {
+        Some((flag, rest)) if flag == RECORD_STDIN_FLAG => (true, rest),
+        _ => (false, rest),
+    };
+    let argv = args.iter().take(1).chain(rest).cloned().collect();
+    Some(RecordRequest { recording, argv, record_stdin })
+}
+
+/// Recording file of `app --replay <file>`
+#[cfg(feature = "kernel")]
+pub fn replay_requested(args: &[String]) -> Option<std::path::PathBuf> {
+    leading_file_flag(args, REPLAY_FLAG).map(|(recording, _)| recording)
+}
+
+/// File of a leading `--flag <file>` or `--flag=<file>`, and the arguments after it
+#[cfg(feature = "kernel")]
+fn leading_file_flag<'a>(
+    args: &'a [String],
+    flag: &str,
+) -> Option<(std::path::PathBuf, &'a [String])> {
+    let arg = args.get(1)?;
+    if arg == flag {
+        return Some((args.get(2)?.into(), args.get(3..).unwrap_or_default()));
+    }
+    let file = arg.strip_prefix(flag)?.strip_prefix('=')?;
+    Some((file.into(), args.get(2..).unwrap_or_default()))
+}
+
+/// Whether an invocation asks for dynamic completions (`__complete`)
+pub fn completion_requested(args: &[String]) -> bool {
+    args.get(1).is_some_and(|arg| arg == COMPLETE_COMMAND)
+}
+
+/// Argument of `cmd` named by a typed `--long`, alias or `-s` flag
+fn find_flag<'a>(cmd: &'a clap::Command, flag: &str) -> Option<&'a clap::Arg> {
+    if let Some(long) = flag.strip_prefix("--") {
+        return cmd.get_arguments().find(|arg| {
+            arg.get_long() == Some(long)
+                || arg.get_all_aliases().is_some_and(|aliases| aliases.contains(&long))
+        });
+    }
+    let mut shorts = flag.strip_prefix('-')?.chars();
+    let short = shorts.next().filter(|_| shorts.next().is_none())?;
+    cmd.get_arguments().find(|arg| arg.get_short() == Some(short))
+}
+
+/// Positional argument receiving the `n`th positional word, staying on a trailing vararg
+fn nth_positional(cmd: &clap::Command, n: usize) -> Option<&clap::Arg> {
+    let mut positionals: Vec<&clap::Arg> = cmd.get_positionals().collect();
+    positionals.sort_by_key(|arg| arg.get_index());
+    positionals.get(n).copied().or_else(|| {
+        positionals
+            .last()
+            .copied()
+            .filter(|arg| arg.get_num_args().is_some_and(|range| range.max_values() > 1))
+    })
+}
+
+/// Argument id of the global `--profile` option added with a layered configuration
+#[cfg(feature = "config-formats")]
+pub const PROFILE_ARG_ID: &str = "__cnv_profile";
+
+/// Noun of the built-in `config show` command
+#[cfg(feature = "config-formats")]
+pub const CONFIG_NOUN: &str = "config";
+
+/// Environment variable naming a policy file loaded when no engine is set
+#[cfg(feature = "autonomic")]
+pub const POLICY_FILE_ENV_VAR: &str = "CNV_POLICY_FILE";
+
+/// Environment variable with the tenant environment seen by policy rules
+#[cfg(feature = "autonomic")]
+pub const ENVIRONMENT_ENV_VAR: &str = "CNV_ENVIRONMENT";
+
+/// Environment variable with the ID of the invoking agent
+#[cfg(feature = "autonomic")]
+pub const AGENT_ID_ENV_VAR: &str = "CNV_AGENT_ID";
+
+/// Environment variable with the type of the invoking agent (default `agent`)
+#[cfg(feature = "autonomic")]
+pub const AGENT_TYPE_ENV_VAR: &str = "CNV_AGENT_TYPE";
+
+/// Environment variable with the tenant the invocation runs for
+#[cfg(feature = "autonomic")]
+pub const TENANT_ID_ENV_VAR: &str = "CNV_TENANT_ID";
+
+/// Environment variable with the correlation ID joining the invocation to its caller
+#[cfg(feature = "autonomic")]
+pub const CORRELATION_ID_ENV_VAR: &str = "CNV_CORRELATION_ID";
+
+/// Environment variable with the invocation's priority class
+#[cfg(feature = "autonomic")]
+pub const PRIORITY_ENV_VAR: &str = "CNV_PRIORITY";
+
+/// Environment variable with the JSON delegation chain the agent acts under
+#[cfg(feature = "autonomic")]
+pub const DELEGATION_ENV_VAR: &str = "CNV_DELEGATION";
+
+/// Argument ids of the global `--agent`, `--tenant`, `--correlation-id` and `--priority` options
+#[cfg(feature = "autonomic")]
+const INVOCATION_ARG_IDS: [&str; 4] =
+    ["__cnv_agent", "__cnv_tenant", "__cnv_correlation_id", "__cnv_priority"];
+
+/// Operation name of the span recorded around every verb invocation
+#[cfg(feature = "autonomic")]
+const INVOKE_SPAN: &str = "invoke";
+
+/// Redirects followed for one invocation before the policy is considered looping
+#[cfg(feature = "autonomic")]
+const MAX_POLICY_REDIRECTS: usize = 8;
+
+/// Noun of the built-in `receipts list/show/verify` commands
+#[cfg(feature = "autonomic")]
+pub const RECEIPTS_NOUN: &str = "receipts";
+
+/// Noun of the built-in `serve mcp` command
+pub const SERVE_NOUN: &str = "serve";
+
+/// Verb of the built-in `serve mcp` command
+pub const MCP_VERB: &str = "mcp";
+
+/// Argument id collecting everything after an external verb plugin's name
+const EXTERNAL_ARGS_ID: &str = "__cnv_plugin_args";
+
+/// Name of the value carrying an external plugin's arguments in policy
+/// requests, middleware and receipts
+const EXTERNAL_ARGS_VALUE: &str = "args";
+
+/// Environment variable enabling auto-correct of mistyped nouns, verbs and flags
+pub const AUTO_CORRECT_ENV_VAR: &str = "CNV_AUTO_CORRECT";
+
+/// Confirmation callback for auto-correct, given the typed name and its correction
+pub type AutoCorrectConfirm = Box<dyn Fn(&str, &str) -> bool + Send + Sync>;
+
+/// Ask on stderr whether to run the corrected command (interactive stdin only)
+fn prompt_correction(typed: &str, correction: &str) -> bool {
+    use std::io::Write;
+
+    if !atty::is(atty::Stream::Stdin) {
+        return false;
+    }
+    eprint!("'{}' is not a command. Did you mean '{}'? [y/N] ", typed, correction);
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// The `--dry-run` flag added to verbs declaring `dry_run` without such a parameter
+fn dry_run_arg() -> ArgMetadata {
+    ArgMetadata {
+        name: DRY_RUN_ARG.to_string(),
+        is_flag: true,
+        help: Some("Show what would change without changing anything".to_string()),
+        long: Some(DRY_RUN_FLAG.to_string()),
+        action: Some(clap::ArgAction::SetTrue),
+        value_type: Some("bool".to_string()),
+        ..plain_arg()
+    }
+}
+
+/// An optional string option with no other settings
+fn plain_arg() -> ArgMetadata {
+    ArgMetadata {
+        name: String::new(),
+        required: false,
+        is_flag: false,
+        help: None,
+        min_value: None,
+        max_value: None,
+        min_length: None,
+        max_length: None,
+        short: None,
+        long: None,
+        default_value: None,
+        env: None,
+        multiple: false,
+        value_name: None,
+        aliases: Vec::new(),
+        positional: None,
+        action: None,
+        group: None,
+        requires: Vec::new(),
+        conflicts_with: Vec::new(),
+        value_parser: None,
+        hide: false,
+        next_help_heading: None,
+        long_help: None,
+        next_line_help: false,
+        display_order: None,
+        exclusive: None,
+        trailing_vararg: false,
+        allow_negative_numbers: false,
+        value_hint: None,
+        global: false,
+        value_type: None,
+        complete: None,
+    }
+}
+
+/// A builder noun's dotted path, description and verbs
+type NounCommandEntry = (String, &'static str, Vec<std::sync::Arc<dyn crate::verb::VerbCommand>>);
+
+/// Flatten a builder noun and its sub-nouns into `entries`
+fn collect_noun_commands(
+    parent: Option<&str>,
+    noun: &dyn crate::noun::NounCommand,
+    entries: &mut Vec<NounCommandEntry>,
+) {
+    let path = match parent {
+        Some(parent) => format!("{}{}{}", parent, NOUN_PATH_SEPARATOR, noun.name()),
+        None => noun.name().to_string(),
+    };
+    let verbs = noun.verbs().into_iter().map(std::sync::Arc::from).collect();
+    let sub_nouns = noun.sub_nouns();
+    entries.push((path.clone(), noun.about(), verbs));
+    for sub_noun in &sub_nouns {
+        collect_noun_commands(Some(&path), sub_noun.as_ref(), entries);
+    }
+}
+
+/// Argument metadata for the clap arguments a builder verb declares
+fn builder_args(mut command: clap::Command) -> Vec<ArgMetadata> {
+    use clap::ArgAction;
+
+    command.build();
+    let text = |value: &std::ffi::OsStr| value.to_string_lossy().into_owned();
+    command
+        .get_arguments()
+        .filter(|arg| {
+            !matches!(
+                arg.get_action(),
+                ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version
+            )
+        })
+        .map(|arg| {
+            let action = arg.get_action().clone();
+            let is_flag = matches!(action, ArgAction::SetTrue | ArgAction::SetFalse);
+            let multiple = matches!(action, ArgAction::Append)
+                || arg.get_num_args().is_some_and(|range| range.max_values() > 1);
+            let positional = if arg.is_positional() { arg.get_index() } else { None };
+            let name = arg.get_id().to_string();
+            ArgMetadata {
+                required: arg.is_required_set(),
+                is_flag,
+                help: arg.get_help().map(ToString::to_string),
+                short: arg.get_short(),
+                long: arg.get_long().filter(|long| *long != name).map(String::from),
+                default_value: arg.get_default_values().first().map(|value| text(value)),
+                env: arg.get_env().map(text),
+                multiple,
+                value_name: arg
+                    .get_value_names()
+                    .and_then(|names| names.first())
+                    .map(ToString::to_string),
+                aliases: arg
+                    .get_all_aliases()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                trailing_vararg: arg.is_trailing_var_arg_set()
+                    || (positional.is_some() && multiple),
+                positional,
+                action: Some(action),
+                hide: arg.is_hide_set(),
+                next_help_heading: arg.get_help_heading().map(String::from),
+                long_help: arg.get_long_help().map(ToString::to_string),
+                allow_negative_numbers: arg.is_allow_negative_numbers_set(),
+                global: arg.is_global_set(),
+                value_type: is_flag.then(|| "bool".to_string()),
+                name,
+                ..plain_arg()
+            }
+        })
+        .collect()
+}
+
+/// Result of [`CommandRegistry::pre_dispatch`]
+enum PreDispatch {
+    /// A built-in entry point ran, with its exit code
+    Handled(Result<i32>),
+    /// The arguments name a verb to parse and dispatch
+    Dispatch(Vec<String>),
+}
+
+/// A mistyped noun, verb or flag located in the command line
+struct Typo {
+    /// Index of the offending argument
+    index: usize,
+    /// Name as typed (`lst`, `--idd`)
+    typed: String,
+    /// Value attached with `=`, kept when correcting a flag
+    suffix: String,
+    /// Close matches from the command tree, closest first
+    suggestions: Vec<String>,
+}
+
+impl Typo {
+    /// Locate the argument clap rejected and collect close matches for it
+    ///
+    /// Walks `args` through `root` the way clap does, so suggestions come from
+    /// the subcommands or flags valid at the level where parsing failed.
+    fn locate(root: &clap::Command, args: &[String], error: &clap::Error) -> Option<Self> {
+        use clap::error::{ContextKind, ContextValue, ErrorKind};
+
+        let invalid = error.context().find_map(|(kind, value)| match (kind, value) {
+            (ContextKind::InvalidSubcommand | ContextKind::InvalidArg, ContextValue::String(s)) => {
+                Some(s.as_str())
+            }
+            _ => None,
+        })?;
+        let find_long = |path: &[&clap::Command], name: &str| {
+            path.iter()
+                .flat_map(|cmd| cmd.get_arguments())
+                .find(|arg| arg.get_long() == Some(name))
+                .cloned()
+        };
+
+        let mut path: Vec<&clap::Command> = vec![root];
+        let mut tokens = args.iter().enumerate().skip(1);
+        while let Some((index, token)) = tokens.next() {
+            let cmd = *path.last()?;
+            if token == "--" {
+                break;
+            }
+
+            if let Some(flag) = token.strip_prefix("--") {
+                let (name, suffix) = flag.split_at(flag.find('=').unwrap_or(flag.len()));
+                if let Some(arg) = find_long(&path, name) {
+                    if arg.get_action().takes_values() && suffix.is_empty() {
+                        tokens.next();
+                    }
+                } else if error.kind() == ErrorKind::UnknownArgument
+                    && invalid.trim_start_matches('-') == name
+                {
+                    let longs: Vec<&str> = path
+                        .iter()
+                        .flat_map(|cmd| cmd.get_arguments())
+                        .filter(|arg| !arg.is_hide_set())
+                        .filter_map(|arg| arg.get_long())
+                        .collect();
+                    return Some(Self {
+                        index,
+                        typed: format!("--{}", name),
+                        suffix: suffix.to_string(),
+                        suggestions: crate::cli::discovery::did_you_mean(name, longs)
+                            .into_iter()
+                            .map(|long| format!("--{}", long))
+                            .collect(),
+                    });
+                }
+                continue;
+            }
+
+            if let Some(short) = token.strip_prefix('-').filter(|s| !s.is_empty()) {
+                let single = short.chars().next().filter(|_| short.chars().count() == 1);
+                let takes_value = single.is_some_and(|c| {
+                    path.iter()
+                        .flat_map(|cmd| cmd.get_arguments())
+                        .any(|arg| arg.get_short() == Some(c) && arg.get_action().takes_values())
+                });
+                if takes_value {
+                    tokens.next();
+                }
+                continue;
+            }
+
+            if let Some(sub) = cmd.find_subcommand(token) {
+                path.push(sub);
+            } else if error.kind() == ErrorKind::InvalidSubcommand
+                && cmd.has_subcommands()
+                && token == invalid
+            {
+                let names: Vec<&str> = cmd
+                    .get_subcommands()
+                    .filter(|sub| !sub.is_hide_set())
+                    .flat_map(|sub| {
+                        std::iter::once(sub.get_name()).chain(sub.get_visible_aliases())
+                    })
+                    .collect();
+                return Some(Self {
+                    index,
+                    typed: token.clone(),
+                    suffix: String::new(),
+                    suggestions: crate::cli::discovery::did_you_mean(token, names),
+                });
+            }
+        }
+        None
+    }
+
+    /// The only close match, if exactly one exists
+    fn unique_match(&self) -> Option<&str> {
+        match self.suggestions.as_slice() {
+            [only] => Some(only.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Replace clap's suggestions in `error` with ours
+    fn annotate(&self, error: &mut clap::Error) {
+        use clap::error::{ContextKind, ContextValue};
+
+        if self.suggestions.is_empty() {
+            return;
+        }
+        let kind = if self.typed.starts_with("--") {
+            ContextKind::SuggestedArg
+        } else {
+            ContextKind::SuggestedSubcommand
+        };
+        error.insert(kind, ContextValue::Strings(self.suggestions.clone()));
+    }
+}
+
+/// Separator between segments of a nested noun path (e.g. `cloud.vm.snapshot`)
+pub const NOUN_PATH_SEPARATOR: char = '.';
+
+/// Iterate over the proper ancestors of a noun path, outermost first
+///
+/// `cloud.vm.snapshot` yields `cloud` then `cloud.vm`.
+fn noun_ancestors(path: &str) -> impl Iterator<Item = &str> {
+    path.match_indices(NOUN_PATH_SEPARATOR).map(move |(idx, _)| &path[..idx])
+}
+
+/// Parent path of a nested noun, or `None` for a top-level noun
+fn parent_noun_path(path: &str) -> Option<&str> {
+    path.rfind(NOUN_PATH_SEPARATOR).map(|idx| &path[..idx])
+}
+
+/// Command as typed by the user: `cloud vm start` for verb `start` of noun `cloud.vm`
+fn command_display(noun_path: Option<&str>, verb_name: &str) -> String {
+    match noun_path {
+        Some(noun) => format!("{} {}", noun.replace(NOUN_PATH_SEPARATOR, " "), verb_name),
+        None => verb_name.to_string(),
+    }
+}
+
+/// Leak `value` once and return the shared `'static` copy on later calls
+///
+/// Used for plugin names and descriptions, which are added to the clap tree
+/// on every build; interning keeps repeated builds from leaking them again.
+fn intern_static(value: &str) -> &'static str {
+    static INTERNED: OnceLock<Mutex<std::collections::HashSet<&'static str>>> = OnceLock::new();
+    let mut interned =
+        INTERNED.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(existing) = interned.get(value) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(value.to_string().into_boxed_str());
+    interned.insert(leaked);
+    leaked
+}
+
+/// The built-in `receipts list/show/verify` commands
+#[cfg(feature = "autonomic")]
+fn receipts_command() -> clap::Command {
+    clap::Command::new(RECEIPTS_NOUN)
+        .about("Inspect the execution receipt log")
+        .subcommand(
+            clap::Command::new("list").about("List recorded invocations").arg(
+                clap::Arg::new("limit")
+                    .long("limit")
+                    .value_name("N")
+                    .value_parser(clap::value_parser!(usize))
+                    .help("Only list the most recent N receipts"),
+            ),
+        )
+        .subcommand(
+            clap::Command::new("show").about("Show one receipt in full").arg(
+                clap::Arg::new("seq")
+                    .value_name("SEQ")
+                    .required(true)
+                    .value_parser(clap::value_parser!(u64))
+                    .help("Sequence number of the receipt"),
+            ),
+        )
+        .subcommand(
+            clap::Command::new("verify").about("Check the log for edited or missing receipts"),
+        )
+}
+
+/// The global `--agent`, `--tenant`, `--correlation-id` and `--priority` options
+#[cfg(feature = "autonomic")]
+fn invocation_args() -> [clap::Arg; 4] {
+    let [agent, tenant, correlation_id, priority] = INVOCATION_ARG_IDS;
+    let arg = |id: &'static str, long: &'static str, value_name: &'static str| {
+        clap::Arg::new(id).long(long).value_name(value_name).global(true).display_order(usize::MAX)
+    };
+    [
+        arg(agent, "agent", "ID").help("ID of the invoking agent [env: CNV_AGENT_ID]"),
+        arg(tenant, "tenant", "ID").help("Tenant the command runs for [env: CNV_TENANT_ID]"),
+        arg(correlation_id, "correlation-id", "ID")
+            .help("Correlation ID joining the invocation to its caller [env: CNV_CORRELATION_ID]"),
+        arg(priority, "priority", "CLASS")
+            .help("Priority class: best_effort, normal, high, critical [env: CNV_PRIORITY]"),
+    ]
+}
+
 /// Global registry for registered commands
 static REGISTRY: OnceLock<Mutex<CommandRegistry>> = OnceLock::new();
 
 /// Command registry for attribute macro discovered functions
 pub struct CommandRegistry {
-    /// Registered nouns (name -> noun metadata)
+    /// Registered nouns (dotted path -> noun metadata), including implicit parents
     nouns: HashMap<String, NounMetadata>,
     /// Registered verbs (noun_name -> verb_name -> verb metadata)
     verbs: HashMap<String, HashMap<String, VerbMetadata>>,
     /// Root-level verbs (verb_name -> verb metadata) - verbs without a noun
     root_verbs: HashMap<String, VerbMetadata>,
+    /// How `run_to_exit_code` reports errors
+    error_format: ErrorFormat,
+    /// Confirmation for running the unique close match of a mistyped command
+    auto_correct: Option<AutoCorrectConfirm>,
+    /// Confirmation for running destructive verbs, replacing the stdin prompt
+    confirm: Option<ConfirmPrompt>,
+    /// Policy evaluated before every verb
+    #[cfg(feature = "autonomic")]
+    policy: Option<Box<dyn crate::autonomic::PolicyEngine>>,
+    /// Log receiving an execution receipt for every verb invocation
+    #[cfg(feature = "autonomic")]
+    receipts: Option<crate::autonomic::ReceiptLog>,
+    /// Whether the global invocation identity options are added
+    #[cfg(feature = "autonomic")]
+    invocation_flags: bool,
+    /// Keys whose signatures delegation chains must carry
+    #[cfg(all(feature = "autonomic", feature = "signing"))]
+    trust_store: Option<crate::autonomic::TrustStore>,
+    /// Middleware run around every verb handler
+    middleware: MiddlewarePipeline,
+    /// External `<app>-<noun>[-<verb>]` executables merged into the command tree
+    external: Vec<ExternalCommand>,
+    /// Application name passed to external plugins
+    external_app_name: String,
+    /// Whether the built-in `serve mcp` command is available
+    serve_mcp: bool,
+    /// Layered configuration supplying argument values
+    #[cfg(feature = "config-formats")]
+    config: Option<crate::config::LayeredConfig>,
+    /// Clap commands of verbs called as MCP tools, keyed by noun path and verb
+    tool_commands: Mutex<HashMap<(String, String), clap::Command>>,
 }
 
 /// Metadata for a registered noun
 struct NounMetadata {
-    #[allow(dead_code)] // Reserved for future use
-    name: String,
     about: String,
     long_about: Option<String>,
 }
@@ -194,6 +853,8 @@ pub struct ArgMetadata {
     pub max_length: Option<usize>,
     /// Short flag character (e.g., 'v' for -v)
     pub short: Option<char>,
+    /// Long flag when it differs from the argument name (e.g., "dry-run")
+    pub long: Option<String>,
     /// Default value as string (will be parsed by clap)
     pub default_value: Option<String>,
     /// Environment variable name
@@ -236,20 +897,103 @@ pub struct ArgMetadata {
     pub value_hint: Option<String>,
     /// Global flag - propagates to subcommands
     pub global: bool,
+    /// Rust type of the value as written in the verb signature (e.g. `u16`, `PathBuf`)
+    pub value_type: Option<String>,
+    /// Runtime completion for this argument's values (`#[arg(complete = fn_name)]`)
+    pub complete: Option<crate::cli::complete::CompleteFn>,
 }
 
+/// A registered verb as `(noun path, verb, metadata)`, `None` noun for root verbs
+type VerbTarget<'a> = (Option<&'a str>, &'a str, &'a VerbMetadata);
+
 /// Metadata for a registered verb
 struct VerbMetadata {
-    #[allow(dead_code)] // Reserved for future use
     noun_name: String,
-    #[allow(dead_code)] // Reserved for future use
     verb_name: String,
     about: String,
     args: Vec<ArgMetadata>,
-    handler_fn: Box<dyn Fn(HandlerInput) -> Result<HandlerOutput> + Send + Sync>,
+    /// Declared side effects and dry-run support
+    safety: VerbSafety,
+    /// Latency, CPU and memory limits enforced around the handler
+    budget: VerbBudget,
+    /// Example command lines from `#[verb(example = "...")]`
+    examples: Vec<String>,
+    handler: VerbHandler,
+}
+
+/// How a registered verb runs
+enum VerbHandler {
+    /// `#[verb]` functions and agent commands, called with the extracted input
+    Function(Box<dyn Fn(HandlerInput) -> Result<HandlerOutput> + Send + Sync>),
+    /// A builder [`VerbCommand`](crate::verb::VerbCommand), run with its parsed
+    /// matches; it declares its own arguments and prints its own output
+    Command(std::sync::Arc<dyn crate::verb::VerbCommand>),
+}
+
+impl VerbHandler {
+    /// Run the handler for `noun_path` `verb_name`
+    fn call(
+        &self,
+        noun_path: Option<&str>,
+        verb_name: &str,
+        input: HandlerInput,
+        verb_matches: Option<&clap::ArgMatches>,
+    ) -> Result<HandlerOutput> {
+        match self {
+            VerbHandler::Function(handler) => handler(input),
+            VerbHandler::Command(command) => {
+                let verb_matches = verb_matches.ok_or_else(|| {
+                    crate::error::NounVerbError::execution_error(format!(
+                        "'{}' is a builder command and runs from parsed arguments",
+                        command_display(noun_path, verb_name)
+                    ))
+                })?;
+                let mut context = crate::verb::VerbContext::new(verb_name);
+                if let Some(noun_path) = noun_path {
+                    let noun = noun_path.rsplit(NOUN_PATH_SEPARATOR).next().unwrap_or(noun_path);
+                    context = context.with_noun(noun);
+                }
+                command
+                    .run(&crate::verb::VerbArgs::new(verb_matches.clone()).with_context(context))?;
+                Ok(HandlerOutput { data: serde_json::Value::Null, message: None })
+            }
+        }
+    }
+
+    /// Whether the handler prints its own output instead of returning it
+    fn prints_output(&self) -> bool {
+        matches!(self, VerbHandler::Command(_))
+    }
 }
 
 impl CommandRegistry {
+    /// Registry with no commands and default settings
+    fn empty() -> Self {
+        CommandRegistry {
+            nouns: HashMap::new(),
+            verbs: HashMap::new(),
+            root_verbs: HashMap::new(),
+            error_format: ErrorFormat::default(),
+            auto_correct: None,
+            confirm: None,
+            #[cfg(feature = "autonomic")]
+            policy: None,
+            #[cfg(feature = "autonomic")]
+            receipts: None,
+            #[cfg(feature = "autonomic")]
+            invocation_flags: false,
+            #[cfg(all(feature = "autonomic", feature = "signing"))]
+            trust_store: None,
+            middleware: MiddlewarePipeline::new(),
+            external: Vec::new(),
+            external_app_name: "cli".to_string(),
+            serve_mcp: false,
+            #[cfg(feature = "config-formats")]
+            config: None,
+            tool_commands: Mutex::new(HashMap::new()),
+        }
+    }
+
     /// Initialize the registry (called once during first access)
     pub fn init() -> &'static Mutex<CommandRegistry> {
         // Use get_or_init to create and store the registry
@@ -259,11 +1003,7 @@ impl CommandRegistry {
         // until initialization completes, so we need a different approach
         let registry = REGISTRY.get_or_init(|| {
             // Create empty registry
-            Mutex::new(CommandRegistry {
-                nouns: HashMap::new(),
-                verbs: HashMap::new(),
-                root_verbs: HashMap::new(),
-            })
+            Mutex::new(CommandRegistry::empty())
         });
 
         // After registry is stored, run registration functions
@@ -285,23 +1025,35 @@ impl CommandRegistry {
     }
 
     /// Register a noun (called by macro-generated code)
+    ///
+    /// `name` may be a dotted path such as `cloud.vm.snapshot`; missing parent
+    /// nouns (`cloud`, `cloud.vm`) are registered implicitly with an empty about.
     pub fn register_noun(name: &'static str, about: &'static str) {
         // Get the registry - this will initialize it if needed
         // During initialization, this will wait until init() completes
-        let registry = REGISTRY.get_or_init(|| {
-            Mutex::new(CommandRegistry {
-                nouns: HashMap::new(),
-                verbs: HashMap::new(),
-                root_verbs: HashMap::new(),
-            })
-        });
+        let registry = REGISTRY.get_or_init(|| Mutex::new(CommandRegistry::empty()));
         // Lock poisoning should not happen in practice, but handle it gracefully
         let mut reg = registry.lock().unwrap_or_else(|e| e.into_inner());
-        // Only insert if noun doesn't already exist — first registration wins.
-        // This prevents later verbs from overwriting the noun description.
-        reg.nouns.entry(name.to_string()).or_insert_with(|| {
-            NounMetadata { name: name.to_string(), about: about.to_string(), long_about: None }
-        });
+        reg.insert_noun(name, about);
+    }
+
+    /// Add a noun and its implicit parents
+    fn insert_noun(&mut self, name: &str, about: &str) {
+        for parent in noun_ancestors(name) {
+            self.nouns
+                .entry(parent.to_string())
+                .or_insert_with(|| NounMetadata { about: String::new(), long_about: None });
+        }
+        // First non-empty description wins. This prevents later verbs from
+        // overwriting the noun description, while still letting a described
+        // registration fill in an implicitly created parent.
+        let meta = self
+            .nouns
+            .entry(name.to_string())
+            .or_insert_with(|| NounMetadata { about: String::new(), long_about: None });
+        if meta.about.is_empty() {
+            meta.about = about.to_string();
+        }
     }
 
     /// Register a verb (called by macro-generated code)
@@ -331,35 +1083,272 @@ impl CommandRegistry {
     {
         // Get the registry - this will initialize it if needed
         // During initialization, this will wait until init() completes
-        let registry = REGISTRY.get_or_init(|| {
-            Mutex::new(CommandRegistry {
-                nouns: HashMap::new(),
-                verbs: HashMap::new(),
-                root_verbs: HashMap::new(),
-            })
-        });
+        let registry = REGISTRY.get_or_init(|| Mutex::new(CommandRegistry::empty()));
         // Lock poisoning should not happen in practice, but handle it gracefully
         let mut reg = registry.lock().unwrap_or_else(|e| e.into_inner());
 
+        reg.insert_verb(
+            noun_name,
+            verb_name,
+            about,
+            args,
+            VerbHandler::Function(Box::new(handler)),
+        );
+    }
+
+    /// Add a verb, as a root verb when `noun_name` is empty
+    fn insert_verb(
+        &mut self,
+        noun_name: &str,
+        verb_name: &str,
+        about: &str,
+        args: Vec<ArgMetadata>,
+        handler: VerbHandler,
+    ) {
         let verb_metadata = VerbMetadata {
             noun_name: noun_name.to_string(),
             verb_name: verb_name.to_string(),
             about: about.to_string(),
             args,
-            handler_fn: Box::new(handler),
+            safety: VerbSafety::default(),
+            budget: VerbBudget::default(),
+            examples: Vec::new(),
+            handler,
         };
 
+        self.clear_tool_commands();
         // If noun_name is empty, register as root verb
         if noun_name.is_empty() {
-            reg.root_verbs.insert(verb_name.to_string(), verb_metadata);
+            self.root_verbs.insert(verb_name.to_string(), verb_metadata);
         } else {
-            reg.verbs
+            self.verbs
                 .entry(noun_name.to_string())
                 .or_default()
                 .insert(verb_name.to_string(), verb_metadata);
         }
     }
 
+    /// Drop the cached tool commands after verbs or their arguments change
+    fn clear_tool_commands(&mut self) {
+        self.tool_commands.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Whether a verb exists at `noun_path` (`""` for root verbs)
+    fn has_verb(&self, noun_path: &str, verb_name: &str) -> bool {
+        if noun_path.is_empty() {
+            self.root_verbs.contains_key(verb_name)
+        } else {
+            self.verbs.get(noun_path).is_some_and(|verbs| verbs.contains_key(verb_name))
+        }
+    }
+
+    /// Add a noun defined with the builder API to the command tree
+    ///
+    /// The noun, its verbs and its sub-nouns (as `noun.sub` paths) join the
+    /// `#[verb]` functions: they share help, the `--format` option, grammar,
+    /// completions, MCP tools, policy and receipts. Builder verbs keep the clap
+    /// arguments they declare and print their own output, so their tool calls
+    /// return `null` data.
+    ///
+    /// Fails without changing the tree when a verb already exists at the same path.
+    pub fn add_noun_command(&mut self, noun: Box<dyn crate::noun::NounCommand>) -> Result<()> {
+        let mut nouns = Vec::new();
+        collect_noun_commands(None, noun.as_ref(), &mut nouns);
+        for (path, _, verbs) in &nouns {
+            if let Some(verb) = verbs.iter().find(|verb| self.has_verb(path, verb.name())) {
+                return Err(crate::error::NounVerbError::invalid_structure(format!(
+                    "Verb '{}' is already registered",
+                    command_display(Some(path), verb.name())
+                )));
+            }
+        }
+
+        for (path, about, verbs) in nouns {
+            self.insert_noun(&path, about);
+            for verb in verbs {
+                let args = builder_args(verb.build_command());
+                self.insert_verb(
+                    &path,
+                    verb.name(),
+                    verb.about(),
+                    args,
+                    VerbHandler::Command(verb),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Add every noun of a builder [`CommandRegistry`](crate::registry::CommandRegistry)
+    ///
+    /// See [`add_noun_command`](Self::add_noun_command).
+    pub fn add_registry(&mut self, registry: crate::registry::CommandRegistry) -> Result<()> {
+        for noun in registry.into_nouns() {
+            self.add_noun_command(noun)?;
+        }
+        Ok(())
+    }
+
+    /// Add the commands of an [`AgentCli`](crate::agent_cli::AgentCli) under `noun_path`
+    ///
+    /// With an empty `noun_path` the commands become root verbs. Declared
+    /// arguments become positionals in order; handlers receive them by name
+    /// and their JSON result is the verb's output.
+    ///
+    /// Fails without changing the tree when a verb already exists at the same path.
+    pub fn add_agent_cli(
+        &mut self,
+        noun_path: &str,
+        cli: &crate::agent_cli::AgentCli,
+    ) -> Result<()> {
+        let commands = cli.command_handlers();
+        if let Some((metadata, _)) =
+            commands.iter().find(|(metadata, _)| self.has_verb(noun_path, &metadata.name))
+        {
+            let noun = if noun_path.is_empty() { None } else { Some(noun_path) };
+            return Err(crate::error::NounVerbError::invalid_structure(format!(
+                "Verb '{}' is already registered",
+                command_display(noun, &metadata.name)
+            )));
+        }
+
+        if !noun_path.is_empty() {
+            self.insert_noun(noun_path, cli.description());
+        }
+        for (metadata, handler) in commands {
+            // Commands registered by name only take their arguments from the handler
+            let specs = if metadata.arguments.is_empty() {
+                handler.metadata().arguments
+            } else {
+                metadata.arguments
+            };
+            let args = specs
+                .iter()
+                .enumerate()
+                .map(|(index, spec)| ArgMetadata {
+                    name: spec.name.clone(),
+                    required: spec.required,
+                    help: Some(spec.description.clone()),
+                    default_value: spec.default.clone(),
+                    // clap positional indices start at 1
+                    positional: Some(index + 1),
+                    ..plain_arg()
+                })
+                .collect();
+            let run = move |input: HandlerInput| {
+                let mut args = crate::agent_cli::CommandArgs::new();
+                for spec in &specs {
+                    if let Some(value) = input.args.get(&spec.name) {
+                        args = args.with_arg(&spec.name, value).with_positional(value);
+                    }
+                }
+                let data = handler
+                    .execute(&args)
+                    .map_err(|e| crate::error::NounVerbError::execution_error(e.to_string()))?;
+                Ok(HandlerOutput { data, message: None })
+            };
+            self.insert_verb(
+                noun_path,
+                &metadata.name,
+                &metadata.description,
+                args,
+                VerbHandler::Function(Box::new(run)),
+            );
+        }
+        Ok(())
+    }
+
+    /// Declare a verb's side effects (called by macro-generated code)
+    ///
+    /// Verbs supporting dry runs get a `--dry-run` flag; a `dry_run` parameter
+    /// of the handler becomes that flag instead of `--dry_run`.
+    pub fn set_verb_safety(noun_name: &'static str, verb_name: &'static str, safety: VerbSafety) {
+        let registry = REGISTRY.get_or_init(|| Mutex::new(CommandRegistry::empty()));
+        // Lock poisoning should not happen in practice, but handle it gracefully
+        let mut reg = registry.lock().unwrap_or_else(|e| e.into_inner());
+        reg.clear_tool_commands();
+
+        let verb_meta = if noun_name.is_empty() {
+            reg.root_verbs.get_mut(verb_name)
+        } else {
+            reg.verbs.get_mut(noun_name).and_then(|verbs| verbs.get_mut(verb_name))
+        };
+        let Some(verb_meta) = verb_meta else {
+            return;
+        };
+        verb_meta.safety = safety;
+        if safety.dry_run {
+            match verb_meta.args.iter_mut().find(|arg| arg.name == DRY_RUN_ARG) {
+                Some(arg) => {
+                    arg.long.get_or_insert_with(|| DRY_RUN_FLAG.to_string());
+                }
+                None => verb_meta.args.push(dry_run_arg()),
+            }
+        }
+    }
+
+    /// Declared side effects of a verb (`""` noun path for root verbs)
+    pub fn verb_safety(&self, noun_path: &str, verb_name: &str) -> Option<VerbSafety> {
+        let verb_meta = if noun_path.is_empty() {
+            self.root_verbs.get(verb_name)
+        } else {
+            self.verbs.get(noun_path).and_then(|verbs| verbs.get(verb_name))
+        };
+        verb_meta.map(|verb_meta| verb_meta.safety)
+    }
+
+    /// Declare a verb's resource budget (called by macro-generated code)
+    pub fn set_verb_budget(noun_name: &'static str, verb_name: &'static str, budget: VerbBudget) {
+        let registry = REGISTRY.get_or_init(|| Mutex::new(CommandRegistry::empty()));
+        // Lock poisoning should not happen in practice, but handle it gracefully
+        let mut reg = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+        let verb_meta = if noun_name.is_empty() {
+            reg.root_verbs.get_mut(verb_name)
+        } else {
+            reg.verbs.get_mut(noun_name).and_then(|verbs| verbs.get_mut(verb_name))
+        };
+        if let Some(verb_meta) = verb_meta {
+            verb_meta.budget = budget;
+        }
+    }
+
+    /// Declared resource budget of a verb (`""` noun path for root verbs)
+    pub fn verb_budget(&self, noun_path: &str, verb_name: &str) -> Option<VerbBudget> {
+        let verb_meta = if noun_path.is_empty() {
+            self.root_verbs.get(verb_name)
+        } else {
+            self.verbs.get(noun_path).and_then(|verbs| verbs.get(verb_name))
+        };
+        verb_meta.map(|verb_meta| verb_meta.budget)
+    }
+
+    /// Record example command lines for a verb (called by macro-generated code)
+    pub fn set_verb_examples(noun_name: &'static str, verb_name: &'static str, examples: &[&str]) {
+        let registry = REGISTRY.get_or_init(|| Mutex::new(CommandRegistry::empty()));
+        // Lock poisoning should not happen in practice, but handle it gracefully
+        let mut reg = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+        let verb_meta = if noun_name.is_empty() {
+            reg.root_verbs.get_mut(verb_name)
+        } else {
+            reg.verbs.get_mut(noun_name).and_then(|verbs| verbs.get_mut(verb_name))
+        };
+        if let Some(verb_meta) = verb_meta {
+            verb_meta.examples = examples.iter().map(|example| example.to_string()).collect();
+        }
+    }
+
+    /// Example command lines of a verb (`""` noun path for root verbs)
+    pub fn verb_examples(&self, noun_path: &str, verb_name: &str) -> Option<&[String]> {
+        let verb_meta = if noun_path.is_empty() {
+            self.root_verbs.get(verb_name)
+        } else {
+            self.verbs.get(noun_path).and_then(|verbs| verbs.get(verb_name))
+        };
+        verb_meta.map(|verb_meta| verb_meta.examples.as_slice())
+    }
+
     /// Get all registered nouns
     pub fn get_nouns(&self) -> Vec<(&str, &str)> {
         self.nouns.iter().map(|(name, meta)| (name.as_str(), meta.about.as_str())).collect()
@@ -388,12 +1377,39 @@ impl CommandRegistry {
             .unwrap_or_default()
     }
 
+    /// Get all root verbs (verbs without a noun) with their full metadata
+    pub fn get_root_verbs_with_metadata(&self) -> Vec<(&str, &str, &Vec<ArgMetadata>)> {
+        self.root_verbs
+            .iter()
+            .map(|(name, meta)| (name.as_str(), meta.about.as_str(), &meta.args))
+            .collect()
+    }
+
     /// Get all registered noun names
     pub fn get_all_noun_names(&self) -> Vec<&str> {
         self.nouns.keys().map(|n| n.as_str()).collect()
     }
 
+    /// Get the full paths of the nouns nested directly under `noun_path`
+    ///
+    /// Pass `""` to get the top-level nouns.
+    pub fn get_sub_nouns(&self, noun_path: &str) -> Vec<&str> {
+        let parent = if noun_path.is_empty() { None } else { Some(noun_path) };
+        let mut children: Vec<&str> = self
+            .nouns
+            .keys()
+            .map(|n| n.as_str())
+            .filter(|n| parent_noun_path(n) == parent)
+            .collect();
+        children.sort_unstable();
+        children
+    }
+
     /// Execute a verb handler
+    ///
+    /// Builder commands added with [`add_noun_command`](Self::add_noun_command)
+    /// need parsed arguments and fail here; they run through [`run`](Self::run)
+    /// and [`call_tool`](Self::call_tool).
     pub fn execute_verb(
         &self,
         noun_name: &str,
@@ -409,35 +1425,95 @@ impl CommandRegistry {
             .get(verb_name)
             .ok_or_else(|| crate::error::NounVerbError::verb_not_found(noun_name, verb_name))?;
 
-        (verb.handler_fn)(input)
+        verb.handler.call(Some(noun_name), verb_name, input, None)
     }
 
     /// Build clap command structure from registry
     pub fn build_command(&self) -> clap::Command {
+        self.build_command_for(&[])
+    }
+
+    /// Build the command tree for an invocation
+    ///
+    /// `args` only selects the configuration profile whose values become
+    /// argument defaults; see [`set_config`](Self::set_config).
+    fn build_command_for(&self, args: &[String]) -> clap::Command {
+        let profile = self.active_profile(args);
+        let profile = profile.as_deref();
         let mut cmd = clap::Command::new("cli")
             .version(env!("CARGO_PKG_VERSION"))
-            .arg_required_else_help(true);
-
-        // Add root-level verbs directly as subcommands
-        for (verb_name, verb_meta) in &self.root_verbs {
-            let verb_cmd = self.build_verb_command(verb_name, verb_meta);
+            .arg_required_else_help(true)
+            .arg(build_format_arg(true));
+
+        // Add root-level verbs directly as subcommands, in a stable order
+        let mut root_verbs: Vec<_> = self.root_verbs.iter().collect();
+        root_verbs.sort_by_key(|(verb_name, _)| verb_name.as_str());
+        for (verb_name, verb_meta) in root_verbs {
+            let verb_cmd = self.build_verb_command(verb_name, verb_meta, profile);
             cmd = cmd.subcommand(verb_cmd);
         }
 
-        // Add nouns with their nested verbs
-        for (noun_name, noun_meta) in &self.nouns {
-            let noun_cmd = self.build_noun_command(noun_name, noun_meta);
-            cmd = cmd.subcommand(noun_cmd);
+        // Add top-level nouns; nested nouns are added recursively beneath them
+        for noun_path in self.get_sub_nouns("") {
+            if let Some(noun_meta) = self.nouns.get(noun_path) {
+                cmd = cmd.subcommand(self.build_noun_command(noun_path, noun_meta, profile));
+            }
         }
 
-        cmd
-    }
+        #[cfg(feature = "config-formats")]
+        {
+            cmd = self.add_config_commands(cmd);
+        }
+
+        #[cfg(feature = "autonomic")]
+        if self.provides_receipts_command() {
+            cmd = cmd.subcommand(receipts_command());
+        }
+
+        #[cfg(feature = "autonomic")]
+        if self.invocation_flags {
+            cmd = cmd.args(invocation_args());
+        }
+
+        if self.provides_serve_command() {
+            cmd = cmd.subcommand(
+                clap::Command::new(SERVE_NOUN).about("Serve the CLI to other programs").subcommand(
+                    clap::Command::new(MCP_VERB)
+                        .about("Serve every verb as an MCP tool over stdio JSON-RPC"),
+                ),
+            );
+        }
+
+        // Add nouns provided only by external plugin executables
+        let mut external_nouns: Vec<&str> = self
+            .external
+            .iter()
+            .map(ExternalCommand::noun)
+            .filter(|noun| !self.nouns.contains_key(*noun) && !self.root_verbs.contains_key(*noun))
+            .collect();
+        external_nouns.sort_unstable();
+        external_nouns.dedup();
+        for noun in external_nouns {
+            let noun_cmd = clap::Command::new(intern_static(noun));
+            cmd = cmd.subcommand(self.add_external_commands(noun_cmd, noun));
+        }
+
+        cmd
+    }
 
-    /// Build a noun command with all its verb subcommands
+    /// Build a noun command with all its verb and sub-noun subcommands
+    ///
+    /// `noun_path` is the full dotted path; the command is named after its last segment.
     ///
     /// Note: Uses Box::leak to convert owned strings to &'static str required by clap.
     /// This is acceptable for CLI apps - see module documentation for details.
-    fn build_noun_command(&self, noun_name: &str, noun_meta: &NounMetadata) -> clap::Command {
+    fn build_noun_command(
+        &self,
+        noun_path: &str,
+        noun_meta: &NounMetadata,
+        profile: Option<&str>,
+    ) -> clap::Command {
+        let noun_name = noun_path.rsplit(NOUN_PATH_SEPARATOR).next().unwrap_or(noun_path);
         // Box::leak: Converts dynamic String to &'static str for clap's Command::new()
         // This is necessary because clap requires static lifetimes for performance
         let noun_name_static: &'static str = Box::leak(noun_name.to_string().into_boxed_str());
@@ -450,34 +1526,101 @@ impl CommandRegistry {
             noun_cmd = noun_cmd.long_about(long_about_static);
         }
 
-        // Add verbs as subcommands
-        if let Some(verbs) = self.verbs.get(noun_name) {
+        // Add verbs as subcommands, in a stable order
+        if let Some(verbs) = self.verbs.get(noun_path) {
+            let mut verbs: Vec<_> = verbs.iter().collect();
+            verbs.sort_by_key(|(verb_name, _)| verb_name.as_str());
             for (verb_name, verb_meta) in verbs {
-                let verb_cmd = self.build_verb_command(verb_name, verb_meta);
+                let verb_cmd = self.build_verb_command(verb_name, verb_meta, profile);
                 noun_cmd = noun_cmd.subcommand(verb_cmd);
             }
         }
 
-        noun_cmd
+        // Add nested nouns as subcommands
+        for sub_noun_path in self.get_sub_nouns(noun_path) {
+            if let Some(sub_noun_meta) = self.nouns.get(sub_noun_path) {
+                noun_cmd = noun_cmd.subcommand(self.build_noun_command(
+                    sub_noun_path,
+                    sub_noun_meta,
+                    profile,
+                ));
+            }
+        }
+
+        self.add_external_commands(noun_cmd, noun_path)
     }
 
     /// Build a verb command with all its arguments
     ///
     /// Note: Uses Box::leak to convert owned strings to &'static str required by clap.
     /// This is acceptable for CLI apps - see module documentation for details.
-    fn build_verb_command(&self, verb_name: &str, verb_meta: &VerbMetadata) -> clap::Command {
+    fn build_verb_command(
+        &self,
+        verb_name: &str,
+        verb_meta: &VerbMetadata,
+        profile: Option<&str>,
+    ) -> clap::Command {
         // Box::leak: Converts dynamic String to &'static str for clap's Command::new()
         let verb_name_static: &'static str = Box::leak(verb_name.to_string().into_boxed_str());
         let about: &'static str = Box::leak(verb_meta.about.clone().into_boxed_str());
-        let mut verb_cmd = clap::Command::new(verb_name_static).about(about);
+        let mut verb_cmd = match &verb_meta.handler {
+            // Builder commands keep the arguments they declare
+            VerbHandler::Command(command) => command.build_command().name(verb_name_static),
+            VerbHandler::Function(_) => {
+                let verb_cmd = clap::Command::new(verb_name_static).about(about);
+                let verb_cmd = self.add_arg_groups(verb_cmd, verb_meta);
+                self.add_arguments(verb_cmd, verb_meta, profile)
+            }
+        };
+        verb_cmd = self.add_format_arg(verb_cmd, verb_meta);
+        verb_cmd = Self::add_confirm_arg(verb_cmd, verb_meta);
 
-        // Add argument groups and arguments
-        verb_cmd = self.add_arg_groups(verb_cmd, verb_meta);
-        verb_cmd = self.add_arguments(verb_cmd, verb_meta);
+        if !verb_meta.examples.is_empty() {
+            let examples: Vec<String> =
+                verb_meta.examples.iter().map(|example| format!("  $ {}", example)).collect();
+            verb_cmd = verb_cmd.after_help(format!("Examples:\n{}", examples.join("\n")));
+        }
 
         verb_cmd
     }
 
+    /// Inject `--yes` into destructive verbs
+    fn add_confirm_arg(verb_cmd: clap::Command, verb_meta: &VerbMetadata) -> clap::Command {
+        if !Self::injects_confirm_arg(verb_meta) {
+            return verb_cmd;
+        }
+        verb_cmd.arg(
+            clap::Arg::new(CONFIRM_ARG_ID)
+                .long("yes")
+                .action(clap::ArgAction::SetTrue)
+                .help("Run without asking for confirmation"),
+        )
+    }
+
+    /// Whether a verb gets the framework `--yes` (destructive, no own `--yes`)
+    fn injects_confirm_arg(verb_meta: &VerbMetadata) -> bool {
+        verb_meta.safety.effect.requires_confirmation()
+            && !verb_meta.args.iter().any(|a| a.name == "yes" || a.long.as_deref() == Some("yes"))
+    }
+
+    /// Inject the framework `--format` option into a verb command
+    ///
+    /// Verbs that declare their own `--format` keep it; users can still pass
+    /// `--format` before the noun or set `CNV_FORMAT`.
+    fn add_format_arg(&self, verb_cmd: clap::Command, verb_meta: &VerbMetadata) -> clap::Command {
+        let mut named = verb_meta.args.iter().filter(|a| a.positional.is_none());
+        let owns_long = named.clone().any(|a| {
+            a.name == "format"
+                || a.long.as_deref() == Some("format")
+                || a.aliases.iter().any(|alias| alias == "format")
+        });
+        if owns_long {
+            return verb_cmd;
+        }
+        let owns_short = named.any(|a| a.short == Some('o'));
+        verb_cmd.arg(build_format_arg(!owns_short))
+    }
+
     /// Add argument groups to a command
     fn add_arg_groups(
         &self,
@@ -519,13 +1662,20 @@ impl CommandRegistry {
     }
 
     /// Add arguments to a command
+    ///
+    /// Values from the layered configuration replace built-in defaults and
+    /// satisfy required arguments.
     fn add_arguments(
         &self,
         mut verb_cmd: clap::Command,
         verb_meta: &VerbMetadata,
+        profile: Option<&str>,
     ) -> clap::Command {
         for arg_meta in &verb_meta.args {
-            let arg = self.build_argument(arg_meta);
+            let mut arg = self.build_argument(arg_meta);
+            if let Some(defaults) = self.config_defaults(verb_meta, arg_meta, profile) {
+                arg = arg.required(false).default_values(defaults);
+            }
             verb_cmd = verb_cmd.arg(arg);
         }
         verb_cmd
@@ -551,7 +1701,12 @@ impl CommandRegistry {
             }
             pos_arg
         } else {
-            clap::Arg::new(arg_name).long(arg_name)
+            match &arg_meta.long {
+                Some(long) => clap::Arg::new(arg_name)
+                    .long(Box::leak(long.clone().into_boxed_str()) as &str)
+                    .alias(arg_name),
+                None => clap::Arg::new(arg_name).long(arg_name),
+            }
         };
 
         // Apply short flag if specified (only for non-positional args)
@@ -676,201 +1831,1989 @@ impl CommandRegistry {
         arg
     }
 
-    /// Extract a value from ArgMatches as a string
+    /// Extract the raw CLI strings of an argument
     ///
-    /// Uses get_raw() to get the original CLI string value, avoiding type mismatch
+    /// Uses get_raw() to get the original CLI string values, avoiding type mismatch
     /// panics that occur when value_parser stores values as numeric types.
-    fn extract_value_as_string(verb_matches: &clap::ArgMatches, arg_name: &str) -> Option<String> {
-        // Use get_raw() to get the original string value from CLI
-        // This works regardless of what value_parser was used
-        if let Some(raw_values) = verb_matches.get_raw(arg_name) {
-            // get_raw returns an iterator of OsStrings, take the first one
-            if let Some(os_str) = raw_values.into_iter().next() {
-                return os_str.to_str().map(|s| s.to_string());
-            }
+    fn extract_raw_values(verb_matches: &clap::ArgMatches, arg_name: &str) -> Vec<String> {
+        verb_matches
+            .get_raw(arg_name)
+            .map(|raw| raw.filter_map(|os_str| os_str.to_str().map(str::to_string)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Extract a single-valued argument, keeping integers typed when clap parsed them
+    fn extract_scalar(
+        verb_matches: &clap::ArgMatches,
+        arg_name: &str,
+    ) -> Option<serde_json::Value> {
+        if let Ok(Some(n)) = verb_matches.try_get_one::<i64>(arg_name) {
+            return Some((*n).into());
+        }
+        if let Ok(Some(n)) = verb_matches.try_get_one::<u64>(arg_name) {
+            return Some((*n).into());
+        }
+        Self::extract_raw_values(verb_matches, arg_name).into_iter().next().map(Into::into)
+    }
+
+    /// Extract a multi-valued argument as an array, keeping integers typed
+    fn extract_many(verb_matches: &clap::ArgMatches, arg_name: &str) -> Option<serde_json::Value> {
+        if let Ok(Some(values)) = verb_matches.try_get_many::<i64>(arg_name) {
+            return Some(values.copied().collect::<Vec<_>>().into());
+        }
+        if let Ok(Some(values)) = verb_matches.try_get_many::<u64>(arg_name) {
+            return Some(values.copied().collect::<Vec<_>>().into());
+        }
+        let raw = Self::extract_raw_values(verb_matches, arg_name);
+        if raw.is_empty() {
+            None
+        } else {
+            Some(raw.into())
         }
-        None
     }
 
-    /// Extract arguments from clap matches into a HashMap
-    fn extract_args(
+    /// Extract typed argument values from clap matches
+    ///
+    /// Flags become booleans, counts become numbers and multi-valued arguments
+    /// become arrays. Absent arguments are left out.
+    fn extract_values(
         &self,
         verb_meta: &VerbMetadata,
         verb_matches: &clap::ArgMatches,
-    ) -> std::collections::HashMap<String, String> {
-        let mut args_map = std::collections::HashMap::new();
+    ) -> HashMap<String, serde_json::Value> {
+        let mut values = HashMap::new();
 
         for arg_meta in &verb_meta.args {
-            let arg_name = &arg_meta.name;
-
-            // Handle positional arguments differently
-            if let Some(_index) = arg_meta.positional {
-                // For positional args, clap extracts by name automatically
-                if let Some(value) = Self::extract_value_as_string(verb_matches, arg_name) {
-                    args_map.insert(arg_name.clone(), value);
-                }
-            } else if let Some(action) = &arg_meta.action {
-                // Handle custom actions
-                match action {
-                    clap::ArgAction::Count => {
-                        let count = verb_matches.get_count(arg_name);
-                        args_map.insert(arg_name.clone(), count.to_string());
-                    }
-                    clap::ArgAction::SetTrue => {
-                        if verb_matches.get_flag(arg_name) {
-                            args_map.insert(arg_name.clone(), "true".to_string());
-                        }
-                    }
-                    clap::ArgAction::SetFalse => {
-                        // SetFalse is handled differently - need to check if present
-                        // Note: clap doesn't have get_flag for SetFalse, so we check presence
-                        if verb_matches.contains_id(arg_name) {
-                            args_map.insert(arg_name.clone(), "false".to_string());
-                        }
-                    }
-                    clap::ArgAction::Append => {
-                        // Append collects multiple values
-                        if let Some(values) = verb_matches.get_many::<String>(arg_name) {
-                            let values_vec: Vec<String> = values.cloned().collect();
-                            args_map.insert(arg_name.clone(), values_vec.join(","));
-                        }
-                    }
-                    _ => {
-                        // For Set and other actions, try type-aware extraction
-                        if let Some(value) = Self::extract_value_as_string(verb_matches, arg_name) {
-                            args_map.insert(arg_name.clone(), value);
-                        }
-                    }
-                }
-            } else if arg_meta.is_flag {
-                // For flags, check if they're set
-                if verb_matches.get_flag(arg_name) {
-                    args_map.insert(arg_name.clone(), "true".to_string());
+            let arg_name = arg_meta.name.as_str();
+
+            let value = if arg_meta.positional.is_some() {
+                if arg_meta.trailing_vararg {
+                    Self::extract_many(verb_matches, arg_name)
+                } else {
+                    Self::extract_scalar(verb_matches, arg_name)
                 }
             } else {
-                // For regular named arguments - use type-aware extraction
-                if let Some(value) = Self::extract_value_as_string(verb_matches, arg_name) {
-                    args_map.insert(arg_name.clone(), value);
+                match &arg_meta.action {
+                    Some(clap::ArgAction::Count) => Some(verb_matches.get_count(arg_name).into()),
+                    Some(clap::ArgAction::SetTrue | clap::ArgAction::SetFalse) => {
+                        Some(verb_matches.get_flag(arg_name).into())
+                    }
+                    Some(clap::ArgAction::Append) => Self::extract_many(verb_matches, arg_name),
+                    Some(_) => Self::extract_scalar(verb_matches, arg_name),
+                    None if arg_meta.is_flag => Some(verb_matches.get_flag(arg_name).into()),
+                    None if arg_meta.multiple => Self::extract_many(verb_matches, arg_name),
+                    None => Self::extract_scalar(verb_matches, arg_name),
                 }
+            };
+
+            if let Some(value) = value {
+                values.insert(arg_meta.name.clone(), value);
             }
         }
 
-        args_map
+        values
     }
 
-    /// Run CLI with auto-discovered commands
-    pub fn run(&self, args: Vec<String>) -> Result<()> {
-        let cmd = self.build_command();
-        let matches = match cmd.try_get_matches_from(args) {
-            Ok(m) => m,
-            Err(e) => {
-                // Clap returns an error for help/version, but with exit code 0
-                // In this case, we should print it and exit successfully
-                let exit_code = e.exit_code();
-                let help_or_version_msg = e.to_string();
+    /// Flatten typed values into the string map carried in `HandlerInput::args`
+    ///
+    /// Multi-valued arguments are comma-joined and unset flags are omitted.
+    /// `SetFalse` flags are always `"false"`, as they were before typed
+    /// values; their state is in `HandlerInput::values`.
+    fn flatten_values(
+        verb_meta: &VerbMetadata,
+        values: &HashMap<String, serde_json::Value>,
+    ) -> HashMap<String, String> {
+        fn flatten(value: &serde_json::Value) -> Option<String> {
+            match value {
+                serde_json::Value::Bool(false) | serde_json::Value::Null => None,
+                serde_json::Value::String(s) => Some(s.clone()),
+                serde_json::Value::Array(items) => {
+                    Some(items.iter().filter_map(flatten).collect::<Vec<_>>().join(","))
+                }
+                other => Some(other.to_string()),
+            }
+        }
 
-                // Print the message (help or version)
-                print!("{}", help_or_version_msg);
+        let mut args: HashMap<String, String> = values
+            .iter()
+            .filter_map(|(name, value)| Some((name.clone(), flatten(value)?)))
+            .collect();
+        for arg in &verb_meta.args {
+            if matches!(arg.action, Some(clap::ArgAction::SetFalse)) {
+                args.insert(arg.name.clone(), "false".to_string());
+            }
+        }
+        args
+    }
 
-                // Exit with the code clap wants (0 for help/version, non-0 for errors)
-                if exit_code == 0 {
-                    return Ok(());
-                } else {
-                    return Err(crate::error::NounVerbError::argument_error(help_or_version_msg));
+    /// Build handler input for a verb from its clap matches
+    fn build_handler_input(
+        &self,
+        verb_meta: &VerbMetadata,
+        verb_matches: &clap::ArgMatches,
+        context: crate::logic::HandlerContext,
+    ) -> HandlerInput {
+        let values = self.extract_values(verb_meta, verb_matches);
+        HandlerInput {
+            args: Self::flatten_values(verb_meta, &values),
+            opts: HashMap::new(),
+            values,
+            context,
+        }
+    }
+
+    /// Enable or disable auto-correct of mistyped nouns, verbs and flags
+    ///
+    /// When enabled and a mistyped name has exactly one close match, the user
+    /// is asked on stderr whether to run the corrected command (only when stdin
+    /// is a terminal). Setting `CNV_AUTO_CORRECT=1` enables it as well.
+    pub fn set_auto_correct(&mut self, enabled: bool) {
+        self.auto_correct =
+            if enabled { Some(Box::new(prompt_correction) as AutoCorrectConfirm) } else { None };
+    }
+
+    /// Enable auto-correct with a custom confirmation instead of the stdin prompt
+    pub fn set_auto_correct_confirm<F>(&mut self, confirm: F)
+    where
+        F: Fn(&str, &str) -> bool + Send + Sync + 'static,
+    {
+        self.auto_correct = Some(Box::new(confirm));
+    }
+
+    /// Replace the stdin prompt confirming destructive verbs
+    ///
+    /// By default destructive verbs ask on stderr when stdin is a terminal and
+    /// are rejected otherwise unless `--yes` is passed.
+    pub fn set_confirm<F>(&mut self, confirm: F)
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.confirm = Some(Box::new(confirm));
+    }
+
+    /// Evaluate `engine` before every verb
+    ///
+    /// Denials fail with [`NounVerbError::PolicyDenied`](crate::error::NounVerbError::PolicyDenied),
+    /// rewrites replace argument values before the handler runs and redirects
+    /// run another verb, whose invocation is evaluated again. Without an
+    /// engine, the policy file named by `CNV_POLICY_FILE` is used (requires the
+    /// `config-formats` feature; without it, a set variable is an error).
+    #[cfg(feature = "autonomic")]
+    pub fn set_policy_engine<E>(&mut self, engine: E)
+    where
+        E: crate::autonomic::PolicyEngine + 'static,
+    {
+        self.policy = Some(Box::new(engine));
+    }
+
+    /// Apply the policy decision for an invocation, returning the verb to run
+    #[cfg(feature = "autonomic")]
+    fn apply_policy<'a>(
+        &'a self,
+        mut target: VerbTarget<'a>,
+        mut verb_matches: clap::ArgMatches,
+        invocation: &crate::autonomic::InvocationContext,
+    ) -> Result<(VerbTarget<'a>, clap::ArgMatches)> {
+        use crate::autonomic::PolicyDecision;
+
+        let loaded;
+        let engine: &dyn crate::autonomic::PolicyEngine = match &self.policy {
+            Some(engine) => engine.as_ref(),
+            None => match Self::policy_from_env()? {
+                Some(engine) => {
+                    loaded = engine;
+                    &loaded
+                }
+                None => return Ok((target, verb_matches)),
+            },
+        };
+
+        for _ in 0..=MAX_POLICY_REDIRECTS {
+            let (noun, verb, verb_meta) = target;
+            let mut values = self.extract_values(verb_meta, &verb_matches);
+            let request = Self::policy_request(target, values.clone(), invocation);
+            // `--yes` survives re-parsing the rewritten or redirected arguments
+            let confirmed =
+                verb_matches.try_get_one::<bool>(CONFIRM_ARG_ID).ok().flatten() == Some(&true);
+            let arguments = |target: VerbTarget<'_>, mut values: HashMap<_, _>| {
+                if confirmed && Self::injects_confirm_arg(target.2) {
+                    values.insert("yes".to_string(), serde_json::Value::Bool(true));
+                }
+                serde_json::Value::Object(values.into_iter().collect())
+            };
+            match engine.evaluate(&request)?.decision {
+                PolicyDecision::Allow => return Ok((target, verb_matches)),
+                PolicyDecision::Deny { reason, suggestion } => {
+                    return Err(crate::error::NounVerbError::policy_denied(
+                        command_display(noun, verb),
+                        reason,
+                        suggestion,
+                    ));
+                }
+                PolicyDecision::Rewrite { new_args } => {
+                    values.extend(new_args);
+                    verb_matches =
+                        self.parse_tool_args(verb, verb_meta, &arguments(target, values))?;
+                    return Ok((target, verb_matches));
+                }
+                PolicyDecision::Redirect { noun, verb, args } => {
+                    let noun = Some(noun.as_str()).filter(|noun| !noun.is_empty());
+                    target = self
+                        .tool_targets()
+                        .find(|(n, v, _)| *n == noun && *v == verb)
+                        .ok_or_else(|| match noun {
+                            Some(noun) => crate::error::NounVerbError::verb_not_found(noun, &verb),
+                            None => crate::error::NounVerbError::command_not_found(&verb),
+                        })?;
+                    // Arguments the target also declares carry over unless replaced
+                    values.retain(|name, _| target.2.args.iter().any(|arg| &arg.name == name));
+                    values.extend(args);
+                    verb_matches =
+                        self.parse_tool_args(target.1, target.2, &arguments(target, values))?;
                 }
             }
+        }
+
+        Err(crate::error::NounVerbError::execution_error(format!(
+            "Policy redirected more than {} times",
+            MAX_POLICY_REDIRECTS
+        )))
+    }
+
+    /// Engine loaded from the policy file named by `CNV_POLICY_FILE`, if set
+    ///
+    /// Builds without `config-formats` cannot read policy files, so a set
+    /// variable fails the invocation instead of running without the policy.
+    #[cfg(feature = "autonomic")]
+    fn policy_from_env() -> Result<Option<crate::autonomic::RuleBasedPolicyEngine>> {
+        let Some(path) = std::env::var_os(POLICY_FILE_ENV_VAR) else {
+            return Ok(None);
         };
+        #[cfg(feature = "config-formats")]
+        return crate::autonomic::RuleBasedPolicyEngine::from_file(path).map(Some);
+        #[cfg(not(feature = "config-formats"))]
+        Err(crate::error::NounVerbError::execution_error(format!(
+            "{} is set to '{}', but this build cannot load policy files \
+             (enable the `config-formats` feature)",
+            POLICY_FILE_ENV_VAR,
+            std::path::Path::new(&path).display()
+        )))
+    }
 
-        // Route command
-        if let Some((subcommand_name, sub_matches)) = matches.subcommand() {
-            // First check if this is a root-level verb
-            if let Some(verb_meta) = self.root_verbs.get(subcommand_name) {
-                // Execute root verb directly
-                let args_map = self.extract_args(verb_meta, sub_matches);
+    /// Policy request for invoking a verb with typed argument values
+    #[cfg(feature = "autonomic")]
+    fn policy_request(
+        (noun, verb, verb_meta): VerbTarget<'_>,
+        values: HashMap<String, serde_json::Value>,
+        invocation: &crate::autonomic::InvocationContext,
+    ) -> crate::autonomic::PolicyRequest {
+        let dry_run = verb_meta.safety.dry_run
+            && values.get(DRY_RUN_ARG).and_then(serde_json::Value::as_bool).unwrap_or(false);
+        let request = crate::autonomic::PolicyRequest::new(
+            invocation.clone(),
+            noun.unwrap_or_default(),
+            verb,
+            values,
+            verb_meta.safety.effect_metadata(),
+        );
+        if dry_run {
+            request.dry_run()
+        } else {
+            request
+        }
+    }
 
-                let input = crate::logic::HandlerInput {
-                    args: args_map,
-                    opts: std::collections::HashMap::new(),
-                    context: crate::logic::HandlerContext::new(subcommand_name),
-                };
+    /// Write an execution receipt for every verb invocation to `log`
+    ///
+    /// Receipts record the command, duration, outcome and, as configured by
+    /// the log's [`ReceiptConfig`](crate::autonomic::ReceiptConfig), argument
+    /// values and a hash of the result. Invocations denied by policy or left
+    /// unconfirmed are recorded too. Setting a log also enables the built-in
+    /// `receipts list/show/verify` commands unless the application registers
+    /// a `receipts` noun or root verb itself.
+    #[cfg(feature = "autonomic")]
+    pub fn set_receipt_log(&mut self, log: crate::autonomic::ReceiptLog) {
+        self.receipts = Some(log);
+    }
 
-                let output = self.execute_root_verb(subcommand_name, input)?;
-                let json = output.to_json()?;
-                println!("{}", json);
-            } else if let Some((verb_name, verb_matches)) = sub_matches.subcommand() {
-                // This is a noun with a verb subcommand
-                let noun_name = subcommand_name;
-                // Execute verb - extract arguments from matches
-                let args_map = if let Some(verbs) = self.verbs.get(noun_name) {
-                    if let Some(verb_meta) = verbs.get(verb_name) {
-                        self.extract_args(verb_meta, verb_matches)
-                    } else {
-                        std::collections::HashMap::new()
-                    }
-                } else {
-                    std::collections::HashMap::new()
-                };
+    /// Log receiving execution receipts, if set
+    #[cfg(feature = "autonomic")]
+    pub fn receipt_log(&self) -> Option<&crate::autonomic::ReceiptLog> {
+        self.receipts.as_ref()
+    }
 
-                let input = crate::logic::HandlerInput {
-                    args: args_map,
-                    opts: std::collections::HashMap::new(),
-                    context: crate::logic::HandlerContext::new(verb_name).with_noun(noun_name),
-                };
+    /// Add the global `--agent`, `--tenant`, `--correlation-id` and `--priority` options
+    ///
+    /// They override the `CNV_AGENT_ID`, `CNV_TENANT_ID`, `CNV_CORRELATION_ID`
+    /// and `CNV_PRIORITY` environment variables that identify the invocation
+    /// otherwise; see [`invocation_context`](Self::invocation_context).
+    #[cfg(feature = "autonomic")]
+    pub fn set_invocation_flags(&mut self, enabled: bool) {
+        self.invocation_flags = enabled;
+    }
 
-                let output = self.execute_verb(noun_name, verb_name, input)?;
-                let json = output.to_json()?;
-                println!("{}", json);
-            } else {
-                // No verb specified - show help for the noun
-                let noun_name = subcommand_name;
-                if let Some(noun_meta) = self.nouns.get(noun_name) {
-                    let noun_name_static: &'static str =
-                        Box::leak(noun_name.to_string().into_boxed_str());
-                    let about_static: &'static str =
-                        Box::leak(noun_meta.about.clone().into_boxed_str());
-
-                    let mut noun_cmd = clap::Command::new(noun_name_static).about(about_static);
-
-                    // Apply long_about if available
-                    if let Some(ref long_about) = noun_meta.long_about {
-                        let long_about_static: &'static str =
-                            Box::leak(long_about.clone().into_boxed_str());
-                        noun_cmd = noun_cmd.long_about(long_about_static);
-                    }
+    /// Require every hop of a `CNV_DELEGATION` chain to be signed by a key in `trust_store`
+    ///
+    /// Without a trust store, invocations under a delegation chain are denied.
+    #[cfg(all(feature = "autonomic", feature = "signing"))]
+    pub fn set_trust_store(&mut self, trust_store: crate::autonomic::TrustStore) {
+        self.trust_store = Some(trust_store);
+    }
 
-                    // Add verbs as subcommands for help display
-                    if let Some(verbs) = self.verbs.get(noun_name) {
-                        for (verb_name, verb_meta) in verbs {
-                            let verb_name_static: &'static str =
-                                Box::leak(verb_name.clone().into_boxed_str());
-                            let verb_about_static: &'static str =
-                                Box::leak(verb_meta.about.clone().into_boxed_str());
-                            noun_cmd = noun_cmd.subcommand(
-                                clap::Command::new(verb_name_static).about(verb_about_static),
-                            );
-                        }
-                    }
+    /// Identity of an invocation of `noun verb`, as seen by handlers, policy and receipts
+    ///
+    /// A delegation chain in `CNV_DELEGATION` makes its executor the agent and
+    /// tenant; the chain must be signed by keys of the trust store set with
+    /// `set_trust_store` (`signing` feature), which is required, and allow the
+    /// command. Otherwise the agent and tenant come from
+    /// `--agent`/`CNV_AGENT_ID` (typed by `CNV_AGENT_TYPE`) and
+    /// `--tenant`/`CNV_TENANT_ID`; under a delegation, any given agent or
+    /// tenant must match the executor's. `--correlation-id`/`CNV_CORRELATION_ID`,
+    /// `--priority`/`CNV_PRIORITY` and `CNV_ENVIRONMENT` complete the context;
+    /// anything unset keeps the anonymous default.
+    #[cfg(feature = "autonomic")]
+    pub fn invocation_context(
+        &self,
+        noun: Option<&str>,
+        verb: &str,
+        verb_matches: &clap::ArgMatches,
+    ) -> Result<crate::autonomic::InvocationContext> {
+        use crate::autonomic::{AgentIdentity, InvocationContext, TenantIdentity};
+
+        let [agent_arg, tenant_arg, correlation_arg, priority_arg] = INVOCATION_ARG_IDS;
+        let env = |var: &str| std::env::var(var).ok().filter(|value| !value.trim().is_empty());
+        let setting = |id: &str, var: &str| {
+            let flag = verb_matches.try_get_one::<String>(id).ok().flatten().cloned();
+            flag.filter(|value| !value.trim().is_empty()).or_else(|| env(var))
+        };
+        let command = command_display(noun, verb);
 
-                    noun_cmd.print_help().map_err(|e| {
-                        crate::error::NounVerbError::execution_error(format!(
-                            "Failed to print help: {}",
-                            e
-                        ))
-                    })?;
-                } else {
-                    return Err(crate::error::NounVerbError::invalid_structure(
-                        "No verb specified",
-                    ));
+        let mut context = match env(DELEGATION_ENV_VAR) {
+            Some(chain) => {
+                InvocationContext::anonymous().with_delegation(self.delegation(&chain, noun, verb)?)
+            }
+            None => InvocationContext::anonymous(),
+        };
+        let delegated = context.delegation.is_some();
+        let mismatch = |what: &str, given: &str, executor: &str| {
+            crate::error::NounVerbError::policy_denied(
+                &command,
+                format!("The delegation was issued to {} '{}', not '{}'", what, executor, given),
+                None,
+            )
+        };
+        if let Some(agent_id) = setting(agent_arg, AGENT_ID_ENV_VAR) {
+            if delegated && agent_id != context.agent.agent_id {
+                return Err(mismatch("agent", &agent_id, &context.agent.agent_id));
+            }
+            if !delegated {
+                let agent_type = std::env::var(AGENT_TYPE_ENV_VAR).ok();
+                context.agent =
+                    AgentIdentity::new(agent_id, agent_type.as_deref().unwrap_or("agent"));
+            }
+        }
+        if let Some(tenant_id) = setting(tenant_arg, TENANT_ID_ENV_VAR) {
+            if delegated && tenant_id != context.tenant.tenant_id {
+                return Err(mismatch("tenant", &tenant_id, &context.tenant.tenant_id));
+            }
+            if !delegated {
+                context.tenant = TenantIdentity::new(tenant_id);
+            }
+        }
+        if let Some(environment) = env(ENVIRONMENT_ENV_VAR) {
+            context.tenant.environment = Some(environment);
+        }
+        if let Some(correlation_id) = setting(correlation_arg, CORRELATION_ID_ENV_VAR) {
+            context.correlation_id = correlation_id;
+        }
+        if let Some(priority) = setting(priority_arg, PRIORITY_ENV_VAR) {
+            context.qos.priority =
+                priority.parse().map_err(crate::error::NounVerbError::argument_error)?;
+        }
+        Ok(context)
+    }
+
+    /// Parse and check the delegation chain an invocation of `noun verb` acts under
+    #[cfg(feature = "autonomic")]
+    fn delegation(
+        &self,
+        chain: &str,
+        noun: Option<&str>,
+        verb: &str,
+    ) -> Result<crate::autonomic::DelegationChain> {
+        let command = command_display(noun, verb);
+        let denied = |reason: String| {
+            crate::error::NounVerbError::policy_denied(
+                &command,
+                reason,
+                Some(format!("Request a delegation for '{}'", command)),
+            )
+        };
+        let chain: crate::autonomic::DelegationChain =
+            serde_json::from_str(chain).map_err(|e| {
+                crate::error::NounVerbError::argument_error(format!(
+                    "{}: invalid delegation chain: {}",
+                    DELEGATION_ENV_VAR, e
+                ))
+            })?;
+        self.verify_delegation(&chain, &command)?;
+        if !chain.effective_constraints().allows_command(noun.unwrap_or_default(), verb) {
+            return Err(denied("The delegation does not allow this command".to_string()));
+        }
+        Ok(chain)
+    }
+
+    /// Check the signature of every hop of `chain` against the trust store
+    #[cfg(all(feature = "autonomic", feature = "signing"))]
+    fn verify_delegation(
+        &self,
+        chain: &crate::autonomic::DelegationChain,
+        command: &str,
+    ) -> Result<()> {
+        let trust_store = self.trust_store.as_ref().ok_or_else(|| {
+            crate::error::NounVerbError::policy_denied(
+                command,
+                "No trust store is configured to verify the delegation",
+                Some(format!("Unset {} or configure a trust store", DELEGATION_ENV_VAR)),
+            )
+        })?;
+        chain.verify_against(trust_store).map_err(|e| {
+            crate::error::NounVerbError::policy_denied(
+                command,
+                format!("Invalid delegation: {}", e),
+                Some(format!("Request a delegation for '{}'", command)),
+            )
+        })
+    }
+
+    /// Without the `signing` feature there is no trust store, so delegation chains are denied
+    #[cfg(all(feature = "autonomic", not(feature = "signing")))]
+    fn verify_delegation(
+        &self,
+        _chain: &crate::autonomic::DelegationChain,
+        command: &str,
+    ) -> Result<()> {
+        Err(crate::error::NounVerbError::policy_denied(
+            command,
+            "No trust store is configured to verify the delegation",
+            Some(format!(
+                "Unset {} or build with the `signing` feature and configure a trust store",
+                DELEGATION_ENV_VAR
+            )),
+        ))
+    }
+
+    /// Append the receipt of an invocation and pass its result through
+    ///
+    /// Failing to write the receipt fails an otherwise successful invocation.
+    #[cfg(feature = "autonomic")]
+    fn record_receipt(
+        &self,
+        (noun, verb, verb_meta): VerbTarget<'_>,
+        verb_matches: &clap::ArgMatches,
+        invocation: &crate::autonomic::InvocationContext,
+        elapsed: std::time::Duration,
+        usage: Option<&BudgetUsage>,
+        result: Result<HandlerOutput>,
+    ) -> Result<HandlerOutput> {
+        use crate::autonomic::{ExecutionReceipt, StructuredError as ReceiptError};
+
+        let Some(log) = &self.receipts else {
+            return result;
+        };
+        let config = log.config();
+        let mut receipt = ExecutionReceipt::new(command_display(noun, verb))
+            .with_invocation(invocation)
+            .with_duration_ms(u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX));
+        if config.include_args {
+            receipt = receipt.with_args(self.extract_values(verb_meta, verb_matches));
+        }
+        if let Some(usage) = usage {
+            receipt = receipt.with_guard(usage.guard_result());
+        }
+        match &result {
+            Ok(output) if config.hash_result => {
+                if let Some(hash) = ExecutionReceipt::compute_hash(&output.data) {
+                    receipt = receipt.with_result_hash(hash);
                 }
             }
+            Ok(_) => {}
+            Err(error) => receipt = receipt.with_error(ReceiptError::from_noun_verb_error(error)),
+        }
+
+        match log.append(receipt) {
+            Err(error) if result.is_ok() => Err(error),
+            _ => result,
+        }
+    }
+
+    /// Check that a destructive verb may run
+    ///
+    /// The framework `--yes` and dry runs pass; a verb parameter named `yes`
+    /// does not. Otherwise the confirmation decides; without a terminal, or
+    /// when `prompt` is false, the verb is rejected.
+    fn confirm_effect(
+        &self,
+        noun_path: Option<&str>,
+        verb_name: &str,
+        verb_meta: &VerbMetadata,
+        verb_matches: &clap::ArgMatches,
+        prompt: bool,
+    ) -> Result<()> {
+        if !verb_meta.safety.effect.requires_confirmation() {
+            return Ok(());
+        }
+        let flag = |id: &str| {
+            verb_matches.try_get_one::<bool>(id).ok().flatten().copied().unwrap_or(false)
+        };
+        let dry_run = verb_meta.safety.dry_run && flag(DRY_RUN_ARG);
+        if dry_run || flag(CONFIRM_ARG_ID) {
+            return Ok(());
+        }
+
+        let command = command_display(noun_path, verb_name);
+        let confirmed = match &self.confirm {
+            Some(confirm) if prompt => confirm(&command),
+            None if prompt && atty::is(atty::Stream::Stdin) => prompt_confirmation(&command),
+            _ => {
+                return Err(crate::error::NounVerbError::argument_error(format!(
+                    "'{}' is destructive; pass --yes to run it without confirmation",
+                    command
+                )))
+            }
+        };
+        if confirmed {
+            Ok(())
         } else {
-            // No noun specified - show root help
-            let mut cmd = self.build_command();
-            cmd.print_help().map_err(|e| {
-                crate::error::NounVerbError::execution_error(format!("Failed to print help: {}", e))
-            })?;
+            Err(crate::error::NounVerbError::interrupted(format!(
+                "'{}' was not confirmed",
+                command
+            )))
+        }
+    }
+
+    /// Run `middleware` around every verb handler, after those already added
+    ///
+    /// Each invocation calls the pipeline's `before` hooks with a
+    /// [`MiddlewareRequest`] holding the noun/verb path and parsed arguments,
+    /// then the handler, then `after`. If the handler fails, `handle_error`
+    /// may recover: the first recovery message becomes the verb's output.
+    pub fn add_middleware(&mut self, middleware: Box<dyn Middleware>) {
+        self.middleware = std::mem::take(&mut self.middleware).add(middleware);
+    }
+
+    /// Replace the middleware pipeline run around every verb handler
+    pub fn set_middleware(&mut self, pipeline: MiddlewarePipeline) {
+        self.middleware = pipeline;
+    }
+
+    /// Middleware pipeline run around every verb handler
+    pub fn middleware(&self) -> &MiddlewarePipeline {
+        &self.middleware
+    }
+
+    /// Merge external plugin executables into the command tree
+    ///
+    /// Every `<app>-<noun>-<verb>` executable found by `discovery` becomes a
+    /// verb of `<noun>`, and every `<app>-<noun>` executable receives the verbs
+    /// of `<noun>` that are not otherwise defined. Verbs registered with
+    /// `#[verb]` take precedence over executables of the same name. Returns the
+    /// number of executables added.
+    ///
+    /// # Errors
+    ///
+    /// Returns a plugin error if the manifest directory cannot be scanned.
+    pub fn discover_external_plugins(
+        &mut self,
+        discovery: &ExternalPluginDiscovery,
+    ) -> Result<usize> {
+        self.external_app_name = discovery.app_name().to_string();
+        let commands = discovery.discover()?;
+        let count = commands.len();
+        for command in commands {
+            self.add_external_command(command);
+        }
+        Ok(count)
+    }
+
+    /// Add a single external plugin executable to the command tree
+    ///
+    /// An executable already registered for the same noun and verb is replaced.
+    pub fn add_external_command(&mut self, command: ExternalCommand) {
+        // Leak the strings the clap tree needs once, here, not on every build
+        if let Some(verb) = command.verb() {
+            intern_static(verb);
+        }
+        intern_static(command.about());
+        self.external.retain(|c| c.noun() != command.noun() || c.verb() != command.verb());
+        self.external.push(command);
+    }
+
+    /// External plugin executables merged into the command tree
+    pub fn external_commands(&self) -> &[ExternalCommand] {
+        &self.external
+    }
+
+    /// External executable implementing `verb` of `noun_path`, if any
+    fn external_verb(&self, noun_path: &str, verb: &str) -> Option<&ExternalCommand> {
+        self.external.iter().find(|c| c.noun() == noun_path && c.verb() == Some(verb))
+    }
+
+    /// External executable handling the whole of `noun_path`, if any
+    fn external_noun(&self, noun_path: &str) -> Option<&ExternalCommand> {
+        self.external.iter().find(|c| c.noun() == noun_path && c.verb().is_none())
+    }
+
+    /// Run an external plugin like a verb and print its output
+    ///
+    /// The plugin's arguments become the `args` value of a verb with no
+    /// declared effect, so policy, confirmation, middleware and receipts apply
+    /// as for `#[verb]` functions. `levels` (innermost first) supply the output
+    /// format and the global invocation options.
+    fn run_external(
+        &self,
+        command: &ExternalCommand,
+        noun_path: &str,
+        verb: &str,
+        args: Vec<String>,
+        levels: &[&clap::ArgMatches],
+    ) -> Result<()> {
+        let format = resolve_output_format(levels)?;
+        let verb_meta = self.external_verb_meta(command, noun_path, verb, format);
+        let matches = self.parse_tool_args(
+            verb,
+            &verb_meta,
+            &serde_json::json!({ EXTERNAL_ARGS_VALUE: args }),
+        )?;
+        let flag_matches = levels.first().copied().unwrap_or(&matches);
+        let output = self.invoke_verb_with(
+            (Some(noun_path), verb, &verb_meta),
+            &matches,
+            flag_matches,
+            true,
+        )?;
+        Self::print_output(&output, format)
+    }
+
+    /// Verb metadata running `command` with the values of its `args` argument
+    fn external_verb_meta(
+        &self,
+        command: &ExternalCommand,
+        noun_path: &str,
+        verb: &str,
+        format: OutputFormat,
+    ) -> VerbMetadata {
+        let (plugin, app_name, plugin_verb) =
+            (command.clone(), self.external_app_name.clone(), verb.to_string());
+        let handler = move |input: HandlerInput| {
+            let args: Vec<String> = input.get(EXTERNAL_ARGS_VALUE)?.unwrap_or_default();
+            plugin.invoke(&app_name, &plugin_verb, &args, format)
+        };
+        VerbMetadata {
+            noun_name: noun_path.to_string(),
+            verb_name: verb.to_string(),
+            about: command.about().to_string(),
+            args: vec![ArgMetadata {
+                name: EXTERNAL_ARGS_VALUE.to_string(),
+                multiple: true,
+                positional: Some(1),
+                trailing_vararg: true,
+                ..plain_arg()
+            }],
+            safety: VerbSafety::default(),
+            budget: VerbBudget::default(),
+            examples: Vec::new(),
+            handler: VerbHandler::Function(Box::new(handler)),
+        }
+    }
+
+    /// Add external verb executables (and the whole-noun executable) to a noun command
+    fn add_external_commands(&self, mut noun_cmd: clap::Command, noun_path: &str) -> clap::Command {
+        let own_verbs = self.verbs.get(noun_path);
+        for command in &self.external {
+            let Some(verb) = command.verb().filter(|_| command.noun() == noun_path) else {
+                continue;
+            };
+            if own_verbs.is_some_and(|verbs| verbs.contains_key(verb)) {
+                continue;
+            }
+            noun_cmd = noun_cmd.subcommand(
+                clap::Command::new(intern_static(verb))
+                    .about(intern_static(command.about()))
+                    .disable_help_flag(true)
+                    .arg(
+                        clap::Arg::new(EXTERNAL_ARGS_ID)
+                            .num_args(0..)
+                            .trailing_var_arg(true)
+                            .allow_hyphen_values(true),
+                    ),
+            );
+        }
+
+        if let Some(command) = self.external_noun(noun_path) {
+            noun_cmd = noun_cmd
+                .allow_external_subcommands(true)
+                .external_subcommand_value_parser(clap::value_parser!(String));
+            if noun_cmd.get_about().is_none() && !command.about().is_empty() {
+                noun_cmd = noun_cmd.about(intern_static(command.about()));
+            }
         }
+        noun_cmd
+    }
+
+    /// Configuration key of an argument: `noun.verb.arg`, or `verb.arg` for root verbs
+    fn config_key(verb_meta: &VerbMetadata, arg_meta: &ArgMetadata) -> String {
+        if verb_meta.noun_name.is_empty() {
+            format!("{}.{}", verb_meta.verb_name, arg_meta.name)
+        } else {
+            format!("{}.{}.{}", verb_meta.noun_name, verb_meta.verb_name, arg_meta.name)
+        }
+    }
+
+    /// Configuration profile selected for an invocation
+    #[cfg(feature = "config-formats")]
+    fn active_profile(&self, args: &[String]) -> Option<String> {
+        self.config.as_ref().and_then(|config| config.active_profile(args))
+    }
+
+    /// Configuration profile selected for an invocation
+    #[cfg(not(feature = "config-formats"))]
+    fn active_profile(&self, _args: &[String]) -> Option<String> {
+        None
+    }
+
+    /// Default values for an argument taken from the layered configuration
+    #[cfg(feature = "config-formats")]
+    fn config_defaults(
+        &self,
+        verb_meta: &VerbMetadata,
+        arg_meta: &ArgMetadata,
+        profile: Option<&str>,
+    ) -> Option<Vec<&'static str>> {
+        use serde_json::Value;
+
+        let resolved =
+            self.config.as_ref()?.resolve(&Self::config_key(verb_meta, arg_meta), profile)?;
+        let multiple = arg_meta.multiple
+            || arg_meta.trailing_vararg
+            || matches!(arg_meta.action, Some(clap::ArgAction::Append));
+        let values: Vec<String> = match resolved.value {
+            Value::Array(items) => items
+                .into_iter()
+                .map(|item| match item {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                })
+                .collect(),
+            Value::String(s) if multiple && resolved.origin == crate::config::ConfigSource::Env => {
+                s.split(',').map(|item| item.trim().to_string()).collect()
+            }
+            Value::String(s) => vec![s],
+            Value::Number(n) => vec![n.to_string()],
+            Value::Bool(b) => vec![b.to_string()],
+            Value::Null | Value::Object(_) => return None,
+        };
+        // Box::leak: clap requires static default values (see module documentation)
+        Some(values.into_iter().map(|v| &*Box::leak(v.into_boxed_str())).collect())
+    }
+
+    /// Default values for an argument taken from the layered configuration
+    #[cfg(not(feature = "config-formats"))]
+    fn config_defaults(
+        &self,
+        _verb_meta: &VerbMetadata,
+        _arg_meta: &ArgMetadata,
+        _profile: Option<&str>,
+    ) -> Option<Vec<&'static str>> {
+        None
+    }
+
+    /// Feed verb arguments from a layered configuration
+    ///
+    /// Values keyed `noun.verb.arg` become argument defaults, so precedence is
+    /// built-in default < system < user < project < `--profile` section <
+    /// environment < CLI flag. This adds a global `--profile <name>` option and,
+    /// unless the application defines its own `config` noun, a built-in
+    /// `config show [--origin]` command reporting each effective value and
+    /// where it came from.
+    #[cfg(feature = "config-formats")]
+    pub fn set_config(&mut self, config: crate::config::LayeredConfig) {
+        self.config = Some(config);
+        self.clear_tool_commands();
+    }
+
+    /// Layered configuration supplying argument values, if set
+    #[cfg(feature = "config-formats")]
+    pub fn config(&self) -> Option<&crate::config::LayeredConfig> {
+        self.config.as_ref()
+    }
+
+    /// Whether `config` is the built-in command rather than an application noun
+    #[cfg(feature = "config-formats")]
+    fn provides_config_command(&self) -> bool {
+        self.config.is_some()
+            && !self.nouns.contains_key(CONFIG_NOUN)
+            && !self.root_verbs.contains_key(CONFIG_NOUN)
+            && !self.external.iter().any(|command| command.noun() == CONFIG_NOUN)
+    }
+
+    /// Add the global `--profile` option and the built-in `config show` command
+    #[cfg(feature = "config-formats")]
+    fn add_config_commands(&self, mut cmd: clap::Command) -> clap::Command {
+        if self.config.is_none() {
+            return cmd;
+        }
+        cmd = cmd.arg(
+            clap::Arg::new(PROFILE_ARG_ID)
+                .long("profile")
+                .value_name("PROFILE")
+                .help("Configuration profile to apply")
+                .global(true)
+                .display_order(usize::MAX),
+        );
+        if self.provides_config_command() {
+            cmd = cmd.subcommand(
+                clap::Command::new(CONFIG_NOUN)
+                    .about("Inspect the effective configuration")
+                    .subcommand(
+                        clap::Command::new("show").about("Show effective argument values").arg(
+                            clap::Arg::new("origin")
+                                .long("origin")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Report where each value came from"),
+                        ),
+                    ),
+            );
+        }
+        cmd
+    }
+
+    /// Effective configuration value of every verb argument, keyed `noun.verb.arg`
+    ///
+    /// An argument's own environment variable beats the configuration layers,
+    /// which beat its built-in default. Arguments without any value are omitted.
+    #[cfg(feature = "config-formats")]
+    pub fn effective_config(
+        &self,
+        profile: Option<&str>,
+    ) -> std::collections::BTreeMap<String, crate::config::ConfigValue> {
+        use crate::config::{ConfigSource, ConfigValue};
+
+        let verbs = self.root_verbs.values().chain(self.verbs.values().flat_map(HashMap::values));
+        let mut effective = std::collections::BTreeMap::new();
+        for verb_meta in verbs {
+            for arg_meta in &verb_meta.args {
+                let key = Self::config_key(verb_meta, arg_meta);
+                let from_env = arg_meta.env.as_ref().and_then(|var| {
+                    let value = std::env::var(var).ok()?;
+                    Some(ConfigValue::new(value.into(), ConfigSource::Env, Some(var.clone())))
+                });
+                let value = from_env
+                    .or_else(|| self.config.as_ref()?.resolve(&key, profile))
+                    .or_else(|| {
+                        let default = arg_meta.default_value.clone()?;
+                        Some(ConfigValue::new(default.into(), ConfigSource::Default, None))
+                    });
+                if let Some(value) = value {
+                    effective.insert(key, value);
+                }
+            }
+        }
+        effective
+    }
+
+    /// Run the built-in `config` command
+    #[cfg(feature = "config-formats")]
+    fn run_config_command(
+        &self,
+        config_matches: &clap::ArgMatches,
+        root_matches: &clap::ArgMatches,
+    ) -> Result<()> {
+        let Some(("show", show_matches)) = config_matches.subcommand() else {
+            let mut cmd = self.build_command();
+            let config_cmd = cmd.find_subcommand_mut(CONFIG_NOUN).ok_or_else(|| {
+                crate::error::NounVerbError::invalid_structure("No verb specified")
+            })?;
+            return config_cmd.print_help().map_err(|e| {
+                crate::error::NounVerbError::execution_error(format!("Failed to print help: {}", e))
+            });
+        };
+
+        let levels = [show_matches, config_matches, root_matches];
+        let profile = levels
+            .iter()
+            .find_map(|level| level.try_get_one::<String>(PROFILE_ARG_ID).ok().flatten().cloned())
+            .or_else(|| self.active_profile(&[]));
+        let effective = self.effective_config(profile.as_deref());
+        let output = if show_matches.get_flag("origin") {
+            HandlerOutput::from_data(effective)?
+        } else {
+            let values: std::collections::BTreeMap<String, serde_json::Value> =
+                effective.into_iter().map(|(key, value)| (key, value.value)).collect();
+            HandlerOutput::from_data(values)?
+        };
+        Self::print_output(&output, resolve_output_format(&levels)?)
+    }
+
+    /// Enable or disable the built-in `serve mcp` command
+    ///
+    /// `app serve mcp` then runs an [`McpServer`](crate::mcp::McpServer) on
+    /// stdio. A registered `serve` noun or root verb takes precedence.
+    pub fn set_serve_mcp(&mut self, enabled: bool) {
+        self.serve_mcp = enabled;
+    }
+
+    /// Whether `serve mcp` is handled by the registry itself
+    fn provides_serve_command(&self) -> bool {
+        self.serve_mcp
+            && !self.nouns.contains_key(SERVE_NOUN)
+            && !self.root_verbs.contains_key(SERVE_NOUN)
+            && !self.external.iter().any(|command| command.noun() == SERVE_NOUN)
+    }
+
+    /// Whether `receipts` is the built-in command rather than an application noun
+    #[cfg(feature = "autonomic")]
+    fn provides_receipts_command(&self) -> bool {
+        self.receipts.is_some()
+            && !self.nouns.contains_key(RECEIPTS_NOUN)
+            && !self.root_verbs.contains_key(RECEIPTS_NOUN)
+            && !self.external.iter().any(|command| command.noun() == RECEIPTS_NOUN)
+    }
+
+    /// Run the built-in `receipts` command
+    ///
+    /// `verify` prints its report and fails when the log was tampered with.
+    #[cfg(feature = "autonomic")]
+    fn run_receipts_command(
+        &self,
+        receipts_matches: &clap::ArgMatches,
+        root_matches: &clap::ArgMatches,
+    ) -> Result<()> {
+        let log = self.receipts.as_ref().ok_or_else(|| {
+            crate::error::NounVerbError::invalid_structure("No receipt log configured")
+        })?;
+        let Some((verb, verb_matches)) = receipts_matches.subcommand() else {
+            let mut cmd = self.build_command();
+            let receipts_cmd = cmd.find_subcommand_mut(RECEIPTS_NOUN).ok_or_else(|| {
+                crate::error::NounVerbError::invalid_structure("No verb specified")
+            })?;
+            return receipts_cmd.print_help().map_err(|e| {
+                crate::error::NounVerbError::execution_error(format!("Failed to print help: {}", e))
+            });
+        };
+        let format = resolve_output_format(&[verb_matches, receipts_matches, root_matches])?;
+
+        match verb {
+            "list" => {
+                let records = log.records()?;
+                let limit = verb_matches.get_one::<usize>("limit").copied();
+                let skip = limit.map_or(0, |limit| records.len().saturating_sub(limit));
+                let summaries: Vec<serde_json::Value> = records
+                    .iter()
+                    .skip(skip)
+                    .map(|record| {
+                        serde_json::json!({
+                            "seq": record.seq,
+                            "timestamp": record.receipt.timestamp,
+                            "command": record.receipt.command,
+                            "success": record.receipt.success,
+                            "duration_ms": record.receipt.duration_ms,
+                        })
+                    })
+                    .collect();
+                Self::print_output(&HandlerOutput::from_data(summaries)?, format)
+            }
+            "show" => {
+                let seq = verb_matches.get_one::<u64>("seq").copied().unwrap_or_default();
+                let record = log.get(seq)?.ok_or_else(|| {
+                    crate::error::NounVerbError::argument_error(format!(
+                        "No receipt with sequence number {}",
+                        seq
+                    ))
+                })?;
+                Self::print_output(&HandlerOutput::from_data(record)?, format)
+            }
+            _ => {
+                let verification = log.verify()?;
+                Self::print_output(&HandlerOutput::from_data(&verification)?, format)?;
+                match (verification.broken_at, verification.problem) {
+                    (Some(seq), Some(problem)) => {
+                        Err(crate::error::NounVerbError::execution_error(format!(
+                            "Receipt log {} is broken at record {}: {}",
+                            log.path().display(),
+                            seq,
+                            problem
+                        )))
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Every registered verb described as an MCP tool, sorted by name
+    pub fn mcp_tools(&self) -> Vec<crate::mcp::McpTool> {
+        let mut tools: Vec<crate::mcp::McpTool> = self
+            .tool_targets()
+            .map(|(noun, verb, verb_meta)| {
+                let possible_values = verb_meta
+                    .args
+                    .iter()
+                    .map(|arg_meta| (arg_meta.name.clone(), self.possible_values(arg_meta)))
+                    .collect();
+                let description = if verb_meta.about.is_empty() {
+                    format!("{} {}", noun.unwrap_or_default(), verb).trim().to_string()
+                } else {
+                    verb_meta.about.clone()
+                };
+                let mut input_schema = crate::mcp::input_schema(&verb_meta.args, &possible_values);
+                if Self::injects_confirm_arg(verb_meta) {
+                    input_schema["properties"]["yes"] = serde_json::json!({
+                        "type": "boolean",
+                        "description": "Confirm running this destructive command",
+                    });
+                }
+                crate::mcp::McpTool {
+                    name: crate::mcp::tool_name(noun, verb),
+                    description,
+                    input_schema,
+                }
+            })
+            .collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
+    /// Visible possible values of an argument, as clap would accept them
+    pub(crate) fn possible_values(&self, arg_meta: &ArgMetadata) -> Vec<String> {
+        self.build_argument(arg_meta)
+            .get_possible_values()
+            .into_iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect()
+    }
+
+    /// Run the verb behind an MCP tool with JSON `arguments`
+    ///
+    /// Arguments are validated by the verb's clap command, so defaults,
+    /// configuration values and value parsers apply as on the command line.
+    /// The handler runs through the middleware pipeline.
+    pub fn call_tool(&self, name: &str, arguments: &serde_json::Value) -> Result<HandlerOutput> {
+        let (noun, verb, verb_meta) = self
+            .tool_targets()
+            .find(|(noun, verb, _)| crate::mcp::tool_name(*noun, verb) == name)
+            .ok_or_else(|| crate::error::NounVerbError::command_not_found(name))?;
+
+        let matches = self.parse_tool_args(verb, verb_meta, arguments)?;
+        self.invoke_verb((noun, verb, verb_meta), &matches, false)
+    }
+
+    /// Command line equivalent to calling a verb with JSON `arguments`
+    ///
+    /// The tokens start with the noun path and verb (no binary name), followed
+    /// by the options and positionals [`call_tool`](Self::call_tool) would parse.
+    pub fn command_line(
+        &self,
+        noun_path: Option<&str>,
+        verb_name: &str,
+        arguments: &serde_json::Value,
+    ) -> Result<Vec<String>> {
+        let (noun, verb, verb_meta) = self
+            .tool_targets()
+            .find(|(noun, verb, _)| *noun == noun_path && *verb == verb_name)
+            .ok_or_else(|| match noun_path {
+                Some(noun) => crate::error::NounVerbError::verb_not_found(noun, verb_name),
+                None => crate::error::NounVerbError::command_not_found(verb_name),
+            })?;
+
+        let mut tokens: Vec<String> = noun
+            .map(|noun| noun.split(NOUN_PATH_SEPARATOR).map(String::from).collect())
+            .unwrap_or_default();
+        tokens.push(verb.to_string());
+        tokens.extend(Self::tool_argv(verb_meta, arguments)?);
+        Ok(tokens)
+    }
+
+    /// Parse JSON arguments through a verb's clap command
+    ///
+    /// The command is built on the first call for each verb and cloned for
+    /// later calls, so its strings are leaked only once.
+    fn parse_tool_args(
+        &self,
+        verb_name: &str,
+        verb_meta: &VerbMetadata,
+        arguments: &serde_json::Value,
+    ) -> Result<clap::ArgMatches> {
+        let argv = Self::tool_argv(verb_meta, arguments)?;
+        let command = self
+            .tool_commands
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry((verb_meta.noun_name.clone(), verb_name.to_string()))
+            .or_insert_with(|| {
+                self.build_verb_command(verb_name, verb_meta, None).no_binary_name(true)
+            })
+            .clone();
+        command
+            .try_get_matches_from(argv)
+            .map_err(|e| crate::error::NounVerbError::argument_error(e.to_string()))
+    }
+
+    /// Run a verb for parsed matches: policy, confirmation, then the middleware pipeline
+    ///
+    /// `prompt` allows asking for confirmation of destructive verbs. With a
+    /// receipt log set, the invocation's receipt is appended afterwards.
+    fn invoke_verb(
+        &self,
+        target: VerbTarget<'_>,
+        verb_matches: &clap::ArgMatches,
+        prompt: bool,
+    ) -> Result<HandlerOutput> {
+        self.invoke_verb_with(target, verb_matches, verb_matches, prompt)
+    }
+
+    /// [`invoke_verb`](Self::invoke_verb) with the global invocation options
+    /// read from `flag_matches` instead of the verb's own matches
+    #[cfg_attr(not(feature = "autonomic"), allow(unused_variables))]
+    fn invoke_verb_with(
+        &self,
+        target: VerbTarget<'_>,
+        verb_matches: &clap::ArgMatches,
+        flag_matches: &clap::ArgMatches,
+        prompt: bool,
+    ) -> Result<HandlerOutput> {
+        let mut usage = None;
+        #[cfg(feature = "autonomic")]
+        {
+            let started = std::time::Instant::now();
+            let (invocation, resolved) =
+                match self.invocation_context(target.0, target.1, flag_matches) {
+                    Ok(invocation) => (invocation, Ok(())),
+                    Err(error) => (crate::autonomic::InvocationContext::anonymous(), Err(error)),
+                };
+            let mut span = invocation.span(INVOKE_SPAN);
+            span.set_attribute("command", command_display(target.0, target.1));
+            let context =
+                crate::logic::HandlerContext::new(target.1).with_invocation(invocation.clone());
+            let (target, verb_matches, result) = match resolved
+                .and_then(|()| self.apply_policy(target, verb_matches.clone(), &invocation))
+            {
+                Ok((target, matches)) => {
+                    let result = self.run_verb(target, &matches, prompt, context, &mut usage);
+                    (target, matches, result)
+                }
+                Err(error) => (target, verb_matches.clone(), Err(error)),
+            };
+            span.set_attribute("success", result.is_ok().to_string());
+            span.finish();
+            self.record_receipt(
+                target,
+                &verb_matches,
+                &invocation,
+                started.elapsed(),
+                usage.as_ref(),
+                result,
+            )
+        }
+        #[cfg(not(feature = "autonomic"))]
+        self.run_verb(
+            target,
+            verb_matches,
+            prompt,
+            crate::logic::HandlerContext::new(target.1),
+            &mut usage,
+        )
+    }
+
+    /// Confirm and run a verb whose policy decision has been applied
+    ///
+    /// The handler gets `context` with the noun and verb of `target`, which
+    /// the policy may have redirected. Verbs with a budget are measured while
+    /// their handler runs; the measurement is stored in `usage`.
+    fn run_verb(
+        &self,
+        target: VerbTarget<'_>,
+        verb_matches: &clap::ArgMatches,
+        prompt: bool,
+        mut context: crate::logic::HandlerContext,
+        usage: &mut Option<BudgetUsage>,
+    ) -> Result<HandlerOutput> {
+        let (noun, verb, verb_meta) = target;
+        self.confirm_effect(noun, verb, verb_meta, verb_matches, prompt)?;
+        context.verb = verb.to_string();
+        context.noun = noun.map(str::to_string);
+        let input = self.build_handler_input(verb_meta, verb_matches, context);
+        let run = |input| {
+            self.run_handler(input, |input| {
+                verb_meta.handler.call(noun, verb, input, Some(verb_matches))
+            })
+        };
+        if !verb_meta.budget.has_limits() {
+            return run(input);
+        }
+
+        let meter = UsageMeter::start();
+        let result = run(input);
+        let measured = usage.insert(meter.finish(verb_meta.budget));
+        measured.enforce(&command_display(noun, verb), result)
+    }
+
+    /// Root verbs and noun verbs as `(noun path, verb, metadata)`
+    fn tool_targets(&self) -> impl Iterator<Item = VerbTarget<'_>> {
+        let root = self.root_verbs.iter().map(|(verb, meta)| (None, verb.as_str(), meta));
+        let nested = self.verbs.iter().flat_map(|(noun, verbs)| {
+            verbs.iter().map(move |(verb, meta)| (Some(noun.as_str()), verb.as_str(), meta))
+        });
+        root.chain(nested)
+    }
+
+    /// Command-line tokens for a tool call's JSON arguments
+    fn tool_argv(verb_meta: &VerbMetadata, arguments: &serde_json::Value) -> Result<Vec<String>> {
+        let object = match arguments {
+            serde_json::Value::Object(object) => object,
+            serde_json::Value::Null => return Ok(Vec::new()),
+            _ => {
+                return Err(crate::error::NounVerbError::argument_error(
+                    "Tool arguments must be a JSON object",
+                ))
+            }
+        };
+
+        let mut argv = Vec::new();
+        let mut positionals = Vec::new();
+        for (name, value) in object {
+            if name == "yes" && Self::injects_confirm_arg(verb_meta) {
+                if value.as_bool() == Some(true) {
+                    argv.push("--yes".to_string());
+                }
+                continue;
+            }
+            let arg_meta = verb_meta.args.iter().find(|a| &a.name == name).ok_or_else(|| {
+                crate::error::NounVerbError::argument_error(format!("Unknown argument '{}'", name))
+            })?;
+            let values: Vec<&serde_json::Value> = match value {
+                serde_json::Value::Null => continue,
+                serde_json::Value::Array(items) => items.iter().collect(),
+                other => vec![other],
+            };
+            let long = arg_meta.long.as_deref().unwrap_or(name);
+            let text = |value: &serde_json::Value| match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+
+            if let Some(index) = arg_meta.positional {
+                positionals.push((index, values.into_iter().map(text).collect::<Vec<_>>()));
+            } else if matches!(arg_meta.action, Some(clap::ArgAction::Count)) {
+                let count = value.as_u64().unwrap_or(0);
+                argv.extend((0..count).map(|_| format!("--{}", long)));
+            } else if arg_meta.is_flag
+                || matches!(
+                    arg_meta.action,
+                    Some(clap::ArgAction::SetTrue) | Some(clap::ArgAction::SetFalse)
+                )
+            {
+                if value.as_bool() == Some(true) {
+                    argv.push(format!("--{}", long));
+                }
+            } else {
+                argv.extend(values.into_iter().map(|value| format!("--{}={}", long, text(value))));
+            }
+        }
+
+        if !positionals.is_empty() {
+            positionals.sort_by_key(|(index, _)| *index);
+            argv.push("--".to_string());
+            argv.extend(positionals.into_iter().flat_map(|(_, values)| values));
+        }
+        Ok(argv)
+    }
+
+    /// Run a verb handler through the middleware pipeline
+    fn run_handler<F>(&self, input: HandlerInput, handler: F) -> Result<HandlerOutput>
+    where
+        F: FnOnce(HandlerInput) -> Result<HandlerOutput>,
+    {
+        if self.middleware.is_empty() {
+            return handler(input);
+        }
+
+        self.middleware.execute_before(&MiddlewareRequest::from_handler_input(&input))?;
+        match handler(input) {
+            Ok(output) => {
+                self.middleware
+                    .execute_after(&MiddlewareResponse::success(output.data.to_string()))?;
+                Ok(output)
+            }
+            Err(error) => match self.middleware.handle_error(&error)? {
+                Some(recovered) => {
+                    self.middleware
+                        .execute_after(&MiddlewareResponse::success(recovered.clone()))?;
+                    HandlerOutput::from_data(recovered)
+                }
+                None => {
+                    self.middleware
+                        .execute_after(&MiddlewareResponse::failure(error.to_string()))?;
+                    Err(error)
+                }
+            },
+        }
+    }
+
+    /// Ask whether to replace `typed` with `correction`, if auto-correct is on
+    fn confirm_correction(&self, typed: &str, correction: &str) -> bool {
+        match &self.auto_correct {
+            Some(confirm) => confirm(typed, correction),
+            None => {
+                let enabled = std::env::var(AUTO_CORRECT_ENV_VAR)
+                    .map(|value| matches!(value.trim(), "1" | "true" | "yes"))
+                    .unwrap_or(false);
+                enabled && prompt_correction(typed, correction)
+            }
+        }
+    }
+
+    /// Parse arguments against the registry's command tree
+    ///
+    /// For mistyped nouns, verbs and flags the error carries close matches from
+    /// the registry; with auto-correct on, a confirmed unique match is
+    /// substituted and parsing retried.
+    fn parse_args(
+        &self,
+        mut args: Vec<String>,
+    ) -> std::result::Result<clap::ArgMatches, clap::Error> {
+        let cmd = self.build_command_for(&args);
+        let mut corrections = 0;
+        loop {
+            let mut error = match cmd.clone().try_get_matches_from(&args) {
+                Ok(matches) => return Ok(matches),
+                Err(error) => error,
+            };
+            let Some(typo) = Typo::locate(&cmd, &args, &error) else {
+                return Err(error);
+            };
+            if let Some(correction) = typo.unique_match() {
+                if corrections < args.len() && self.confirm_correction(&typo.typed, correction) {
+                    args[typo.index] = format!("{}{}", correction, typo.suffix);
+                    corrections += 1;
+                    continue;
+                }
+            }
+            typo.annotate(&mut error);
+            return Err(error);
+        }
+    }
+
+    /// Close matches for a noun or verb that the registry does not know
+    ///
+    /// Used for [`NounVerbError::CommandNotFound`](crate::error::NounVerbError::CommandNotFound)
+    /// and [`NounVerbError::VerbNotFound`](crate::error::NounVerbError::VerbNotFound);
+    /// other errors have no suggestions.
+    pub fn suggestions_for(&self, error: &crate::error::NounVerbError) -> Vec<String> {
+        use crate::cli::discovery::did_you_mean;
+        use crate::error::NounVerbError;
+
+        match error {
+            NounVerbError::CommandNotFound { noun } => {
+                let parent = parent_noun_path(noun).unwrap_or_default();
+                let mut candidates = self.get_sub_nouns(parent);
+                if parent.is_empty() {
+                    candidates.extend(self.root_verbs.keys().map(String::as_str));
+                }
+                did_you_mean(noun, candidates)
+            }
+            NounVerbError::VerbNotFound { noun, verb } => did_you_mean(
+                verb,
+                self.verbs.get(noun).into_iter().flat_map(|verbs| verbs.keys().map(String::as_str)),
+            ),
+            NounVerbError::PolicyDenied { suggestion, .. } => suggestion.iter().cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// [`suggestions_for`](Self::suggestions_for) on the global registry, if it is
+    /// initialized and not currently locked
+    pub(crate) fn try_global_suggestions_for(error: &crate::error::NounVerbError) -> Vec<String> {
+        REGISTRY
+            .get()
+            .and_then(|registry| registry.try_lock().ok())
+            .map(|registry| registry.suggestions_for(error))
+            .unwrap_or_default()
+    }
+
+    /// Grammar of every registered command, named after the program in `args[0]`
+    #[cfg(feature = "kernel")]
+    pub fn grammar(&self, args: &[String]) -> crate::kernel::grammar::GrammarModel {
+        let app_name = args
+            .first()
+            .and_then(|program| std::path::Path::new(program).file_stem())
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("cli");
+        crate::kernel::grammar::GrammarModel::from_registry(app_name, self)
+            .with_version(env!("CARGO_PKG_VERSION"))
+    }
+
+    /// Print the grammar as pretty JSON to stdout
+    #[cfg(feature = "kernel")]
+    fn print_grammar(&self, args: &[String]) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.grammar(args))
+            .map_err(|e| crate::error::NounVerbError::execution_error(e.to_string()))?;
+        println!("{}", json);
+        Ok(())
+    }
+
+    /// Handle `app --record <file> ...`, returning the child's exit code
+    ///
+    /// The command line runs as a child process of the current binary so its
+    /// output, exit code and timing are captured exactly. Stdin is read to the
+    /// end and recorded only with [`RECORD_STDIN_FLAG`]; otherwise the child
+    /// inherits it.
+    #[cfg(feature = "kernel")]
+    fn run_recorded(&self, request: RecordRequest) -> Result<i32> {
+        use std::io::Read;
+
+        let program = std::env::current_exe()
+            .map_err(|e| crate::error::NounVerbError::execution_error(e.to_string()))?;
+        let stdin = if request.record_stdin {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| crate::error::NounVerbError::execution_error(e.to_string()))?;
+            Some(input)
+        } else {
+            None
+        };
+        let (noun, verb) = self.recorded_capability(&request.argv);
+        let outcome = crate::kernel::recording::InvocationRecorder::new(&request.recording)
+            .record(&program, &noun, &verb, request.argv, stdin)
+            .map_err(crate::error::NounVerbError::execution_error)?;
+        Ok(outcome.exit_code)
+    }
+
+    /// Noun path and verb an invocation resolves to, for its recorded frame
+    ///
+    /// A root verb is recorded under the program name; a command line that
+    /// does not reach a verb as `help` or `invalid`.
+    #[cfg(feature = "kernel")]
+    fn recorded_capability(&self, argv: &[String]) -> (String, String) {
+        let app = argv
+            .first()
+            .and_then(|program| std::path::Path::new(program).file_name())
+            .and_then(|name| name.to_str())
+            .unwrap_or("cli")
+            .to_string();
+        let mut path = Vec::new();
+        match self.parse_args(argv.to_vec()) {
+            Ok(matches) => {
+                let mut current = &matches;
+                while let Some((name, sub_matches)) = current.subcommand() {
+                    path.push(name.to_string());
+                    current = sub_matches;
+                }
+            }
+            Err(e) if e.exit_code() == 0 => return (app, "help".to_string()),
+            Err(_) => return (app, "invalid".to_string()),
+        }
+        match path.split_last() {
+            Some((verb, nouns)) if !nouns.is_empty() => (nouns.join("."), verb.clone()),
+            Some((verb, _)) => (app, verb.clone()),
+            None => (app, "help".to_string()),
+        }
+    }
+
+    /// Handle `app --replay <file>`: replay every recorded frame and print the report
+    #[cfg(feature = "kernel")]
+    fn run_replay(&self, recording: &std::path::Path, args: &[String]) -> Result<()> {
+        let program = std::env::current_exe()
+            .map_err(|e| crate::error::NounVerbError::execution_error(e.to_string()))?;
+        let report = crate::kernel::recording::ReplayRunner::new(program)
+            .replay_file(recording)
+            .map_err(crate::error::NounVerbError::execution_error)?;
+        if json_output_requested(args) {
+            let json = serde_json::to_string_pretty(&report)
+                .map_err(|e| crate::error::NounVerbError::execution_error(e.to_string()))?;
+            println!("{}", json);
+        } else {
+            print!("{}", report.render());
+        }
+        match report.failures().count() {
+            0 => Ok(()),
+            failed => Err(crate::error::NounVerbError::execution_error(format!(
+                "{} of {} replayed invocations differ from the recording",
+                failed,
+                report.outcomes.len()
+            ))),
+        }
+    }
+
+    /// Completion candidates for a partially typed command line
+    ///
+    /// `words` is the command line starting with the program name and ending
+    /// with the (possibly empty) word under the cursor. Nouns and verbs complete
+    /// from the command tree and `--flags` from the verb's arguments. Values
+    /// come from the argument's `#[arg(complete = fn_name)]` function, falling
+    /// back to its possible values. Candidates not matching the partial word
+    /// are dropped.
+    pub fn complete(&self, words: &[String]) -> Vec<CompletionCandidate> {
+        let Some((current, typed)) = words.split_last() else {
+            return Vec::new();
+        };
+        let typed = typed.get(1..).unwrap_or_default();
+        // bash splits `--flag=value` into three words on '='
+        let current = if current == "=" { "" } else { current.as_str() };
+
+        let root = self.build_command_for(words);
+        let mut cmd = &root;
+        let mut path: Vec<&str> = Vec::new();
+        let mut given: HashMap<String, Vec<String>> = HashMap::new();
+        let mut pending: Option<&clap::Arg> = None;
+        let mut positionals = 0;
+
+        for word in typed.iter().filter(|word| word.as_str() != "=") {
+            if let Some(arg) = pending.take() {
+                given.entry(arg.get_id().to_string()).or_default().push(word.clone());
+                continue;
+            }
+            if word.starts_with('-') {
+                let (flag, value) = match word.split_once('=') {
+                    Some((flag, value)) => (flag, Some(value)),
+                    None => (word.as_str(), None),
+                };
+                if let Some(arg) = find_flag(cmd, flag) {
+                    match value {
+                        Some(value) => {
+                            given.entry(arg.get_id().to_string()).or_default().push(value.into())
+                        }
+                        None if arg.get_action().takes_values() => pending = Some(arg),
+                        None => {
+                            given.entry(arg.get_id().to_string()).or_default().push("true".into())
+                        }
+                    }
+                }
+                continue;
+            }
+            if let Some(sub) = cmd.find_subcommand(word) {
+                path.push(sub.get_name());
+                cmd = sub;
+                continue;
+            }
+            if let Some(arg) = nth_positional(cmd, positionals) {
+                given.entry(arg.get_id().to_string()).or_default().push(word.clone());
+            }
+            positionals += 1;
+        }
+
+        let candidates = if let Some(arg) = pending {
+            self.complete_value(&path, arg, current, given)
+        } else if let Some((flag, value)) =
+            current.split_once('=').filter(|(flag, _)| flag.starts_with("--"))
+        {
+            find_flag(cmd, flag)
+                .map(|arg| self.complete_value(&path, arg, value, given))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|c| CompletionCandidate { value: format!("{}={}", flag, c.value), ..c })
+                .collect()
+        } else if current.starts_with('-') {
+            cmd.get_arguments()
+                .filter(|arg| !arg.is_hide_set())
+                .filter_map(|arg| {
+                    let candidate = CompletionCandidate::new(format!("--{}", arg.get_long()?));
+                    Some(match arg.get_help() {
+                        Some(help) => candidate.with_description(help.to_string()),
+                        None => candidate,
+                    })
+                })
+                .collect()
+        } else if cmd.has_subcommands() {
+            cmd.get_subcommands()
+                .filter(|sub| !sub.is_hide_set())
+                .map(|sub| {
+                    let candidate = CompletionCandidate::new(sub.get_name());
+                    match sub.get_about() {
+                        Some(about) => candidate.with_description(about.to_string()),
+                        None => candidate,
+                    }
+                })
+                .collect()
+        } else {
+            nth_positional(cmd, positionals)
+                .map(|arg| self.complete_value(&path, arg, current, given))
+                .unwrap_or_default()
+        };
+
+        let mut candidates: Vec<CompletionCandidate> =
+            candidates.into_iter().filter(|c| c.value.starts_with(current)).collect();
+        candidates.sort_by(|a, b| a.value.cmp(&b.value));
+        candidates.dedup_by(|a, b| a.value == b.value);
+        candidates
+    }
+
+    /// Candidates for the value of `arg` on the verb at `path`
+    fn complete_value(
+        &self,
+        path: &[&str],
+        arg: &clap::Arg,
+        current: &str,
+        args: HashMap<String, Vec<String>>,
+    ) -> Vec<CompletionCandidate> {
+        let name = arg.get_id().as_str();
+        let (noun, verb_meta) = match path.split_last() {
+            Some((verb, [])) => (None, self.root_verbs.get(*verb)),
+            Some((verb, nouns)) => {
+                let noun_path = nouns.join(&NOUN_PATH_SEPARATOR.to_string());
+                let meta = self.verbs.get(&noun_path).and_then(|verbs| verbs.get(*verb));
+                (Some(noun_path), meta)
+            }
+            None => (None, None),
+        };
+        let complete = verb_meta
+            .and_then(|meta| meta.args.iter().find(|a| a.name == name))
+            .and_then(|arg_meta| arg_meta.complete);
+
+        match complete {
+            Some(complete) => complete(&CompletionRequest {
+                current: current.to_string(),
+                noun,
+                verb: path.last().map(|verb| verb.to_string()).unwrap_or_default(),
+                arg: name.to_string(),
+                args,
+            }),
+            None => arg
+                .get_possible_values()
+                .into_iter()
+                .filter(|value| !value.is_hide_set())
+                .map(|value| {
+                    let candidate = CompletionCandidate::new(value.get_name());
+                    match value.get_help() {
+                        Some(help) => candidate.with_description(help.to_string()),
+                        None => candidate,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Handle `app __complete <shell> [-- <words...>]`
+    ///
+    /// With words after `--`, prints the candidates in the shell's line format;
+    /// without, prints the completion script for the shell.
+    fn print_completions(&self, args: &[String]) -> Result<()> {
+        let shell: CompletionShell = args
+            .get(2)
+            .ok_or_else(|| {
+                crate::error::NounVerbError::argument_error(format!(
+                    "Usage: {} <bash|zsh|fish> [-- <words>...]",
+                    COMPLETE_COMMAND
+                ))
+            })?
+            .parse()?;
+        match args.get(3).map(String::as_str) {
+            Some("--") => {
+                print!("{}", shell.render(&self.complete(args.get(4..).unwrap_or_default())));
+            }
+            _ => {
+                let app_name = args
+                    .first()
+                    .and_then(|program| std::path::Path::new(program).file_name())
+                    .and_then(|name| name.to_str())
+                    .unwrap_or("cli");
+                print!("{}", dynamic_script(shell, app_name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle the entry points that run before argument parsing
+    ///
+    /// `__complete`, and with the `kernel` feature the grammar export,
+    /// `--record` and `--replay`, are answered here; any other invocation is
+    /// handed back for parsing and dispatch.
+    fn pre_dispatch(&self, args: Vec<String>) -> PreDispatch {
+        if completion_requested(&args) {
+            return PreDispatch::Handled(self.print_completions(&args).map(|()| 0));
+        }
+
+        #[cfg(feature = "kernel")]
+        if grammar_requested(&args) {
+            return PreDispatch::Handled(self.print_grammar(&args).map(|()| 0));
+        }
+
+        #[cfg(feature = "kernel")]
+        if let Some(request) = record_requested(&args) {
+            return PreDispatch::Handled(self.run_recorded(request));
+        }
+
+        #[cfg(feature = "kernel")]
+        if let Some(recording) = replay_requested(&args) {
+            return PreDispatch::Handled(self.run_replay(&recording, &args).map(|()| 0));
+        }
+
+        PreDispatch::Dispatch(args)
+    }
+
+    /// Run CLI with auto-discovered commands
+    pub fn run(&self, args: Vec<String>) -> Result<()> {
+        let args = match self.pre_dispatch(args) {
+            PreDispatch::Handled(result) => {
+                return match result? {
+                    0 => Ok(()),
+                    code => Err(crate::error::NounVerbError::exit_with(
+                        u8::try_from(code)
+                            .unwrap_or(crate::error::ExitCodeClass::GeneralError.code()),
+                        format!("Recorded command exited with code {}", code),
+                    )),
+                };
+            }
+            PreDispatch::Dispatch(args) => args,
+        };
+
+        let matches = match self.parse_args(args) {
+            Ok(m) => m,
+            Err(e) => {
+                // Clap returns an error for help/version, but with exit code 0
+                // In this case, we should print it and exit successfully
+                let exit_code = e.exit_code();
+                let help_or_version_msg = e.to_string();
+
+                // Print the message (help or version)
+                print!("{}", help_or_version_msg);
+
+                // Exit with the code clap wants (0 for help/version, non-0 for errors)
+                if exit_code == 0 {
+                    return Ok(());
+                } else {
+                    return Err(crate::error::NounVerbError::argument_error(help_or_version_msg));
+                }
+            }
+        };
+
+        self.dispatch(&matches)
+    }
+
+    /// Set how [`run_to_exit_code`](Self::run_to_exit_code) reports errors
+    ///
+    /// The `CNV_ERROR_FORMAT` environment variable (`text`, `json`, `auto`)
+    /// takes precedence over this setting.
+    pub fn set_error_format(&mut self, format: ErrorFormat) {
+        self.error_format = format;
+    }
+
+    /// Effective error format, honoring `CNV_ERROR_FORMAT`
+    pub fn error_format(&self) -> ErrorFormat {
+        std::env::var(ERROR_FORMAT_ENV_VAR)
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(self.error_format)
+    }
+
+    /// Run CLI with auto-discovered commands, returning the process exit code
+    ///
+    /// Help and version output exit with 0 and argument errors with
+    /// [`ExitCodeClass::UsageError`](crate::error::ExitCodeClass::UsageError).
+    /// Handler errors are printed to stderr and mapped through
+    /// [`NounVerbError::exit_code`](crate::error::NounVerbError::exit_code).
+    ///
+    /// When the invocation requests JSON output (`--format json`) or the
+    /// [error format](Self::set_error_format) selects it, every failure -
+    /// including parse errors - is written to stderr as a single-line JSON
+    /// [`OutputEnvelope`](crate::envelope::OutputEnvelope).
+    ///
+    /// With the `kernel` feature, `__grammar` / `--grammar` as the first
+    /// argument prints the command grammar as JSON instead of running a verb,
+    /// `--record <file>` runs the rest of the command line and records it (and
+    /// its stdin after `--record-stdin`), and `--replay <file>` replays a
+    /// recording (see [`crate::kernel::recording`]).
+    /// `__complete` prints shell completions (see [`complete`](Self::complete)).
+    pub fn run_to_exit_code(&self, args: Vec<String>) -> std::process::ExitCode {
+        let json_errors = self.error_format().use_json(json_output_requested(&args));
+
+        let args = match self.pre_dispatch(args) {
+            PreDispatch::Handled(Ok(code)) => {
+                return std::process::ExitCode::from(u8::try_from(code).unwrap_or(1));
+            }
+            PreDispatch::Handled(Err(e)) => return self.report_error(&e, json_errors),
+            PreDispatch::Dispatch(args) => args,
+        };
+
+        let matches = match self.parse_args(args) {
+            Ok(m) => m,
+            Err(e) if e.exit_code() == 0 => {
+                let _ = e.print();
+                return std::process::ExitCode::SUCCESS;
+            }
+            Err(e) => {
+                if json_errors {
+                    eprintln!("{}", render_error_envelope(&StructuredError::from_clap_error(&e)));
+                } else {
+                    let _ = e.print();
+                }
+                return crate::error::ExitCodeClass::UsageError.into();
+            }
+        };
+
+        match self.dispatch(&matches) {
+            Ok(()) => std::process::ExitCode::SUCCESS,
+            Err(e) => self.report_error(&e, json_errors),
+        }
+    }
+
+    /// Print `error` to stderr as text or a JSON envelope and return its exit code
+    fn report_error(
+        &self,
+        error: &crate::error::NounVerbError,
+        json_errors: bool,
+    ) -> std::process::ExitCode {
+        let suggestions = self.suggestions_for(error);
+        if json_errors {
+            let envelope = StructuredError::from(error).with_suggestions(suggestions);
+            eprintln!("{}", render_error_envelope(&envelope));
+        } else {
+            eprintln!("Error: {}", error);
+            if !suggestions.is_empty() {
+                eprintln!("\n  Did you mean '{}'?", suggestions.join("', '"));
+            }
+        }
+        std::process::ExitCode::from(error.exit_code())
+    }
+
+    /// Run the built-in `serve mcp` command, printing `serve` help without a verb
+    fn run_serve_command(&self, serve_matches: &clap::ArgMatches) -> Result<()> {
+        if serve_matches.subcommand_name() == Some(MCP_VERB) {
+            return crate::mcp::McpServer::new(self, &self.external_app_name).serve_stdio();
+        }
+        let mut cmd = self.build_command();
+        let serve_cmd = cmd
+            .find_subcommand_mut(SERVE_NOUN)
+            .ok_or_else(|| crate::error::NounVerbError::invalid_structure("No verb specified"))?;
+        serve_cmd.print_help().map_err(|e| {
+            crate::error::NounVerbError::execution_error(format!("Failed to print help: {}", e))
+        })
+    }
+
+    /// Route parsed matches to a root verb or noun
+    fn dispatch(&self, matches: &clap::ArgMatches) -> Result<()> {
+        if let Some((subcommand_name, sub_matches)) = matches.subcommand() {
+            #[cfg(feature = "config-formats")]
+            if subcommand_name == CONFIG_NOUN && self.provides_config_command() {
+                return self.run_config_command(sub_matches, matches);
+            }
+
+            #[cfg(feature = "autonomic")]
+            if subcommand_name == RECEIPTS_NOUN && self.provides_receipts_command() {
+                return self.run_receipts_command(sub_matches, matches);
+            }
+
+            if subcommand_name == SERVE_NOUN && self.provides_serve_command() {
+                return self.run_serve_command(sub_matches);
+            }
+
+            // First check if this is a root-level verb
+            if let Some(verb_meta) = self.root_verbs.get(subcommand_name) {
+                // Execute root verb directly
+                let format = resolve_output_format(&[sub_matches, matches])?;
+                let output =
+                    self.invoke_verb((None, subcommand_name, verb_meta), sub_matches, true)?;
+                if !verb_meta.handler.prints_output() {
+                    Self::print_output(&output, format)?;
+                }
+            } else {
+                self.run_noun(subcommand_name, sub_matches, matches)?;
+            }
+        } else {
+            // No noun specified - show root help
+            let mut cmd = self.build_command();
+            cmd.print_help().map_err(|e| {
+                crate::error::NounVerbError::execution_error(format!("Failed to print help: {}", e))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Route a noun's matches down to a verb, descending through nested nouns
+    ///
+    /// At each level a subcommand that is a verb of the current noun path wins;
+    /// otherwise it is treated as a nested noun. A noun without a verb prints its help.
+    fn run_noun(
+        &self,
+        noun_name: &str,
+        noun_matches: &clap::ArgMatches,
+        root_matches: &clap::ArgMatches,
+    ) -> Result<()> {
+        let mut noun_path = noun_name.to_string();
+        let mut levels = vec![root_matches, noun_matches];
+        let mut current = noun_matches;
+
+        loop {
+            let Some((child_name, child_matches)) = current.subcommand() else {
+                if let Some(command) = self.external_noun(&noun_path) {
+                    levels.reverse();
+                    return self.run_external(command, &noun_path, "", Vec::new(), &levels);
+                }
+
+                // No verb specified - show help for the noun
+                let mut noun_cmd = self.build_command();
+                for segment in noun_path.split(NOUN_PATH_SEPARATOR) {
+                    noun_cmd = noun_cmd.find_subcommand(segment).cloned().ok_or_else(|| {
+                        crate::error::NounVerbError::invalid_structure("No verb specified")
+                    })?;
+                }
+                let bin_name = noun_path.replace(NOUN_PATH_SEPARATOR, " ");
+                noun_cmd = noun_cmd.bin_name(Box::leak(bin_name.into_boxed_str()) as &str);
+                return noun_cmd.print_help().map_err(|e| {
+                    crate::error::NounVerbError::execution_error(format!(
+                        "Failed to print help: {}",
+                        e
+                    ))
+                });
+            };
+
+            let verb_meta = self.verbs.get(&noun_path).and_then(|verbs| verbs.get(child_name));
+            if let Some(verb_meta) = verb_meta {
+                // Execute verb - extract arguments from matches
+                levels.push(child_matches);
+                levels.reverse();
+                let format = resolve_output_format(&levels)?;
+                let output = self.invoke_verb(
+                    (Some(&noun_path), child_name, verb_meta),
+                    child_matches,
+                    true,
+                )?;
+                if verb_meta.handler.prints_output() {
+                    return Ok(());
+                }
+                return Self::print_output(&output, format);
+            }
+
+            let child_path = format!("{}{}{}", noun_path, NOUN_PATH_SEPARATOR, child_name);
+            let external = self
+                .external_verb(&noun_path, child_name)
+                .map(|command| (command, child_matches.get_many::<String>(EXTERNAL_ARGS_ID)));
+            let external = external.or_else(|| {
+                // Unknown verbs of a noun with an `<app>-<noun>` executable
+                let command = self.external_noun(&noun_path)?;
+                (!self.nouns.contains_key(&child_path))
+                    .then(|| (command, child_matches.get_many::<String>("")))
+            });
+            if let Some((command, args)) = external {
+                let args = args.map(|values| values.cloned().collect()).unwrap_or_default();
+                levels.push(child_matches);
+                levels.reverse();
+                return self.run_external(command, &noun_path, child_name, args, &levels);
+            }
+
+            noun_path = child_path;
+            levels.push(child_matches);
+            current = child_matches;
+        }
+    }
 
+    /// Print handler output to stdout in the selected format
+    fn print_output(output: &HandlerOutput, format: OutputFormat) -> Result<()> {
+        let rendered = output.render(format)?;
+        println!("{}", rendered.trim_end_matches('\n'));
         Ok(())
     }
 
@@ -881,6 +3824,6 @@ impl CommandRegistry {
             .get(verb_name)
             .ok_or_else(|| crate::error::NounVerbError::command_not_found(verb_name))?;
 
-        (verb.handler_fn)(input)
+        verb.handler.call(None, verb_name, input, None)
     }
 }
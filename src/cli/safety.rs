@@ -0,0 +1,132 @@
+//! Side-effect declarations for verbs: confirmation prompts and `--dry-run`
+//!
+//! ```rust,ignore
+//! #[verb("delete", "bucket", effect = "destructive", dry_run)]
+//! fn delete_bucket(name: String, dry_run: bool) -> Result<Deleted> { ... }
+//! ```
+//!
+//! Destructive verbs ask for confirmation on an interactive terminal before
+//! they run. `--yes` skips the prompt and is required when stdin is not a
+//! terminal. Verbs declaring `dry_run` get a `--dry-run` flag; a `dry_run: bool`
+//! parameter receives it, and a dry run never asks for confirmation.
+
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Argument name of the `--dry-run` flag added to verbs declaring `dry_run`
+pub const DRY_RUN_ARG: &str = "dry_run";
+
+/// Long flag of the dry-run argument
+pub const DRY_RUN_FLAG: &str = "dry-run";
+
+/// Argument id of the `--yes` flag added to destructive verbs
+pub const CONFIRM_ARG_ID: &str = "__cnv_yes";
+
+/// Confirmation callback for destructive verbs, given the command (`noun verb`)
+pub type ConfirmPrompt = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Side-effect class declared with `#[verb(effect = "...")]`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerbEffect {
+    /// Only reads state (the default)
+    #[default]
+    ReadOnly,
+    /// Changes state in a recoverable way
+    Mutating,
+    /// Deletes or irreversibly changes state; asks for confirmation
+    Destructive,
+}
+
+impl VerbEffect {
+    /// Name as written in `#[verb(effect = "...")]`
+    pub fn name(self) -> &'static str {
+        match self {
+            VerbEffect::ReadOnly => "read_only",
+            VerbEffect::Mutating => "mutating",
+            VerbEffect::Destructive => "destructive",
+        }
+    }
+
+    /// Whether running the verb needs confirmation or `--yes`
+    pub fn requires_confirmation(self) -> bool {
+        matches!(self, VerbEffect::Destructive)
+    }
+}
+
+impl fmt::Display for VerbEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for VerbEffect {
+    type Err = crate::error::NounVerbError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read_only" => Ok(VerbEffect::ReadOnly),
+            "mutating" => Ok(VerbEffect::Mutating),
+            "destructive" => Ok(VerbEffect::Destructive),
+            other => Err(crate::error::NounVerbError::argument_error(format!(
+                "Unknown effect '{}' (expected read_only, mutating or destructive)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Effect and dry-run support of a verb
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct VerbSafety {
+    /// Side-effect class
+    pub effect: VerbEffect,
+    /// Whether the verb accepts `--dry-run`
+    pub dry_run: bool,
+}
+
+impl VerbSafety {
+    /// Safety declaration with the given effect and no dry-run support
+    pub fn new(effect: VerbEffect) -> Self {
+        Self { effect, dry_run: false }
+    }
+
+    /// Advertise `--dry-run`
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Effect metadata as used by the autonomic layer
+    #[cfg(feature = "autonomic")]
+    pub fn effect_metadata(self) -> crate::autonomic::EffectMetadata {
+        use crate::autonomic::{EffectMetadata, EffectType, Sensitivity};
+
+        let metadata = match self.effect {
+            VerbEffect::ReadOnly => EffectMetadata::new(EffectType::ReadOnly),
+            VerbEffect::Mutating => EffectMetadata::new(EffectType::MutateState)
+                .with_sensitivity(Sensitivity::Medium)
+                .with_idempotent(false),
+            VerbEffect::Destructive => EffectMetadata::new(EffectType::MutateState)
+                .with_sensitivity(Sensitivity::High)
+                .with_idempotent(false),
+        };
+        if self.dry_run {
+            metadata.supports_dry_run()
+        } else {
+            metadata
+        }
+    }
+}
+
+/// Ask on stderr whether to run a destructive command (interactive stdin only)
+pub(crate) fn prompt_confirmation(command: &str) -> bool {
+    use std::io::Write;
+
+    eprint!("'{}' is destructive and cannot be undone. Continue? [y/N] ", command);
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
@@ -18,6 +18,23 @@ pub fn apply_value_parser(arg: &mut clap::Arg, vp_str: &str) -> bool {
         return false;
     }
 
+    // Match: PossibleValuesParser::new(["a", "b"]) - fixed set of string values
+    // Example: clap::builder::PossibleValuesParser::new(["fast", "safe"])
+    if vp_str.contains("PossibleValuesParser") {
+        // Box::leak: clap's possible values require &'static str
+        let values: Vec<&'static str> = vp_str
+            .split('"')
+            .skip(1)
+            .step_by(2)
+            // The stored expression may still carry the escapes of its string literal
+            .map(|value| &*Box::leak(value.trim_end_matches('\\').to_string().into_boxed_str()))
+            .collect();
+        if !values.is_empty() {
+            *arg = arg.clone().value_parser(clap::builder::PossibleValuesParser::new(values));
+            return true;
+        }
+    }
+
     // Match: clap::value_parser!(T) - simple type parser (no range)
     // Example: clap::value_parser!(PathBuf)
     if vp_str.contains("value_parser!") {
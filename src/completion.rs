@@ -20,6 +20,9 @@
 //! let completion = generate_completion(&cmd, Shell::Bash, "myapp");
 //! println!("{}", completion);
 //! ```
+//!
+//! These scripts are static. For argument values computed at runtime, see
+//! [`cli::complete`](crate::cli::complete) and `#[arg(complete = fn_name)]`.
 
 use clap::Command;
 use std::fmt;
@@ -19,10 +19,29 @@
 //!
 //! let args: Vec<String> = config.to_cli_args();
 //! ```
+//!
+//! # Layered configuration for `#[verb]` arguments
+//!
+//! [`LayeredConfig`] feeds verb arguments keyed by `noun.verb.arg` (or
+//! `verb.arg` for root verbs) into the command registry. Precedence, lowest
+//! first: built-in defaults, system config, user config (XDG), project
+//! config, the `--profile` section, environment variables, CLI flags.
+//!
+//! ```rust,ignore
+//! use clap_noun_verb::config::LayeredConfig;
+//!
+//! let registry = clap_noun_verb::cli::registry::CommandRegistry::get();
+//! let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+//! registry.set_config(LayeredConfig::discover("myctl")?);
+//! // myctl deploy app --profile prod
+//! // myctl config show --origin
+//! ```
 
 use crate::Result;
+use serde::Serialize;
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::path::{Path, PathBuf};
 
 /// Configuration loader supporting multiple formats
@@ -158,6 +177,35 @@ impl Config {
         self.data.get(key).cloned()
     }
 
+    /// Get a value by dotted key (e.g. `deploy.app.replicas`)
+    ///
+    /// Nested tables are walked segment by segment; a literal dotted key at
+    /// any level is matched as well. Tables themselves are not returned.
+    pub fn lookup(&self, key: &str) -> Option<&Value> {
+        fn walk<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+            let obj = value.as_object()?;
+            if let Some(found) = obj.get(key).filter(|v| !v.is_object()) {
+                return Some(found);
+            }
+            key.match_indices('.').find_map(|(idx, _)| walk(obj.get(&key[..idx])?, &key[idx + 1..]))
+        }
+        walk(&self.data, key)
+    }
+
+    /// The whole configuration as a JSON value
+    pub fn value(&self) -> &Value {
+        &self.data
+    }
+
+    /// Sub-configuration stored under a top-level table (e.g. one profile)
+    pub fn section(&self, path: &str) -> Option<Config> {
+        let mut value = &self.data;
+        for segment in path.split('.') {
+            value = value.get(segment)?;
+        }
+        value.is_object().then(|| Self { data: value.clone() })
+    }
+
     /// Get string value
     pub fn get_string(&self, key: &str) -> Option<String> {
         self.data.get(key)?.as_str().map(String::from)
@@ -253,6 +301,248 @@ impl Default for Config {
     }
 }
 
+/// Where an effective configuration value came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSource {
+    /// Default declared on the argument
+    Default,
+    /// System-wide file (`/etc/<app>/config.*`)
+    System,
+    /// Per-user file (`$XDG_CONFIG_HOME/<app>/config.*`)
+    User,
+    /// Project file in the working directory (`./.<app>.*`)
+    Project,
+    /// `profiles.<name>` section selected with `--profile`
+    Profile,
+    /// Environment variable
+    Env,
+    /// Command-line flag
+    Cli,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::System => write!(f, "system"),
+            Self::User => write!(f, "user"),
+            Self::Project => write!(f, "project"),
+            Self::Profile => write!(f, "profile"),
+            Self::Env => write!(f, "env"),
+            Self::Cli => write!(f, "cli"),
+        }
+    }
+}
+
+/// An effective configuration value and its origin
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigValue {
+    /// The value
+    pub value: Value,
+    /// Layer that supplied it
+    pub origin: ConfigSource,
+    /// File path, environment variable or profile name behind the value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl ConfigValue {
+    /// Create a value from the given layer
+    pub fn new(value: Value, origin: ConfigSource, location: Option<String>) -> Self {
+        Self { value, origin, location }
+    }
+}
+
+/// One configuration file in a [`LayeredConfig`]
+#[derive(Debug, Clone)]
+struct ConfigLayer {
+    source: ConfigSource,
+    path: Option<PathBuf>,
+    config: Config,
+}
+
+/// File extensions tried for each configuration location, in order
+const CONFIG_EXTENSIONS: [&str; 4] = ["toml", "yaml", "yml", "json"];
+
+/// Top-level table holding named profiles
+const PROFILES_KEY: &str = "profiles";
+
+/// Layered configuration feeding `#[verb]` arguments
+///
+/// Values are keyed by `noun.verb.arg`; nested nouns use their dotted path
+/// (`cloud.vm.create.size`). File layers are consulted from the most to the
+/// least specific, after the active profile and environment variables
+/// named `<APP>_<NOUN>_<VERB>_<ARG>`.
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    app_name: String,
+    /// File layers, lowest precedence first
+    layers: Vec<ConfigLayer>,
+    /// Prefix of the per-argument environment variables
+    env_prefix: String,
+}
+
+impl LayeredConfig {
+    /// Create an empty layered configuration for `app_name`
+    pub fn new(app_name: impl Into<String>) -> Self {
+        let app_name = app_name.into();
+        let env_prefix = env_name(&app_name);
+        Self { app_name, layers: Vec::new(), env_prefix }
+    }
+
+    /// Load the system, user and project files for `app_name` that exist
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an existing file cannot be read or parsed.
+    pub fn discover(app_name: impl Into<String>) -> Result<Self> {
+        let mut config = Self::new(app_name);
+        let locations = [
+            (ConfigSource::System, Self::system_dir(&config.app_name).map(|d| d.join("config"))),
+            (ConfigSource::User, Self::user_dir(&config.app_name).map(|d| d.join("config"))),
+            (ConfigSource::Project, Some(PathBuf::from(format!(".{}", config.app_name)))),
+        ];
+        for (source, base) in locations {
+            let Some(base) = base else { continue };
+            let found =
+                CONFIG_EXTENSIONS.iter().map(|ext| base.with_extension(ext)).find(|p| p.is_file());
+            if let Some(path) = found {
+                config = config.with_file(source, path)?;
+            }
+        }
+        Ok(config)
+    }
+
+    /// System configuration directory (`/etc/<app>`), on Unix
+    pub fn system_dir(app_name: &str) -> Option<PathBuf> {
+        cfg!(unix).then(|| Path::new("/etc").join(app_name))
+    }
+
+    /// User configuration directory (`$XDG_CONFIG_HOME/<app>` or `~/.config/<app>`)
+    pub fn user_dir(app_name: &str) -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+        Some(base.join(app_name))
+    }
+
+    /// Add a layer read from `path`
+    ///
+    /// Layers of the same source added later take precedence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn with_file(self, source: ConfigSource, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let config = Config::from_file(path)?;
+        Ok(self.push_layer(source, Some(path.to_path_buf()), config))
+    }
+
+    /// Add an in-memory layer
+    pub fn with_config(self, source: ConfigSource, config: Config) -> Self {
+        self.push_layer(source, None, config)
+    }
+
+    /// Override the environment variable prefix (defaults to the upper-cased app name)
+    pub fn with_env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = prefix.into();
+        self
+    }
+
+    fn push_layer(mut self, source: ConfigSource, path: Option<PathBuf>, config: Config) -> Self {
+        let index =
+            self.layers.iter().rposition(|layer| layer.source <= source).map_or(0, |i| i + 1);
+        self.layers.insert(index, ConfigLayer { source, path, config });
+        self
+    }
+
+    /// Application name
+    pub fn app_name(&self) -> &str {
+        &self.app_name
+    }
+
+    /// Environment variable consulted for `key` (`deploy.app.replicas` -> `MYCTL_DEPLOY_APP_REPLICAS`)
+    pub fn env_var(&self, key: &str) -> String {
+        format!("{}_{}", self.env_prefix, env_name(key))
+    }
+
+    /// Environment variable selecting the profile when `--profile` is absent
+    pub fn profile_env_var(&self) -> String {
+        format!("{}_PROFILE", self.env_prefix)
+    }
+
+    /// Profile selected by `--profile <name>` in `args`, else by the profile environment variable
+    pub fn active_profile(&self, args: &[String]) -> Option<String> {
+        let mut args = args.iter().skip(1).take_while(|arg| arg.as_str() != "--");
+        while let Some(arg) = args.next() {
+            if arg == "--profile" {
+                return args.next().cloned();
+            }
+            if let Some(name) = arg.strip_prefix("--profile=") {
+                return Some(name.to_string());
+            }
+        }
+        std::env::var(self.profile_env_var()).ok().filter(|name| !name.trim().is_empty())
+    }
+
+    /// Effective value for `key`, ignoring built-in defaults and CLI flags
+    pub fn resolve(&self, key: &str, profile: Option<&str>) -> Option<ConfigValue> {
+        let env_var = self.env_var(key);
+        if let Ok(value) = std::env::var(&env_var) {
+            return Some(ConfigValue::new(Value::String(value), ConfigSource::Env, Some(env_var)));
+        }
+
+        if let Some(profile) = profile {
+            let section = format!("{}.{}", PROFILES_KEY, profile);
+            let found = self
+                .layers
+                .iter()
+                .rev()
+                .find_map(|layer| layer.config.section(&section)?.lookup(key).cloned());
+            if let Some(value) = found {
+                return Some(ConfigValue::new(
+                    value,
+                    ConfigSource::Profile,
+                    Some(profile.to_string()),
+                ));
+            }
+        }
+
+        if key.split('.').next() == Some(PROFILES_KEY) {
+            return None;
+        }
+        self.layers.iter().rev().find_map(|layer| {
+            let value = layer.config.lookup(key)?.clone();
+            let location = layer.path.as_ref().map(|path| path.display().to_string());
+            Some(ConfigValue::new(value, layer.source, location))
+        })
+    }
+
+    /// Effective values for `keys`, sorted by key
+    pub fn resolve_all<'a, I>(
+        &self,
+        keys: I,
+        profile: Option<&str>,
+    ) -> BTreeMap<String, ConfigValue>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        keys.into_iter()
+            .filter_map(|key| Some((key.to_string(), self.resolve(key, profile)?)))
+            .collect()
+    }
+}
+
+/// Upper-case a name and replace everything but ASCII alphanumerics with `_`
+fn env_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -291,4 +581,42 @@ mod tests {
         assert_eq!(map.get("port"), Some(&"8080".to_string()));
         assert_eq!(map.get("host"), Some(&"localhost".to_string()));
     }
+
+    #[test]
+    fn test_lookup_nested_and_dotted_keys() {
+        let config =
+            Config::from_json(r#"{"deploy": {"app": {"replicas": 3}}, "db": {"dump.all": true}}"#)
+                .unwrap();
+        assert_eq!(config.lookup("deploy.app.replicas"), Some(&json!(3)));
+        assert_eq!(config.lookup("db.dump.all"), Some(&json!(true)));
+        assert_eq!(config.lookup("deploy.app"), None);
+    }
+
+    #[test]
+    fn test_layer_precedence_and_profiles() {
+        let system =
+            Config::from_json(r#"{"deploy": {"app": {"replicas": 1, "region": "eu"}}}"#).unwrap();
+        let project = Config::from_json(
+            r#"{"deploy": {"app": {"replicas": 2}},
+                "profiles": {"prod": {"deploy": {"app": {"replicas": 5}}}}}"#,
+        )
+        .unwrap();
+        let config = LayeredConfig::new("cnv-config-test")
+            .with_config(ConfigSource::Project, project)
+            .with_config(ConfigSource::System, system);
+
+        let replicas = config.resolve("deploy.app.replicas", None).unwrap();
+        assert_eq!((replicas.value, replicas.origin), (json!(2), ConfigSource::Project));
+        let region = config.resolve("deploy.app.region", Some("prod")).unwrap();
+        assert_eq!(region.origin, ConfigSource::System);
+        let replicas = config.resolve("deploy.app.replicas", Some("prod")).unwrap();
+        assert_eq!((replicas.value, replicas.origin), (json!(5), ConfigSource::Profile));
+    }
+
+    #[test]
+    fn test_env_var_names() {
+        let config = LayeredConfig::new("my-ctl");
+        assert_eq!(config.env_var("deploy.app.dry-run"), "MY_CTL_DEPLOY_APP_DRY_RUN");
+        assert_eq!(config.profile_env_var(), "MY_CTL_PROFILE");
+    }
 }
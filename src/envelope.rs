@@ -0,0 +1,426 @@
+//! Machine-readable result and error envelopes
+//!
+//! [`StructuredError`] carries an error kind, message, context, suggestions,
+//! source chain and exit code; [`OutputEnvelope`] wraps either a result or an
+//! error in a stable `{"status": ...}` shape.
+//!
+//! The CLI runner uses these for its JSON error mode: when JSON output is
+//! requested (`--format json`) or [`ErrorFormat::Auto`] detects a non-TTY,
+//! every failure - including clap parse errors - is written to stderr as one
+//! error envelope:
+//!
+//! ```json
+//! {
+//!   "status": "error",
+//!   "error": {
+//!     "kind": "invalid_subcommand",
+//!     "message": "unrecognized subcommand 'lst'",
+//!     "suggestions": ["list"],
+//!     "exit_code": 2
+//!   }
+//! }
+//! ```
+
+use crate::error::{ExitCodeClass, NounVerbError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Structured error for machine-readable error reporting
+///
+/// Provides:
+/// - Error code/kind for classification
+/// - Human-readable message
+/// - Optional context (key-value pairs)
+/// - Suggestions for recovery (e.g. "did you mean")
+/// - Optional source chain
+/// - Consistent exit code mapping
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructuredError {
+    /// Error kind/code for machine consumption
+    pub kind: String,
+    /// Human-readable error message
+    pub message: String,
+    /// Additional context (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<HashMap<String, serde_json::Value>>,
+    /// Suggested corrections, most likely first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+    /// Source error chain (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<Box<StructuredError>>,
+    /// Exit code for this error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<u8>,
+}
+
+impl StructuredError {
+    /// Create a new structured error
+    pub fn new(kind: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            message: message.into(),
+            context: None,
+            suggestions: Vec::new(),
+            source: None,
+            exit_code: Some(ExitCodeClass::GeneralError as u8),
+        }
+    }
+
+    /// Add context to the error
+    pub fn with_context(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        let context = self.context.get_or_insert_with(HashMap::new);
+        if let Ok(json_value) = serde_json::to_value(value) {
+            context.insert(key.into(), json_value);
+        }
+        self
+    }
+
+    /// Add suggestions, skipping duplicates
+    pub fn with_suggestions<I, S>(mut self, suggestions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for suggestion in suggestions {
+            let suggestion = suggestion.into();
+            if !self.suggestions.contains(&suggestion) {
+                self.suggestions.push(suggestion);
+            }
+        }
+        self
+    }
+
+    /// Set the exit code
+    pub fn with_exit_code(mut self, code: ExitCodeClass) -> Self {
+        self.exit_code = Some(code as u8);
+        self
+    }
+
+    /// Add a source error
+    pub fn with_source(mut self, source: StructuredError) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    /// Get the exit code
+    pub fn exit_code(&self) -> u8 {
+        self.exit_code.unwrap_or(ExitCodeClass::GeneralError as u8)
+    }
+
+    /// Build a structured error from a clap parse error
+    ///
+    /// The kind is the snake_case clap error kind (e.g. `unknown_argument`),
+    /// suggestions come from clap's error context and the exit code is
+    /// [`ExitCodeClass::UsageError`].
+    pub fn from_clap_error(error: &clap::Error) -> Self {
+        use clap::error::{ContextKind, ContextValue};
+
+        let rendered = error.render().to_string();
+        let message = rendered
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches("error: ")
+            .trim()
+            .to_string();
+
+        let mut structured = Self::new(clap_error_kind(error.kind()), message)
+            .with_exit_code(ExitCodeClass::UsageError);
+        for (kind, value) in error.context() {
+            let suggestions = match (kind, value) {
+                (
+                    ContextKind::SuggestedSubcommand
+                    | ContextKind::SuggestedArg
+                    | ContextKind::SuggestedValue,
+                    ContextValue::String(s),
+                ) => vec![s.clone()],
+                (
+                    ContextKind::SuggestedSubcommand
+                    | ContextKind::SuggestedArg
+                    | ContextKind::SuggestedValue,
+                    ContextValue::Strings(items),
+                ) => items.clone(),
+                (
+                    ContextKind::InvalidArg | ContextKind::InvalidSubcommand,
+                    ContextValue::String(s),
+                ) => {
+                    structured = structured.with_context("argument", s);
+                    continue;
+                }
+                (ContextKind::InvalidValue, ContextValue::String(s)) => {
+                    structured = structured.with_context("value", s);
+                    continue;
+                }
+                _ => continue,
+            };
+            structured = structured.with_suggestions(suggestions);
+        }
+        structured
+    }
+}
+
+/// Snake_case name of a clap error kind
+fn clap_error_kind(kind: clap::error::ErrorKind) -> &'static str {
+    use clap::error::ErrorKind;
+
+    match kind {
+        ErrorKind::InvalidValue => "invalid_value",
+        ErrorKind::UnknownArgument => "unknown_argument",
+        ErrorKind::InvalidSubcommand => "invalid_subcommand",
+        ErrorKind::NoEquals => "no_equals",
+        ErrorKind::ValueValidation => "value_validation",
+        ErrorKind::TooManyValues => "too_many_values",
+        ErrorKind::TooFewValues => "too_few_values",
+        ErrorKind::WrongNumberOfValues => "wrong_number_of_values",
+        ErrorKind::ArgumentConflict => "argument_conflict",
+        ErrorKind::MissingRequiredArgument => "missing_required_argument",
+        ErrorKind::MissingSubcommand => "missing_subcommand",
+        ErrorKind::InvalidUtf8 => "invalid_utf8",
+        ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand => "missing_subcommand",
+        _ => "usage_error",
+    }
+}
+
+impl From<&NounVerbError> for StructuredError {
+    fn from(error: &NounVerbError) -> Self {
+        let structured = Self::new(error.kind(), error.to_string());
+        let structured = match error {
+            NounVerbError::CommandNotFound { noun } => structured.with_context("noun", noun),
+            NounVerbError::VerbNotFound { noun, verb } => {
+                structured.with_context("noun", noun).with_context("verb", verb)
+            }
+            NounVerbError::PolicyDenied { command, suggestion, .. } => {
+                structured.with_context("command", command).with_suggestions(suggestion.clone())
+            }
+            NounVerbError::BudgetExceeded { command, usage } => {
+                structured.with_context("command", command).with_context("guard", usage)
+            }
+            _ => structured,
+        };
+        Self { exit_code: Some(error.exit_code()), ..structured }
+    }
+}
+
+impl From<NounVerbError> for StructuredError {
+    fn from(error: NounVerbError) -> Self {
+        Self::from(&error)
+    }
+}
+
+impl fmt::Display for StructuredError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.kind, self.message)?;
+        if let Some(ctx) = &self.context {
+            write!(f, " (context: {:?})", ctx)?;
+        }
+        if let Some(source) = &self.source {
+            write!(f, "\nCaused by: {}", source)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StructuredError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None // We store source as StructuredError, not dyn Error
+    }
+}
+
+/// Convert standard errors to structured errors
+impl From<Box<dyn std::error::Error>> for StructuredError {
+    fn from(err: Box<dyn std::error::Error>) -> Self {
+        Self::new("error", err.to_string())
+    }
+}
+
+impl From<String> for StructuredError {
+    fn from(msg: String) -> Self {
+        Self::new("error", msg)
+    }
+}
+
+impl From<&str> for StructuredError {
+    fn from(msg: &str) -> Self {
+        Self::new("error", msg)
+    }
+}
+
+/// Result type for structured operations
+pub type StructuredResult<T> = Result<T, StructuredError>;
+
+/// Output envelope for wrapping verb results
+///
+/// Provides a consistent structure for all command outputs:
+///
+/// ```json
+/// {
+///   "status": "success" | "error",
+///   "data": { ... },
+///   "error": { ... },
+///   "metadata": { ... }
+/// }
+/// ```
+///
+/// The envelope is optional and can be disabled for simpler output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum OutputEnvelope<T> {
+    /// Success response with data
+    Success {
+        /// The actual payload
+        data: T,
+        /// Optional metadata (execution time, version, etc.)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    },
+    /// Error response
+    Error {
+        /// Structured error information
+        error: StructuredError,
+        /// Optional metadata
+        #[serde(skip_serializing_if = "Option::is_none")]
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    },
+}
+
+impl<T> OutputEnvelope<T> {
+    /// Create a success envelope
+    pub fn success(data: T) -> Self {
+        Self::Success { data, metadata: None }
+    }
+
+    /// Create an error envelope
+    pub fn error(error: StructuredError) -> Self {
+        Self::Error { error, metadata: None }
+    }
+
+    /// Add metadata to the envelope
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        let metadata_map = match &mut self {
+            Self::Success { metadata, .. } => metadata,
+            Self::Error { metadata, .. } => metadata,
+        };
+
+        let map = metadata_map.get_or_insert_with(HashMap::new);
+        if let Ok(json_value) = serde_json::to_value(value) {
+            map.insert(key.into(), json_value);
+        }
+        self
+    }
+
+    /// Check if this is a success
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success { .. })
+    }
+
+    /// Check if this is an error
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Error { .. })
+    }
+}
+
+impl<T> From<Result<T, StructuredError>> for OutputEnvelope<T> {
+    fn from(result: Result<T, StructuredError>) -> Self {
+        match result {
+            Ok(data) => Self::success(data),
+            Err(error) => Self::error(error),
+        }
+    }
+}
+
+/// How the CLI runner reports errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// Human-readable text (`Error: ...`), unless JSON output is requested
+    #[default]
+    Text,
+    /// Always emit a JSON error envelope on stderr
+    Json,
+    /// JSON envelope when stdout is not a terminal, text otherwise
+    Auto,
+}
+
+impl ErrorFormat {
+    /// Whether errors should be emitted as JSON envelopes
+    ///
+    /// `json_requested` is true when the invocation asked for JSON output
+    /// (e.g. `--format json`), which always selects the envelope.
+    pub fn use_json(self, json_requested: bool) -> bool {
+        json_requested
+            || match self {
+                Self::Text => false,
+                Self::Json => true,
+                Self::Auto => !crate::shell::is_interactive(),
+            }
+    }
+}
+
+impl std::str::FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "auto" => Ok(Self::Auto),
+            _ => Err(format!("Unknown error format: '{}'. Valid options: text, json, auto", s)),
+        }
+    }
+}
+
+/// Serialize an error envelope as a single line of JSON
+pub fn render_error_envelope(error: &StructuredError) -> String {
+    let envelope: OutputEnvelope<()> = OutputEnvelope::error(error.clone());
+    serde_json::to_string(&envelope).unwrap_or_else(|_| {
+        serde_json::json!({
+            "status": "error",
+            "error": { "kind": "error", "message": error.message },
+        })
+        .to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noun_verb_error_carries_kind_context_and_exit_code() {
+        let error = StructuredError::from(NounVerbError::verb_not_found("user", "lst"));
+
+        assert_eq!(error.kind, "verb_not_found");
+        assert_eq!(error.exit_code(), 2);
+        let context = error.context.unwrap_or_default();
+        assert_eq!(context.get("verb"), Some(&serde_json::json!("lst")));
+    }
+
+    #[test]
+    fn test_clap_error_suggestions_are_collected() {
+        let cmd = clap::Command::new("cli")
+            .subcommand(clap::Command::new("list"))
+            .subcommand(clap::Command::new("create"));
+
+        let error = cmd
+            .try_get_matches_from(["cli", "lst"])
+            .err()
+            .map(|e| StructuredError::from_clap_error(&e));
+
+        let error = error.unwrap_or_else(|| StructuredError::new("none", ""));
+        assert_eq!(error.kind, "invalid_subcommand");
+        assert_eq!(error.suggestions, vec!["list".to_string()]);
+        assert_eq!(error.exit_code(), 2);
+    }
+
+    #[test]
+    fn test_error_envelope_is_single_line_json() {
+        let rendered = render_error_envelope(&StructuredError::new("timeout", "too slow"));
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap_or_default();
+
+        assert!(!rendered.contains('\n'));
+        assert_eq!(value["status"], "error");
+        assert_eq!(value["error"]["kind"], "timeout");
+        assert!(value["error"].get("suggestions").is_none());
+    }
+}
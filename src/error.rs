@@ -1,9 +1,88 @@
 //! Error types for clap-noun-verb
 
+use std::process::ExitCode;
 use thiserror::Error;
 
+/// Exit codes for structured error reporting
+///
+/// Provides consistent exit codes across all CNV applications:
+/// - 0: Success
+/// - 1: General error
+/// - 2: Usage error (invalid arguments)
+/// - 3: Input error (invalid input data)
+/// - 4: Not found (resource doesn't exist)
+/// - 5: Permission denied
+/// - 6: Timeout
+/// - 7: Unavailable (service or dependency unavailable)
+/// - 8: Budget exceeded (latency, CPU or memory budget of a verb)
+/// - 9-63: Reserved for future use
+/// - 64+: Application-specific
+/// - 130: Interrupted (Ctrl-C, following the shell's 128 + SIGINT convention)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCodeClass {
+    /// Success (0)
+    Success = 0,
+    /// General error (1)
+    GeneralError = 1,
+    /// Usage error - invalid arguments (2)
+    UsageError = 2,
+    /// Input error - invalid input data (3)
+    InputError = 3,
+    /// Resource not found (4)
+    NotFound = 4,
+    /// Permission denied (5)
+    PermissionDenied = 5,
+    /// Operation timeout (6)
+    Timeout = 6,
+    /// Service or dependency unavailable (7)
+    Unavailable = 7,
+    /// Resource budget exceeded (8)
+    BudgetExceeded = 8,
+    /// Interrupted by the user (130)
+    Interrupted = 130,
+}
+
+impl ExitCodeClass {
+    /// Numeric process exit code
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Class of a process exit code, if it is one of the framework's codes
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => Self::Success,
+            1 => Self::GeneralError,
+            2 => Self::UsageError,
+            3 => Self::InputError,
+            4 => Self::NotFound,
+            5 => Self::PermissionDenied,
+            6 => Self::Timeout,
+            7 => Self::Unavailable,
+            8 => Self::BudgetExceeded,
+            130 => Self::Interrupted,
+            _ => return None,
+        })
+    }
+}
+
+impl From<ExitCodeClass> for ExitCode {
+    fn from(class: ExitCodeClass) -> Self {
+        ExitCode::from(class as u8)
+    }
+}
+
+impl From<ExitCodeClass> for u8 {
+    fn from(class: ExitCodeClass) -> Self {
+        class as u8
+    }
+}
+
 /// Errors that can occur in the noun-verb CLI framework
+///
+/// New variants may be added in minor releases; match with a wildcard arm.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum NounVerbError {
     /// Command not found
     #[error("Command '{noun}' not found")]
@@ -44,17 +123,131 @@ pub enum NounVerbError {
     /// Generic error wrapper
     #[error("Error: {0}")]
     Generic(String),
+
+    /// Operation exceeded its time limit
+    #[error("Operation timed out: {message}")]
+    Timeout { message: String },
+
+    /// Operation interrupted by the user (Ctrl-C)
+    #[error("Interrupted: {message}")]
+    Interrupted { message: String },
+
+    /// Invocation refused by the registry's policy engine
+    #[error("Denied by policy: {reason}")]
+    PolicyDenied { command: String, reason: String, suggestion: Option<String> },
+
+    /// Verb exceeded its latency, CPU or memory budget
+    #[error("'{command}' exceeded its budget: {}", .usage.violations().join(", "))]
+    BudgetExceeded { command: String, usage: crate::cli::budget::BudgetUsage },
+
+    /// Failure with an explicit process exit code chosen by the handler
+    #[error("{message}")]
+    Exit { code: u8, message: String },
 }
 
 impl NounVerbError {
-    /// Enhance error with recovery suggestions from RDF guard validation
-    ///
-    /// Attempts to provide helpful suggestions using the RDF ontology and SPARQL queries.
+    /// Render the error with "did you mean" suggestions
     ///
-    /// FUTURE: v5.1 - Complete RDF recovery suggestions
+    /// Unknown nouns and verbs are matched by edit distance against the global
+    /// command registry. If the registry is locked by the caller (e.g. inside a
+    /// handler), the plain message is returned; use
+    /// [`CommandRegistry::suggestions_for`](crate::cli::registry::CommandRegistry::suggestions_for)
+    /// with the held registry instead.
     pub fn with_recovery_suggestions(self) -> String {
-        // RDF-control feature deferred to v5.1
-        self.to_string()
+        let suggestions = crate::cli::registry::CommandRegistry::try_global_suggestions_for(&self);
+        if suggestions.is_empty() {
+            self.to_string()
+        } else {
+            format!("{}\n\n  Did you mean '{}'?", self, suggestions.join("', '"))
+        }
+    }
+
+    /// Process exit code for this error
+    ///
+    /// Framework errors map onto [`ExitCodeClass`]; [`NounVerbError::Exit`]
+    /// carries the handler's own code. An error never exits with 0: an `Exit`
+    /// code of 0 becomes [`ExitCodeClass::GeneralError`].
+    pub fn exit_code(&self) -> u8 {
+        let class = match self {
+            Self::CommandNotFound { .. }
+            | Self::VerbNotFound { .. }
+            | Self::InvalidStructure { .. }
+            | Self::ArgumentError { .. } => ExitCodeClass::UsageError,
+            Self::ValidationFailed(_) => ExitCodeClass::InputError,
+            Self::PluginError(_) => ExitCodeClass::Unavailable,
+            Self::Timeout { .. } => ExitCodeClass::Timeout,
+            Self::Interrupted { .. } => ExitCodeClass::Interrupted,
+            Self::PolicyDenied { .. } => ExitCodeClass::PermissionDenied,
+            Self::BudgetExceeded { .. } => ExitCodeClass::BudgetExceeded,
+            Self::Exit { code: 0, .. } => ExitCodeClass::GeneralError,
+            Self::Exit { code, .. } => return *code,
+            Self::ExecutionError { .. }
+            | Self::MiddlewareError(_)
+            | Self::TelemetryError(_)
+            | Self::Generic(_) => ExitCodeClass::GeneralError,
+        };
+        class.code()
+    }
+
+    /// Stable snake_case name of this error's kind, for machine-readable output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::CommandNotFound { .. } => "command_not_found",
+            Self::VerbNotFound { .. } => "verb_not_found",
+            Self::InvalidStructure { .. } => "invalid_structure",
+            Self::ExecutionError { .. } => "execution_error",
+            Self::ArgumentError { .. } => "argument_error",
+            Self::PluginError(_) => "plugin_error",
+            Self::ValidationFailed(_) => "validation_failed",
+            Self::MiddlewareError(_) => "middleware_error",
+            Self::TelemetryError(_) => "telemetry_error",
+            Self::Generic(_) => "error",
+            Self::Timeout { .. } => "timeout",
+            Self::Interrupted { .. } => "interrupted",
+            Self::PolicyDenied { .. } => "policy_denied",
+            Self::BudgetExceeded { .. } => "budget_exceeded",
+            Self::Exit { .. } => "exit",
+        }
+    }
+
+    /// Create an error for an invocation of `command` denied by policy
+    pub fn policy_denied(
+        command: impl Into<String>,
+        reason: impl Into<String>,
+        suggestion: Option<String>,
+    ) -> Self {
+        Self::PolicyDenied { command: command.into(), reason: reason.into(), suggestion }
+    }
+
+    /// Create an error for an invocation of `command` that exceeded its budget
+    pub fn budget_exceeded(
+        command: impl Into<String>,
+        usage: crate::cli::budget::BudgetUsage,
+    ) -> Self {
+        Self::BudgetExceeded { command: command.into(), usage }
+    }
+
+    /// Create an error that exits the process with `code`
+    ///
+    /// Accepts an [`ExitCodeClass`] or a raw code (use 64+ for
+    /// application-specific failures). Code 0 would report success, so it is
+    /// replaced by [`ExitCodeClass::GeneralError`].
+    pub fn exit_with(code: impl Into<u8>, message: impl Into<String>) -> Self {
+        let code = match code.into() {
+            0 => ExitCodeClass::GeneralError.code(),
+            code => code,
+        };
+        Self::Exit { code, message: message.into() }
+    }
+
+    /// Create a timeout error
+    pub fn timeout(message: impl Into<String>) -> Self {
+        Self::Timeout { message: message.into() }
+    }
+
+    /// Create an interrupted error
+    pub fn interrupted(message: impl Into<String>) -> Self {
+        Self::Interrupted { message: message.into() }
     }
 
     /// Create a command not found error
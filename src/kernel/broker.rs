@@ -270,7 +270,7 @@ impl BrokerKernel {
             AdmissionPolicy::Accept => {
                 // Verify capability exists
                 let registry = self.registry.read();
-                if !registry.has(&request.capability_id.as_str()) {
+                if !registry.has(request.capability_id.as_str()) {
                     return Ok(BrokerResponse {
                         request_id: request.request_id.clone(),
                         session_frame: None,
@@ -404,7 +404,7 @@ mod tests {
 
     #[test]
     fn test_broker_capability_registry() {
-        let mut registry = BrokerCapabilityRegistry::new();
+        let registry = BrokerCapabilityRegistry::new();
         assert!(!registry.has("test"));
 
         // In a real test, we'd add actual capabilities
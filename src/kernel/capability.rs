@@ -1,5 +1,7 @@
 //! Capability-based security module
 
+use serde::{Deserialize, Serialize};
+
 /// Security capability token
 #[derive(Debug, Clone)]
 pub struct Capability {
@@ -47,3 +49,350 @@ impl CapabilityManager {
         self.capabilities.remove(user_id);
     }
 }
+
+/// Side-effect class of a verb, ordered from least to most risky
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapabilityClass {
+    /// No side effects
+    Pure,
+    /// Reads from the filesystem
+    #[serde(rename = "read_only_fs")]
+    ReadOnlyFS,
+    /// Reads and writes the filesystem
+    #[serde(rename = "read_write_fs")]
+    ReadWriteFS,
+    /// Performs network I/O
+    Network,
+    /// Spawns subprocesses
+    Subprocess,
+    /// Reads or modifies the process environment
+    Environment,
+    /// Arbitrary, potentially destructive side effects
+    Dangerous,
+}
+
+impl CapabilityClass {
+    /// Relative risk of the class (0 = pure, 6 = dangerous)
+    pub fn risk_level(&self) -> u8 {
+        match self {
+            Self::Pure => 0,
+            Self::ReadOnlyFS => 1,
+            Self::Environment => 2,
+            Self::ReadWriteFS => 3,
+            Self::Network => 4,
+            Self::Subprocess => 5,
+            Self::Dangerous => 6,
+        }
+    }
+}
+
+impl std::fmt::Display for CapabilityClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Pure => "pure",
+            Self::ReadOnlyFS => "read_only_fs",
+            Self::ReadWriteFS => "read_write_fs",
+            Self::Network => "network",
+            Self::Subprocess => "subprocess",
+            Self::Environment => "environment",
+            Self::Dangerous => "dangerous",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Expected runtime and memory envelope of a verb
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceBand {
+    /// Under 10ms, under 1 MiB
+    Instant,
+    /// Under 100ms, under 10 MiB
+    Fast,
+    /// Under 1s, under 100 MiB
+    Medium,
+    /// Under 10s, under 1 GiB
+    Slow,
+    /// Anything slower or larger
+    Cold,
+}
+
+impl ResourceBand {
+    /// Upper bound on runtime for the band, in milliseconds
+    pub fn max_runtime_ms(&self) -> u64 {
+        match self {
+            Self::Instant => 10,
+            Self::Fast => 100,
+            Self::Medium => 1_000,
+            Self::Slow => 10_000,
+            Self::Cold => u64::MAX,
+        }
+    }
+
+    /// Upper bound on memory for the band, in bytes
+    pub fn max_memory_bytes(&self) -> u64 {
+        match self {
+            Self::Instant => 1 << 20,
+            Self::Fast => 10 << 20,
+            Self::Medium => 100 << 20,
+            Self::Slow => 1 << 30,
+            Self::Cold => u64::MAX,
+        }
+    }
+
+    fn risk_weight(&self) -> u8 {
+        match self {
+            Self::Instant => 0,
+            Self::Fast => 2,
+            Self::Medium => 5,
+            Self::Slow => 10,
+            Self::Cold => 15,
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceBand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Instant => "instant",
+            Self::Fast => "fast",
+            Self::Medium => "medium",
+            Self::Slow => "slow",
+            Self::Cold => "cold",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Interface stability guarantee of a verb
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StabilityProfile {
+    /// Covered by semver guarantees
+    Stable,
+    /// May change before stabilizing
+    Preview,
+    /// May change or disappear at any time
+    Experimental,
+    /// Scheduled for removal
+    Deprecated,
+    /// Output differs between runs with the same input
+    NonDeterministic,
+}
+
+impl std::fmt::Display for StabilityProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Stable => "stable",
+            Self::Preview => "preview",
+            Self::Experimental => "experimental",
+            Self::Deprecated => "deprecated",
+            Self::NonDeterministic => "non_deterministic",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Who may invoke a verb without supervision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafetyProfile {
+    /// Agents may invoke the verb autonomously
+    AgentSafe,
+    /// A human must review the invocation
+    HumanReviewRequired,
+    /// Only usable from an interactive terminal
+    InteractiveOnly,
+}
+
+impl std::fmt::Display for SafetyProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::AgentSafe => "agent_safe",
+            Self::HumanReviewRequired => "human_review_required",
+            Self::InteractiveOnly => "interactive_only",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Machine-checkable description of what a verb may do
+///
+/// Agents use the contract to decide whether a verb can run unattended;
+/// the grammar model and capability broker carry it alongside each verb.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityContract {
+    /// Side-effect class
+    pub capability_class: CapabilityClass,
+    /// Runtime and memory envelope
+    pub resource_band: ResourceBand,
+    /// Interface stability
+    pub stability: StabilityProfile,
+    /// Supervision requirement
+    pub safety: SafetyProfile,
+}
+
+impl CapabilityContract {
+    /// Create a contract
+    pub const fn new(
+        capability_class: CapabilityClass,
+        resource_band: ResourceBand,
+        stability: StabilityProfile,
+        safety: SafetyProfile,
+    ) -> Self {
+        Self { capability_class, resource_band, stability, safety }
+    }
+
+    /// Side-effect free, instant, agent-safe
+    pub const fn pure() -> Self {
+        Self::new(
+            CapabilityClass::Pure,
+            ResourceBand::Instant,
+            StabilityProfile::Stable,
+            SafetyProfile::AgentSafe,
+        )
+    }
+
+    /// Filesystem reads, agent-safe
+    pub const fn read_only() -> Self {
+        Self::new(
+            CapabilityClass::ReadOnlyFS,
+            ResourceBand::Fast,
+            StabilityProfile::Stable,
+            SafetyProfile::AgentSafe,
+        )
+    }
+
+    /// Filesystem writes, requires human review
+    pub const fn read_write() -> Self {
+        Self::new(
+            CapabilityClass::ReadWriteFS,
+            ResourceBand::Fast,
+            StabilityProfile::Stable,
+            SafetyProfile::HumanReviewRequired,
+        )
+    }
+
+    /// Network access, agent-safe
+    pub const fn network() -> Self {
+        Self::new(
+            CapabilityClass::Network,
+            ResourceBand::Medium,
+            StabilityProfile::Stable,
+            SafetyProfile::AgentSafe,
+        )
+    }
+
+    /// Arbitrary side effects, requires human review
+    pub const fn dangerous() -> Self {
+        Self::new(
+            CapabilityClass::Dangerous,
+            ResourceBand::Slow,
+            StabilityProfile::Stable,
+            SafetyProfile::HumanReviewRequired,
+        )
+    }
+
+    /// Aggregate risk in `0..=100`, combining class, resources, stability and safety
+    pub fn risk_score(&self) -> u8 {
+        let class = match self.capability_class {
+            CapabilityClass::Pure => 0,
+            CapabilityClass::ReadOnlyFS => 5,
+            CapabilityClass::Environment => 10,
+            CapabilityClass::ReadWriteFS => 15,
+            CapabilityClass::Network => 20,
+            CapabilityClass::Subprocess => 30,
+            CapabilityClass::Dangerous => 50,
+        };
+        let stability = match self.stability {
+            StabilityProfile::Stable => 0,
+            StabilityProfile::Preview => 5,
+            StabilityProfile::Experimental
+            | StabilityProfile::Deprecated
+            | StabilityProfile::NonDeterministic => 10,
+        };
+        let safety = match self.safety {
+            SafetyProfile::AgentSafe => 0,
+            SafetyProfile::HumanReviewRequired => 10,
+            SafetyProfile::InteractiveOnly => 15,
+        };
+        class + self.resource_band.risk_weight() + stability + safety
+    }
+
+    /// Whether an agent may invoke the verb without supervision
+    ///
+    /// Dangerous verbs are never agent-safe, whatever their safety profile says.
+    pub fn is_agent_safe(&self) -> bool {
+        self.safety == SafetyProfile::AgentSafe
+            && self.capability_class != CapabilityClass::Dangerous
+    }
+
+    /// Whether a verb with this contract can run where `required` is granted
+    ///
+    /// Holds when this contract is no riskier and no heavier than `required`.
+    pub fn is_compatible_with(&self, required: &CapabilityContract) -> bool {
+        self.capability_class.risk_level() <= required.capability_class.risk_level()
+            && self.resource_band <= required.resource_band
+    }
+}
+
+impl std::fmt::Display for CapabilityContract {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}, {}, {})",
+            self.capability_class, self.resource_band, self.stability, self.safety
+        )
+    }
+}
+
+/// Runtime view of the operations a [`CapabilityContract`] permits
+#[derive(Debug, Clone)]
+pub struct CapabilityContext {
+    contract: CapabilityContract,
+}
+
+impl CapabilityContext {
+    /// Create a context for `contract`
+    pub fn new(contract: CapabilityContract) -> Self {
+        Self { contract }
+    }
+
+    /// The contract this context enforces
+    pub fn contract(&self) -> &CapabilityContract {
+        &self.contract
+    }
+
+    /// Whether filesystem reads are permitted
+    pub fn can_read_fs(&self) -> bool {
+        matches!(
+            self.contract.capability_class,
+            CapabilityClass::ReadOnlyFS | CapabilityClass::ReadWriteFS | CapabilityClass::Dangerous
+        )
+    }
+
+    /// Whether filesystem writes are permitted
+    pub fn can_write_fs(&self) -> bool {
+        matches!(
+            self.contract.capability_class,
+            CapabilityClass::ReadWriteFS | CapabilityClass::Dangerous
+        )
+    }
+
+    /// Whether network access is permitted
+    pub fn can_access_network(&self) -> bool {
+        matches!(
+            self.contract.capability_class,
+            CapabilityClass::Network | CapabilityClass::Dangerous
+        )
+    }
+
+    /// Whether spawning subprocesses is permitted
+    pub fn can_spawn_subprocess(&self) -> bool {
+        matches!(
+            self.contract.capability_class,
+            CapabilityClass::Subprocess | CapabilityClass::Dangerous
+        )
+    }
+}
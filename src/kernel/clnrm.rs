@@ -141,13 +141,13 @@ impl HermeticContainer {
 
     /// Record a span (called by instrumented code)
     pub fn record_span(&self, span: RecordedSpan) {
-        let mut spans = self.spans.lock().unwrap();
+        let mut spans = self.spans.lock().unwrap_or_else(|e| e.into_inner());
         spans.push(span);
     }
 
     /// Get all recorded spans
     pub fn recorded_spans(&self) -> Vec<RecordedSpan> {
-        let spans = self.spans.lock().unwrap();
+        let spans = self.spans.lock().unwrap_or_else(|e| e.into_inner());
         spans.clone()
     }
 
@@ -158,7 +158,7 @@ impl HermeticContainer {
 
     /// Get quota budget
     pub fn quota(&self) -> std::sync::MutexGuard<'_, QuotaBudget> {
-        self.quota.lock().unwrap()
+        self.quota.lock().unwrap_or_else(|e| e.into_inner())
     }
 
     /// Verify the test execution was hermetic
@@ -17,8 +17,8 @@
 //! - O(1) memory per session
 //! - Linear scalability to 1000+ cores
 
-// Session types are in autonomic module, not kernel::session
-use crate::autonomic::{SessionId, SessionState};
+// Session handles and frames from the session kernel
+use crate::kernel::session::{Frame, SessionHandle, SessionId};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::collections::HashMap;
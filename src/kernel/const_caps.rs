@@ -378,7 +378,7 @@ mod tests {
 
     #[test]
     fn test_validated_command() {
-        let cmd = ValidatedCommand::<Pure, Fast>::new("test-command");
+        let _cmd = ValidatedCommand::<Pure, Fast>::new("test-command");
         assert_eq!(ValidatedCommand::<Pure, Fast>::risk_level(), 0);
         assert!(ValidatedCommand::<Pure, Fast>::is_agent_safe());
     }
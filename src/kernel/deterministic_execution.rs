@@ -177,7 +177,7 @@ impl<'a> DeterministicExecution<'a> {
         };
 
         self.audit_trail.record(instr)
-            .map_err(|e| SyscallError::AuditTrailFull(e))?;
+            .map_err(SyscallError::AuditTrailFull)?;
 
         Ok(result)
     }
@@ -187,7 +187,7 @@ impl<'a> DeterministicExecution<'a> {
         // Deterministic PRNG seeded from frame hash
         let lcg_a = 1664525u64;
         let lcg_c = 1013904223u64;
-        let seed = (self.deterministic_seed.wrapping_mul(lcg_a).wrapping_add(lcg_c));
+        let seed = self.deterministic_seed.wrapping_mul(lcg_a).wrapping_add(lcg_c);
 
         let timestamp_ns = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -201,7 +201,7 @@ impl<'a> DeterministicExecution<'a> {
         };
 
         self.audit_trail.record(instr)
-            .map_err(|e| SyscallError::AuditTrailFull(e))?;
+            .map_err(SyscallError::AuditTrailFull)?;
 
         Ok(seed)
     }
@@ -216,7 +216,7 @@ impl<'a> DeterministicExecution<'a> {
         let instr = DeterministicInstruction::Clock { timestamp_ns };
 
         self.audit_trail.record(instr)
-            .map_err(|e| SyscallError::AuditTrailFull(e))?;
+            .map_err(SyscallError::AuditTrailFull)?;
 
         // Return deterministic timestamp (from frame)
         Ok(timestamp_ns)
@@ -52,7 +52,7 @@ impl TraceContext {
             "00-{}-{}-{}",
             self.trace_id,
             self.span_id,
-            format!("{:02x}", self.trace_flags.to_byte())
+            format_args!("{:02x}", self.trace_flags.to_byte())
         )
     }
 
@@ -266,11 +266,11 @@ impl InMemoryExporter {
     }
 
     pub fn get_spans(&self) -> Vec<Span> {
-        self.spans.lock().unwrap().clone()
+        self.spans.lock().unwrap_or_else(|e| e.into_inner()).clone()
     }
 
     pub fn clear(&self) {
-        self.spans.lock().unwrap().clear();
+        self.spans.lock().unwrap_or_else(|e| e.into_inner()).clear();
     }
 }
 
@@ -282,7 +282,7 @@ impl Default for InMemoryExporter {
 
 impl SpanExporter for InMemoryExporter {
     fn export(&self, spans: Vec<Span>) -> Result<(), String> {
-        let mut all_spans = self.spans.lock().unwrap();
+        let mut all_spans = self.spans.lock().unwrap_or_else(|e| e.into_inner());
         all_spans.extend(spans);
         Ok(())
     }
@@ -323,13 +323,13 @@ impl TracingProvider {
             trace_flags: TraceFlags::default(),
             baggage: HashMap::new(),
         }) {
-            let mut spans = self.active_spans.lock().unwrap();
+            let mut spans = self.active_spans.lock().unwrap_or_else(|e| e.into_inner());
             spans.push(span);
         }
     }
 
     pub fn end_span(&self, span_id: &str) {
-        let mut spans = self.active_spans.lock().unwrap();
+        let mut spans = self.active_spans.lock().unwrap_or_else(|e| e.into_inner());
         if let Some(pos) = spans.iter().position(|s| s.span_id == span_id) {
             let mut span = spans.remove(pos);
             span.end();
@@ -339,7 +339,7 @@ impl TracingProvider {
     }
 
     pub fn flush(&self) -> Result<(), String> {
-        let spans = self.active_spans.lock().unwrap().clone();
+        let spans = self.active_spans.lock().unwrap_or_else(|e| e.into_inner()).clone();
         if !spans.is_empty() {
             self.exporter.export(spans)?;
         }
@@ -32,10 +32,13 @@
 //! }
 //! ```
 
-use crate::cli::registry::{ArgMetadata, __NOUN_REGISTRY, __VERB_REGISTRY};
+use crate::cli::budget::VerbBudget;
+use crate::cli::registry::{ArgMetadata, CommandRegistry, FORMAT_ENV_VAR, NOUN_PATH_SEPARATOR};
+use crate::cli::safety::VerbSafety;
+use crate::format::OutputFormat;
 use crate::kernel::capability::CapabilityContract;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Grammar schema version
 pub const GRAMMAR_SCHEMA_VERSION: &str = "1.0.0";
@@ -88,6 +91,39 @@ pub struct GrammarArgument {
     pub conflicts_with: Vec<String>,
     /// Position index (for positional args)
     pub index: Option<usize>,
+    /// Rust type of the value (e.g. `u16`, `PathBuf`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_type: Option<String>,
+    /// Shell completion hint (e.g. `file_path`, `url`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_hint: Option<String>,
+    /// Alternative long names
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Long help text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_help: Option<String>,
+    /// Minimum numeric value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<String>,
+    /// Maximum numeric value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_value: Option<String>,
+    /// Minimum string length
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    /// Maximum string length
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// Must be the only argument given
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exclusive: bool,
+    /// Propagated to every subcommand
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub global: bool,
+    /// Hidden from help output
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
 }
 
 impl GrammarArgument {
@@ -108,7 +144,7 @@ impl GrammarArgument {
 
         // Generate long flag from name if not a positional arg
         let long = if meta.positional.is_none() {
-            Some(meta.name.clone())
+            Some(meta.long.clone().unwrap_or_else(|| meta.name.clone()))
         } else {
             None
         };
@@ -129,8 +165,88 @@ impl GrammarArgument {
             requires: meta.requires.clone(),
             conflicts_with: meta.conflicts_with.clone(),
             index: meta.positional,
+            value_type: meta.value_type.clone(),
+            value_hint: meta.value_hint.clone(),
+            aliases: meta.aliases.clone(),
+            long_help: meta.long_help.clone(),
+            min_value: meta.min_value.clone(),
+            max_value: meta.max_value.clone(),
+            min_length: meta.min_length,
+            max_length: meta.max_length,
+            exclusive: meta.exclusive.unwrap_or(false),
+            global: meta.global,
+            hidden: meta.hide,
         }
     }
+
+    /// The framework's global `--format` argument
+    pub fn format_argument() -> Self {
+        Self {
+            name: "format".to_string(),
+            short: Some('o'),
+            long: Some("format".to_string()),
+            arg_type: ArgumentType::Named,
+            help: Some("Output format".to_string()),
+            required: false,
+            default: Some(OutputFormat::Json.to_string()),
+            env: Some(FORMAT_ENV_VAR.to_string()),
+            value_name: Some("FORMAT".to_string()),
+            possible_values: Some(
+                OutputFormat::available_formats().iter().map(|f| f.to_string()).collect(),
+            ),
+            multiple: false,
+            group: None,
+            requires: Vec::new(),
+            conflicts_with: Vec::new(),
+            index: None,
+            value_type: Some("OutputFormat".to_string()),
+            value_hint: None,
+            aliases: Vec::new(),
+            long_help: None,
+            min_value: None,
+            max_value: None,
+            min_length: None,
+            max_length: None,
+            exclusive: false,
+            global: true,
+            hidden: false,
+        }
+    }
+}
+
+/// Argument group in the grammar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrammarGroup {
+    /// Group name
+    pub name: String,
+    /// Member arguments
+    pub arguments: Vec<String>,
+    /// Whether several members may be given together
+    pub multiple: bool,
+}
+
+impl GrammarGroup {
+    /// Collect the groups declared by a verb's arguments
+    ///
+    /// A group is exclusive (`multiple: false`) if any member is exclusive,
+    /// matching how the CLI builds its `ArgGroup`s.
+    pub fn from_metadata(args: &[ArgMetadata]) -> Vec<Self> {
+        let mut groups: BTreeMap<&str, Self> = BTreeMap::new();
+        for arg in args {
+            if let Some(group_name) = arg.group.as_deref() {
+                let group = groups.entry(group_name).or_insert_with(|| Self {
+                    name: group_name.to_string(),
+                    arguments: Vec::new(),
+                    multiple: true,
+                });
+                group.arguments.push(arg.name.clone());
+                if arg.exclusive.unwrap_or(false) {
+                    group.multiple = false;
+                }
+            }
+        }
+        groups.into_values().collect()
+    }
 }
 
 /// Verb metadata in the grammar
@@ -138,7 +254,7 @@ impl GrammarArgument {
 pub struct GrammarVerb {
     /// Verb name
     pub name: String,
-    /// Parent noun
+    /// Parent noun (dotted path for nested nouns, empty for root verbs)
     pub noun: String,
     /// Help text
     pub help: Option<String>,
@@ -146,6 +262,9 @@ pub struct GrammarVerb {
     pub long_help: Option<String>,
     /// Arguments
     pub arguments: Vec<GrammarArgument>,
+    /// Argument groups
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<GrammarGroup>,
     /// Whether deprecated
     pub deprecated: bool,
     /// Deprecation message
@@ -187,6 +306,9 @@ pub struct GrammarModel {
     pub app_version: Option<String>,
     /// Top-level nouns
     pub nouns: Vec<GrammarNoun>,
+    /// Verbs invoked without a noun (`app <verb>`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub root_verbs: Vec<GrammarVerb>,
     /// Global arguments (available to all commands)
     pub global_arguments: Vec<GrammarArgument>,
     /// Additional metadata
@@ -202,11 +324,106 @@ impl GrammarModel {
             app_name: app_name.into(),
             app_version: None,
             nouns: Vec::new(),
+            root_verbs: Vec::new(),
             global_arguments: Vec::new(),
             metadata: HashMap::new(),
         }
     }
 
+    /// Build the grammar of every command in a live registry
+    ///
+    /// Nested nouns become `sub_nouns`, nounless verbs become `root_verbs`
+    /// and the framework's `--format` flag is listed as a global argument.
+    /// Nouns and verbs are sorted by name so the output is deterministic;
+    /// arguments keep their declaration order.
+    pub fn from_registry(app_name: impl Into<String>, registry: &CommandRegistry) -> Self {
+        let abouts: HashMap<&str, &str> = registry.get_nouns().into_iter().collect();
+        let mut model = Self::new(app_name);
+        model.nouns = registry
+            .get_sub_nouns("")
+            .into_iter()
+            .map(|path| Self::noun_from_registry(registry, &abouts, path))
+            .collect();
+        model.root_verbs =
+            Self::verbs_from_metadata(registry, "", registry.get_root_verbs_with_metadata());
+        model.add_global_argument(GrammarArgument::format_argument());
+        model
+    }
+
+    fn noun_from_registry(
+        registry: &CommandRegistry,
+        abouts: &HashMap<&str, &str>,
+        path: &str,
+    ) -> GrammarNoun {
+        let name = path.rsplit(NOUN_PATH_SEPARATOR).next().unwrap_or(path);
+        GrammarNoun {
+            name: name.to_string(),
+            help: abouts.get(path).filter(|about| !about.is_empty()).map(|about| about.to_string()),
+            long_help: None,
+            verbs: Self::verbs_from_metadata(
+                registry,
+                path,
+                registry.get_verbs_with_metadata(path),
+            ),
+            sub_nouns: registry
+                .get_sub_nouns(path)
+                .into_iter()
+                .map(|child| Self::noun_from_registry(registry, abouts, child))
+                .collect(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Grammar verbs for a noun path; declared side effects become `effect`
+    /// and `requires_confirmation` metadata
+    fn verbs_from_metadata(
+        registry: &CommandRegistry,
+        noun: &str,
+        mut verbs: Vec<(&str, &str, &Vec<ArgMetadata>)>,
+    ) -> Vec<GrammarVerb> {
+        verbs.sort_unstable_by_key(|(name, _, _)| *name);
+        verbs
+            .into_iter()
+            .map(|(name, about, args)| GrammarVerb {
+                name: name.to_string(),
+                noun: noun.to_string(),
+                help: Some(about.to_string()).filter(|about| !about.is_empty()),
+                long_help: None,
+                arguments: args.iter().map(GrammarArgument::from_metadata).collect(),
+                groups: GrammarGroup::from_metadata(args),
+                deprecated: false,
+                deprecation_message: None,
+                capability: None,
+                metadata: Self::verb_metadata(registry, noun, name),
+            })
+            .collect()
+    }
+
+    /// Declared effect, budget and examples of a registered verb, when not the defaults
+    fn verb_metadata(
+        registry: &crate::cli::registry::CommandRegistry,
+        noun: &str,
+        verb: &str,
+    ) -> HashMap<String, serde_json::Value> {
+        let mut metadata = HashMap::new();
+        if let Some(safety) =
+            registry.verb_safety(noun, verb).filter(|safety| *safety != VerbSafety::default())
+        {
+            metadata.insert("effect".to_string(), serde_json::json!(safety.effect));
+            metadata.insert(
+                "requires_confirmation".to_string(),
+                safety.effect.requires_confirmation().into(),
+            );
+        }
+        if let Some(budget) = registry.verb_budget(noun, verb).filter(VerbBudget::has_limits) {
+            metadata.insert("budget".to_string(), serde_json::json!(budget));
+        }
+        if let Some(examples) = registry.verb_examples(noun, verb).filter(|e| !e.is_empty()) {
+            metadata.insert("examples".to_string(), serde_json::json!(examples));
+        }
+        metadata
+    }
+
     /// Set application version
     pub fn with_version(mut self, version: impl Into<String>) -> Self {
         self.app_version = Some(version.into());
@@ -242,16 +459,12 @@ impl GrammarModel {
 
     /// Get all verbs (flattened)
     pub fn all_verbs(&self) -> Vec<&GrammarVerb> {
-        self.nouns
-            .iter()
-            .flat_map(|n| n.verbs.iter())
-            .collect()
+        self.nouns.iter().flat_map(|n| n.verbs.iter()).collect()
     }
 
     /// Find a verb by noun and verb name
     pub fn find_verb(&self, noun: &str, verb: &str) -> Option<&GrammarVerb> {
-        self.find_noun(noun)
-            .and_then(|n| n.verbs.iter().find(|v| v.name == verb))
+        self.find_noun(noun).and_then(|n| n.verbs.iter().find(|v| v.name == verb))
     }
 }
 
@@ -269,60 +482,9 @@ impl Grammar {
 
     /// Extract grammar with a specific application name
     pub fn extract_with_name(app_name: &str) -> Result<GrammarModel, Box<dyn std::error::Error>> {
-        // Initialize registries
-        for init in __NOUN_REGISTRY {
-            init();
-        }
-        for init in __VERB_REGISTRY {
-            init();
-        }
-
-        let mut model = GrammarModel::new(app_name);
-
-        // Get the command registry
-        let registry = crate::cli::registry::CommandRegistry::get();
+        let registry = CommandRegistry::get();
         let registry = registry.lock().map_err(|e| format!("Failed to lock registry: {}", e))?;
-
-        // Extract nouns and their verbs from registry
-        for (noun_name, noun_about) in registry.get_nouns() {
-            let mut grammar_verbs = Vec::new();
-
-            // Get all verbs for this noun with their metadata
-            for (verb_name, verb_about, args) in registry.get_verbs_with_metadata(noun_name) {
-                // Convert ArgMetadata to GrammarArgument
-                let grammar_args = args
-                    .iter()
-                    .map(|arg_meta| GrammarArgument::from_metadata(arg_meta))
-                    .collect();
-
-                let grammar_verb = GrammarVerb {
-                    name: verb_name.to_string(),
-                    noun: noun_name.to_string(),
-                    help: Some(verb_about.to_string()),
-                    long_help: None,
-                    arguments: grammar_args,
-                    deprecated: false,
-                    deprecation_message: None,
-                    capability: None,
-                    metadata: HashMap::new(),
-                };
-
-                grammar_verbs.push(grammar_verb);
-            }
-
-            let grammar_noun = GrammarNoun {
-                name: noun_name.to_string(),
-                help: Some(noun_about.to_string()),
-                long_help: None,
-                verbs: grammar_verbs,
-                sub_nouns: Vec::new(),
-                metadata: HashMap::new(),
-            };
-
-            model.add_noun(grammar_noun);
-        }
-
-        Ok(model)
+        Ok(GrammarModel::from_registry(app_name, &registry))
     }
 
     /// Extract grammar and dump as JSON
@@ -332,6 +494,7 @@ impl Grammar {
     }
 
     /// Extract grammar and dump as YAML
+    #[cfg(feature = "config-formats")]
     pub fn dump_yaml() -> Result<String, Box<dyn std::error::Error>> {
         let model = Self::extract()?;
         Ok(serde_yaml::to_string(&model)?)
@@ -364,11 +527,7 @@ impl<'a> GrammarNode<'a> {
     /// Get children of this node
     pub fn children(&self) -> Vec<GrammarNode<'a>> {
         match self {
-            Self::Root(model) => model
-                .nouns
-                .iter()
-                .map(GrammarNode::Noun)
-                .collect(),
+            Self::Root(model) => model.nouns.iter().map(GrammarNode::Noun).collect(),
             Self::Noun(noun) => noun
                 .verbs
                 .iter()
@@ -394,10 +553,7 @@ pub struct GrammarQuery<'a> {
 impl<'a> GrammarQuery<'a> {
     /// Create a new query
     pub fn new(model: &'a GrammarModel) -> Self {
-        Self {
-            model,
-            filters: Vec::new(),
-        }
+        Self { model, filters: Vec::new() }
     }
 
     /// Filter by deprecated status
@@ -444,8 +600,7 @@ mod tests {
 
     #[test]
     fn test_grammar_model_creation() {
-        let model = GrammarModel::new("test-app")
-            .with_version("1.0.0");
+        let model = GrammarModel::new("test-app").with_version("1.0.0");
 
         assert_eq!(model.app_name, "test-app");
         assert_eq!(model.app_version, Some("1.0.0".to_string()));
@@ -466,6 +621,7 @@ mod tests {
             min_length: None,
             max_length: None,
             short: None,
+            long: None,
             default_value: None,
             env: None,
             multiple: false,
@@ -485,6 +641,10 @@ mod tests {
             exclusive: None,
             trailing_vararg: false,
             allow_negative_numbers: false,
+            value_hint: None,
+            global: false,
+            value_type: None,
+            complete: None,
         };
 
         let arg = GrammarArgument::from_metadata(&meta);
@@ -124,7 +124,7 @@ macro_rules! __grammar_dsl_verbs {
             use $crate::kernel::capability::*;
 
             // Build capability contract
-            let mut contract = match stringify!($cap) {
+            let contract = match stringify!($cap) {
                 "Pure" => CapabilityContract::new(
                     CapabilityClass::Pure,
                     $crate::__grammar_dsl_resource!($resource),
@@ -171,6 +171,7 @@ macro_rules! __grammar_dsl_verbs {
             };
 
             // Parse arguments if provided
+            #[allow(unused_mut)]
             let mut arguments = Vec::new();
             $crate::__grammar_dsl_parse_args!(arguments, $($($arg_def)*)?);
 
@@ -180,6 +181,7 @@ macro_rules! __grammar_dsl_verbs {
                 help: Some($help.to_string()),
                 long_help: None,
                 arguments,
+                groups: Vec::new(),
                 deprecated: false,
                 deprecation_message: None,
                 capability: Some(contract),
@@ -222,6 +224,17 @@ macro_rules! __grammar_dsl_parse_args {
                 requires: Vec::new(),
                 conflicts_with: Vec::new(),
                 index: None,
+                value_type: Some(stringify!($arg_type).to_string()),
+                value_hint: None,
+                aliases: Vec::new(),
+                long_help: None,
+                min_value: None,
+                max_value: None,
+                min_length: None,
+                max_length: None,
+                exclusive: false,
+                global: false,
+                hidden: false,
             };
             $args.push(arg);
         }
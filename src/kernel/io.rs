@@ -32,6 +32,10 @@
 //! }
 //! ```
 
+// `StructuredResult` is the pipeline's error surface; boxing the error would
+// leak into every verb signature.
+#![allow(clippy::result_large_err)]
+
 use crate::kernel::output::{ExitCodeClass, StructuredError, StructuredResult};
 use std::fs::File;
 use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
@@ -216,9 +216,9 @@ impl ManpageGenerator {
         // SEE ALSO section
         if self.config.include_see_also && !grammar.nouns.is_empty() {
             writeln!(file, ".SH SEE ALSO")?;
-            for noun in &grammar.nouns {
+            for (i, noun) in grammar.nouns.iter().enumerate() {
                 write!(file, ".BR {}-{} ({})", self.app_name, noun.name, self.config.section.number())?;
-                if noun != grammar.nouns.last().unwrap() {
+                if i + 1 < grammar.nouns.len() {
                     write!(file, ",")?;
                 }
                 writeln!(file)?;
@@ -283,10 +283,10 @@ impl ManpageGenerator {
         // SEE ALSO
         if self.config.include_see_also {
             writeln!(file, ".SH SEE ALSO")?;
-            for verb in &noun_data.verbs {
+            for (i, verb) in noun_data.verbs.iter().enumerate() {
                 write!(file, ".BR {}-{}-{} ({})",
                     self.app_name, noun, verb.name, self.config.section.number())?;
-                if verb != noun_data.verbs.last().unwrap() {
+                if i + 1 < noun_data.verbs.len() {
                     write!(file, ",")?;
                 }
                 writeln!(file)?;
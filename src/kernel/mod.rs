@@ -1,15 +1,71 @@
 //! Kernel module - Core system functionality
+//!
+//! Deterministic execution, session logs and replay, capability brokering,
+//! grammar introspection and the structured output pipeline. Everything here
+//! is available behind the `kernel` feature.
 
 pub mod attestation;
 pub mod capability;
 pub mod quotas;
 pub mod session;
 
+// Output, telemetry and I/O pipeline
+pub mod io;
+pub mod manpage;
+pub mod output;
+pub mod telemetry;
+
+// Grammar model, versioning and introspection
+pub mod grammar;
+pub mod grammar_dsl;
+pub mod introspection;
+pub mod schema_registry;
+pub mod test_harness;
+pub mod version;
+
+// Session logs, replay and streaming
+pub mod concurrent;
+pub mod deterministic_execution;
+pub mod frame_schema;
+pub mod pluggable_persistence;
+pub mod recording;
+pub mod replay_engine;
+pub mod session_log;
+pub mod session_log_store;
+pub mod session_streaming;
+
+// Federation: brokering and transports
+pub mod broker;
+pub mod broker_state;
+pub mod transport;
+
+// Capability contracts, policy and receipts
+pub mod advanced_quota_enforcement;
+pub mod ahi_policy;
+pub mod capability_contracts;
+pub mod const_caps;
+pub mod contract_runtime_view;
+pub mod execution_receipts;
+pub mod policy_governance;
+pub mod type_level_security;
+pub mod typestate;
+
+// Quality and testing tooling
+pub mod clnrm;
+pub mod dflss;
+pub mod distributed_tracing;
+pub mod simd;
+
 // Re-export key types that exist in kernel::session
 pub use attestation::{Attestation, AttestationManager};
-pub use capability::{Capability, CapabilityManager};
+pub use capability::{
+    Capability, CapabilityClass, CapabilityContext, CapabilityContract, CapabilityManager,
+    ResourceBand, SafetyProfile, StabilityProfile,
+};
 pub use quotas::{QuotaManager, ResourceQuota};
-pub use session::{Session, SessionManager};
+pub use session::{
+    Frame, FramePayload, Session, SessionBuilder, SessionHandle, SessionManager, StreamId,
+};
 
 // Note: SessionId, SessionState, SessionMetrics, etc. have moved to autonomic module
 // They are re-exported from autonomic for convenience
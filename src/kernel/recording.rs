@@ -0,0 +1,713 @@
+//! Recording and replay of real CLI invocations
+//!
+//! `app --record calls.jsonl <noun> <verb> ...` runs the invocation as a
+//! child process of the same binary, passes its output through, and appends
+//! one [`SessionLogFrame`] to `calls.jsonl`:
+//!
+//! - `input_args`: `{"argv": [...], "stdin": ...}`, where stdin is read and
+//!   recorded only for `app --record calls.jsonl --record-stdin ...`
+//! - `env_vars`: the environment variables matching the
+//!   [allowlist](ENV_ALLOWLIST)
+//! - `output_result`: stdout and stderr, as an error frame for a non-zero exit
+//! - `exit_code_class` and `quota_footprint.runtime_ms`
+//!
+//! Frames of one recording file share a session and are hash-chained through
+//! `parent_frame_hash`.
+//!
+//! `app --replay calls.jsonl` re-executes every frame against the current
+//! binary with a [`ReplayRunner`] and reports, per invocation, the
+//! [`FrameDelta`] and [`TimingDrift`] against the recording. A recording is
+//! thereby a golden test: any change of exit code, stdout or stderr fails the
+//! replay, and so does timing drift beyond
+//! [`ReplayRunner::with_max_drift_percent`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use clap_noun_verb::kernel::test_harness::assert_replay_matches;
+//!
+//! #[test]
+//! fn test_recorded_invocations_still_match() -> Result<(), String> {
+//!     assert_replay_matches(env!("CARGO_BIN_EXE_myapp"), "tests/golden/calls.jsonl")?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::autonomic::tenancy::InvocationContext;
+use crate::kernel::session_log::{
+    ErrorFrame, ExitCodeClass, FrameDelta, FrameMetadata, LogicalClock, QuotaCheckResult,
+    QuotaFootprint, ReplayMode, ReplayResult, ResultFrame, SessionLogFrame, TimingDrift,
+};
+use crate::kernel::telemetry::TelemetryProfile;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Environment variables recorded by default
+///
+/// A trailing `*` matches any suffix.
+pub const ENV_ALLOWLIST: &[&str] = &["CNV_*", "LANG", "LC_ALL", "TZ", "NO_COLOR"];
+
+/// Credentials that a `*` pattern never matches
+///
+/// A delegation chain grants authority, so it is only recorded when an
+/// allowlist names it exactly.
+pub const ENV_CREDENTIALS: &[&str] = &["CNV_DELEGATION"];
+
+/// Whether `name` matches one of the allowlist `patterns`
+pub fn env_allowed<S: AsRef<str>>(name: &str, patterns: &[S]) -> bool {
+    let credential = ENV_CREDENTIALS.contains(&name);
+    patterns.iter().any(|pattern| match pattern.as_ref().strip_suffix('*') {
+        Some(prefix) => !credential && name.starts_with(prefix),
+        None => name == pattern.as_ref(),
+    })
+}
+
+/// Arguments, environment and stdin of one invocation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedInvocation {
+    /// Command line, starting with the program name
+    pub argv: Vec<String>,
+    /// Allowlisted environment variables
+    pub env: BTreeMap<String, String>,
+    /// Data piped to stdin, if any
+    pub stdin: Option<String>,
+}
+
+impl RecordedInvocation {
+    /// Invocation of `argv` without environment or stdin
+    pub fn new(argv: Vec<String>) -> Self {
+        Self { argv, env: BTreeMap::new(), stdin: None }
+    }
+
+    /// Pipe `stdin` to the invocation
+    pub fn with_stdin(mut self, stdin: impl Into<String>) -> Self {
+        self.stdin = Some(stdin.into());
+        self
+    }
+
+    /// Set an environment variable for the invocation
+    pub fn with_env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(name.into(), value.into());
+        self
+    }
+
+    /// Add the variables of the current environment matching `allowlist`
+    pub fn capture_env<S: AsRef<str>>(mut self, allowlist: &[S]) -> Self {
+        self.env.extend(std::env::vars().filter(|(name, _)| env_allowed(name, allowlist)));
+        self
+    }
+
+    /// Invocation recorded in `frame`
+    pub fn from_frame(frame: &SessionLogFrame) -> Result<Self, String> {
+        let argv = frame
+            .input_args
+            .get("argv")
+            .and_then(Value::as_array)
+            .and_then(|argv| argv.iter().map(|arg| arg.as_str().map(str::to_string)).collect())
+            .ok_or_else(|| {
+                format!("frame {} does not record an argv", frame.metadata.sequence_number)
+            })?;
+        let stdin = frame.input_args.get("stdin").and_then(Value::as_str).map(str::to_string);
+        Ok(Self { argv, env: frame.env_vars.clone(), stdin })
+    }
+
+    /// Command line as a single string, for reports
+    pub fn command_line(&self) -> String {
+        self.argv.join(" ")
+    }
+
+    fn input_args(&self) -> Value {
+        json!({ "argv": self.argv, "stdin": self.stdin })
+    }
+}
+
+/// What a finished invocation produced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOutcome {
+    /// Captured stdout
+    pub stdout: String,
+    /// Captured stderr
+    pub stderr: String,
+    /// Process exit code, `-1` when terminated by a signal
+    pub exit_code: i32,
+    /// Wall-clock time from spawn to exit
+    pub duration: Duration,
+}
+
+impl RunOutcome {
+    /// Outcome recorded in `frame`
+    pub fn from_frame(frame: &SessionLogFrame) -> Self {
+        let duration = Duration::from_millis(frame.quota_footprint.runtime_ms);
+        match &frame.output_result {
+            ResultFrame::Success(output) => Self {
+                stdout: text_field(output, "stdout"),
+                stderr: text_field(output, "stderr"),
+                exit_code: 0,
+                duration,
+            },
+            ResultFrame::Error(error) => {
+                let details = error.error_details.clone().unwrap_or(Value::Null);
+                Self {
+                    stdout: text_field(&details, "stdout"),
+                    stderr: error.error_message.clone(),
+                    exit_code: details
+                        .get("exit_code")
+                        .and_then(Value::as_i64)
+                        .and_then(|code| i32::try_from(code).ok())
+                        .unwrap_or_else(|| frame.exit_code_class.code()),
+                    duration,
+                }
+            }
+        }
+    }
+
+    /// Session log class of the exit code
+    ///
+    /// Framework codes map through [`crate::error::ExitCodeClass`]; a death by
+    /// signal is an internal error and any other code a user error.
+    pub fn exit_class(&self) -> ExitCodeClass {
+        match u8::try_from(self.exit_code) {
+            Ok(code) => crate::error::ExitCodeClass::from_code(code)
+                .map_or(ExitCodeClass::UserError, ExitCodeClass::from),
+            Err(_) if self.exit_code < 0 => ExitCodeClass::InternalError,
+            Err(_) => ExitCodeClass::UserError,
+        }
+    }
+
+    /// Output as a session log result
+    pub fn result_frame(&self) -> ResultFrame {
+        if self.exit_code == 0 {
+            return ResultFrame::Success(json!({ "stdout": self.stdout, "stderr": self.stderr }));
+        }
+        ResultFrame::Error(ErrorFrame {
+            error_code: format!("{:?}", self.exit_class()),
+            error_message: self.stderr.clone(),
+            error_details: Some(json!({ "stdout": self.stdout, "exit_code": self.exit_code })),
+        })
+    }
+}
+
+fn text_field(value: &Value, field: &str) -> String {
+    value.get(field).and_then(Value::as_str).unwrap_or_default().to_string()
+}
+
+/// Run `invocation` with `program`, capturing its output
+///
+/// `argv[0]` is replaced by `program`. The child inherits the environment,
+/// with the recorded variables set on top and other variables matching
+/// `allowlist` removed. When `live`, output is also passed through to this
+/// process as it arrives and, without recorded stdin, stdin is inherited;
+/// otherwise the child gets an empty stdin.
+pub fn run_invocation<S: AsRef<str>>(
+    program: &Path,
+    invocation: &RecordedInvocation,
+    allowlist: &[S],
+    live: bool,
+) -> Result<RunOutcome, String> {
+    let mut command = Command::new(program);
+    command.args(invocation.argv.iter().skip(1));
+    for (name, _) in std::env::vars() {
+        if env_allowed(&name, allowlist) && !invocation.env.contains_key(&name) {
+            command.env_remove(name);
+        }
+    }
+    command.envs(&invocation.env);
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    command.stdin(match (&invocation.stdin, live) {
+        (Some(_), _) => Stdio::piped(),
+        (None, true) => Stdio::inherit(),
+        (None, false) => Stdio::null(),
+    });
+
+    let started = Instant::now();
+    let mut child =
+        command.spawn().map_err(|e| format!("Failed to run {}: {}", program.display(), e))?;
+    let feeder = child.stdin.take().zip(invocation.stdin.clone()).map(|(mut pipe, stdin)| {
+        std::thread::spawn(move || {
+            // The child may exit without reading its input
+            let _ = pipe.write_all(stdin.as_bytes());
+        })
+    });
+    let stdout = child
+        .stdout
+        .take()
+        .map(|pipe| std::thread::spawn(move || pump(pipe, live.then(std::io::stdout))));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|pipe| std::thread::spawn(move || pump(pipe, live.then(std::io::stderr))));
+    let status =
+        child.wait().map_err(|e| format!("Failed to wait for {}: {}", program.display(), e))?;
+    let duration = started.elapsed();
+
+    if let Some(feeder) = feeder {
+        let _ = feeder.join();
+    }
+    let collect = |reader: Option<std::thread::JoinHandle<Vec<u8>>>| {
+        reader
+            .map(|reader| reader.join().unwrap_or_default())
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default()
+    };
+    Ok(RunOutcome {
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+        exit_code: status.code().unwrap_or(-1),
+        duration,
+    })
+}
+
+/// Read `reader` to the end, copying each chunk to `tee`
+fn pump<R: Read, W: Write>(mut reader: R, mut tee: Option<W>) -> Vec<u8> {
+    let mut captured = Vec::new();
+    let mut buffer = [0u8; 8192];
+    while let Ok(read) = reader.read(&mut buffer) {
+        if read == 0 {
+            break;
+        }
+        captured.extend_from_slice(&buffer[..read]);
+        if let Some(tee) = tee.as_mut() {
+            let _ = tee.write_all(&buffer[..read]).and_then(|()| tee.flush());
+        }
+    }
+    captured
+}
+
+/// Frame recording `invocation` of `noun` `verb` and its `outcome`
+pub fn invocation_frame(
+    noun: &str,
+    verb: &str,
+    invocation: &RecordedInvocation,
+    outcome: &RunOutcome,
+    clock: LogicalClock,
+    metadata: FrameMetadata,
+) -> Result<SessionLogFrame, String> {
+    let footprint = QuotaFootprint {
+        runtime_ms: u64::try_from(outcome.duration.as_millis()).unwrap_or(u64::MAX),
+        ..QuotaFootprint::zero()
+    };
+    SessionLogFrame::new(
+        noun.to_string(),
+        verb.to_string(),
+        format!("{}.{}", noun, verb),
+        1,
+        Arc::new(InvocationContext::anonymous()),
+        None,
+        "standard".to_string(),
+        footprint,
+        invocation.input_args(),
+        invocation.env.clone(),
+        clock,
+        outcome.result_frame(),
+        outcome.exit_class(),
+        TelemetryProfile::default(),
+        metadata,
+    )
+    .map_err(|e| format!("Invalid frame for {} {}: {}", noun, verb, e))
+}
+
+/// Frames of a recording file, in order
+pub fn read_recording(path: &Path) -> Result<Vec<SessionLogFrame>, String> {
+    let file = File::open(path)
+        .map_err(|e| format!("Failed to open recording {}: {}", path.display(), e))?;
+    let mut frames = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let frame: SessionLogFrame = serde_json::from_str(&line)
+            .map_err(|e| format!("{} line {}: {}", path.display(), index + 1, e))?;
+        frame
+            .verify_integrity()
+            .map_err(|e| format!("{} line {}: {}", path.display(), index + 1, e))?;
+        frames.push(frame);
+    }
+    Ok(frames)
+}
+
+/// Appends invocation frames to a recording file
+#[derive(Debug, Clone)]
+pub struct InvocationRecorder {
+    path: PathBuf,
+    env_allowlist: Vec<String>,
+}
+
+impl InvocationRecorder {
+    /// Recorder appending to the JSONL file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            env_allowlist: ENV_ALLOWLIST.iter().map(|pattern| pattern.to_string()).collect(),
+        }
+    }
+
+    /// Replace the [default allowlist](ENV_ALLOWLIST) of recorded variables
+    pub fn with_env_allowlist(
+        mut self,
+        patterns: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.env_allowlist = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Recording file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Run `argv` live with `program` and append its frame as `noun` `verb`
+    ///
+    /// `stdin` is passed to the child and recorded; without it the child
+    /// reads this process's stdin, which is not recorded.
+    pub fn record(
+        &self,
+        program: &Path,
+        noun: &str,
+        verb: &str,
+        argv: Vec<String>,
+        stdin: Option<String>,
+    ) -> Result<RunOutcome, String> {
+        let mut invocation = RecordedInvocation::new(argv).capture_env(&self.env_allowlist);
+        invocation.stdin = stdin;
+        let outcome = run_invocation(program, &invocation, &self.env_allowlist, true)?;
+        self.append(noun, verb, &invocation, &outcome)?;
+        Ok(outcome)
+    }
+
+    /// Append the frame of an invocation that already ran
+    ///
+    /// The frame continues the session of the last frame in the file, or
+    /// starts a new session in an empty file.
+    pub fn append(
+        &self,
+        noun: &str,
+        verb: &str,
+        invocation: &RecordedInvocation,
+        outcome: &RunOutcome,
+    ) -> Result<SessionLogFrame, String> {
+        let previous = if self.path.exists() { read_recording(&self.path)?.pop() } else { None };
+        let now_ns = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX))
+            .unwrap_or_default();
+        let (session_id, sequence, clock) = match &previous {
+            Some(previous) => (
+                previous.metadata.session_id.clone(),
+                previous.metadata.sequence_number + 1,
+                LogicalClock::new(
+                    previous.logical_clock.logical_tick + 1,
+                    now_ns.max(previous.logical_clock.wall_clock_ns),
+                ),
+            ),
+            None => (uuid::Uuid::new_v4().to_string(), 1, LogicalClock::new(1, now_ns)),
+        };
+        let context = InvocationContext::anonymous();
+        let metadata = FrameMetadata {
+            frame_id: uuid::Uuid::new_v4().to_string(),
+            session_id,
+            agent_id: context.agent.agent_id,
+            sequence_number: sequence,
+            parent_frame_hash: previous.map(|previous| previous.content_hash),
+            tags: vec!["recorded".to_string()],
+        };
+        let frame = invocation_frame(noun, verb, invocation, outcome, clock, metadata)?;
+
+        let line = serde_json::to_string(&frame)
+            .map_err(|e| format!("Failed to serialize frame: {}", e))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Failed to open recording {}: {}", self.path.display(), e))?;
+        writeln!(file, "{}", line)
+            .and_then(|()| file.sync_data())
+            .map_err(|e| format!("Failed to write recording {}: {}", self.path.display(), e))?;
+        Ok(frame)
+    }
+}
+
+/// Replay of one recorded invocation
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayOutcome {
+    /// Replayed command line
+    pub command: Vec<String>,
+    /// Sequence number of the recorded frame
+    pub sequence: u64,
+    /// Verification result, including the timing drift
+    pub result: ReplayResult,
+    /// Difference between the recorded and the replayed frame
+    pub delta: FrameDelta,
+    /// Exit code of the recording
+    pub original_exit_code: i32,
+    /// Exit code of the replay
+    pub replayed_exit_code: i32,
+    /// Human-readable differences that fail the replay
+    pub mismatches: Vec<String>,
+}
+
+impl ReplayOutcome {
+    /// Whether the replay matched the recording
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Runtime of the recording and the replay
+    pub fn timing(&self) -> Option<&TimingDrift> {
+        self.result.timing_envelope_drift.as_ref()
+    }
+}
+
+/// Replay of a whole recording file
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    /// Replayed recording file
+    pub recording: PathBuf,
+    /// One outcome per recorded frame
+    pub outcomes: Vec<ReplayOutcome>,
+}
+
+impl ReplayReport {
+    /// Whether every invocation matched its recording
+    pub fn passed(&self) -> bool {
+        self.outcomes.iter().all(ReplayOutcome::passed)
+    }
+
+    /// Invocations that did not match their recording
+    pub fn failures(&self) -> impl Iterator<Item = &ReplayOutcome> {
+        self.outcomes.iter().filter(|outcome| !outcome.passed())
+    }
+
+    /// One line per invocation, each mismatch indented below it
+    pub fn render(&self) -> String {
+        let mut report = String::new();
+        for outcome in &self.outcomes {
+            let status = if outcome.passed() { "ok  " } else { "FAIL" };
+            report.push_str(&format!(
+                "{} #{} {}",
+                status,
+                outcome.sequence,
+                outcome.command.join(" ")
+            ));
+            if let Some(timing) = outcome.timing() {
+                report.push_str(&format!(
+                    " ({}ms -> {}ms, {:+.1}%)",
+                    timing.original_ms, timing.replayed_ms, timing.drift_percent
+                ));
+            }
+            report.push('\n');
+            for mismatch in &outcome.mismatches {
+                report.push_str(&format!("     {}\n", mismatch));
+            }
+        }
+        let failed = self.failures().count();
+        report.push_str(&format!(
+            "{} invocations replayed, {} failed\n",
+            self.outcomes.len(),
+            failed
+        ));
+        report
+    }
+
+    /// `Err` with the rendered report when any invocation did not match
+    pub fn ensure_passed(&self) -> Result<(), String> {
+        if self.passed() {
+            Ok(())
+        } else {
+            Err(self.render())
+        }
+    }
+}
+
+/// Re-executes recorded frames against a binary
+#[derive(Debug, Clone)]
+pub struct ReplayRunner {
+    binary: PathBuf,
+    env_allowlist: Vec<String>,
+    max_drift_percent: Option<f64>,
+}
+
+impl ReplayRunner {
+    /// Runner executing frames with `binary`
+    ///
+    /// Timing drift is reported but does not fail a replay unless a
+    /// [limit](Self::with_max_drift_percent) is set.
+    pub fn new(binary: impl Into<PathBuf>) -> Self {
+        Self {
+            binary: binary.into(),
+            env_allowlist: ENV_ALLOWLIST.iter().map(|pattern| pattern.to_string()).collect(),
+            max_drift_percent: None,
+        }
+    }
+
+    /// Replace the [default allowlist](ENV_ALLOWLIST)
+    ///
+    /// Variables matching it that a frame did not record are unset for the
+    /// replay, so the recording environment is reproduced.
+    pub fn with_env_allowlist(
+        mut self,
+        patterns: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.env_allowlist = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Fail replays whose runtime grew by more than `percent`
+    pub fn with_max_drift_percent(mut self, percent: f64) -> Self {
+        self.max_drift_percent = Some(percent);
+        self
+    }
+
+    /// Re-execute one recorded frame and compare it with the recording
+    pub fn replay_frame(&self, frame: &SessionLogFrame) -> Result<ReplayOutcome, String> {
+        let invocation = RecordedInvocation::from_frame(frame)?;
+        let original = RunOutcome::from_frame(frame);
+        let replayed = run_invocation(&self.binary, &invocation, &self.env_allowlist, false)?;
+        let replayed_frame = invocation_frame(
+            &frame.noun_id,
+            &frame.verb_id,
+            &invocation,
+            &replayed,
+            frame.logical_clock,
+            frame.metadata.clone(),
+        )?;
+        let delta = FrameDelta::compute(frame, &replayed_frame);
+
+        let original_ms = frame.quota_footprint.runtime_ms;
+        let replayed_ms = replayed_frame.quota_footprint.runtime_ms;
+        let drift_percent =
+            (replayed_ms as f64 - original_ms as f64) / original_ms.max(1) as f64 * 100.0;
+        let timing = TimingDrift { original_ms, replayed_ms, drift_percent };
+
+        let mut mismatches = Vec::new();
+        if original.exit_code != replayed.exit_code {
+            mismatches.push(format!("exit code {} -> {}", original.exit_code, replayed.exit_code));
+        }
+        mismatches.extend(output_mismatch("stdout", &original.stdout, &replayed.stdout));
+        mismatches.extend(output_mismatch("stderr", &original.stderr, &replayed.stderr));
+        let outcome_match = mismatches.is_empty();
+        let mut exceeded_resources = Vec::new();
+        if let Some(max) = self.max_drift_percent.filter(|max| drift_percent > *max) {
+            mismatches.push(format!(
+                "runtime {}ms -> {}ms ({:+.1}%) exceeds the {:.1}% drift limit",
+                original_ms, replayed_ms, drift_percent, max
+            ));
+            exceeded_resources.push("runtime_ms".to_string());
+        }
+
+        let result = ReplayResult {
+            frame: frame.clone(),
+            mode: ReplayMode::Verify,
+            success: true,
+            outcome_match,
+            timing_envelope_drift: Some(timing),
+            quota_check: QuotaCheckResult {
+                under_quota: exceeded_resources.is_empty(),
+                original_usage: frame.quota_footprint.clone(),
+                replayed_usage: replayed_frame.quota_footprint.clone(),
+                exceeded_resources,
+            },
+            error_details: (!mismatches.is_empty()).then(|| mismatches.join("; ")),
+        };
+        Ok(ReplayOutcome {
+            command: invocation.argv,
+            sequence: frame.metadata.sequence_number,
+            result,
+            delta,
+            original_exit_code: original.exit_code,
+            replayed_exit_code: replayed.exit_code,
+            mismatches,
+        })
+    }
+
+    /// Re-execute every frame of a recording file
+    pub fn replay_file(&self, recording: &Path) -> Result<ReplayReport, String> {
+        let outcomes = read_recording(recording)?
+            .iter()
+            .map(|frame| self.replay_frame(frame))
+            .collect::<Result<_, _>>()?;
+        Ok(ReplayReport { recording: recording.to_path_buf(), outcomes })
+    }
+}
+
+/// Mismatch naming the first differing line of `stream`, if any
+fn output_mismatch(stream: &str, expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line += 1,
+            (None, None) => return Some(format!("{} differs in line endings", stream)),
+            (e, a) => {
+                return Some(format!(
+                    "{} line {}: expected {:?}, got {:?}",
+                    stream,
+                    line,
+                    e.unwrap_or("<end>"),
+                    a.unwrap_or("<end>")
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_allowlist_patterns() {
+        let allowlist = ["CNV_*", "TZ"];
+
+        assert!(env_allowed("CNV_FORMAT", &allowlist));
+        assert!(env_allowed("TZ", &allowlist));
+        assert!(!env_allowed("TZDIR", &allowlist));
+        assert!(!env_allowed("HOME", &allowlist));
+        assert!(!env_allowed("CNV_DELEGATION", &allowlist));
+        assert!(env_allowed("CNV_DELEGATION", &["CNV_DELEGATION"]));
+    }
+
+    #[test]
+    fn test_exit_class_follows_cli_exit_codes() {
+        let class = |exit_code| {
+            RunOutcome {
+                stdout: String::new(),
+                stderr: String::new(),
+                exit_code,
+                duration: Duration::ZERO,
+            }
+            .exit_class()
+        };
+
+        assert_eq!(class(0), ExitCodeClass::Success);
+        assert_eq!(class(2), ExitCodeClass::ValidationError);
+        assert_eq!(class(7), ExitCodeClass::SystemError);
+        assert_eq!(class(130), ExitCodeClass::InternalError);
+        assert_eq!(class(-1), ExitCodeClass::InternalError);
+        assert_eq!(class(42), ExitCodeClass::UserError);
+    }
+
+    #[test]
+    fn test_output_mismatch_names_first_differing_line() {
+        assert_eq!(output_mismatch("stdout", "a\nb\n", "a\nb\n"), None);
+        assert_eq!(
+            output_mismatch("stdout", "a\nb\n", "a\nc\n"),
+            Some("stdout line 2: expected \"b\", got \"c\"".to_string())
+        );
+        assert_eq!(
+            output_mismatch("stderr", "a\n", "a\nb\n"),
+            Some("stderr line 2: expected \"<end>\", got \"b\"".to_string())
+        );
+    }
+}
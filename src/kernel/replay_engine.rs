@@ -67,8 +67,6 @@ impl DeterministicContext {
 
 /// Sealed trait for replay implementations
 mod sealed {
-    use super::*;
-
     pub trait Sealed: Send + Sync {}
 }
 
@@ -535,6 +533,7 @@ mod tests {
             qos: Default::default(),
             correlation_id: uuid::Uuid::new_v4().to_string(),
             parent_invocation_id: None,
+            delegation: None,
         })
     }
 
@@ -15,15 +15,17 @@ pub struct SchemaVersion {
     pub patch: u32,
 }
 
+impl std::fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
 impl SchemaVersion {
     pub fn new(major: u32, minor: u32, patch: u32) -> Self {
         Self { major, minor, patch }
     }
 
-    pub fn to_string(&self) -> String {
-        format!("{}.{}.{}", self.major, self.minor, self.patch)
-    }
-
     /// Semantic versioning comparison
     pub fn is_compatible_with(&self, other: &Self) -> bool {
         // Same major version means compatible
@@ -152,16 +154,17 @@ impl SchemaRegistry {
             return Err("Schema integrity check failed".to_string());
         }
 
-        let mut entries = self.entries.write().unwrap();
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
 
         // Check if version already registered (immutable)
         if entries.contains_key(&entry.version) {
-            return Err(format!("Schema version {} already registered", entry.version.to_string()));
+            return Err(format!("Schema version {} already registered", entry.version));
         }
 
         entries.insert(entry.version.clone(), entry);
+        drop(entries);
 
-        // Recompute merkle root
+        // Recompute merkle root (takes its own read lock on the entries)
         self.update_merkle_root();
 
         Ok(())
@@ -169,7 +172,7 @@ impl SchemaRegistry {
 
     /// Get schema entry by version
     pub fn get_schema(&self, version: &SchemaVersion) -> Option<SchemaEntry> {
-        self.entries.read().unwrap().get(version).cloned()
+        self.entries.read().unwrap_or_else(|e| e.into_inner()).get(version).cloned()
     }
 
     /// List all registered schema versions
@@ -177,7 +180,7 @@ impl SchemaRegistry {
         let mut versions: Vec<_> = self
             .entries
             .read()
-            .unwrap()
+            .unwrap_or_else(|e| e.into_inner())
             .keys()
             .cloned()
             .collect();
@@ -196,7 +199,7 @@ impl SchemaRegistry {
             return Err("Incompatible evolution must be marked as Breaking".to_string());
         }
 
-        let mut rules = self.evolution_rules.write().unwrap();
+        let mut rules = self.evolution_rules.write().unwrap_or_else(|e| e.into_inner());
         rules.push(rule);
         Ok(())
     }
@@ -207,7 +210,7 @@ impl SchemaRegistry {
         from: &SchemaVersion,
         to: &SchemaVersion,
     ) -> Option<CompatibilityType> {
-        let rules = self.evolution_rules.read().unwrap();
+        let rules = self.evolution_rules.read().unwrap_or_else(|e| e.into_inner());
 
         for rule in rules.iter() {
             if rule.from_version == *from && rule.to_version == *to {
@@ -235,7 +238,7 @@ impl SchemaRegistry {
         from: &SchemaVersion,
         to: &SchemaVersion,
     ) -> Option<Vec<EvolutionRule>> {
-        let rules = self.evolution_rules.read().unwrap();
+        let rules = self.evolution_rules.read().unwrap_or_else(|e| e.into_inner());
 
         // Simple path finding (could be enhanced with graph algorithms)
         let mut path = Vec::new();
@@ -270,14 +273,14 @@ impl SchemaRegistry {
 
     /// Get merkle root for verification
     pub fn merkle_root(&self) -> Option<String> {
-        self.merkle_root.read().unwrap().as_ref().map(|n| n.root_hash().to_string())
+        self.merkle_root.read().unwrap_or_else(|e| e.into_inner()).as_ref().map(|n| n.root_hash().to_string())
     }
 
     fn update_merkle_root(&self) {
-        let entries = self.entries.read().unwrap();
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
 
         if entries.is_empty() {
-            *self.merkle_root.write().unwrap() = None;
+            *self.merkle_root.write().unwrap_or_else(|e| e.into_inner()) = None;
             return;
         }
 
@@ -286,17 +289,11 @@ impl SchemaRegistry {
         hashes.sort();
 
         // Build merkle tree from hashes
-        let mut tree = vec![MerkleNode::new_leaf(hashes[0].clone())];
-
-        for hash in hashes.iter().skip(1) {
-            let prev = tree.pop().unwrap();
-            let curr = MerkleNode::new_leaf(hash.to_string());
-            tree.push(MerkleNode::new_branch(prev, curr));
-        }
+        let root = hashes.iter().skip(1).fold(MerkleNode::new_leaf(hashes[0].clone()), |prev, hash| {
+            MerkleNode::new_branch(prev, MerkleNode::new_leaf(hash.to_string()))
+        });
 
-        if tree.len() == 1 {
-            *self.merkle_root.write().unwrap() = tree.pop();
-        }
+        *self.merkle_root.write().unwrap_or_else(|e| e.into_inner()) = Some(root);
     }
 }
 
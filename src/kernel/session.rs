@@ -1,53 +1,150 @@
 //! Session management module
 
+use crate::kernel::capability::CapabilityContract;
+use serde::{Deserialize, Serialize};
+
 // ============================================================================
-// Placeholder types for forward compatibility
-// These will be properly implemented in future versions
+// Session kernel: identifiers, handles and frames
 // ============================================================================
 
-/// Session identifier (placeholder)
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct SessionId(pub String);
+/// Session identifier
+///
+/// A random UUID; its 16 raw bytes are used on the wire by the frame
+/// serializer and for shard selection in the concurrent session registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SessionId(uuid::Uuid);
 
 impl SessionId {
-    pub fn as_bytes(&self) -> &[u8] {
+    /// Generate a new random session ID
+    pub fn new() -> Self {
+        Self(uuid::Uuid::new_v4())
+    }
+
+    /// Wrap an existing UUID
+    pub fn from_uuid(uuid: uuid::Uuid) -> Self {
+        Self(uuid)
+    }
+
+    /// Raw UUID bytes
+    pub fn as_bytes(&self) -> &[u8; 16] {
         self.0.as_bytes()
     }
 }
 
-/// Session handle (placeholder)
+impl Default for SessionId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for SessionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Handle to a live session and the capability contract it runs under
 #[derive(Debug, Clone)]
 pub struct SessionHandle {
-    id: String,
+    id: SessionId,
+    contract: CapabilityContract,
+    created_at: std::time::SystemTime,
 }
 
 impl SessionHandle {
-    pub fn id(&self) -> &str {
-        &self.id
+    /// Session ID
+    pub fn id(&self) -> SessionId {
+        self.id
+    }
+
+    /// Capability contract of the session
+    pub fn capability(&self) -> &CapabilityContract {
+        &self.contract
+    }
+
+    /// When the session was created
+    pub fn created_at(&self) -> std::time::SystemTime {
+        self.created_at
     }
 }
 
-/// Frame data structure (placeholder)
-#[derive(Debug, Clone)]
+/// A single message on one of a session's logical streams
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Frame {
+    /// Owning session
+    pub session_id: SessionId,
+    /// Logical stream the frame belongs to
+    pub stream_id: StreamId,
+    /// Per-stream sequence number
+    pub sequence: u64,
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+    /// Frame contents
     pub payload: FramePayload,
 }
 
-/// Frame payload (placeholder)
-#[derive(Debug, Clone)]
-pub struct FramePayload;
+/// Frame contents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FramePayload {
+    /// Structured output data
+    Data { data: serde_json::Value },
+    /// Log line
+    Log { level: String, message: String },
+    /// Error report
+    Error { kind: String, message: String },
+}
 
-/// Stream identifier (placeholder)
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct StreamId;
+/// Logical stream multiplexed over a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamId {
+    Stdout,
+    Stderr,
+    Logs,
+    Metrics,
+    Control,
+    /// Application-defined stream
+    Custom(u8),
+}
 
 /// Control command (placeholder)
 #[derive(Debug, Clone)]
 pub struct ControlCommand;
 
-/// Session builder (placeholder)
-#[derive(Debug, Clone, Default)]
-pub struct SessionBuilder;
+/// Builder for [`SessionHandle`]
+#[derive(Debug, Clone)]
+pub struct SessionBuilder {
+    contract: CapabilityContract,
+}
+
+impl SessionBuilder {
+    /// Start a session with the [pure](CapabilityContract::pure) contract
+    pub fn new() -> Self {
+        Self { contract: CapabilityContract::pure() }
+    }
+
+    /// Set the capability contract
+    pub fn capability(mut self, contract: CapabilityContract) -> Self {
+        self.contract = contract;
+        self
+    }
+
+    /// Create the session with a fresh ID
+    pub fn build(self) -> SessionHandle {
+        SessionHandle {
+            id: SessionId::new(),
+            contract: self.contract,
+            created_at: std::time::SystemTime::now(),
+        }
+    }
+}
+
+impl Default for SessionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// Session config (placeholder)
 #[derive(Debug, Clone, Default)]
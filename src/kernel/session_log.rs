@@ -43,7 +43,6 @@ use std::collections::BTreeMap;
 use std::fmt;
 use std::hash::Hash;
 use std::sync::Arc;
-use uuid::Uuid;
 
 /// Deterministic clock for replay - uses logical ticks + wall-clock envelope
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Hash)]
@@ -170,6 +169,7 @@ fn default_invocation_context() -> Arc<InvocationContext> {
         qos: QoSHints::default(),
         correlation_id: String::from("default"),
         parent_invocation_id: None,
+        delegation: None,
     })
 }
 
@@ -231,11 +231,14 @@ pub struct SessionLogFrame {
 }
 
 /// Result frame - captures both success and error outcomes deterministically
+///
+/// `Error` is listed first so that deserializing tries the error shape before
+/// falling back to an arbitrary success value.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ResultFrame {
-    Success(serde_json::Value),
     Error(ErrorFrame),
+    Success(serde_json::Value),
 }
 
 /// Error frame - structured error information
@@ -265,6 +268,24 @@ impl ExitCodeClass {
     }
 }
 
+impl From<crate::error::ExitCodeClass> for ExitCodeClass {
+    /// Usage and input errors are validation errors; timeouts, unavailable
+    /// services and exceeded budgets system errors; an interrupt, like a
+    /// signal, an internal error.
+    fn from(class: crate::error::ExitCodeClass) -> Self {
+        use crate::error::ExitCodeClass as Cli;
+        match class {
+            Cli::Success => Self::Success,
+            Cli::GeneralError => Self::UserError,
+            Cli::UsageError | Cli::InputError => Self::ValidationError,
+            Cli::NotFound => Self::NotFound,
+            Cli::PermissionDenied => Self::PermissionDenied,
+            Cli::Timeout | Cli::Unavailable | Cli::BudgetExceeded => Self::SystemError,
+            Cli::Interrupted => Self::InternalError,
+        }
+    }
+}
+
 /// Frame metadata for ordering and tracing
 #[derive(Debug, Clone, Serialize, Deserialize, Hash)]
 pub struct FrameMetadata {
@@ -659,6 +680,72 @@ pub struct ResourceStats {
     pub peak_concurrent_agents: u64,
 }
 
+impl SessionCompression {
+    /// Summarize frames: capability histogram, runtime percentiles and resource totals
+    pub fn from_frames(selected: &[&SessionLogFrame]) -> Self {
+        if selected.is_empty() {
+            return SessionCompression {
+                original_frame_count: 0,
+                compressed_frame_count: 0,
+                compression_ratio: 1.0,
+                invocation_histogram: BTreeMap::new(),
+                timing_percentiles: TimingPercentiles {
+                    p50_ms: 0,
+                    p95_ms: 0,
+                    p99_ms: 0,
+                    p999_ms: 0,
+                },
+                resource_stats: ResourceStats {
+                    total_memory_gb: 0.0,
+                    total_io_ops: 0,
+                    total_network_bytes: 0,
+                    peak_concurrent_agents: 0,
+                },
+            };
+        }
+
+        let mut histogram: BTreeMap<String, u64> = BTreeMap::new();
+        let mut timings: Vec<u64> = Vec::new();
+        let mut total_memory: u64 = 0;
+        let mut total_io: u64 = 0;
+        let mut total_network: u64 = 0;
+        let mut agent_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+        for frame in selected {
+            *histogram
+                .entry(frame.capability_id.clone())
+                .or_insert(0) += 1;
+            timings.push(frame.quota_footprint.runtime_ms);
+            total_memory += frame.quota_footprint.peak_memory_bytes;
+            total_io += frame.quota_footprint.io_operations;
+            total_network += frame.quota_footprint.network_bytes;
+            agent_ids.insert(frame.metadata.agent_id.clone());
+        }
+
+        timings.sort_unstable();
+        let len = timings.len();
+
+        SessionCompression {
+            original_frame_count: selected.len(),
+            compressed_frame_count: histogram.len(),
+            compression_ratio: histogram.len() as f64 / selected.len() as f64,
+            invocation_histogram: histogram,
+            timing_percentiles: TimingPercentiles {
+                p50_ms: timings[len / 2],
+                p95_ms: timings[(len * 95) / 100],
+                p99_ms: timings[(len * 99) / 100],
+                p999_ms: timings[(len * 999) / 1000],
+            },
+            resource_stats: ResourceStats {
+                total_memory_gb: total_memory as f64 / (1024.0 * 1024.0 * 1024.0),
+                total_io_ops: total_io,
+                total_network_bytes: total_network,
+                peak_concurrent_agents: agent_ids.len() as u64,
+            },
+        }
+    }
+}
+
 /// Session log store trait for pluggable backends
 pub trait SessionLogStore: Send + Sync {
     /// Append a frame to the log
@@ -770,7 +857,7 @@ impl SessionLogStore for InMemorySessionLogStore {
                 .map_err(|e| format!("Frame integrity check failed for session {}: {}", session_id, e))?;
         }
 
-        result.sort_by(|a, b| a.order_key().cmp(&b.order_key()));
+        result.sort_by_key(|a| a.order_key());
         Ok(result)
     }
 
@@ -781,66 +868,7 @@ impl SessionLogStore for InMemorySessionLogStore {
             .filter(|f| f.metadata.sequence_number >= start_seq && f.metadata.sequence_number <= end_seq)
             .collect();
 
-        if selected.is_empty() {
-            return Ok(SessionCompression {
-                original_frame_count: 0,
-                compressed_frame_count: 0,
-                compression_ratio: 1.0,
-                invocation_histogram: BTreeMap::new(),
-                timing_percentiles: TimingPercentiles {
-                    p50_ms: 0,
-                    p95_ms: 0,
-                    p99_ms: 0,
-                    p999_ms: 0,
-                },
-                resource_stats: ResourceStats {
-                    total_memory_gb: 0.0,
-                    total_io_ops: 0,
-                    total_network_bytes: 0,
-                    peak_concurrent_agents: 0,
-                },
-            });
-        }
-
-        let mut histogram: BTreeMap<String, u64> = BTreeMap::new();
-        let mut timings: Vec<u64> = Vec::new();
-        let mut total_memory: u64 = 0;
-        let mut total_io: u64 = 0;
-        let mut total_network: u64 = 0;
-        let mut agent_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
-
-        for frame in &selected {
-            *histogram
-                .entry(frame.capability_id.clone())
-                .or_insert(0) += 1;
-            timings.push(frame.quota_footprint.runtime_ms);
-            total_memory += frame.quota_footprint.peak_memory_bytes;
-            total_io += frame.quota_footprint.io_operations;
-            total_network += frame.quota_footprint.network_bytes;
-            agent_ids.insert(frame.metadata.agent_id.clone());
-        }
-
-        timings.sort_unstable();
-        let len = timings.len();
-
-        Ok(SessionCompression {
-            original_frame_count: selected.len(),
-            compressed_frame_count: histogram.len(),
-            compression_ratio: histogram.len() as f64 / selected.len() as f64,
-            invocation_histogram: histogram,
-            timing_percentiles: TimingPercentiles {
-                p50_ms: timings[len / 2],
-                p95_ms: timings[(len * 95) / 100],
-                p99_ms: timings[(len * 99) / 100],
-                p999_ms: timings[(len * 999) / 1000],
-            },
-            resource_stats: ResourceStats {
-                total_memory_gb: total_memory as f64 / (1024.0 * 1024.0 * 1024.0),
-                total_io_ops: total_io,
-                total_network_bytes: total_network,
-                peak_concurrent_agents: agent_ids.len() as u64,
-            },
-        })
+        Ok(SessionCompression::from_frames(&selected))
     }
 }
 
@@ -864,6 +892,7 @@ mod tests {
             qos: Default::default(),
             correlation_id: uuid::Uuid::new_v4().to_string(),
             parent_invocation_id: None,
+            delegation: None,
         })
     }
 
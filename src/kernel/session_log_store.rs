@@ -0,0 +1,955 @@
+//! Durable, file-backed [`SessionLogStore`]
+//!
+//! A [`FileSessionLogStore`] keeps frames in a directory of append-only
+//! segment files, one JSON frame per line:
+//!
+//! ```text
+//! sessions/
+//! ├── MANIFEST.json               live segments in order
+//! ├── 00000000000000000001.seg    sealed segment
+//! ├── 00000000000000000001.idx    its index: hash, session, sequence, offset
+//! └── 00000000000000000002.seg    active segment, appended to
+//! ```
+//!
+//! ## Durability
+//!
+//! An append is acknowledged once its line is written and, with
+//! [`FileStoreConfig::sync_on_append`] (the default), synced to disk. When the
+//! active segment reaches its size or frame limit it is sealed: synced,
+//! indexed, and followed by a new segment. The manifest is the commit point
+//! of rotation and compaction; it is written to a temporary file, synced and
+//! renamed, so a crash leaves either the old or the new set of segments.
+//! On open, files the manifest does not name are removed as leftovers of an
+//! interrupted operation, and a torn last line of the active segment is
+//! truncated.
+//!
+//! ## Compaction
+//!
+//! [`FileSessionLogStore::compact`] merges the sealed segments into one,
+//! drops frames whose content hash is already stored, and records the
+//! [`SessionCompression`] of the merged frames in the new segment's index
+//! (see [`SegmentInfo::compression`]). With
+//! [`FileStoreConfig::compact_after`], compaction runs on rotation.
+//!
+//! [`verify_integrity`] audits a whole directory without opening it for
+//! writing: frame hashes, per-session ordering, and sealed segment indexes.
+
+use super::session_log::{SessionCompression, SessionLogFrame, SessionLogStore};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest listing the live segments
+pub const MANIFEST_FILE: &str = "MANIFEST.json";
+
+/// Manifest format version
+const MANIFEST_VERSION: u32 = 1;
+
+const SEGMENT_EXTENSION: &str = "seg";
+const INDEX_EXTENSION: &str = "idx";
+const TEMP_EXTENSION: &str = "tmp";
+
+/// Rotation, sync and compaction settings of a [`FileSessionLogStore`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStoreConfig {
+    /// Seal the active segment before it grows past this many bytes
+    pub max_segment_bytes: u64,
+    /// Seal the active segment once it holds this many frames
+    pub max_segment_frames: u64,
+    /// Sync every appended frame to disk before acknowledging it
+    pub sync_on_append: bool,
+    /// Compact on rotation once more than this many segments are sealed
+    pub compact_after: Option<usize>,
+}
+
+impl Default for FileStoreConfig {
+    fn default() -> Self {
+        Self {
+            max_segment_bytes: 64 * 1024 * 1024,
+            max_segment_frames: 100_000,
+            sync_on_append: true,
+            compact_after: None,
+        }
+    }
+}
+
+impl FileStoreConfig {
+    /// Default settings: 64 MiB or 100 000 frames per segment, synced appends
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the segment size limit in bytes
+    pub fn with_max_segment_bytes(mut self, bytes: u64) -> Self {
+        self.max_segment_bytes = bytes;
+        self
+    }
+
+    /// Set the segment frame limit
+    pub fn with_max_segment_frames(mut self, frames: u64) -> Self {
+        self.max_segment_frames = frames;
+        self
+    }
+
+    /// Leave syncing appended frames to the operating system
+    ///
+    /// Frames acknowledged since the last rotation may be lost on power failure.
+    pub fn without_sync(mut self) -> Self {
+        self.sync_on_append = false;
+        self
+    }
+
+    /// Compact on rotation once more than `segments` segments are sealed
+    pub fn compact_after(mut self, segments: usize) -> Self {
+        self.compact_after = Some(segments);
+        self
+    }
+}
+
+/// Index entry of one frame in a segment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Canonical content hash of the frame
+    pub content_hash: String,
+    /// Session the frame belongs to
+    pub session_id: String,
+    /// Sequence number within the session
+    pub sequence_number: u64,
+    /// Byte offset of the frame's line in the segment
+    pub offset: u64,
+    /// Length of the line in bytes, without the newline
+    pub len: u64,
+}
+
+/// A live segment of a store
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentInfo {
+    /// Segment id, increasing with every new segment
+    pub id: u64,
+    /// Path of the segment file
+    pub path: PathBuf,
+    /// Whether the segment is sealed (only the last segment is not)
+    pub sealed: bool,
+    /// Number of frames
+    pub frames: u64,
+    /// Size in bytes
+    pub bytes: u64,
+    /// Summary recorded when the segment was produced by compaction
+    pub compression: Option<SessionCompression>,
+}
+
+/// Outcome of [`FileSessionLogStore::compact`]
+#[derive(Debug, Clone, Serialize)]
+pub struct CompactionReport {
+    /// Segments merged, in order
+    pub merged_segments: Vec<u64>,
+    /// Segment holding the merged frames
+    pub segment: u64,
+    /// Frames in the merged segments
+    pub frames_before: u64,
+    /// Frames kept after dropping duplicates
+    pub frames_after: u64,
+    /// Summary of the kept frames
+    pub compression: SessionCompression,
+}
+
+/// A problem found by [`verify_integrity`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IntegrityProblem {
+    /// Segment the problem was found in, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub segment: Option<u64>,
+    /// 1-based line in the segment, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// What is wrong
+    pub message: String,
+}
+
+/// Outcome of [`verify_integrity`]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IntegrityReport {
+    /// Segments listed in the manifest
+    pub segments: usize,
+    /// Frames read
+    pub frames: u64,
+    /// Distinct sessions seen
+    pub sessions: usize,
+    /// Every problem found, in directory order
+    pub problems: Vec<IntegrityProblem>,
+}
+
+impl IntegrityReport {
+    /// Whether no problem was found
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Session log store persisted as append-only segment files in a directory
+///
+/// ```rust,no_run
+/// use clap_noun_verb::kernel::session_log_store::{FileSessionLogStore, FileStoreConfig};
+///
+/// let store = FileSessionLogStore::open_with(
+///     "/var/lib/myapp/sessions",
+///     FileStoreConfig::new().with_max_segment_bytes(8 * 1024 * 1024).compact_after(16),
+/// )?;
+/// # Ok::<(), String>(())
+/// ```
+///
+/// Frames are indexed in memory by content hash and session id; frame
+/// contents are read from disk and verified on every read.
+pub struct FileSessionLogStore {
+    dir: PathBuf,
+    config: FileStoreConfig,
+    state: Mutex<StoreState>,
+}
+
+/// Live segments listed in the manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    next_segment: u64,
+    segments: Vec<ManifestSegment>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct ManifestSegment {
+    id: u64,
+    sealed: bool,
+}
+
+/// Contents of a `.idx` file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SegmentIndex {
+    segment: u64,
+    bytes: u64,
+    frames: Vec<IndexEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    compression: Option<SessionCompression>,
+}
+
+/// Position of a frame on disk
+#[derive(Debug, Clone, Copy)]
+struct FrameLocation {
+    segment: u64,
+    offset: u64,
+    len: u64,
+}
+
+struct StoreState {
+    manifest: Manifest,
+    indexes: HashMap<u64, SegmentIndex>,
+    active: File,
+    by_hash: HashMap<String, FrameLocation>,
+    by_session: HashMap<String, Vec<FrameLocation>>,
+}
+
+impl StoreState {
+    fn active_id(&self) -> u64 {
+        self.manifest.segments.last().map_or(0, |segment| segment.id)
+    }
+
+    fn active_index(&self) -> Option<&SegmentIndex> {
+        self.indexes.get(&self.active_id())
+    }
+
+    fn add_to_lookup(&mut self, segment: u64, entry: &IndexEntry) {
+        let location = FrameLocation { segment, offset: entry.offset, len: entry.len };
+        self.by_hash.insert(entry.content_hash.clone(), location);
+        self.by_session.entry(entry.session_id.clone()).or_default().push(location);
+    }
+
+    fn rebuild_lookup(&mut self) {
+        self.by_hash.clear();
+        self.by_session.clear();
+        let segments: Vec<u64> = self.manifest.segments.iter().map(|s| s.id).collect();
+        for id in segments {
+            let entries =
+                self.indexes.get(&id).map(|index| index.frames.clone()).unwrap_or_default();
+            for entry in &entries {
+                self.add_to_lookup(id, entry);
+            }
+        }
+    }
+}
+
+impl FileSessionLogStore {
+    /// Open or create a store in `dir` with default settings
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, String> {
+        Self::open_with(dir, FileStoreConfig::default())
+    }
+
+    /// Open or create a store in `dir`
+    ///
+    /// Leftovers of an interrupted rotation or compaction are removed, missing
+    /// indexes of sealed segments are rebuilt, and a torn last line of the
+    /// active segment is truncated.
+    pub fn open_with(dir: impl Into<PathBuf>, config: FileStoreConfig) -> Result<Self, String> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(|e| io_error("create", &dir, e))?;
+
+        let manifest = match read_manifest(&dir)? {
+            Some(manifest) => manifest,
+            None => {
+                let manifest = Manifest {
+                    version: MANIFEST_VERSION,
+                    next_segment: 2,
+                    segments: vec![ManifestSegment { id: 1, sealed: false }],
+                };
+                create_segment(&dir, 1)?;
+                write_manifest(&dir, &manifest)?;
+                manifest
+            }
+        };
+        remove_leftovers(&dir, &manifest)?;
+
+        let mut indexes = HashMap::new();
+        for segment in &manifest.segments {
+            let index = if segment.sealed {
+                load_sealed_index(&dir, segment.id)?
+            } else {
+                recover_active_segment(&dir, segment.id)?
+            };
+            indexes.insert(segment.id, index);
+        }
+
+        let active_id = manifest.segments.last().map_or(0, |segment| segment.id);
+        let active = open_for_append(&segment_path(&dir, active_id))?;
+        let mut state = StoreState {
+            manifest,
+            indexes,
+            active,
+            by_hash: HashMap::new(),
+            by_session: HashMap::new(),
+        };
+        state.rebuild_lookup();
+        Ok(Self { dir, config, state: Mutex::new(state) })
+    }
+
+    /// Directory of the store
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Rotation, sync and compaction settings
+    pub fn config(&self) -> &FileStoreConfig {
+        &self.config
+    }
+
+    /// Live segments in order; the last one is active
+    pub fn segments(&self) -> Vec<SegmentInfo> {
+        let state = self.state.lock();
+        state
+            .manifest
+            .segments
+            .iter()
+            .map(|segment| {
+                let index = state.indexes.get(&segment.id);
+                SegmentInfo {
+                    id: segment.id,
+                    path: segment_path(&self.dir, segment.id),
+                    sealed: segment.sealed,
+                    frames: index.map_or(0, |index| index.frames.len() as u64),
+                    bytes: index.map_or(0, |index| index.bytes),
+                    compression: index.and_then(|index| index.compression.clone()),
+                }
+            })
+            .collect()
+    }
+
+    /// Seal the active segment and start a new one
+    pub fn rotate(&self) -> Result<(), String> {
+        let mut state = self.state.lock();
+        self.rotate_locked(&mut state)
+    }
+
+    /// Merge all sealed segments into one, dropping duplicate frames
+    ///
+    /// Returns `None` when fewer than two segments are sealed.
+    pub fn compact(&self) -> Result<Option<CompactionReport>, String> {
+        let mut state = self.state.lock();
+        self.compact_locked(&mut state)
+    }
+
+    /// Check every frame and index of the store (see [`verify_integrity`])
+    pub fn verify(&self) -> Result<IntegrityReport, String> {
+        let _state = self.state.lock();
+        verify_integrity(&self.dir)
+    }
+
+    fn rotate_locked(&self, state: &mut StoreState) -> Result<(), String> {
+        let active_id = state.active_id();
+        state
+            .active
+            .sync_all()
+            .map_err(|e| io_error("sync", &segment_path(&self.dir, active_id), e))?;
+        if let Some(index) = state.indexes.get(&active_id) {
+            write_index(&self.dir, index)?;
+        }
+
+        let new_id = state.manifest.next_segment;
+        create_segment(&self.dir, new_id)?;
+        let mut manifest = state.manifest.clone();
+        if let Some(active) = manifest.segments.last_mut() {
+            active.sealed = true;
+        }
+        manifest.segments.push(ManifestSegment { id: new_id, sealed: false });
+        manifest.next_segment = new_id + 1;
+        write_manifest(&self.dir, &manifest)?;
+
+        state.manifest = manifest;
+        state.active = open_for_append(&segment_path(&self.dir, new_id))?;
+        state.indexes.insert(new_id, SegmentIndex::empty(new_id));
+
+        let sealed = state.manifest.segments.iter().filter(|segment| segment.sealed).count();
+        if self.config.compact_after.is_some_and(|limit| sealed > limit) {
+            self.compact_locked(state)?;
+        }
+        Ok(())
+    }
+
+    fn compact_locked(&self, state: &mut StoreState) -> Result<Option<CompactionReport>, String> {
+        let merged: Vec<u64> = state
+            .manifest
+            .segments
+            .iter()
+            .filter(|segment| segment.sealed)
+            .map(|segment| segment.id)
+            .collect();
+        if merged.len() < 2 {
+            return Ok(None);
+        }
+
+        let new_id = state.manifest.next_segment;
+        let path = segment_path(&self.dir, new_id);
+        let mut file = OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .open(&path)
+            .map_err(|e| io_error("create", &path, e))?;
+
+        let mut seen = HashSet::new();
+        let mut kept = Vec::new();
+        let mut index = SegmentIndex::empty(new_id);
+        let mut frames_before = 0;
+        for id in &merged {
+            for (line, frame) in read_segment_frames(&self.dir, *id)? {
+                frames_before += 1;
+                if !seen.insert(frame.content_hash.clone()) {
+                    continue;
+                }
+                let entry = IndexEntry::for_frame(&frame, index.bytes, line.len() as u64);
+                file.write_all(line.as_bytes())
+                    .and_then(|()| file.write_all(b"\n"))
+                    .map_err(|e| io_error("write", &path, e))?;
+                index.bytes += line.len() as u64 + 1;
+                index.frames.push(entry);
+                kept.push(frame);
+            }
+        }
+        file.sync_all().map_err(|e| io_error("sync", &path, e))?;
+
+        let compression = SessionCompression::from_frames(&kept.iter().collect::<Vec<_>>());
+        index.compression = Some(compression.clone());
+        write_index(&self.dir, &index)?;
+
+        let mut manifest = state.manifest.clone();
+        manifest.segments.retain(|segment| !segment.sealed);
+        manifest.segments.insert(0, ManifestSegment { id: new_id, sealed: true });
+        manifest.next_segment = new_id + 1;
+        write_manifest(&self.dir, &manifest)?;
+        state.manifest = manifest;
+
+        for id in &merged {
+            state.indexes.remove(id);
+            for path in [segment_path(&self.dir, *id), index_path(&self.dir, *id)] {
+                fs::remove_file(&path).map_err(|e| io_error("remove", &path, e))?;
+            }
+        }
+        sync_dir(&self.dir)?;
+
+        let report = CompactionReport {
+            merged_segments: merged,
+            segment: new_id,
+            frames_before,
+            frames_after: index.frames.len() as u64,
+            compression,
+        };
+        state.indexes.insert(new_id, index);
+        state.rebuild_lookup();
+        Ok(Some(report))
+    }
+
+    /// Read and verify the frame at `location`
+    fn read_frame(&self, location: FrameLocation) -> Result<SessionLogFrame, String> {
+        let path = segment_path(&self.dir, location.segment);
+        let mut file = File::open(&path).map_err(|e| io_error("open", &path, e))?;
+        let mut line = vec![0; location.len as usize];
+        file.seek(SeekFrom::Start(location.offset))
+            .and_then(|_| file.read_exact(&mut line))
+            .map_err(|e| io_error("read", &path, e))?;
+        let frame: SessionLogFrame = serde_json::from_slice(&line).map_err(|e| {
+            format!("{}@{}: unreadable frame ({})", path.display(), location.offset, e)
+        })?;
+        frame
+            .verify_integrity()
+            .map_err(|e| format!("Frame integrity check failed on read: {}", e))?;
+        Ok(frame)
+    }
+
+    /// Every frame of the store in segment order
+    fn all_frames(&self, state: &StoreState) -> Result<Vec<SessionLogFrame>, String> {
+        let mut frames = Vec::new();
+        for segment in &state.manifest.segments {
+            frames.extend(
+                read_segment_frames(&self.dir, segment.id)?.into_iter().map(|(_, frame)| frame),
+            );
+        }
+        Ok(frames)
+    }
+}
+
+impl SessionLogStore for FileSessionLogStore {
+    fn append(&self, frame: SessionLogFrame) -> Result<(), String> {
+        frame.verify_integrity().map_err(|e| format!("Frame validation failed: {}", e))?;
+
+        let mut state = self.state.lock();
+        let previous =
+            state.by_session.get(&frame.metadata.session_id).and_then(|l| l.last()).copied();
+        if let Some(location) = previous {
+            let previous = self.read_frame(location)?;
+            frame
+                .validate_against_previous(&previous)
+                .map_err(|e| format!("Frame validation against previous failed: {}", e))?;
+        }
+
+        let line =
+            serde_json::to_string(&frame).map_err(|e| format!("Failed to encode frame: {}", e))?;
+        let len = line.len() as u64;
+        let needs_rotation = state.active_index().is_some_and(|index| {
+            let frames = index.frames.len() as u64;
+            frames >= self.config.max_segment_frames
+                || (frames > 0 && index.bytes + len + 1 > self.config.max_segment_bytes)
+        });
+        if needs_rotation {
+            self.rotate_locked(&mut state)?;
+        }
+
+        let active_id = state.active_id();
+        let path = segment_path(&self.dir, active_id);
+        let offset = state.active_index().map_or(0, |index| index.bytes);
+        let mut record = line.into_bytes();
+        record.push(b'\n');
+        let written = state.active.write_all(&record).and_then(|()| {
+            if self.config.sync_on_append {
+                state.active.sync_data()
+            } else {
+                Ok(())
+            }
+        });
+        if let Err(e) = written {
+            // Drop a partially written line so the segment stays well-formed
+            let _ = state.active.set_len(offset);
+            return Err(io_error("write", &path, e));
+        }
+
+        let entry = IndexEntry::for_frame(&frame, offset, len);
+        state.add_to_lookup(active_id, &entry);
+        if let Some(index) = state.indexes.get_mut(&active_id) {
+            index.bytes += len + 1;
+            index.frames.push(entry);
+        }
+        Ok(())
+    }
+
+    fn get_by_hash(&self, hash: &str) -> Result<Option<SessionLogFrame>, String> {
+        let state = self.state.lock();
+        state.by_hash.get(hash).map(|location| self.read_frame(*location)).transpose()
+    }
+
+    fn query(
+        &self,
+        predicate: &dyn Fn(&SessionLogFrame) -> bool,
+    ) -> Result<Vec<SessionLogFrame>, String> {
+        let state = self.state.lock();
+        let frames = self.all_frames(&state)?;
+        Ok(frames
+            .into_iter()
+            .filter(|f| {
+                if let Err(e) = f.verify_integrity() {
+                    eprintln!("Frame integrity check failed in query: {}", e);
+                    return false;
+                }
+                predicate(f)
+            })
+            .collect())
+    }
+
+    fn get_session_frames(&self, session_id: &str) -> Result<Vec<SessionLogFrame>, String> {
+        let state = self.state.lock();
+        let mut frames = state
+            .by_session
+            .get(session_id)
+            .map(|locations| {
+                locations
+                    .iter()
+                    .map(|location| self.read_frame(*location))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()
+            .map_err(|e| format!("Frame integrity check failed for session {}: {}", session_id, e))?
+            .unwrap_or_default();
+        frames.sort_by_key(|frame| frame.order_key());
+        Ok(frames)
+    }
+
+    fn compute_compression(
+        &self,
+        start_seq: u64,
+        end_seq: u64,
+    ) -> Result<SessionCompression, String> {
+        let state = self.state.lock();
+        let frames = self.all_frames(&state)?;
+        let selected: Vec<_> = frames
+            .iter()
+            .filter(|f| {
+                f.metadata.sequence_number >= start_seq && f.metadata.sequence_number <= end_seq
+            })
+            .collect();
+        Ok(SessionCompression::from_frames(&selected))
+    }
+}
+
+/// Check a store directory without modifying it
+///
+/// Every frame must parse and match its content hash, frames of a session
+/// must follow each other (see [`SessionLogFrame::validate_against_previous`])
+/// across segments, sealed segments must match their index, and the active
+/// segment must end with a complete line. Files the manifest does not name
+/// are reported too.
+pub fn verify_integrity(dir: impl AsRef<Path>) -> Result<IntegrityReport, String> {
+    let dir = dir.as_ref();
+    let manifest = read_manifest(dir)?.ok_or_else(|| {
+        format!("{} is not a session log store (no {})", dir.display(), MANIFEST_FILE)
+    })?;
+
+    let mut report = IntegrityReport { segments: manifest.segments.len(), ..Default::default() };
+    let mut last_in_session: HashMap<String, SessionLogFrame> = HashMap::new();
+    let problem = |segment: Option<u64>, line: Option<usize>, message: String| IntegrityProblem {
+        segment,
+        line,
+        message,
+    };
+
+    for stray in stray_files(dir, &manifest)? {
+        report.problems.push(problem(
+            None,
+            None,
+            format!("{} is not listed in the manifest", stray.display()),
+        ));
+    }
+
+    for segment in &manifest.segments {
+        let path = segment_path(dir, segment.id);
+        let content = match fs::read(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                report.problems.push(problem(
+                    Some(segment.id),
+                    None,
+                    format!("cannot read segment: {}", e),
+                ));
+                continue;
+            }
+        };
+
+        let mut entries = Vec::new();
+        for (number, offset, line) in split_lines(&content) {
+            let Some(line) = line else {
+                report.problems.push(problem(
+                    Some(segment.id),
+                    Some(number),
+                    "incomplete last line".to_string(),
+                ));
+                break;
+            };
+            let frame: SessionLogFrame = match serde_json::from_slice(line) {
+                Ok(frame) => frame,
+                Err(e) => {
+                    report.problems.push(problem(
+                        Some(segment.id),
+                        Some(number),
+                        format!("unreadable frame ({})", e),
+                    ));
+                    continue;
+                }
+            };
+            report.frames += 1;
+            entries.push(IndexEntry::for_frame(&frame, offset, line.len() as u64));
+            if let Err(e) = frame.verify_integrity() {
+                report.problems.push(problem(Some(segment.id), Some(number), e.to_string()));
+            }
+            if let Some(previous) = last_in_session.get(&frame.metadata.session_id) {
+                if let Err(e) = frame.validate_against_previous(previous) {
+                    report.problems.push(problem(Some(segment.id), Some(number), e.to_string()));
+                }
+            }
+            last_in_session.insert(frame.metadata.session_id.clone(), frame);
+        }
+
+        if segment.sealed {
+            match read_index(dir, segment.id) {
+                Ok(index) if index.frames == entries && index.bytes == content.len() as u64 => {}
+                Ok(_) => report.problems.push(problem(
+                    Some(segment.id),
+                    None,
+                    "index does not match segment".to_string(),
+                )),
+                Err(e) => report.problems.push(problem(Some(segment.id), None, e)),
+            }
+        }
+    }
+
+    report.sessions = last_in_session.len();
+    Ok(report)
+}
+
+impl IndexEntry {
+    fn for_frame(frame: &SessionLogFrame, offset: u64, len: u64) -> Self {
+        Self {
+            content_hash: frame.content_hash.clone(),
+            session_id: frame.metadata.session_id.clone(),
+            sequence_number: frame.metadata.sequence_number,
+            offset,
+            len,
+        }
+    }
+}
+
+impl SegmentIndex {
+    fn empty(segment: u64) -> Self {
+        Self { segment, bytes: 0, frames: Vec::new(), compression: None }
+    }
+}
+
+fn segment_path(dir: &Path, id: u64) -> PathBuf {
+    dir.join(format!("{:020}.{}", id, SEGMENT_EXTENSION))
+}
+
+fn index_path(dir: &Path, id: u64) -> PathBuf {
+    dir.join(format!("{:020}.{}", id, INDEX_EXTENSION))
+}
+
+fn io_error(action: &str, path: &Path, error: std::io::Error) -> String {
+    format!("Failed to {} {}: {}", action, path.display(), error)
+}
+
+/// Sync a directory so created, renamed and removed entries are durable
+fn sync_dir(dir: &Path) -> Result<(), String> {
+    #[cfg(unix)]
+    File::open(dir).and_then(|d| d.sync_all()).map_err(|e| io_error("sync", dir, e))?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+/// Replace `path` with `bytes`: write a temporary file, sync it, rename it over
+fn write_atomically(dir: &Path, path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let temp = path.with_extension(TEMP_EXTENSION);
+    let mut file = File::create(&temp).map_err(|e| io_error("create", &temp, e))?;
+    file.write_all(bytes)
+        .and_then(|()| file.sync_all())
+        .map_err(|e| io_error("write", &temp, e))?;
+    fs::rename(&temp, path).map_err(|e| io_error("rename", &temp, e))?;
+    sync_dir(dir)
+}
+
+fn read_manifest(dir: &Path) -> Result<Option<Manifest>, String> {
+    let path = dir.join(MANIFEST_FILE);
+    let content = match fs::read(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(io_error("read", &path, e)),
+    };
+    let manifest: Manifest = serde_json::from_slice(&content)
+        .map_err(|e| format!("{}: unreadable manifest ({})", path.display(), e))?;
+    if manifest.version != MANIFEST_VERSION {
+        return Err(format!(
+            "{}: unsupported manifest version {} (expected {})",
+            path.display(),
+            manifest.version,
+            MANIFEST_VERSION
+        ));
+    }
+    Ok(Some(manifest))
+}
+
+fn write_manifest(dir: &Path, manifest: &Manifest) -> Result<(), String> {
+    let bytes = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| format!("Failed to encode manifest: {}", e))?;
+    write_atomically(dir, &dir.join(MANIFEST_FILE), &bytes)
+}
+
+fn read_index(dir: &Path, id: u64) -> Result<SegmentIndex, String> {
+    let path = index_path(dir, id);
+    let content = fs::read(&path).map_err(|e| io_error("read", &path, e))?;
+    serde_json::from_slice(&content)
+        .map_err(|e| format!("{}: unreadable index ({})", path.display(), e))
+}
+
+fn write_index(dir: &Path, index: &SegmentIndex) -> Result<(), String> {
+    let bytes =
+        serde_json::to_vec(index).map_err(|e| format!("Failed to encode segment index: {}", e))?;
+    write_atomically(dir, &index_path(dir, index.segment), &bytes)
+}
+
+/// Create an empty segment file and make its directory entry durable
+fn create_segment(dir: &Path, id: u64) -> Result<(), String> {
+    let path = segment_path(dir, id);
+    OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(&path)
+        .and_then(|file| file.sync_all())
+        .map_err(|e| io_error("create", &path, e))?;
+    sync_dir(dir)
+}
+
+fn open_for_append(path: &Path) -> Result<File, String> {
+    OpenOptions::new().append(true).open(path).map_err(|e| io_error("open", path, e))
+}
+
+/// Segment, index and temporary files the manifest does not name
+fn stray_files(dir: &Path, manifest: &Manifest) -> Result<Vec<PathBuf>, String> {
+    let mut live: HashSet<PathBuf> = HashSet::new();
+    for segment in &manifest.segments {
+        live.insert(segment_path(dir, segment.id));
+        if segment.sealed {
+            live.insert(index_path(dir, segment.id));
+        }
+    }
+
+    let mut stray = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| io_error("list", dir, e))? {
+        let path = entry.map_err(|e| io_error("list", dir, e))?.path();
+        let managed = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| [SEGMENT_EXTENSION, INDEX_EXTENSION, TEMP_EXTENSION].contains(&ext));
+        if managed && !live.contains(&path) {
+            stray.push(path);
+        }
+    }
+    stray.sort();
+    Ok(stray)
+}
+
+fn remove_leftovers(dir: &Path, manifest: &Manifest) -> Result<(), String> {
+    let stray = stray_files(dir, manifest)?;
+    for path in &stray {
+        fs::remove_file(path).map_err(|e| io_error("remove", path, e))?;
+    }
+    if !stray.is_empty() {
+        sync_dir(dir)?;
+    }
+    Ok(())
+}
+
+/// Index of a sealed segment, rebuilt from the segment when missing or stale
+fn load_sealed_index(dir: &Path, id: u64) -> Result<SegmentIndex, String> {
+    let path = segment_path(dir, id);
+    let bytes = fs::metadata(&path).map_err(|e| io_error("read", &path, e))?.len();
+    match read_index(dir, id) {
+        Ok(index) if index.bytes == bytes => Ok(index),
+        _ => {
+            let index = scan_segment(dir, id, false)?;
+            write_index(dir, &index)?;
+            Ok(index)
+        }
+    }
+}
+
+/// Index the active segment, truncating a torn last line
+fn recover_active_segment(dir: &Path, id: u64) -> Result<SegmentIndex, String> {
+    let index = scan_segment(dir, id, true)?;
+    let path = segment_path(dir, id);
+    let len = fs::metadata(&path).map_err(|e| io_error("read", &path, e))?.len();
+    if len != index.bytes {
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_len(index.bytes).and_then(|()| file.sync_all()))
+            .map_err(|e| io_error("truncate", &path, e))?;
+    }
+    Ok(index)
+}
+
+/// Build the index of a segment from its lines
+///
+/// With `allow_torn_tail`, an incomplete or unreadable last line ends the
+/// index instead of failing.
+fn scan_segment(dir: &Path, id: u64, allow_torn_tail: bool) -> Result<SegmentIndex, String> {
+    let path = segment_path(dir, id);
+    let content = fs::read(&path).map_err(|e| io_error("read", &path, e))?;
+    let lines = split_lines(&content);
+    let count = lines.len();
+    let mut index = SegmentIndex::empty(id);
+
+    for (position, (number, offset, line)) in lines.into_iter().enumerate() {
+        let is_last = position + 1 == count;
+        let frame = line.ok_or_else(|| "incomplete last line".to_string()).and_then(|line| {
+            serde_json::from_slice::<SessionLogFrame>(line)
+                .map_err(|e| format!("unreadable frame ({})", e))
+        });
+        match frame {
+            Ok(frame) => {
+                let len = line.map_or(0, |line| line.len() as u64);
+                index.frames.push(IndexEntry::for_frame(&frame, offset, len));
+                index.bytes = offset + len + 1;
+            }
+            Err(_) if allow_torn_tail && is_last => break,
+            Err(problem) => return Err(format!("{}:{}: {}", path.display(), number, problem)),
+        }
+    }
+    Ok(index)
+}
+
+/// Lines of a segment as `(1-based number, offset, line)`; `None` for a last
+/// line without its newline
+fn split_lines(content: &[u8]) -> Vec<(usize, u64, Option<&[u8]>)> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    while offset < content.len() {
+        let number = lines.len() + 1;
+        match content[offset..].iter().position(|b| *b == b'\n') {
+            Some(end) => {
+                lines.push((number, offset as u64, Some(&content[offset..offset + end])));
+                offset += end + 1;
+            }
+            None => {
+                lines.push((number, offset as u64, None));
+                break;
+            }
+        }
+    }
+    lines
+}
+
+/// Every line of a segment with its parsed frame, in order
+fn read_segment_frames(dir: &Path, id: u64) -> Result<Vec<(String, SessionLogFrame)>, String> {
+    let path = segment_path(dir, id);
+    let file = File::open(&path).map_err(|e| io_error("open", &path, e))?;
+    let mut frames = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| io_error("read", &path, e))?;
+        let frame = serde_json::from_str(&line)
+            .map_err(|e| format!("{}:{}: unreadable frame ({})", path.display(), number + 1, e))?;
+        frames.push((line, frame));
+    }
+    Ok(frames)
+}
@@ -10,6 +10,7 @@
 //! - **Argument introspection**: Query expected arguments and types
 //! - **Parsing simulation**: Test argument parsing without execution
 //! - **Golden snapshots**: Serialize grammar for regression testing
+//! - **Golden recordings**: Replay invocations recorded with `--record`
 //!
 //! # Example
 //!
@@ -36,9 +37,11 @@
 
 use crate::kernel::capability::{CapabilityClass, CapabilityContract};
 use crate::kernel::grammar::{Grammar, GrammarModel, GrammarVerb};
+use crate::kernel::recording::{ReplayReport, ReplayRunner};
 use crate::kernel::version::GrammarDelta;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// Test harness for CNV applications
 ///
@@ -150,6 +153,7 @@ impl TestHarness {
     }
 
     /// Generate a YAML grammar snapshot
+    #[cfg(feature = "config-formats")]
     pub fn grammar_snapshot_yaml(&self) -> String {
         serde_yaml::to_string(&self.grammar)
             .unwrap_or_else(|e| format!("Error serializing grammar: {}", e))
@@ -558,6 +562,19 @@ impl TestHarnessBuilder {
     }
 }
 
+/// Replay a recording made with `app --record` against `binary`
+///
+/// Returns the report, or `Err` with the rendered report when the exit code,
+/// stdout or stderr of any invocation differs from the recording.
+pub fn assert_replay_matches(
+    binary: impl AsRef<Path>,
+    recording: impl AsRef<Path>,
+) -> Result<ReplayReport, String> {
+    let report = ReplayRunner::new(binary.as_ref()).replay_file(recording.as_ref())?;
+    report.ensure_passed()?;
+    Ok(report)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
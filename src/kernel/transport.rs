@@ -10,12 +10,35 @@
 //! - **TcpTransport**: TCP for remote deployments
 //!
 //! All share the same **binary frame format** and **multiplexing protocol**.
+//!
+//! ## Wire Format
+//!
+//! Each frame is a big-endian `u32` byte length followed by the encoded frame
+//! (see [`InvocationFrame::encode`]). Stdio, Unix socket and TCP transports
+//! exchange frames over any byte stream, so a CLI can run as a long-lived
+//! daemon that sibling processes invoke without re-spawning it:
+//!
+//! ```rust,ignore
+//! let listener = tokio::net::UnixListener::bind("/tmp/cli.sock")?;
+//! let (stream, _) = listener.accept().await?;
+//! let transport = UnixSocketTransport::from_stream(stream);
+//! let request = transport.recv_frame().await?;
+//! ```
 
 use crate::kernel::broker::{BrokerRequest, BrokerResponse};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::fmt;
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Largest encoded frame accepted by [`read_frame`] (16 MiB)
+pub const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// Size of the length prefix preceding every frame on the wire
+pub const LENGTH_PREFIX_SIZE: usize = 4;
 
 /// Binary invocation frame - transport-agnostic message unit
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -137,10 +160,139 @@ impl InvocationFrame {
     }
 
     /// Get frame size
+    ///
+    /// Size of [`encode`](Self::encode)'s output, excluding the length prefix.
     pub fn frame_size(&self) -> usize {
-        // Version (4) + correlation_id + tenant_id + agent_id + frame_type (1) + payload_len + sequence (8) + flags (1)
-        4 + self.correlation_id.len() + self.tenant_id.len() + self.agent_id.len()
-            + 1 + 4 + self.payload.len() + 8 + 1
+        // Version (4) + frame_type (1) + flags (1) + sequence (8)
+        // + 3 x (u16 length + id) + payload_len (4) + payload
+        4 + 1
+            + 1
+            + 8
+            + 2
+            + self.correlation_id.len()
+            + 2
+            + self.tenant_id.len()
+            + 2
+            + self.agent_id.len()
+            + 4
+            + self.payload.len()
+    }
+
+    /// Encode the frame body (without the length prefix)
+    ///
+    /// Layout, all integers big-endian: `version: u32`, `frame_type: u8`,
+    /// `flags: u8` (bit 0 backpressure, bit 1 end of stream, bit 2 priority),
+    /// `sequence_number: u64`, then `correlation_id`, `tenant_id` and
+    /// `agent_id` as `u16` length + UTF-8, then `payload` as `u32` length + bytes.
+    pub fn encode(&self) -> Result<Vec<u8>, TransportError> {
+        let mut buf = Vec::with_capacity(self.frame_size());
+        buf.extend_from_slice(&self.version.to_be_bytes());
+        buf.push(self.frame_type.as_u8());
+        buf.push(self.flags.to_bits());
+        buf.extend_from_slice(&self.sequence_number.to_be_bytes());
+        for (field, value) in [
+            ("correlation_id", &self.correlation_id),
+            ("tenant_id", &self.tenant_id),
+            ("agent_id", &self.agent_id),
+        ] {
+            let len = u16::try_from(value.len()).map_err(|_| {
+                TransportError::EncodingError(format!("{} exceeds {} bytes", field, u16::MAX))
+            })?;
+            buf.extend_from_slice(&len.to_be_bytes());
+            buf.extend_from_slice(value.as_bytes());
+        }
+        let payload_len = u32::try_from(self.payload.len())
+            .ok()
+            .filter(|_| self.frame_size() <= MAX_FRAME_SIZE)
+            .ok_or_else(|| {
+                TransportError::EncodingError(format!(
+                    "frame exceeds the {} byte limit",
+                    MAX_FRAME_SIZE
+                ))
+            })?;
+        buf.extend_from_slice(&payload_len.to_be_bytes());
+        buf.extend_from_slice(&self.payload);
+        Ok(buf)
+    }
+
+    /// Decode a frame body produced by [`encode`](Self::encode)
+    pub fn decode(bytes: &[u8]) -> Result<Self, TransportError> {
+        let mut cursor = FrameCursor { bytes };
+        let version = u32::from_be_bytes(cursor.array()?);
+        let [frame_type] = cursor.array()?;
+        let frame_type = FrameType::from_u8(frame_type).ok_or_else(|| {
+            TransportError::FrameFormatError(format!("unknown frame type {}", frame_type))
+        })?;
+        let [flags] = cursor.array()?;
+        let sequence_number = u64::from_be_bytes(cursor.array()?);
+        let correlation_id = cursor.string()?;
+        let tenant_id = cursor.string()?;
+        let agent_id = cursor.string()?;
+        let payload_len = u32::from_be_bytes(cursor.array()?) as usize;
+        let payload = cursor.take(payload_len)?.to_vec();
+        if !cursor.bytes.is_empty() {
+            return Err(TransportError::FrameFormatError(format!(
+                "{} trailing bytes after payload",
+                cursor.bytes.len()
+            )));
+        }
+
+        Ok(Self {
+            version,
+            correlation_id,
+            tenant_id,
+            agent_id,
+            frame_type,
+            payload,
+            sequence_number,
+            flags: FrameFlags::from_bits(flags),
+        })
+    }
+}
+
+impl FrameFlags {
+    /// Pack the flags into a byte
+    pub fn to_bits(self) -> u8 {
+        u8::from(self.backpressure)
+            | (u8::from(self.end_of_stream) << 1)
+            | (u8::from(self.priority) << 2)
+    }
+
+    /// Unpack flags from a byte, ignoring unknown bits
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            backpressure: bits & 0b001 != 0,
+            end_of_stream: bits & 0b010 != 0,
+            priority: bits & 0b100 != 0,
+        }
+    }
+}
+
+/// Bounds-checked reader over an encoded frame
+struct FrameCursor<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> FrameCursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TransportError> {
+        if self.bytes.len() < len {
+            return Err(TransportError::FrameFormatError("frame truncated".to_string()));
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], TransportError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn string(&mut self) -> Result<String, TransportError> {
+        let len = u16::from_be_bytes(self.array()?) as usize;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|e| TransportError::FrameFormatError(format!("invalid UTF-8: {}", e)))
     }
 }
 
@@ -168,6 +320,159 @@ impl fmt::Display for TransportError {
     }
 }
 
+impl std::error::Error for TransportError {}
+
+/// Write one length-prefixed frame and flush
+pub async fn write_frame<W>(writer: &mut W, frame: &InvocationFrame) -> Result<(), TransportError>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let body = frame.encode()?;
+    let len = u32::try_from(body.len())
+        .map_err(|_| TransportError::EncodingError("frame too large".to_string()))?;
+    let send_error = |e: std::io::Error| io_error(e, TransportError::SendFailed);
+
+    writer.write_all(&len.to_be_bytes()).await.map_err(send_error)?;
+    writer.write_all(&body).await.map_err(send_error)?;
+    writer.flush().await.map_err(send_error)
+}
+
+/// Read one length-prefixed frame
+///
+/// A clean end of stream before the length prefix is reported as
+/// [`TransportError::ConnectionFailed`]; frames over [`MAX_FRAME_SIZE`] are
+/// rejected without reading their body.
+pub async fn read_frame<R>(reader: &mut R) -> Result<InvocationFrame, TransportError>
+where
+    R: AsyncRead + Unpin + ?Sized,
+{
+    let mut prefix = [0u8; LENGTH_PREFIX_SIZE];
+    if let Err(e) = reader.read_exact(&mut prefix).await {
+        return Err(match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => {
+                TransportError::ConnectionFailed("peer closed the connection".to_string())
+            }
+            _ => io_error(e, TransportError::ReceiveFailed),
+        });
+    }
+
+    let len = u32::from_be_bytes(prefix) as usize;
+    if len > MAX_FRAME_SIZE {
+        return Err(TransportError::FrameFormatError(format!(
+            "frame of {} bytes exceeds the {} byte limit",
+            len, MAX_FRAME_SIZE
+        )));
+    }
+
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body).await.map_err(|e| io_error(e, TransportError::ReceiveFailed))?;
+    InvocationFrame::decode(&body)
+}
+
+/// Map an I/O error, turning timeouts into [`TransportError::TimeoutError`]
+fn io_error(error: std::io::Error, wrap: impl FnOnce(String) -> TransportError) -> TransportError {
+    match error.kind() {
+        std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => {
+            TransportError::TimeoutError
+        }
+        _ => wrap(error.to_string()),
+    }
+}
+
+/// Run `operation`, failing with [`TransportError::TimeoutError`] after `limit`
+async fn with_timeout<T>(
+    limit: Option<Duration>,
+    operation: impl Future<Output = Result<T, TransportError>>,
+) -> Result<T, TransportError> {
+    match limit {
+        Some(limit) => tokio::time::timeout(limit, operation)
+            .await
+            .map_err(|_| TransportError::TimeoutError)?,
+        None => operation.await,
+    }
+}
+
+type BoxedReader = Box<dyn AsyncRead + Send + Unpin>;
+type BoxedWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Byte stream halves shared by the stream-based transports
+///
+/// Reads and writes are serialized independently, so one task can wait in
+/// `recv_frame` while another sends. A receive that times out or fails
+/// disconnects the stream.
+struct FramedStream {
+    reader: tokio::sync::Mutex<Option<BoxedReader>>,
+    writer: tokio::sync::Mutex<Option<BoxedWriter>>,
+    connected: AtomicBool,
+    timeout: Option<Duration>,
+}
+
+impl FramedStream {
+    fn disconnected() -> Self {
+        Self {
+            reader: tokio::sync::Mutex::new(None),
+            writer: tokio::sync::Mutex::new(None),
+            connected: AtomicBool::new(false),
+            timeout: None,
+        }
+    }
+
+    fn attached(reader: BoxedReader, writer: BoxedWriter) -> Self {
+        Self {
+            reader: tokio::sync::Mutex::new(Some(reader)),
+            writer: tokio::sync::Mutex::new(Some(writer)),
+            connected: AtomicBool::new(true),
+            timeout: None,
+        }
+    }
+
+    async fn attach(&self, reader: BoxedReader, writer: BoxedWriter) {
+        *self.reader.lock().await = Some(reader);
+        *self.writer.lock().await = Some(writer);
+        self.connected.store(true, Ordering::SeqCst);
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    async fn send(&self, frame: &InvocationFrame, peer: &str) -> Result<(), TransportError> {
+        let mut writer = self.writer.lock().await;
+        let writer = writer.as_mut().filter(|_| self.is_connected()).ok_or_else(|| {
+            TransportError::ConnectionFailed(format!("not connected to {}", peer))
+        })?;
+        with_timeout(self.timeout, write_frame(writer, frame)).await
+    }
+
+    async fn recv(&self, peer: &str) -> Result<InvocationFrame, TransportError> {
+        let mut reader = self.reader.lock().await;
+        let reader = reader.as_mut().filter(|_| self.is_connected()).ok_or_else(|| {
+            TransportError::ConnectionFailed(format!("not connected to {}", peer))
+        })?;
+        let result = with_timeout(self.timeout, read_frame(reader)).await;
+        // A timeout or read error may leave part of a frame consumed; the next
+        // read would start mid-frame, so the stream cannot be reused.
+        if let Err(
+            TransportError::ConnectionFailed(_)
+            | TransportError::TimeoutError
+            | TransportError::ReceiveFailed(_),
+        ) = result
+        {
+            self.connected.store(false, Ordering::SeqCst);
+        }
+        result
+    }
+
+    async fn close(&self) -> Result<(), TransportError> {
+        self.connected.store(false, Ordering::SeqCst);
+        self.reader.lock().await.take();
+        if let Some(mut writer) = self.writer.lock().await.take() {
+            writer.shutdown().await.map_err(|e| io_error(e, TransportError::SendFailed))?;
+        }
+        Ok(())
+    }
+}
+
 /// Transport trait - sealed interface for pluggable backends
 #[async_trait]
 pub trait InvocationTransport: Send + Sync {
@@ -188,15 +493,31 @@ pub trait InvocationTransport: Send + Sync {
 }
 
 /// Stdio transport - local process
+///
+/// Reads frames from stdin and writes them to stdout. Use
+/// [`from_streams`](Self::from_streams) to run the same protocol over pipes.
 pub struct StdioTransport {
-    connected: std::sync::atomic::AtomicBool,
+    stream: FramedStream,
 }
 
 impl StdioTransport {
     pub fn new() -> Self {
-        Self {
-            connected: std::sync::atomic::AtomicBool::new(true),
-        }
+        Self::from_streams(tokio::io::stdin(), tokio::io::stdout())
+    }
+
+    /// Frame over an arbitrary reader/writer pair (e.g. a child's pipes)
+    pub fn from_streams<R, W>(reader: R, writer: W) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        Self { stream: FramedStream::attached(Box::new(reader), Box::new(writer)) }
+    }
+
+    /// Fail sends and receives that take longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.stream.timeout = Some(timeout);
+        self
     }
 }
 
@@ -208,21 +529,12 @@ impl Default for StdioTransport {
 
 #[async_trait]
 impl InvocationTransport for StdioTransport {
-    async fn send_frame(&self, _frame: InvocationFrame) -> Result<(), TransportError> {
-        // In a real implementation, serialize to JSON and write to stdout
-        if !self.is_connected() {
-            return Err(TransportError::ConnectionFailed(
-                "Stdio transport not connected".to_string(),
-            ));
-        }
-        Ok(())
+    async fn send_frame(&self, frame: InvocationFrame) -> Result<(), TransportError> {
+        self.stream.send(&frame, "stdio").await
     }
 
     async fn recv_frame(&self) -> Result<InvocationFrame, TransportError> {
-        // In a real implementation, read from stdin and deserialize
-        Err(TransportError::ReceiveFailed(
-            "Stdio transport recv not implemented".to_string(),
-        ))
+        self.stream.recv("stdio").await
     }
 
     fn transport_name(&self) -> &'static str {
@@ -230,52 +542,68 @@ impl InvocationTransport for StdioTransport {
     }
 
     fn is_connected(&self) -> bool {
-        self.connected.load(std::sync::atomic::Ordering::SeqCst)
+        self.stream.is_connected()
     }
 
     async fn close(&self) -> Result<(), TransportError> {
-        self.connected.store(false, std::sync::atomic::Ordering::SeqCst);
-        Ok(())
+        self.stream.close().await
     }
 }
 
 /// Unix socket transport for local IPC
+#[cfg(unix)]
 pub struct UnixSocketTransport {
-    connected: std::sync::atomic::AtomicBool,
+    stream: FramedStream,
     path: String,
 }
 
+#[cfg(unix)]
 impl UnixSocketTransport {
     pub fn new(path: String) -> Self {
-        Self {
-            connected: std::sync::atomic::AtomicBool::new(false),
-            path,
-        }
+        Self { stream: FramedStream::disconnected(), path }
+    }
+
+    /// Wrap an accepted connection (server side of a daemon)
+    pub fn from_stream(stream: tokio::net::UnixStream) -> Self {
+        let path = stream
+            .peer_addr()
+            .ok()
+            .and_then(|addr| addr.as_pathname().map(|p| p.display().to_string()))
+            .unwrap_or_else(|| "unix-peer".to_string());
+        let (reader, writer) = stream.into_split();
+        Self { stream: FramedStream::attached(Box::new(reader), Box::new(writer)), path }
+    }
+
+    /// Fail connects, sends and receives that take longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.stream.timeout = Some(timeout);
+        self
     }
 
     pub async fn connect(&self) -> Result<(), TransportError> {
-        // In a real implementation, connect to Unix socket
-        self.connected.store(true, std::sync::atomic::Ordering::SeqCst);
+        let stream = with_timeout(self.stream.timeout, async {
+            tokio::net::UnixStream::connect(&self.path).await.map_err(|e| {
+                io_error(e, |msg| {
+                    TransportError::ConnectionFailed(format!("{}: {}", self.path, msg))
+                })
+            })
+        })
+        .await?;
+        let (reader, writer) = stream.into_split();
+        self.stream.attach(Box::new(reader), Box::new(writer)).await;
         Ok(())
     }
 }
 
+#[cfg(unix)]
 #[async_trait]
 impl InvocationTransport for UnixSocketTransport {
-    async fn send_frame(&self, _frame: InvocationFrame) -> Result<(), TransportError> {
-        if !self.is_connected() {
-            return Err(TransportError::ConnectionFailed(format!(
-                "Unix socket not connected to {}",
-                self.path
-            )));
-        }
-        Ok(())
+    async fn send_frame(&self, frame: InvocationFrame) -> Result<(), TransportError> {
+        self.stream.send(&frame, &self.path).await
     }
 
     async fn recv_frame(&self) -> Result<InvocationFrame, TransportError> {
-        Err(TransportError::ReceiveFailed(
-            "Unix socket recv not implemented".to_string(),
-        ))
+        self.stream.recv(&self.path).await
     }
 
     fn transport_name(&self) -> &'static str {
@@ -283,52 +611,49 @@ impl InvocationTransport for UnixSocketTransport {
     }
 
     fn is_connected(&self) -> bool {
-        self.connected.load(std::sync::atomic::Ordering::SeqCst)
+        self.stream.is_connected()
     }
 
     async fn close(&self) -> Result<(), TransportError> {
-        self.connected.store(false, std::sync::atomic::Ordering::SeqCst);
-        Ok(())
+        self.stream.close().await
     }
 }
 
 /// QUIC transport for cluster-level federation
+///
+/// Not implemented yet: `connect` only records the peer, and sending or
+/// receiving fails with [`TransportError::ConnectionFailed`].
 pub struct QuicTransport {
-    connected: std::sync::atomic::AtomicBool,
+    connected: AtomicBool,
     peer_addr: String,
 }
 
 impl QuicTransport {
     pub fn new(peer_addr: String) -> Self {
-        Self {
-            connected: std::sync::atomic::AtomicBool::new(false),
-            peer_addr,
-        }
+        Self { connected: AtomicBool::new(false), peer_addr }
     }
 
     pub async fn connect(&self) -> Result<(), TransportError> {
-        // In a real implementation, establish QUIC connection
-        self.connected.store(true, std::sync::atomic::Ordering::SeqCst);
+        self.connected.store(true, Ordering::SeqCst);
         Ok(())
     }
+
+    fn unsupported(&self) -> TransportError {
+        TransportError::ConnectionFailed(format!(
+            "QUIC transport to {} is not supported yet",
+            self.peer_addr
+        ))
+    }
 }
 
 #[async_trait]
 impl InvocationTransport for QuicTransport {
     async fn send_frame(&self, _frame: InvocationFrame) -> Result<(), TransportError> {
-        if !self.is_connected() {
-            return Err(TransportError::ConnectionFailed(format!(
-                "QUIC not connected to {}",
-                self.peer_addr
-            )));
-        }
-        Ok(())
+        Err(self.unsupported())
     }
 
     async fn recv_frame(&self) -> Result<InvocationFrame, TransportError> {
-        Err(TransportError::ReceiveFailed(
-            "QUIC recv not implemented".to_string(),
-        ))
+        Err(self.unsupported())
     }
 
     fn transport_name(&self) -> &'static str {
@@ -336,52 +661,67 @@ impl InvocationTransport for QuicTransport {
     }
 
     fn is_connected(&self) -> bool {
-        self.connected.load(std::sync::atomic::Ordering::SeqCst)
+        self.connected.load(Ordering::SeqCst)
     }
 
     async fn close(&self) -> Result<(), TransportError> {
-        self.connected.store(false, std::sync::atomic::Ordering::SeqCst);
+        self.connected.store(false, Ordering::SeqCst);
         Ok(())
     }
 }
 
 /// TCP transport for remote deployments
 pub struct TcpTransport {
-    connected: std::sync::atomic::AtomicBool,
+    stream: FramedStream,
     peer_addr: String,
 }
 
 impl TcpTransport {
     pub fn new(peer_addr: String) -> Self {
-        Self {
-            connected: std::sync::atomic::AtomicBool::new(false),
-            peer_addr,
-        }
+        Self { stream: FramedStream::disconnected(), peer_addr }
+    }
+
+    /// Wrap an accepted connection (server side of a daemon)
+    pub fn from_stream(stream: tokio::net::TcpStream) -> Self {
+        let peer_addr = stream
+            .peer_addr()
+            .map(|addr| addr.to_string())
+            .unwrap_or_else(|_| "tcp-peer".to_string());
+        let _ = stream.set_nodelay(true);
+        let (reader, writer) = stream.into_split();
+        Self { stream: FramedStream::attached(Box::new(reader), Box::new(writer)), peer_addr }
+    }
+
+    /// Fail connects, sends and receives that take longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.stream.timeout = Some(timeout);
+        self
     }
 
     pub async fn connect(&self) -> Result<(), TransportError> {
-        // In a real implementation, establish TCP connection
-        self.connected.store(true, std::sync::atomic::Ordering::SeqCst);
+        let stream = with_timeout(self.stream.timeout, async {
+            tokio::net::TcpStream::connect(&self.peer_addr).await.map_err(|e| {
+                io_error(e, |msg| {
+                    TransportError::ConnectionFailed(format!("{}: {}", self.peer_addr, msg))
+                })
+            })
+        })
+        .await?;
+        let _ = stream.set_nodelay(true);
+        let (reader, writer) = stream.into_split();
+        self.stream.attach(Box::new(reader), Box::new(writer)).await;
         Ok(())
     }
 }
 
 #[async_trait]
 impl InvocationTransport for TcpTransport {
-    async fn send_frame(&self, _frame: InvocationFrame) -> Result<(), TransportError> {
-        if !self.is_connected() {
-            return Err(TransportError::ConnectionFailed(format!(
-                "TCP not connected to {}",
-                self.peer_addr
-            )));
-        }
-        Ok(())
+    async fn send_frame(&self, frame: InvocationFrame) -> Result<(), TransportError> {
+        self.stream.send(&frame, &self.peer_addr).await
     }
 
     async fn recv_frame(&self) -> Result<InvocationFrame, TransportError> {
-        Err(TransportError::ReceiveFailed(
-            "TCP recv not implemented".to_string(),
-        ))
+        self.stream.recv(&self.peer_addr).await
     }
 
     fn transport_name(&self) -> &'static str {
@@ -389,12 +729,11 @@ impl InvocationTransport for TcpTransport {
     }
 
     fn is_connected(&self) -> bool {
-        self.connected.load(std::sync::atomic::Ordering::SeqCst)
+        self.stream.is_connected()
     }
 
     async fn close(&self) -> Result<(), TransportError> {
-        self.connected.store(false, std::sync::atomic::Ordering::SeqCst);
-        Ok(())
+        self.stream.close().await
     }
 }
 
@@ -430,4 +769,192 @@ mod tests {
         assert!(transport.is_connected());
         assert_eq!(transport.transport_name(), "stdio");
     }
+
+    fn sample_frame(payload: &[u8]) -> InvocationFrame {
+        InvocationFrame {
+            version: 1,
+            correlation_id: "corr-1".to_string(),
+            tenant_id: "tenant".to_string(),
+            agent_id: "agent".to_string(),
+            frame_type: FrameType::Request,
+            payload: payload.to_vec(),
+            sequence_number: 42,
+            flags: FrameFlags { backpressure: false, end_of_stream: true, priority: true },
+        }
+    }
+
+    fn assert_same_frame(actual: &InvocationFrame, expected: &InvocationFrame) {
+        assert_eq!(actual.version, expected.version);
+        assert_eq!(actual.correlation_id, expected.correlation_id);
+        assert_eq!(actual.tenant_id, expected.tenant_id);
+        assert_eq!(actual.agent_id, expected.agent_id);
+        assert_eq!(actual.frame_type, expected.frame_type);
+        assert_eq!(actual.payload, expected.payload);
+        assert_eq!(actual.sequence_number, expected.sequence_number);
+        assert_eq!(actual.flags.to_bits(), expected.flags.to_bits());
+    }
+
+    #[test]
+    fn test_frame_encode_decode_roundtrip() {
+        let frame = sample_frame(b"{\"hello\":1}");
+
+        let encoded = frame.encode();
+        assert!(encoded.is_ok());
+        let encoded = encoded.unwrap_or_default();
+        assert_eq!(encoded.len(), frame.frame_size());
+
+        let decoded = InvocationFrame::decode(&encoded);
+        assert!(decoded.is_ok());
+        if let Ok(decoded) = decoded {
+            assert_same_frame(&decoded, &frame);
+        }
+    }
+
+    #[test]
+    fn test_frame_decode_rejects_truncated_and_trailing_bytes() {
+        let encoded = sample_frame(b"abc").encode().unwrap_or_default();
+
+        let truncated = InvocationFrame::decode(&encoded[..encoded.len() - 1]);
+        assert!(matches!(truncated, Err(TransportError::FrameFormatError(_))));
+
+        let mut trailing = encoded.clone();
+        trailing.push(0);
+        assert!(matches!(
+            InvocationFrame::decode(&trailing),
+            Err(TransportError::FrameFormatError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_frame_rejects_oversized_length_prefix() {
+        let bytes = ((MAX_FRAME_SIZE + 1) as u32).to_be_bytes();
+        let result = read_frame(&mut &bytes[..]).await;
+        assert!(matches!(result, Err(TransportError::FrameFormatError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stdio_transport_over_pipes() {
+        let (client_io, server_io) = tokio::io::duplex(1024);
+        let (client_read, client_write) = tokio::io::split(client_io);
+        let (server_read, server_write) = tokio::io::split(server_io);
+        let client = StdioTransport::from_streams(client_read, client_write);
+        let server = StdioTransport::from_streams(server_read, server_write);
+        let frame = sample_frame(b"ping");
+
+        assert!(client.send_frame(frame.clone()).await.is_ok());
+        let received = server.recv_frame().await;
+
+        assert!(received.is_ok());
+        if let Ok(received) = received {
+            assert_same_frame(&received, &frame);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recv_timeout_maps_to_timeout_error() {
+        let (_peer, io) = tokio::io::duplex(64);
+        let (reader, writer) = tokio::io::split(io);
+        let transport =
+            StdioTransport::from_streams(reader, writer).with_timeout(Duration::from_millis(20));
+
+        let result = transport.recv_frame().await;
+
+        assert!(matches!(result, Err(TransportError::TimeoutError)));
+    }
+
+    #[tokio::test]
+    async fn test_recv_timeout_mid_frame_disconnects_transport() {
+        let (mut peer, io) = tokio::io::duplex(1024);
+        let (reader, writer) = tokio::io::split(io);
+        let transport =
+            StdioTransport::from_streams(reader, writer).with_timeout(Duration::from_millis(20));
+        let mut bytes = Vec::new();
+        assert!(write_frame(&mut bytes, &sample_frame(b"ping")).await.is_ok());
+        assert!(peer.write_all(&bytes[..bytes.len() / 2]).await.is_ok());
+
+        let first = transport.recv_frame().await;
+        assert!(peer.write_all(&bytes[bytes.len() / 2..]).await.is_ok());
+        let second = transport.recv_frame().await;
+
+        assert!(matches!(first, Err(TransportError::TimeoutError)));
+        assert!(matches!(second, Err(TransportError::ConnectionFailed(_))));
+        assert!(!transport.is_connected());
+    }
+
+    #[tokio::test]
+    async fn test_closed_peer_disconnects_transport() {
+        let (peer, io) = tokio::io::duplex(64);
+        let (reader, writer) = tokio::io::split(io);
+        let transport = StdioTransport::from_streams(reader, writer);
+        drop(peer);
+
+        let result = transport.recv_frame().await;
+
+        assert!(matches!(result, Err(TransportError::ConnectionFailed(_))));
+        assert!(!transport.is_connected());
+    }
+
+    #[tokio::test]
+    async fn test_tcp_transport_roundtrip() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await;
+        assert!(listener.is_ok());
+        let Ok(listener) = listener else { return };
+        let addr = listener.local_addr().map(|a| a.to_string()).unwrap_or_default();
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.map_err(|e| e.to_string())?;
+            let transport = TcpTransport::from_stream(stream);
+            let request = transport.recv_frame().await.map_err(|e| e.to_string())?;
+            let mut response = request.clone();
+            response.frame_type = FrameType::Response;
+            transport.send_frame(response).await.map_err(|e| e.to_string())
+        });
+
+        let client = TcpTransport::new(addr).with_timeout(Duration::from_secs(5));
+        assert!(!client.is_connected());
+        assert!(client.connect().await.is_ok());
+        assert!(client.send_frame(sample_frame(b"tcp")).await.is_ok());
+        let response = client.recv_frame().await;
+
+        assert!(matches!(server.await, Ok(Ok(()))));
+        assert!(matches!(response, Ok(ref frame) if frame.frame_type == FrameType::Response));
+        assert!(client.close().await.is_ok());
+        assert!(!client.is_connected());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_socket_transport_roundtrip() {
+        let path = std::env::temp_dir()
+            .join(format!("cnv-transport-{}.sock", std::process::id()))
+            .display()
+            .to_string();
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path);
+        assert!(listener.is_ok());
+        let Ok(listener) = listener else { return };
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.map_err(|e| e.to_string())?;
+            let transport = UnixSocketTransport::from_stream(stream);
+            let request = transport.recv_frame().await.map_err(|e| e.to_string())?;
+            transport.send_frame(request).await.map_err(|e| e.to_string())
+        });
+
+        let client = UnixSocketTransport::new(path.clone());
+        assert!(client.connect().await.is_ok());
+        assert!(client.send_frame(sample_frame(b"unix")).await.is_ok());
+        let echoed = client.recv_frame().await;
+
+        assert!(matches!(server.await, Ok(Ok(()))));
+        assert!(matches!(echoed, Ok(ref frame) if frame.payload == b"unix"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_send_without_connect_fails() {
+        let transport = TcpTransport::new("127.0.0.1:1".to_string());
+        let result = transport.send_frame(sample_frame(b"")).await;
+        assert!(matches!(result, Err(TransportError::ConnectionFailed(_))));
+    }
 }
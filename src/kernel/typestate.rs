@@ -66,7 +66,7 @@ pub struct Escalated<C1, C2> {
 /// ```
 pub struct TypedSession<State> {
     name: String,
-    contract: Option<CapabilityContract>,
+    contract: CapabilityContract,
     audit_log: Vec<AuditEntry>,
     _state: PhantomData<State>,
 }
@@ -96,7 +96,7 @@ impl TypedSession<Unverified> {
     pub const fn new(_name: &str) -> Self {
         Self {
             name: String::new(), // Can't use name.to_string() in const
-            contract: None,
+            contract: CapabilityContract::pure(), // Nothing beyond pure computation until verified
             audit_log: Vec::new(),
             _state: PhantomData,
         }
@@ -107,7 +107,7 @@ impl TypedSession<Unverified> {
         let name = name.into();
         let mut session = Self {
             name: name.clone(),
-            contract: None,
+            contract: CapabilityContract::pure(),
             audit_log: Vec::new(),
             _state: PhantomData,
         };
@@ -131,7 +131,7 @@ impl TypedSession<Unverified> {
 
         TypedSession {
             name: self.name,
-            contract: Some(contract),
+            contract,
             audit_log: self.audit_log,
             _state: PhantomData,
         }
@@ -153,7 +153,7 @@ impl<C> TypedSession<Verified<C>> {
         session.audit_log.push(AuditEntry {
             timestamp: current_timestamp(),
             event: AuditEvent::OperationExecuted {
-                capability: format!("{:?}", self.contract.as_ref().unwrap().capability_class),
+                capability: format!("{:?}", self.contract.capability_class),
             },
         });
 
@@ -171,7 +171,7 @@ impl<C> TypedSession<Verified<C>> {
         let reason = reason.into();
 
         // Check if escalation is allowed
-        let old_contract = self.contract.as_ref().unwrap();
+        let old_contract = &self.contract;
 
         if !is_escalation_allowed(old_contract, &new_contract, &reason) {
             self.audit_log.push(AuditEntry {
@@ -184,8 +184,8 @@ impl<C> TypedSession<Verified<C>> {
             });
 
             return Err(EscalationError::PolicyViolation {
-                from: old_contract.capability_class.clone(),
-                to: new_contract.capability_class.clone(),
+                from: old_contract.capability_class,
+                to: new_contract.capability_class,
                 reason,
             });
         }
@@ -201,7 +201,7 @@ impl<C> TypedSession<Verified<C>> {
 
         Ok(TypedSession {
             name: self.name,
-            contract: Some(new_contract),
+            contract: new_contract,
             audit_log: self.audit_log,
             _state: PhantomData,
         })
@@ -209,7 +209,7 @@ impl<C> TypedSession<Verified<C>> {
 
     /// Get current capability contract
     pub fn capability(&self) -> &CapabilityContract {
-        self.contract.as_ref().unwrap()
+        &self.contract
     }
 
     /// Get audit log
@@ -247,12 +247,12 @@ impl<C1, C2> TypedSession<Escalated<C1, C2>> {
         reason: impl Into<String>,
     ) -> Result<TypedSession<Escalated<C2, C3>>, EscalationError> {
         let reason = reason.into();
-        let old_contract = self.contract.as_ref().unwrap();
+        let old_contract = &self.contract;
 
         if !is_escalation_allowed(old_contract, &new_contract, &reason) {
             return Err(EscalationError::PolicyViolation {
-                from: old_contract.capability_class.clone(),
-                to: new_contract.capability_class.clone(),
+                from: old_contract.capability_class,
+                to: new_contract.capability_class,
                 reason,
             });
         }
@@ -268,7 +268,7 @@ impl<C1, C2> TypedSession<Escalated<C1, C2>> {
 
         Ok(TypedSession {
             name: self.name,
-            contract: Some(new_contract),
+            contract: new_contract,
             audit_log: self.audit_log,
             _state: PhantomData,
         })
@@ -276,7 +276,7 @@ impl<C1, C2> TypedSession<Escalated<C1, C2>> {
 
     /// Get current capability
     pub fn capability(&self) -> &CapabilityContract {
-        self.contract.as_ref().unwrap()
+        &self.contract
     }
 
     /// Get audit log
@@ -365,7 +365,7 @@ impl std::error::Error for EscalationError {}
 fn current_timestamp() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
+        .unwrap_or_default()
         .as_millis() as u64
 }
 
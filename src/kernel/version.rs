@@ -362,13 +362,11 @@ impl GrammarDelta {
                     name: (*name).clone(),
                     noun: to_noun.name.clone(),
                     argument_changes: Vec::new(),
-                    capability_changes: if verb.capability.is_some() {
-                        vec![CapabilityChange::Added {
-                            capability: verb.capability.clone().unwrap(),
-                        }]
-                    } else {
-                        Vec::new()
-                    },
+                    capability_changes: verb
+                        .capability
+                        .iter()
+                        .map(|capability| CapabilityChange::Added { capability: capability.clone() })
+                        .collect(),
                     help_changed: false,
                 });
             }
@@ -711,7 +709,6 @@ impl VersionNegotiator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::kernel::capability::{CapabilityClass, ResourceBand, SafetyProfile, StabilityProfile};
 
     #[test]
     fn test_change_type_breaking() {
@@ -743,6 +740,17 @@ mod tests {
                 requires: Vec::new(),
                 conflicts_with: Vec::new(),
                 index: None,
+                value_type: None,
+                value_hint: None,
+                aliases: Vec::new(),
+                long_help: None,
+                min_value: None,
+                max_value: None,
+                min_length: None,
+                max_length: None,
+                exclusive: false,
+                global: false,
+                hidden: false,
             },
             breaking: true,
         };
@@ -89,6 +89,12 @@ pub mod context;
 // Deprecation warnings
 pub mod deprecation;
 
+// Machine-readable result and error envelopes
+pub mod envelope;
+
+// MCP server exposing every #[verb] as a tool (stdio JSON-RPC)
+pub mod mcp;
+
 // Output formatting
 pub mod format;
 
@@ -118,12 +124,10 @@ pub mod io;
 // Advanced clap Integration
 pub mod clap_ext;
 
-// Plugin System (requires "full" feature)
-#[cfg(feature = "full")]
+// Plugin System (in-process plugins and git-style external subcommands)
 pub mod plugin;
 
-// Middleware System (requires "full" feature)
-#[cfg(feature = "full")]
+// Middleware System (run around every verb by the command registry)
 pub mod middleware;
 
 // Telemetry & Observability (requires "observability" feature)
@@ -187,11 +191,11 @@ pub mod wizard;
 // =============================================================================
 
 // Re-export CLI run function for convenience
-pub use cli::run;
+pub use cli::{run, run_interactive, run_with_exit_code};
 
 // Core framework types
 pub use builder::{build_cli, run_cli, run_cli_with_args, CliBuilder};
-pub use error::{NounVerbError, Result};
+pub use error::{ExitCodeClass, NounVerbError, Result};
 pub use noun::{CompoundNounCommand, NounCommand, NounContext};
 pub use registry::CommandRegistry;
 pub use router::CommandRouter;
@@ -214,7 +218,7 @@ pub use clap::{Arg, ArgAction, ArgMatches, Command};
 
 // Async support (requires "async" feature)
 #[cfg(feature = "async")]
-pub use async_verb::{create_runtime, run_async};
+pub use async_verb::{create_runtime, run_async, CancellationToken, RuntimeFlavor};
 
 // Shell completion (requires "completions" feature)
 #[cfg(feature = "completions")]
@@ -19,16 +19,105 @@ pub trait CommandHandler: Send + Sync {
 }
 
 /// Input to a command handler (validated by CLI layer)
+///
+/// Construct it with [`HandlerInput::new`] and the `with_*` builders; new
+/// fields may be added in minor releases.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct HandlerInput {
     /// Validated arguments as key-value pairs
     pub args: std::collections::HashMap<String, String>,
     /// Validated options as key-value pairs
     pub opts: std::collections::HashMap<String, String>,
+    /// Typed argument values (booleans, counts, numbers, arrays)
+    ///
+    /// Populated by the registry; prefer [`HandlerInput::get`] and
+    /// [`HandlerInput::deserialize`] over reading this map directly.
+    pub values: std::collections::HashMap<String, serde_json::Value>,
     /// Context information (noun, verb names, etc.)
     pub context: HandlerContext,
 }
 
+impl HandlerInput {
+    /// Create an input with no arguments for the given context
+    pub fn new(context: HandlerContext) -> Self {
+        Self {
+            args: std::collections::HashMap::new(),
+            opts: std::collections::HashMap::new(),
+            values: std::collections::HashMap::new(),
+            context,
+        }
+    }
+
+    /// Set the validated arguments
+    pub fn with_args(mut self, args: std::collections::HashMap<String, String>) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Set the validated options
+    pub fn with_opts(mut self, opts: std::collections::HashMap<String, String>) -> Self {
+        self.opts = opts;
+        self
+    }
+
+    /// Set the typed argument values
+    pub fn with_values(
+        mut self,
+        values: std::collections::HashMap<String, serde_json::Value>,
+    ) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Get an argument deserialized into `T`
+    ///
+    /// Looks in [`values`](Self::values) first and falls back to the string
+    /// `args` and `opts` maps. Returns `Ok(None)` when the argument is absent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an argument error if the value cannot be interpreted as `T`.
+    pub fn get<T: serde::de::DeserializeOwned>(&self, name: &str) -> Result<Option<T>> {
+        let Some(value) = self.lookup(name) else {
+            return Ok(None);
+        };
+        super::values::from_arg_value(value).map(Some).map_err(|e| {
+            crate::error::NounVerbError::argument_error(format!(
+                "Invalid value for argument '{}': {}",
+                name, e
+            ))
+        })
+    }
+
+    /// Deserialize all arguments into `T`, typically a `#[derive(Deserialize)]` struct
+    ///
+    /// # Errors
+    ///
+    /// Returns an argument error if the arguments do not match `T`.
+    pub fn deserialize<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        let mut bag = serde_json::Map::new();
+        for (name, value) in self.opts.iter().chain(self.args.iter()) {
+            bag.insert(name.clone(), serde_json::Value::String(value.clone()));
+        }
+        for (name, value) in &self.values {
+            bag.insert(name.clone(), value.clone());
+        }
+        super::values::from_arg_value(serde_json::Value::Object(bag)).map_err(|e| {
+            crate::error::NounVerbError::argument_error(format!("Invalid arguments: {}", e))
+        })
+    }
+
+    fn lookup(&self, name: &str) -> Option<serde_json::Value> {
+        self.values.get(name).cloned().or_else(|| {
+            self.args
+                .get(name)
+                .or_else(|| self.opts.get(name))
+                .map(|value| serde_json::Value::String(value.clone()))
+        })
+    }
+}
+
 /// Output from a command handler
 ///
 /// In v3, output is automatically serialized to JSON for agent/MCP consumption.
@@ -61,6 +150,18 @@ impl HandlerOutput {
         self
     }
 
+    /// Render output data using the given output format
+    ///
+    /// Used by the registry runner to honor the global `--format` option.
+    pub fn render(&self, format: crate::format::OutputFormat) -> Result<String> {
+        format.format(&self.data).map_err(|e| {
+            crate::error::NounVerbError::execution_error(format!(
+                "Failed to render output as {}: {}",
+                format, e
+            ))
+        })
+    }
+
     /// Serialize output to JSON string
     pub fn to_json(&self) -> Result<String> {
         serde_json::to_string(&self.data).map_err(|e| {
@@ -73,7 +174,11 @@ impl HandlerOutput {
 }
 
 /// Context information for handler execution
+///
+/// Construct it with [`HandlerContext::new`] and the `with_*` builders; new
+/// fields may be added in minor releases.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct HandlerContext {
     /// Noun name (if applicable)
     pub noun: Option<String>,
@@ -81,12 +186,21 @@ pub struct HandlerContext {
     pub verb: String,
     /// Additional context data
     pub data: std::collections::HashMap<String, String>,
+    /// Agent, tenant, QoS and correlation ID of the invocation
+    #[cfg(feature = "autonomic")]
+    pub invocation: crate::autonomic::InvocationContext,
 }
 
 impl HandlerContext {
     /// Create a new handler context
     pub fn new(verb: impl Into<String>) -> Self {
-        Self { noun: None, verb: verb.into(), data: std::collections::HashMap::new() }
+        Self {
+            noun: None,
+            verb: verb.into(),
+            data: std::collections::HashMap::new(),
+            #[cfg(feature = "autonomic")]
+            invocation: crate::autonomic::InvocationContext::anonymous(),
+        }
     }
 
     /// Set the noun name
@@ -100,4 +214,11 @@ impl HandlerContext {
         self.data.insert(key.into(), value.into());
         self
     }
+
+    /// Set the invocation context
+    #[cfg(feature = "autonomic")]
+    pub fn with_invocation(mut self, invocation: crate::autonomic::InvocationContext) -> Self {
+        self.invocation = invocation;
+        self
+    }
 }
@@ -11,6 +11,7 @@
 
 pub mod core;
 pub mod handler;
+pub mod values;
 
 pub use core::CoreFunction;
 pub use handler::{CommandHandler, HandlerContext, HandlerInput, HandlerOutput};
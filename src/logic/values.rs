@@ -0,0 +1,249 @@
+//! Typed argument values for handlers
+//!
+//! The registry collects each parsed argument into a `serde_json::Value`:
+//! flags become booleans, counts become numbers, multi-valued arguments become
+//! arrays and absent optional arguments are simply missing. Handlers read them
+//! back with [`HandlerInput::get`](super::HandlerInput::get) or deserialize
+//! the whole bag with [`HandlerInput::deserialize`](super::HandlerInput::deserialize).
+//!
+//! Command-line values are strings at heart, so deserialization is lenient:
+//! a string such as `"8080"` deserializes into a `u16`, `"true"` into a `bool`,
+//! and a comma-separated string into a `Vec<T>`.
+
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, Deserializer, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use serde_json::Value;
+
+/// Deserialize an argument value into `T`, coercing strings where needed
+///
+/// # Errors
+///
+/// Returns an error if the value cannot be interpreted as `T`.
+pub fn from_arg_value<T: DeserializeOwned>(value: Value) -> Result<T, serde_json::Error> {
+    T::deserialize(ArgValueDeserializer(value))
+}
+
+/// Lenient deserializer over a `serde_json::Value` produced from CLI arguments
+struct ArgValueDeserializer(Value);
+
+impl ArgValueDeserializer {
+    fn visit_number<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, serde_json::Error> {
+        match self.0 {
+            Value::String(s) => {
+                let trimmed = s.trim();
+                if let Ok(n) = trimmed.parse::<u64>() {
+                    visitor.visit_u64(n)
+                } else if let Ok(n) = trimmed.parse::<i64>() {
+                    visitor.visit_i64(n)
+                } else if let Ok(n) = trimmed.parse::<f64>() {
+                    visitor.visit_f64(n)
+                } else {
+                    Err(de::Error::invalid_value(de::Unexpected::Str(&s), &visitor))
+                }
+            }
+            other => ArgValueDeserializer(other).deserialize_any_value(visitor),
+        }
+    }
+
+    fn deserialize_any_value<'de, V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, serde_json::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(b),
+            Value::Number(n) => {
+                if let Some(u) = n.as_u64() {
+                    visitor.visit_u64(u)
+                } else if let Some(i) = n.as_i64() {
+                    visitor.visit_i64(i)
+                } else {
+                    visitor.visit_f64(n.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            Value::String(s) => visitor.visit_string(s),
+            Value::Array(items) => {
+                let mut seq = SeqDeserializer::<_, serde_json::Error>::new(
+                    items.into_iter().map(ArgValueDeserializer),
+                );
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Object(map) => {
+                let mut map = MapDeserializer::<_, serde_json::Error>::new(
+                    map.into_iter().map(|(k, v)| (k, ArgValueDeserializer(v))),
+                );
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, serde_json::Error> for ArgValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_number {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.visit_number(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for ArgValueDeserializer {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_any_value(visitor)
+    }
+
+    deserialize_number! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(s) => match s.trim().parse::<bool>() {
+                Ok(b) => visitor.visit_bool(b),
+                Err(_) => Err(de::Error::invalid_value(de::Unexpected::Str(&s), &visitor)),
+            },
+            other => ArgValueDeserializer(other).deserialize_any_value(visitor),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Bool(b) => visitor.visit_string(b.to_string()),
+            Value::Number(n) => visitor.visit_string(n.to_string()),
+            other => ArgValueDeserializer(other).deserialize_any_value(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            other => visitor.visit_some(ArgValueDeserializer(other)),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_any_value(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            // Multi-valued arguments flattened into the legacy `args` map are comma-joined
+            Value::String(s) => {
+                let items: Vec<Value> = s
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| Value::String(item.to_string()))
+                    .collect();
+                ArgValueDeserializer(Value::Array(items)).deserialize_any_value(visitor)
+            }
+            Value::Array(items) => {
+                ArgValueDeserializer(Value::Array(items)).deserialize_any_value(visitor)
+            }
+            Value::Null => {
+                ArgValueDeserializer(Value::Array(Vec::new())).deserialize_any_value(visitor)
+            }
+            scalar => {
+                ArgValueDeserializer(Value::Array(vec![scalar])).deserialize_any_value(visitor)
+            }
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(s) => visitor.visit_enum(s.into_deserializer()),
+            other => other.deserialize_enum(name, variants, visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        char bytes byte_buf unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Args {
+        name: String,
+        port: u16,
+        verbose: bool,
+        tags: Vec<String>,
+        level: Option<u8>,
+    }
+
+    #[test]
+    fn test_coerces_strings_into_scalars() {
+        assert_eq!(from_arg_value::<u16>(json!("8080")).ok(), Some(8080));
+        assert_eq!(from_arg_value::<i32>(json!("-3")).ok(), Some(-3));
+        assert_eq!(from_arg_value::<bool>(json!("true")).ok(), Some(true));
+        assert_eq!(from_arg_value::<String>(json!(42)).ok(), Some("42".to_string()));
+        assert!(from_arg_value::<u8>(json!("300")).is_err());
+    }
+
+    #[test]
+    fn test_splits_comma_joined_sequences() {
+        let values: Option<Vec<u32>> = from_arg_value(json!("1, 2,3")).ok();
+        assert_eq!(values, Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_deserializes_struct_with_mixed_values() {
+        let args: Option<Args> = from_arg_value(json!({
+            "name": "007",
+            "port": "8080",
+            "verbose": true,
+            "tags": ["a", "b"],
+        }))
+        .ok();
+        assert_eq!(
+            args,
+            Some(Args {
+                name: "007".to_string(),
+                port: 8080,
+                verbose: true,
+                tags: vec!["a".to_string(), "b".to_string()],
+                level: None,
+            })
+        );
+    }
+}
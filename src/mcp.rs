@@ -0,0 +1,264 @@
+//! Model Context Protocol server exposing every `#[verb]` as a tool
+//!
+//! [`McpServer`] speaks newline-delimited JSON-RPC 2.0 over stdio. `tools/list`
+//! returns one tool per registered noun/verb (see
+//! [`CommandRegistry::mcp_tools`](crate::cli::registry::CommandRegistry::mcp_tools))
+//! whose `inputSchema` is generated from the verb's [`ArgMetadata`], and
+//! `tools/call` dispatches to the verb handler through the registry's
+//! middleware pipeline.
+//!
+//! ```rust,no_run
+//! use clap_noun_verb::cli::registry::CommandRegistry;
+//! use clap_noun_verb::mcp::McpServer;
+//!
+//! let registry = CommandRegistry::get();
+//! let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+//! McpServer::new(&registry, "myapp").serve_stdio()?;
+//! # Ok::<(), clap_noun_verb::NounVerbError>(())
+//! ```
+//!
+//! With [`CommandRegistry::set_serve_mcp`](crate::cli::registry::CommandRegistry::set_serve_mcp)
+//! the same server runs as `myapp serve mcp`.
+
+use crate::cli::registry::{ArgMetadata, CommandRegistry};
+use crate::error::{NounVerbError, Result};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+/// MCP protocol revision reported by `initialize`
+pub const MCP_PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// JSON-RPC error code for unparseable requests
+const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code for unknown methods
+const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for malformed parameters
+const INVALID_PARAMS: i64 = -32602;
+
+/// A verb described as an MCP tool
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct McpTool {
+    /// Tool name (`noun_verb`, nested noun segments joined with `_`)
+    pub name: String,
+    /// Verb description
+    pub description: String,
+    /// JSON Schema of the tool arguments
+    #[serde(rename = "inputSchema")]
+    pub input_schema: Value,
+}
+
+/// Tool name for a verb, `None` noun meaning a root-level verb
+pub fn tool_name(noun_path: Option<&str>, verb: &str) -> String {
+    match noun_path {
+        Some(noun) => format!("{}_{}", noun.replace('.', "_"), verb),
+        None => verb.to_string(),
+    }
+}
+
+/// JSON Schema for a verb's arguments
+///
+/// Types come from the Rust value type (`bool`, integers, floats, `Vec<T>`),
+/// flags become booleans and counted flags integers. Required arguments
+/// without a default are listed in `required`; defaults, `min`/`max` bounds,
+/// length limits and `possible_values` (keyed by argument name) are carried over.
+pub fn input_schema(args: &[ArgMetadata], possible_values: &HashMap<String, Vec<String>>) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for arg in args {
+        let mut property = Map::new();
+        let counted = matches!(arg.action, Some(clap::ArgAction::Count));
+        let value_type = arg.value_type.as_deref().unwrap_or("String");
+        let (is_array, item_type) = match value_type.strip_prefix("Vec<") {
+            Some(inner) => (true, inner.strip_suffix('>').unwrap_or(inner)),
+            None => (arg.multiple, value_type),
+        };
+        let item_kind = if counted {
+            "integer"
+        } else if arg.is_flag {
+            "boolean"
+        } else {
+            json_type(item_type)
+        };
+
+        let mut item = Map::new();
+        item.insert("type".into(), item_kind.into());
+        if let Some(values) = possible_values.get(&arg.name).filter(|v| !v.is_empty()) {
+            item.insert("enum".into(), values.clone().into());
+        }
+        if let Some(min) = arg.min_value.as_deref().and_then(|v| number(v, item_kind)) {
+            item.insert("minimum".into(), min);
+        } else if item_kind == "integer" && (counted || item_type.starts_with('u')) {
+            item.insert("minimum".into(), 0.into());
+        }
+        if let Some(max) = arg.max_value.as_deref().and_then(|v| number(v, item_kind)) {
+            item.insert("maximum".into(), max);
+        }
+        if let Some(min) = arg.min_length {
+            item.insert("minLength".into(), min.into());
+        }
+        if let Some(max) = arg.max_length {
+            item.insert("maxLength".into(), max.into());
+        }
+
+        if is_array && !arg.is_flag && !counted {
+            property.insert("type".into(), "array".into());
+            property.insert("items".into(), Value::Object(item));
+        } else {
+            property.extend(item);
+        }
+        if let Some(help) = &arg.help {
+            property.insert("description".into(), help.clone().into());
+        }
+        if let Some(default) = &arg.default_value {
+            let value = typed_value(default, item_kind);
+            let value = if is_array && !counted { Value::Array(vec![value]) } else { value };
+            property.insert("default".into(), value);
+        }
+
+        if arg.required && arg.default_value.is_none() && !arg.is_flag && !counted {
+            required.push(Value::from(arg.name.clone()));
+        }
+        properties.insert(arg.name.clone(), Value::Object(property));
+    }
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// JSON Schema type of a Rust value type
+fn json_type(value_type: &str) -> &'static str {
+    match value_type {
+        "bool" => "boolean",
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" => "integer",
+        "f32" | "f64" => "number",
+        _ => "string",
+    }
+}
+
+/// Parse a bound as a JSON number of the given schema type
+fn number(value: &str, kind: &str) -> Option<Value> {
+    match kind {
+        "integer" => value.parse::<i64>().ok().map(Value::from),
+        "number" => value.parse::<f64>().ok().map(Value::from),
+        _ => None,
+    }
+}
+
+/// Convert a default value string to the schema type, keeping strings as-is
+fn typed_value(value: &str, kind: &str) -> Value {
+    match kind {
+        "boolean" => value.parse::<bool>().map(Value::from).unwrap_or_else(|_| value.into()),
+        "integer" | "number" => number(value, kind).unwrap_or_else(|| value.into()),
+        _ => value.into(),
+    }
+}
+
+/// Stdio JSON-RPC server exposing a registry's verbs as MCP tools
+pub struct McpServer<'a> {
+    registry: &'a CommandRegistry,
+    name: String,
+    version: String,
+}
+
+impl<'a> McpServer<'a> {
+    /// Create a server for `registry`, reporting `name` as the server name
+    pub fn new(registry: &'a CommandRegistry, name: impl Into<String>) -> Self {
+        Self { registry, name: name.into(), version: env!("CARGO_PKG_VERSION").to_string() }
+    }
+
+    /// Override the version reported by `initialize`
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Serve requests from stdin, writing responses to stdout, until EOF
+    pub fn serve_stdio(&self) -> Result<()> {
+        let stdin = std::io::stdin();
+        let stdout = std::io::stdout();
+        self.serve(stdin.lock(), stdout.lock())
+    }
+
+    /// Serve newline-delimited JSON-RPC requests from `reader` until EOF
+    pub fn serve<R: BufRead, W: Write>(&self, reader: R, mut writer: W) -> Result<()> {
+        for line in reader.lines() {
+            let line = line.map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(request) => self.handle(&request),
+                Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+            };
+            if let Some(response) = response {
+                writeln!(writer, "{}", response)
+                    .and_then(|()| writer.flush())
+                    .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle one JSON-RPC message, returning `None` for notifications
+    pub fn handle(&self, request: &Value) -> Option<Value> {
+        let id = request.get("id").cloned();
+        let method = request["method"].as_str().unwrap_or_default();
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": MCP_PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": self.name, "version": self.version },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": self.registry.mcp_tools() })),
+            "tools/call" => self.call_tool(&request["params"]),
+            _ => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+        };
+
+        // Notifications carry no id and get no response
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    /// Run a tool; handler failures are reported in the result with `isError`
+    fn call_tool(&self, params: &Value) -> std::result::Result<Value, (i64, String)> {
+        let name = params["name"]
+            .as_str()
+            .ok_or_else(|| (INVALID_PARAMS, "Missing tool name".to_string()))?;
+        let arguments = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+
+        Ok(match self.registry.call_tool(name, &arguments) {
+            Ok(output) => {
+                let mut content = vec![json!({ "type": "text", "text": output.data.to_string() })];
+                if let Some(message) = output.message {
+                    content.push(json!({ "type": "text", "text": message }));
+                }
+                let mut result = json!({ "content": content, "isError": false });
+                if output.data.is_object() {
+                    result["structuredContent"] = output.data;
+                }
+                result
+            }
+            Err(e) => json!({
+                "content": [{ "type": "text", "text": e.to_string() }],
+                "isError": true,
+            }),
+        })
+    }
+}
+
+/// JSON-RPC error response
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
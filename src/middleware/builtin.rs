@@ -7,6 +7,8 @@ use std::sync::Arc;
 use std::time::SystemTime;
 
 /// Logging middleware for structured command logging.
+///
+/// Logs through `tracing` with the `observability` feature, otherwise to stderr.
 #[derive(Debug)]
 pub struct LoggingMiddleware {
     /// Enable verbose logging
@@ -44,8 +46,7 @@ impl Middleware for LoggingMiddleware {
             format!("Executing command: {}", request.command())
         };
 
-        // Always use tracing (it's a required dependency)
-        tracing::info!("{}", log_msg);
+        log(&log_msg);
 
         Ok(true)
     }
@@ -54,13 +55,20 @@ impl Middleware for LoggingMiddleware {
         let status = if response.is_success() { "OK" } else { "FAILED" };
         let log_msg = format!("Command completed: {} - {}", status, response.message());
 
-        // Always use tracing (it's a required dependency)
-        tracing::info!("{}", log_msg);
+        log(&log_msg);
 
         Ok(())
     }
 }
 
+/// Emit a log line through `tracing` (`observability` feature) or to stderr.
+fn log(message: &str) {
+    #[cfg(feature = "observability")]
+    tracing::info!("{}", message);
+    #[cfg(not(feature = "observability"))]
+    eprintln!("{}", message);
+}
+
 /// Error recovery middleware with retry logic.
 #[derive(Debug, Clone)]
 pub struct ErrorRecoveryMiddleware {
@@ -104,7 +112,9 @@ impl Middleware for ErrorRecoveryMiddleware {
         // Determine if this error is retryable
         let is_retryable = matches!(
             error,
-            crate::NounVerbError::ExecutionError { .. } | crate::NounVerbError::Generic(_)
+            crate::NounVerbError::ExecutionError { .. }
+                | crate::NounVerbError::Timeout { .. }
+                | crate::NounVerbError::Generic(_)
         );
 
         if is_retryable {
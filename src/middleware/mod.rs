@@ -18,9 +18,23 @@
 //!     .add(LoggingMiddleware::new())
 //!     .add(ErrorRecoveryMiddleware::new());
 //! ```
+//!
+//! Middleware added to the command registry runs around every `#[verb]`:
+//!
+//! ```ignore
+//! use clap_noun_verb::cli::registry::CommandRegistry;
+//! use clap_noun_verb::middleware::LoggingMiddleware;
+//!
+//! CommandRegistry::get()
+//!     .lock()
+//!     .unwrap_or_else(|e| e.into_inner())
+//!     .add_middleware(Box::new(LoggingMiddleware::new()));
+//! clap_noun_verb::run()
+//! ```
 
 pub mod builtin;
 
+use crate::cli::registry::NOUN_PATH_SEPARATOR;
 use std::fmt;
 
 pub use builtin::{
@@ -37,12 +51,45 @@ pub struct MiddlewareRequest {
     args: Vec<String>,
     /// User/requester identification
     requester: Option<String>,
+    /// Noun path of the invoked verb (dotted for nested nouns)
+    noun: Option<String>,
+    /// Invoked verb
+    verb: Option<String>,
 }
 
 impl MiddlewareRequest {
     /// Create a new middleware request.
     pub fn new(command: impl Into<String>) -> Self {
-        Self { command: command.into(), args: Vec::new(), requester: None }
+        Self { command: command.into(), args: Vec::new(), requester: None, noun: None, verb: None }
+    }
+
+    /// Create a request for a verb invocation.
+    ///
+    /// The command is the invocation path (e.g. `"vault key list"`) and the
+    /// arguments are the parsed values as `name=value` pairs, sorted by name.
+    /// With the `autonomic` feature, a non-anonymous invoking agent is the requester.
+    pub fn from_handler_input(input: &crate::logic::HandlerInput) -> Self {
+        let noun = input.context.noun.clone();
+        let verb = input.context.verb.clone();
+        let command = match &noun {
+            Some(noun) => format!("{} {}", noun.replace(NOUN_PATH_SEPARATOR, " "), verb),
+            None => verb.clone(),
+        };
+        let mut args: Vec<(&String, &String)> = input.args.iter().chain(&input.opts).collect();
+        args.sort_unstable();
+        #[cfg(feature = "autonomic")]
+        let requester = Some(&input.context.invocation.agent)
+            .filter(|agent| **agent != crate::autonomic::AgentIdentity::anonymous())
+            .map(|agent| agent.agent_id.clone());
+        #[cfg(not(feature = "autonomic"))]
+        let requester = None;
+        Self {
+            command,
+            args: args.into_iter().map(|(name, value)| format!("{}={}", name, value)).collect(),
+            requester,
+            noun,
+            verb: Some(verb),
+        }
     }
 
     /// Add an argument.
@@ -71,6 +118,16 @@ impl MiddlewareRequest {
     pub fn requester(&self) -> Option<&str> {
         self.requester.as_deref()
     }
+
+    /// Get the noun path, if this request is for a noun's verb.
+    pub fn noun(&self) -> Option<&str> {
+        self.noun.as_deref()
+    }
+
+    /// Get the verb, if this request is for a verb invocation.
+    pub fn verb(&self) -> Option<&str> {
+        self.verb.as_deref()
+    }
 }
 
 /// Middleware response context.
@@ -0,0 +1,382 @@
+//! Git-style external subcommands.
+//!
+//! Executables named `<app>-<noun>` or `<app>-<noun>-<verb>` found on `PATH`
+//! or in the plugin manifest directory are merged into the command tree by
+//! [`CommandRegistry::discover_external_plugins`](crate::cli::registry::CommandRegistry::discover_external_plugins).
+//!
+//! # Protocol
+//!
+//! - **argv**: everything after the verb (or after the noun for `<app>-<noun>`
+//!   plugins, so the verb is the first argument) is forwarded unchanged.
+//! - **env**: the parent environment plus `CNV_PLUGIN_APP`, `CNV_PLUGIN_NOUN`,
+//!   `CNV_PLUGIN_VERB` and `CNV_FORMAT`.
+//! - **stdin**: a single JSON [`ExternalContext`] document.
+//! - **stdout**: a JSON value used as the verb's output. An object of the form
+//!   `{"data": ..., "message": "..."}` maps onto [`HandlerOutput`] field by field.
+//! - **stderr** is passed through; a non-zero exit status is a plugin error.
+
+use super::PluginLoader;
+use crate::logic::HandlerOutput;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Version of the stdin/stdout protocol spoken with external plugins
+pub const EXTERNAL_PROTOCOL_VERSION: u32 = 1;
+
+/// An external executable that provides a noun or a single verb
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalCommand {
+    /// Noun the executable provides or extends
+    noun: String,
+    /// Verb the executable implements; `None` when it handles the whole noun
+    verb: Option<String>,
+    /// Path to the executable
+    program: PathBuf,
+    /// One-line description shown in help
+    about: String,
+}
+
+impl ExternalCommand {
+    /// Create an external command for `noun` (and optionally one `verb`)
+    pub fn new(noun: impl Into<String>, verb: Option<String>, program: impl Into<PathBuf>) -> Self {
+        Self { noun: noun.into(), verb, program: program.into(), about: String::new() }
+    }
+
+    /// Parse a plugin executable name such as `app-noun` or `app-noun-verb`
+    ///
+    /// The first segment after the `<app>-` prefix is the noun and the rest,
+    /// which may itself contain hyphens, is the verb. A trailing `.exe` is ignored.
+    pub fn from_file_name(
+        app_name: &str,
+        file_name: &str,
+        program: impl Into<PathBuf>,
+    ) -> Option<Self> {
+        let stem = file_name.strip_suffix(".exe").unwrap_or(file_name);
+        let rest = stem.strip_prefix(app_name)?.strip_prefix('-')?;
+        let (noun, verb) = match rest.split_once('-') {
+            Some((noun, verb)) => (noun, Some(verb)),
+            None => (rest, None),
+        };
+        if noun.is_empty() || verb.is_some_and(str::is_empty) {
+            return None;
+        }
+        Some(Self::new(noun, verb.map(str::to_string), program))
+    }
+
+    /// Set the description shown in help
+    pub fn with_about(mut self, about: impl Into<String>) -> Self {
+        self.about = about.into();
+        self
+    }
+
+    /// Noun the executable provides or extends
+    pub fn noun(&self) -> &str {
+        &self.noun
+    }
+
+    /// Verb the executable implements, or `None` for a whole-noun plugin
+    pub fn verb(&self) -> Option<&str> {
+        self.verb.as_deref()
+    }
+
+    /// Path to the executable
+    pub fn program(&self) -> &Path {
+        &self.program
+    }
+
+    /// Description shown in help
+    pub fn about(&self) -> &str {
+        &self.about
+    }
+
+    /// Run the executable and parse its JSON stdout
+    ///
+    /// `verb` is the verb actually invoked; for whole-noun plugins it is also
+    /// passed as the first argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns a plugin error if the executable cannot be started, exits
+    /// unsuccessfully or prints something other than JSON.
+    pub fn invoke(
+        &self,
+        app_name: &str,
+        verb: &str,
+        args: &[String],
+        format: crate::format::OutputFormat,
+    ) -> crate::Result<HandlerOutput> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let mut argv: Vec<String> = Vec::with_capacity(args.len() + 1);
+        if self.verb.is_none() {
+            argv.push(verb.to_string());
+        }
+        argv.extend(args.iter().cloned());
+
+        let context = ExternalContext {
+            protocol: EXTERNAL_PROTOCOL_VERSION,
+            app: app_name,
+            noun: &self.noun,
+            verb,
+            args: &argv,
+            format: format.to_string(),
+        };
+        let context = serde_json::to_vec(&context).map_err(|e| {
+            crate::NounVerbError::PluginError(format!("Failed to encode plugin context: {}", e))
+        })?;
+
+        let mut child = Command::new(&self.program)
+            .args(&argv)
+            .env("CNV_PLUGIN_APP", app_name)
+            .env("CNV_PLUGIN_NOUN", &self.noun)
+            .env("CNV_PLUGIN_VERB", verb)
+            .env(crate::cli::registry::FORMAT_ENV_VAR, format.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| {
+                crate::NounVerbError::PluginError(format!(
+                    "Failed to start plugin '{}': {}",
+                    self.program.display(),
+                    e
+                ))
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // Plugins that ignore their context may exit before reading it
+            match stdin.write_all(&context) {
+                Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                    return Err(crate::NounVerbError::PluginError(format!(
+                        "Failed to send context to plugin '{}': {}",
+                        self.program.display(),
+                        e
+                    )));
+                }
+                _ => {}
+            }
+        }
+
+        let output = child.wait_with_output().map_err(|e| {
+            crate::NounVerbError::PluginError(format!(
+                "Failed to wait for plugin '{}': {}",
+                self.program.display(),
+                e
+            ))
+        })?;
+        if !output.status.success() {
+            return Err(crate::NounVerbError::PluginError(format!(
+                "Plugin '{}' failed with {}",
+                self.program.display(),
+                output.status
+            )));
+        }
+
+        parse_output(&output.stdout).map_err(|e| {
+            crate::NounVerbError::PluginError(format!(
+                "Plugin '{}' did not print JSON: {}",
+                self.program.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// JSON document written to an external plugin's stdin
+#[derive(Debug, Serialize)]
+pub struct ExternalContext<'a> {
+    /// Protocol version ([`EXTERNAL_PROTOCOL_VERSION`])
+    pub protocol: u32,
+    /// Name of the host application
+    pub app: &'a str,
+    /// Invoked noun
+    pub noun: &'a str,
+    /// Invoked verb
+    pub verb: &'a str,
+    /// Arguments passed on the command line
+    pub args: &'a [String],
+    /// Output format selected for the invocation
+    pub format: String,
+}
+
+/// Interpret a plugin's stdout as handler output
+fn parse_output(stdout: &[u8]) -> serde_json::Result<HandlerOutput> {
+    if stdout.iter().all(u8::is_ascii_whitespace) {
+        return Ok(HandlerOutput { data: serde_json::Value::Null, message: None });
+    }
+
+    let value: serde_json::Value = serde_json::from_slice(stdout)?;
+    if let serde_json::Value::Object(fields) = &value {
+        let is_envelope = fields.contains_key("data")
+            && fields.keys().all(|key| key == "data" || key == "message")
+            && fields.get("message").map_or(true, |m| m.is_string() || m.is_null());
+        if is_envelope {
+            let message = fields.get("message").and_then(|m| m.as_str()).map(str::to_string);
+            let data = fields.get("data").cloned().unwrap_or_default();
+            return Ok(HandlerOutput { data, message });
+        }
+    }
+    Ok(HandlerOutput { data: value, message: None })
+}
+
+/// Discovers external plugin executables for one application
+#[derive(Debug, Clone)]
+pub struct ExternalPluginDiscovery {
+    /// Executable name prefix (without the trailing `-`)
+    app_name: String,
+    /// Directories searched in order; earlier entries win
+    search_dirs: Vec<PathBuf>,
+    /// Directory holding plugin manifests and executables
+    manifest_dir: Option<PathBuf>,
+}
+
+impl ExternalPluginDiscovery {
+    /// Discover `<app_name>-*` executables on `PATH`
+    pub fn new(app_name: impl Into<String>) -> Self {
+        let search_dirs = std::env::var_os("PATH")
+            .map(|path| std::env::split_paths(&path).collect())
+            .unwrap_or_default();
+        Self { app_name: app_name.into(), search_dirs, manifest_dir: None }
+    }
+
+    /// Discover only in the given directories, ignoring `PATH`
+    pub fn with_search_dirs<I, P>(mut self, dirs: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.search_dirs = dirs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Also load plugins from a manifest directory
+    ///
+    /// Manifests read by [`PluginLoader`] take precedence over `PATH`; their
+    /// `entry_point` is resolved against the directory and their description
+    /// becomes the help text. Executables placed directly in the directory are
+    /// discovered as well.
+    pub fn with_manifest_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.manifest_dir = Some(dir.into());
+        self
+    }
+
+    /// Application name used as the executable prefix
+    pub fn app_name(&self) -> &str {
+        &self.app_name
+    }
+
+    /// Find every plugin executable
+    ///
+    /// Each noun/verb pair is reported once, from the first place it was found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest directory cannot be scanned.
+    pub fn discover(&self) -> crate::Result<Vec<ExternalCommand>> {
+        let mut found = Vec::new();
+
+        if let Some(dir) = &self.manifest_dir {
+            let mut loader = PluginLoader::new(dir);
+            loader.discover()?;
+            for manifest in loader.manifests() {
+                let program = dir.join(manifest.entry_point());
+                let command = program
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| {
+                        ExternalCommand::from_file_name(&self.app_name, name, &program)
+                    })
+                    .or_else(|| {
+                        ExternalCommand::from_file_name(&self.app_name, manifest.name(), &program)
+                    });
+                if let Some(command) = command {
+                    found.push(command.with_about(manifest.description()));
+                }
+            }
+            found.extend(self.scan_dir(dir));
+        }
+
+        for dir in &self.search_dirs {
+            found.extend(self.scan_dir(dir));
+        }
+
+        let mut seen = HashSet::new();
+        found.retain(|command| seen.insert((command.noun.clone(), command.verb.clone())));
+        Ok(found)
+    }
+
+    /// Executables in `dir` named after the application, sorted by name
+    fn scan_dir(&self, dir: &Path) -> Vec<ExternalCommand> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut commands: Vec<ExternalCommand> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| is_executable(path))
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?.to_string();
+                ExternalCommand::from_file_name(&self.app_name, &name, path)
+            })
+            .collect();
+        commands.sort_by(|a, b| a.program.cmp(&b.program));
+        commands
+    }
+}
+
+/// Whether `path` is a file the current user may execute
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+/// Whether `path` is a file the current user may execute
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_file_name_noun_plugin() {
+        let command = ExternalCommand::from_file_name("myapp", "myapp-deploy", "/bin/myapp-deploy");
+        assert_eq!(command.as_ref().map(ExternalCommand::noun), Some("deploy"));
+        assert_eq!(command.and_then(|c| c.verb), None);
+    }
+
+    #[test]
+    fn test_from_file_name_verb_plugin() {
+        let command = ExternalCommand::from_file_name("myapp", "myapp-db-dump-all.exe", "x");
+        assert_eq!(command.as_ref().map(ExternalCommand::noun), Some("db"));
+        assert_eq!(command.as_ref().and_then(ExternalCommand::verb), Some("dump-all"));
+    }
+
+    #[test]
+    fn test_from_file_name_rejects_other_apps() {
+        assert!(ExternalCommand::from_file_name("myapp", "myapplication-x", "x").is_none());
+        assert!(ExternalCommand::from_file_name("myapp", "myapp-", "x").is_none());
+        assert!(ExternalCommand::from_file_name("myapp", "myapp-db-", "x").is_none());
+    }
+
+    #[test]
+    fn test_parse_output_envelope_and_raw_value() -> serde_json::Result<()> {
+        let output = parse_output(br#"{"data": [1, 2], "message": "done"}"#)?;
+        assert_eq!(output.data, serde_json::json!([1, 2]));
+        assert_eq!(output.message.as_deref(), Some("done"));
+
+        let output = parse_output(br#"{"data": 1, "extra": true}"#)?;
+        assert_eq!(output.data, serde_json::json!({"data": 1, "extra": true}));
+
+        let output = parse_output(b"\n")?;
+        assert!(output.data.is_null());
+        Ok(())
+    }
+}
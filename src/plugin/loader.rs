@@ -138,6 +138,7 @@ impl PluginLoader {
         let mut name = String::new();
         let mut version = String::new();
         let mut entry_point = String::new();
+        let mut description = String::new();
 
         for line in content.lines() {
             if let Some(value) = line.strip_prefix("name = \"") {
@@ -146,6 +147,8 @@ impl PluginLoader {
                 version = value.trim_end_matches('"').to_string();
             } else if let Some(value) = line.strip_prefix("entry_point = \"") {
                 entry_point = value.trim_end_matches('"').to_string();
+            } else if let Some(value) = line.strip_prefix("description = \"") {
+                description = value.trim_end_matches('"').to_string();
             }
         }
 
@@ -153,7 +156,8 @@ impl PluginLoader {
             return Err(crate::NounVerbError::PluginError("Invalid manifest format".to_string()));
         }
 
-        let manifest = PluginManifest::new(&name, &version, &entry_point);
+        let manifest =
+            PluginManifest::new(&name, &version, &entry_point).with_description(description);
         self.manifests.push(manifest);
 
         Ok(name)
@@ -8,6 +8,7 @@
 //! - **Plugin trait** - Base interface for all plugins
 //! - **PluginRegistry** - Central registry for plugin discovery and management
 //! - **PluginLoader** - Dynamic loading from manifests
+//! - **External commands** - Git-style `<app>-<noun>[-<verb>]` executables
 //! - **Built-in plugins** - Help, history, and alias plugins
 //!
 //! # Example
@@ -31,12 +32,14 @@
 //! ```
 
 pub mod builtin;
+pub mod external;
 pub mod loader;
 pub mod registry;
 
 use std::fmt;
 
 pub use builtin::{AliasPlugin, HelpPlugin, HistoryPlugin};
+pub use external::{ExternalCommand, ExternalPluginDiscovery};
 pub use loader::PluginLoader;
 pub use registry::PluginRegistry;
 
@@ -141,6 +141,13 @@ impl CommandRegistry {
         self.nouns.remove(name)
     }
 
+    /// Take the registered nouns, sorted by name
+    pub fn into_nouns(self) -> Vec<Box<dyn NounCommand>> {
+        let mut nouns: Vec<_> = self.nouns.into_iter().collect();
+        nouns.sort_by(|a, b| a.0.cmp(&b.0));
+        nouns.into_iter().map(|(_, noun)| noun).collect()
+    }
+
     /// Clear all registered commands
     pub fn clear(&mut self) {
         self.nouns.clear();
@@ -112,10 +112,6 @@ pub fn default_validate(
         }
     }
 
-    Ok(HandlerInput {
-        args,
-        opts,
-        context,
-    })
+    Ok(HandlerInput::new(context).with_args(args).with_opts(opts))
 }
 
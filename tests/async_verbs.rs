@@ -0,0 +1,91 @@
+#![cfg(feature = "async")]
+
+//! Tests for `async fn` verbs in the auto-discovery registry
+//!
+//! Async verbs must register like sync ones, receive an injected
+//! `CancellationToken`, honor `timeout = "..."` and run on the requested runtime.
+
+use clap_noun_verb::async_verb::CancellationToken;
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::Result;
+use clap_noun_verb::logic::{HandlerContext, HandlerInput};
+use clap_noun_verb_macros::verb;
+use std::time::Duration;
+
+/// Fetch a resource
+///
+/// # Arguments
+/// * `name` - Resource name
+#[verb("fetch", "remote")]
+async fn fetch_remote(name: String, cancel: CancellationToken) -> Result<String> {
+    tokio::task::yield_now().await;
+    Ok(format!("{}:{}", name, cancel.is_cancelled()))
+}
+
+/// Wait longer than allowed
+#[verb("hang", "remote", timeout = "50ms")]
+async fn hang_remote() -> Result<String> {
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    Ok("finished".to_string())
+}
+
+/// Report the runtime flavor
+#[verb("flavor", "remote", runtime = "multi_thread")]
+async fn runtime_flavor() -> Result<String> {
+    Ok(format!("{:?}", tokio::runtime::Handle::current().runtime_flavor()))
+}
+
+fn execute(verb: &str, args: &[(&str, &str)]) -> Result<serde_json::Value> {
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    let input = HandlerInput::new(HandlerContext::new(verb).with_noun("remote"))
+        .with_args(args.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect());
+    registry.execute_verb("remote", verb, input).map(|output| output.data)
+}
+
+#[test]
+fn test_async_verb_runs_with_injected_token() -> Result<()> {
+    // Arrange + Act
+    let data = execute("fetch", &[("name", "origin")])?;
+
+    // Assert
+    assert_eq!(data, serde_json::json!("origin:false"));
+    Ok(())
+}
+
+#[test]
+fn test_cancellation_token_is_not_a_cli_argument() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let cmd = registry.build_command();
+    let fetch = cmd.find_subcommand("remote").and_then(|noun| noun.find_subcommand("fetch"));
+
+    // Assert
+    let args: Vec<_> =
+        fetch.into_iter().flat_map(|c| c.get_arguments()).map(|a| a.get_id().to_string()).collect();
+    assert!(args.contains(&"name".to_string()));
+    assert!(!args.contains(&"cancel".to_string()));
+}
+
+#[test]
+fn test_async_verb_timeout_is_enforced() {
+    // Arrange + Act
+    let result = execute("hang", &[]);
+
+    // Assert
+    let message = result.err().map(|e| e.to_string()).unwrap_or_default();
+    assert!(message.contains("timed out"), "unexpected result: {}", message);
+}
+
+#[test]
+fn test_async_verb_runtime_override() -> Result<()> {
+    // Arrange + Act
+    let data = execute("flavor", &[])?;
+
+    // Assert
+    assert_eq!(data, serde_json::json!("MultiThread"));
+    Ok(())
+}
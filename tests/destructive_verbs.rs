@@ -0,0 +1,188 @@
+//! Tests for verbs declaring side effects with `#[verb(effect = "...", dry_run)]`
+//!
+//! Destructive verbs need confirmation or `--yes` before their handler runs;
+//! `dry_run` adds a `--dry-run` flag that reaches the handler and skips the prompt.
+
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::cli::safety::{VerbEffect, VerbSafety};
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use serde_json::json;
+use std::sync::Mutex;
+
+/// Buckets the handler was asked to delete, with the dry-run flag it received
+static DELETED: Mutex<Vec<(String, bool)>> = Mutex::new(Vec::new());
+
+/// Delete a bucket and everything in it
+///
+/// # Arguments
+/// * `name` - Bucket to delete
+#[verb("delete", "bucket", effect = "destructive", dry_run)]
+fn delete_bucket(name: String, dry_run: bool) -> Result<String> {
+    DELETED.lock().unwrap_or_else(|e| e.into_inner()).push((name.clone(), dry_run));
+    Ok(name)
+}
+
+/// Empty a cache
+#[verb("purge", "cache", effect = "destructive", dry_run)]
+fn purge_cache() -> Result<String> {
+    Ok("purged".to_string())
+}
+
+/// Drop a table
+///
+/// # Arguments
+/// * `name` - Table to drop
+/// * `yes` - Answer yes to the database's own prompts
+#[verb("drop", "table", effect = "destructive")]
+fn drop_table(name: String, yes: bool) -> Result<String> {
+    DELETED.lock().unwrap_or_else(|e| e.into_inner()).push((name.clone(), yes));
+    Ok(name)
+}
+
+/// Resize a bucket
+#[verb("resize", "bucket", effect = "mutating")]
+fn resize_bucket(name: String) -> Result<String> {
+    Ok(name)
+}
+
+fn argv(args: &[&str]) -> Vec<String> {
+    std::iter::once("app").chain(args.iter().copied()).map(String::from).collect()
+}
+
+fn deleted(name: &str) -> Vec<bool> {
+    let deleted = DELETED.lock().unwrap_or_else(|e| e.into_inner());
+    deleted.iter().filter(|(bucket, _)| bucket == name).map(|(_, dry_run)| *dry_run).collect()
+}
+
+#[test]
+fn test_destructive_verb_requires_confirmation() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    let prompted = std::sync::Arc::new(Mutex::new(Vec::new()));
+    let seen = prompted.clone();
+    registry.set_confirm(move |command| {
+        seen.lock().unwrap_or_else(|e| e.into_inner()).push(command.to_string());
+        false
+    });
+
+    // Act
+    let declined = registry.run(argv(&["bucket", "delete", "--name", "logs"]));
+    let declined_deleted = deleted("logs");
+    registry.set_confirm(|_| true);
+    let confirmed = registry.run(argv(&["bucket", "delete", "--name", "logs"]));
+
+    // Assert
+    assert!(matches!(declined, Err(NounVerbError::Interrupted { .. })));
+    assert!(declined_deleted.is_empty(), "handler must not run when declined");
+    assert_eq!(*prompted.lock().unwrap_or_else(|e| e.into_inner()), vec!["bucket delete"]);
+    assert!(confirmed.is_ok());
+    assert_eq!(deleted("logs"), vec![false]);
+}
+
+#[test]
+fn test_yes_and_dry_run_skip_confirmation() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let confirmed = registry.call_tool("bucket_delete", &json!({"name": "assets", "yes": true}))?;
+    let dry_run = registry.call_tool("bucket_delete", &json!({"name": "tmp", "dry_run": true}))?;
+    let mutating = registry.call_tool("bucket_resize", &json!({"name": "tmp"}))?;
+
+    // Assert
+    assert_eq!(confirmed.data, json!("assets"));
+    assert_eq!(deleted("assets"), vec![false]);
+    assert_eq!(dry_run.data, json!("tmp"));
+    assert_eq!(deleted("tmp"), vec![true]);
+    assert_eq!(mutating.data, json!("tmp"));
+    Ok(())
+}
+
+#[test]
+fn test_unconfirmed_destructive_tool_call_is_rejected() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let result = registry.call_tool("bucket_delete", &json!({"name": "prod"}));
+
+    // Assert
+    assert!(
+        matches!(result, Err(NounVerbError::ArgumentError { message }) if message.contains("--yes"))
+    );
+    assert!(deleted("prod").is_empty());
+}
+
+#[test]
+fn test_verb_parameter_named_yes_does_not_confirm() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let result = registry.call_tool("table_drop", &json!({"name": "users", "yes": true}));
+
+    // Assert
+    assert!(matches!(result, Err(NounVerbError::ArgumentError { .. })));
+    assert!(deleted("users").is_empty());
+}
+
+#[test]
+fn test_dry_run_and_yes_flags_are_advertised() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let cmd = registry.build_command();
+    let tools = registry.mcp_tools();
+    let safety = registry.verb_safety("cache", "purge");
+
+    // Assert
+    let purge = cmd.find_subcommand("cache").and_then(|noun| noun.find_subcommand("purge"));
+    let longs: Vec<&str> = purge
+        .map(|verb| verb.get_arguments().filter_map(|a| a.get_long()).collect())
+        .unwrap_or_default();
+    assert!(longs.contains(&"dry-run"));
+    assert!(longs.contains(&"yes"));
+    assert_eq!(safety, Some(VerbSafety::new(VerbEffect::Destructive).with_dry_run()));
+    assert_eq!(
+        registry.verb_safety("bucket", "resize"),
+        Some(VerbSafety::new(VerbEffect::Mutating))
+    );
+
+    let purge_tool = tools.iter().find(|tool| tool.name == "cache_purge");
+    let properties = purge_tool.map(|tool| tool.input_schema["properties"].clone());
+    assert_eq!(properties.as_ref().map(|p| p["dry_run"]["type"].clone()), Some(json!("boolean")));
+    assert_eq!(properties.map(|p| p["yes"]["type"].clone()), Some(json!("boolean")));
+}
+
+#[cfg(feature = "kernel")]
+#[test]
+fn test_grammar_exports_effect() {
+    use clap_noun_verb::kernel::grammar::GrammarModel;
+
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let grammar = GrammarModel::from_registry("app", &registry);
+
+    // Assert
+    let bucket = grammar.nouns.iter().find(|noun| noun.name == "bucket");
+    let delete = bucket.and_then(|noun| noun.verbs.iter().find(|verb| verb.name == "delete"));
+    let delete = delete.map(|verb| {
+        let dry_run = verb.arguments.iter().find(|arg| arg.name == "dry_run");
+        (
+            verb.metadata["effect"].clone(),
+            verb.metadata["requires_confirmation"].clone(),
+            dry_run.and_then(|arg| arg.long.clone()),
+        )
+    });
+    assert_eq!(delete, Some((json!("destructive"), json!(true), Some("dry-run".to_string()))));
+}
@@ -0,0 +1,121 @@
+//! Tests for "did you mean" suggestions and auto-correct
+//!
+//! Mistyped nouns, verbs and flags must be matched by edit distance against the
+//! live registry, and auto-correct must only run a unique, confirmed match.
+
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::envelope::StructuredError;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use std::sync::Mutex;
+
+static POSTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// List ledger entries
+#[verb("list", "ledger")]
+fn list_entries() -> Result<Vec<String>> {
+    Ok(Vec::new())
+}
+
+/// Post a ledger entry
+///
+/// # Arguments
+/// * `account` - Account to post to
+#[verb("post", "ledger")]
+fn post_entry(account: String) -> Result<String> {
+    POSTED.lock().unwrap_or_else(|e| e.into_inner()).push(account.clone());
+    Ok(account)
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+fn run_error(registry: &CommandRegistry, argv: &[&str]) -> String {
+    match registry.run(args(argv)) {
+        Ok(()) => String::new(),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[test]
+fn test_unknown_verb_suggests_registered_verb() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let message = run_error(&registry, &["cli", "ledger", "lst"]);
+
+    // Assert
+    assert!(message.contains("a similar subcommand exists: 'list'"), "{}", message);
+}
+
+#[test]
+fn test_unknown_noun_suggests_registered_noun() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let message = run_error(&registry, &["cli", "ledgr", "list"]);
+
+    // Assert
+    assert!(message.contains("'ledger'"), "{}", message);
+}
+
+#[test]
+fn test_unknown_flag_suggests_verb_flag() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    let error = registry
+        .build_command()
+        .try_get_matches_from(["cli", "ledger", "post", "--acount", "cash"])
+        .err()
+        .ok_or_else(|| NounVerbError::execution_error("expected a parse error"))?;
+
+    // Act
+    let message = run_error(&registry, &["cli", "ledger", "post", "--acount", "cash"]);
+
+    // Assert
+    assert!(message.contains("'--account'"), "{}", message);
+    assert_eq!(StructuredError::from_clap_error(&error).kind, "unknown_argument");
+    Ok(())
+}
+
+#[test]
+fn test_auto_correct_runs_confirmed_unique_match() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_auto_correct_confirm(|typed, correction| typed == "pst" && correction == "post");
+    POSTED.lock().unwrap_or_else(|e| e.into_inner()).clear();
+
+    // Act
+    let result = registry.run(args(&["cli", "ledger", "pst", "--account", "cash"]));
+    let declined = registry.run(args(&["cli", "ledger", "lst"]));
+    registry.set_auto_correct(false);
+
+    // Assert
+    result?;
+    assert!(declined.is_err());
+    let posted = POSTED.lock().unwrap_or_else(|e| e.into_inner());
+    assert_eq!(posted.as_slice(), ["cash".to_string()]);
+    Ok(())
+}
+
+#[test]
+fn test_not_found_errors_get_registry_suggestions() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let verbs = registry.suggestions_for(&NounVerbError::verb_not_found("ledger", "psot"));
+    let nouns = registry.suggestions_for(&NounVerbError::command_not_found("leger"));
+
+    // Assert
+    assert_eq!(verbs, vec!["post".to_string()]);
+    assert_eq!(nouns, vec!["ledger".to_string()]);
+}
@@ -0,0 +1,123 @@
+//! Tests for dynamic (runtime) shell completions
+//!
+//! `app __complete <shell> -- <words...>` completes nouns, verbs and flags from
+//! the command tree and argument values from `#[arg(complete = fn_name)]`.
+
+use clap_noun_verb::cli::complete::{
+    dynamic_script, CompletionCandidate, CompletionRequest, CompletionShell,
+};
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::Result;
+use clap_noun_verb_macros::verb;
+
+/// Services per namespace, used as runtime completion candidates
+fn complete_service(request: &CompletionRequest) -> Vec<CompletionCandidate> {
+    let services: &[(&str, &str)] = match request.arg("namespace") {
+        Some("prod") => &[("api", "running"), ("auth", "stopped")],
+        _ => &[("web", "running"), ("worker", "running")],
+    };
+    services
+        .iter()
+        .map(|(name, status)| CompletionCandidate::new(*name).with_description(*status))
+        .collect()
+}
+
+/// Restart a service
+///
+/// # Arguments
+/// * `name` - Service to restart
+/// * `namespace` - Namespace of the service
+#[verb("restart", "service")]
+fn restart_service(
+    #[arg(complete = complete_service)] name: String,
+    namespace: Option<String>,
+) -> Result<String> {
+    Ok(format!("{}/{}", namespace.unwrap_or_default(), name))
+}
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+fn complete(line: &[&str]) -> Vec<CompletionCandidate> {
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.complete(&words(line))
+}
+
+fn values(candidates: &[CompletionCandidate]) -> Vec<&str> {
+    candidates.iter().map(|c| c.value.as_str()).collect()
+}
+
+#[test]
+fn test_completes_nouns_and_verbs_from_command_tree() {
+    // Act
+    let nouns = complete(&["app", "se"]);
+    let verbs = complete(&["app", "service", ""]);
+
+    // Assert
+    assert_eq!(values(&nouns), vec!["service"]);
+    assert_eq!(values(&verbs), vec!["restart"]);
+    assert_eq!(verbs[0].description.as_deref(), Some("Restart a service"));
+}
+
+#[test]
+fn test_completes_long_flags_with_help() {
+    // Act
+    let flags = complete(&["app", "service", "restart", "--na"]);
+
+    // Assert
+    assert_eq!(values(&flags), vec!["--name", "--namespace"]);
+    assert_eq!(flags[0].description.as_deref(), Some("Service to restart"));
+}
+
+#[test]
+fn test_complete_fn_sees_partial_input_and_other_args() {
+    // Act
+    let default_ns = complete(&["app", "service", "restart", "--name", "w"]);
+    let prod = complete(&["app", "service", "restart", "--namespace", "prod", "--name", "a"]);
+    let none = complete(&["app", "service", "restart", "--namespace=prod", "--name", "x"]);
+
+    // Assert
+    assert_eq!(values(&default_ns), vec!["web", "worker"]);
+    assert_eq!(
+        prod,
+        vec![
+            CompletionCandidate::new("api").with_description("running"),
+            CompletionCandidate::new("auth").with_description("stopped"),
+        ]
+    );
+    assert!(none.is_empty());
+}
+
+#[test]
+fn test_completes_values_after_equals_sign() {
+    // Act
+    let joined = complete(&["app", "service", "restart", "--name=wo"]);
+    let bash_split = complete(&["app", "service", "restart", "--name", "=", "wo"]);
+
+    // Assert
+    assert_eq!(values(&joined), vec!["--name=worker"]);
+    assert_eq!(values(&bash_split), vec!["worker"]);
+}
+
+#[test]
+fn test_complete_command_prints_scripts_and_rejects_unknown_shells() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let script = registry.run(words(&["app", "__complete", "fish"]));
+    let candidates = registry.run(words(&["app", "__complete", "zsh", "--", "app", ""]));
+    let unknown = registry.run(words(&["app", "__complete", "tcsh"]));
+    let restarted = registry.run(words(&["app", "service", "restart", "--name", "web"]));
+
+    // Assert
+    script?;
+    candidates?;
+    restarted?;
+    assert!(unknown.is_err());
+    assert!(dynamic_script(CompletionShell::Bash, "app").contains("complete -o default -F"));
+    Ok(())
+}
@@ -38,6 +38,7 @@ static __init_test_opt: fn() = || {
         min_length: None,
         max_length: None,
         short: None,
+        long: None,
         default_value: None,
         env: None,
         multiple: false,
@@ -59,6 +60,8 @@ static __init_test_opt: fn() = || {
         allow_negative_numbers: false,
         value_hint: None,
         global: false,
+        value_type: None,
+        complete: None,
     }];
     CommandRegistry::register_verb_with_args::<_>(
         noun_name_static,
@@ -0,0 +1,115 @@
+//! Tests for writing an execution receipt for every verb invocation
+//!
+//! Receipts are appended to a JSONL log set on the registry; a chained log
+//! links records by blake3 hash so `receipts verify` detects tampering.
+#![cfg(feature = "autonomic")]
+
+use clap_noun_verb::autonomic::{ReceiptConfig, ReceiptLog};
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use serde_json::json;
+
+/// Rotate a signing key
+///
+/// # Arguments
+/// * `key` - Key to rotate
+#[verb("rotate", "key")]
+fn rotate_key(key: String) -> Result<String> {
+    if key == "root" {
+        return Err(NounVerbError::execution_error("the root key cannot be rotated"));
+    }
+    Ok(format!("rotated {}", key))
+}
+
+fn argv(args: &[&str]) -> Vec<String> {
+    std::iter::once("app").chain(args.iter().copied()).map(String::from).collect()
+}
+
+fn temp_dir() -> Result<tempfile::TempDir> {
+    tempfile::tempdir().map_err(|e| NounVerbError::execution_error(e.to_string()))
+}
+
+#[test]
+fn test_every_invocation_is_recorded() -> Result<()> {
+    // Arrange
+    let dir = temp_dir()?;
+    let log = ReceiptLog::new(dir.path().join("receipts.jsonl")).chained();
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_receipt_log(log.clone());
+
+    // Act
+    registry.call_tool("key_rotate", &json!({"key": "deploy"}))?;
+    let failed = registry.run(argv(&["key", "rotate", "--key", "root"]));
+    let records = log.records()?;
+
+    // Assert
+    assert!(failed.is_err());
+    assert_eq!(records.len(), 2);
+    let (ok, err) = (&records[0], &records[1]);
+    assert_eq!((ok.seq, err.seq), (1, 2));
+    assert_eq!(ok.receipt.command, "key rotate");
+    assert!(ok.receipt.success);
+    assert_eq!(ok.receipt.args.as_ref().map(|args| args["key"].clone()), Some(json!("deploy")));
+    assert!(ok.receipt.result_hash.as_deref().is_some_and(|hash| hash.starts_with("sha256:")));
+    assert!(!err.receipt.success);
+    assert!(err.receipt.error.is_some());
+    assert_eq!(err.prev_hash, ok.hash);
+    assert!(log.verify()?.is_intact());
+    Ok(())
+}
+
+#[test]
+fn test_verify_command_fails_on_tampered_log() -> Result<()> {
+    // Arrange
+    let dir = temp_dir()?;
+    let log = ReceiptLog::new(dir.path().join("receipts.jsonl")).chained();
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_receipt_log(log.clone());
+    registry.call_tool("key_rotate", &json!({"key": "a"}))?;
+    registry.call_tool("key_rotate", &json!({"key": "b"}))?;
+
+    // Act
+    let intact = registry.run(argv(&["receipts", "verify"]));
+    let content = std::fs::read_to_string(log.path())
+        .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    std::fs::write(log.path(), content.replacen("\"a\"", "\"z\"", 1))
+        .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let tampered = registry.run(argv(&["receipts", "verify"]));
+
+    // Assert
+    assert!(intact.is_ok());
+    assert!(matches!(
+        tampered,
+        Err(NounVerbError::ExecutionError { message }) if message.contains("broken at record 1")
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_receipts_list_and_show() -> Result<()> {
+    // Arrange
+    let dir = temp_dir()?;
+    let log = ReceiptLog::new(dir.path().join("receipts.jsonl"))
+        .with_config(ReceiptConfig::new().without_args());
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_receipt_log(log.clone());
+    registry.call_tool("key_rotate", &json!({"key": "secret"}))?;
+
+    // Act
+    let list = registry.run(argv(&["receipts", "list", "--limit", "5"]));
+    let show = registry.run(argv(&["receipts", "show", "1"]));
+    let missing = registry.run(argv(&["receipts", "show", "9"]));
+    let record = log.get(1)?;
+
+    // Assert
+    assert!(list.is_ok());
+    assert!(show.is_ok());
+    assert!(matches!(missing, Err(NounVerbError::ArgumentError { .. })));
+    assert!(record.as_ref().is_some_and(|record| record.receipt.args.is_none()));
+    assert!(record.is_some_and(|record| record.hash.is_none()));
+    Ok(())
+}
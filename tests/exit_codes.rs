@@ -0,0 +1,103 @@
+//! Tests for semantic process exit codes
+//!
+//! Framework errors map onto `ExitCodeClass`, handlers can pick their own code
+//! with `NounVerbError::exit_with`, and `run_to_exit_code` surfaces both.
+
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::{ExitCodeClass, NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use std::process::ExitCode;
+
+/// Check the database schema
+#[verb("check", "schema")]
+fn check_schema() -> Result<String> {
+    Err(NounVerbError::exit_with(70, "schema drift detected"))
+}
+
+/// Apply the database schema
+///
+/// # Arguments
+/// * `target` - Target database
+#[verb("apply", "schema")]
+fn apply_schema(target: String) -> Result<String> {
+    if target == "missing" {
+        return Err(NounVerbError::exit_with(ExitCodeClass::NotFound, "no such database"));
+    }
+    Ok(target)
+}
+
+fn run(args: &[&str]) -> ExitCode {
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.run_to_exit_code(args.iter().map(|a| a.to_string()).collect())
+}
+
+#[test]
+fn test_framework_errors_map_to_exit_classes() {
+    // Arrange
+    let cases = [
+        (NounVerbError::command_not_found("x"), ExitCodeClass::UsageError),
+        (NounVerbError::verb_not_found("x", "y"), ExitCodeClass::UsageError),
+        (NounVerbError::argument_error("bad"), ExitCodeClass::UsageError),
+        (NounVerbError::ValidationFailed("bad".to_string()), ExitCodeClass::InputError),
+        (NounVerbError::timeout("slow"), ExitCodeClass::Timeout),
+        (NounVerbError::interrupted("ctrl-c"), ExitCodeClass::Interrupted),
+        (NounVerbError::execution_error("boom"), ExitCodeClass::GeneralError),
+    ];
+
+    // Act + Assert
+    for (error, class) in cases {
+        assert_eq!(error.exit_code(), class.code(), "wrong code for {:?}", error);
+    }
+}
+
+#[test]
+fn test_exit_code_zero_is_reported_as_failure() {
+    // Arrange
+    let error = NounVerbError::exit_with(0u8, "claimed success");
+    let literal = NounVerbError::Exit { code: 0, message: "claimed success".to_string() };
+
+    // Act + Assert
+    assert_eq!(error.exit_code(), ExitCodeClass::GeneralError.code());
+    assert_eq!(literal.exit_code(), ExitCodeClass::GeneralError.code());
+}
+
+#[test]
+fn test_handler_exit_code_is_returned() {
+    // Arrange + Act
+    let code = run(&["cli", "schema", "check"]);
+
+    // Assert
+    assert_eq!(code, ExitCode::from(70));
+}
+
+#[test]
+fn test_handler_exit_class_is_returned() {
+    // Arrange + Act
+    let missing = run(&["cli", "schema", "apply", "--target", "missing"]);
+    let present = run(&["cli", "schema", "apply", "--target", "main"]);
+
+    // Assert
+    assert_eq!(missing, ExitCode::from(4));
+    assert_eq!(present, ExitCode::SUCCESS);
+}
+
+#[test]
+fn test_usage_errors_exit_with_two() {
+    // Arrange + Act
+    let unknown_verb = run(&["cli", "schema", "drop"]);
+    let missing_arg = run(&["cli", "schema", "apply"]);
+
+    // Assert
+    assert_eq!(unknown_verb, ExitCode::from(2));
+    assert_eq!(missing_arg, ExitCode::from(2));
+}
+
+#[test]
+fn test_help_exits_successfully() {
+    // Arrange + Act
+    let code = run(&["cli", "--help"]);
+
+    // Assert
+    assert_eq!(code, ExitCode::SUCCESS);
+}
@@ -0,0 +1,192 @@
+//! Tests for git-style external subcommand plugins
+//!
+//! Executables named `<app>-<noun>` or `<app>-<noun>-<verb>` are merged into
+//! the command tree, receive their args, env and a JSON context on stdin, and
+//! their JSON stdout becomes the verb's output.
+#![cfg(unix)]
+
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb::format::OutputFormat;
+use clap_noun_verb::plugin::{ExternalCommand, ExternalPluginDiscovery};
+use clap_noun_verb_macros::verb;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// List repositories
+#[verb("list", "repo")]
+fn list_repos() -> Result<Vec<String>> {
+    Ok(vec!["core".to_string()])
+}
+
+/// Write an executable shell script into `dir`
+fn write_script(dir: &Path, name: &str, body: &str) -> std::io::Result<PathBuf> {
+    let path = dir.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{}\n", body))?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(path)
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn test_discovery_finds_noun_and_verb_executables() -> std::io::Result<()> {
+    // Arrange
+    let dir = tempfile::tempdir()?;
+    write_script(dir.path(), "tool-deploy", "echo null")?;
+    write_script(dir.path(), "tool-repo-sync", "echo null")?;
+    write_script(dir.path(), "other-deploy", "echo null")?;
+    std::fs::write(dir.path().join("tool-not-executable"), "")?;
+
+    // Act
+    let found = ExternalPluginDiscovery::new("tool")
+        .with_search_dirs([dir.path()])
+        .discover()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+
+    // Assert
+    let names: Vec<(&str, Option<&str>)> = found.iter().map(|c| (c.noun(), c.verb())).collect();
+    assert_eq!(names, vec![("deploy", None), ("repo", Some("sync"))]);
+    Ok(())
+}
+
+#[test]
+fn test_manifest_entry_point_and_description() -> std::io::Result<()> {
+    // Arrange
+    let dir = tempfile::tempdir()?;
+    write_script(dir.path(), "bin-sync", "echo null")?;
+    std::fs::write(
+        dir.path().join("sync.json"),
+        r#"{"name": "tool-repo-sync", "version": "1.0.0", "entry_point": "bin-sync",
+            "description": "Sync repositories"}"#,
+    )?;
+
+    // Act
+    let found = ExternalPluginDiscovery::new("tool")
+        .with_search_dirs(Vec::<PathBuf>::new())
+        .with_manifest_dir(dir.path())
+        .discover()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+
+    // Assert
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].verb(), Some("sync"));
+    assert_eq!(found[0].about(), "Sync repositories");
+    assert_eq!(found[0].program(), dir.path().join("bin-sync"));
+    Ok(())
+}
+
+#[test]
+fn test_invoke_forwards_args_env_and_context() -> Result<()> {
+    // Arrange
+    let dir = tempfile::tempdir().map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let script = write_script(
+        dir.path(),
+        "tool-repo-sync",
+        r#"context=$(cat)
+printf '{"data": {"args": "%s", "verb": "%s", "context": %s}, "message": "synced"}' \
+  "$*" "$CNV_PLUGIN_VERB" "$context""#,
+    )
+    .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let command = ExternalCommand::new("repo", Some("sync".to_string()), script);
+
+    // Act
+    let output = command.invoke("tool", "sync", &args(&["--all", "core"]), OutputFormat::Json)?;
+
+    // Assert
+    assert_eq!(output.message.as_deref(), Some("synced"));
+    assert_eq!(output.data["args"], "--all core");
+    assert_eq!(output.data["verb"], "sync");
+    assert_eq!(output.data["context"]["app"], "tool");
+    assert_eq!(output.data["context"]["noun"], "repo");
+    assert_eq!(output.data["context"]["args"], serde_json::json!(["--all", "core"]));
+    Ok(())
+}
+
+#[test]
+fn test_invoke_reports_failures_as_plugin_errors() -> Result<()> {
+    // Arrange
+    let dir = tempfile::tempdir().map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let failing = write_script(dir.path(), "tool-a", "exit 3")
+        .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let garbled = write_script(dir.path(), "tool-b", "echo not json")
+        .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+
+    // Act
+    let failed =
+        ExternalCommand::new("a", None, failing).invoke("tool", "x", &[], OutputFormat::Json);
+    let garbled =
+        ExternalCommand::new("b", None, garbled).invoke("tool", "x", &[], OutputFormat::Json);
+
+    // Assert
+    assert!(matches!(failed, Err(NounVerbError::PluginError(_))));
+    assert!(matches!(garbled, Err(NounVerbError::PluginError(_))));
+    Ok(())
+}
+
+#[test]
+fn test_registry_merges_and_dispatches_external_commands() -> Result<()> {
+    // Arrange
+    let dir = tempfile::tempdir().map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let log = dir.path().join("calls.log");
+    let body =
+        format!("echo \"$CNV_PLUGIN_NOUN $CNV_PLUGIN_VERB $*\" >> {}\necho null", log.display());
+    write_script(dir.path(), "tool-repo-sync", &body)
+        .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    write_script(dir.path(), "tool-deploy", &body)
+        .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let discovery = ExternalPluginDiscovery::new("tool").with_search_dirs([dir.path()]);
+
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let added = registry.discover_external_plugins(&discovery)?;
+    let cmd = registry.build_command();
+    registry.run(args(&["cli", "repo", "sync", "--force", "core"]))?;
+    registry.run(args(&["cli", "deploy", "staging", "--dry-run"]))?;
+    registry.run(args(&["cli", "repo", "list"]))?;
+
+    // Assert
+    assert_eq!(added, 2);
+    let repo = cmd.find_subcommand("repo");
+    assert!(repo.and_then(|r| r.find_subcommand("list")).is_some());
+    assert!(repo.and_then(|r| r.find_subcommand("sync")).is_some());
+    assert!(cmd.find_subcommand("deploy").is_some());
+    let calls = std::fs::read_to_string(&log).unwrap_or_default();
+    assert_eq!(calls, "repo sync --force core\ndeploy staging staging --dry-run\n");
+    Ok(())
+}
+
+#[cfg(feature = "autonomic")]
+#[test]
+fn test_policy_is_evaluated_before_external_commands() -> Result<()> {
+    // Arrange
+    let dir = tempfile::tempdir().map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let log = dir.path().join("calls.log");
+    let script =
+        write_script(dir.path(), "tool-repo-purge", &format!("echo ran > {}", log.display()))
+            .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let policy = clap_noun_verb::autonomic::RuleBasedPolicyEngine::from_value(serde_json::json!({
+        "rules": [{
+            "name": "no-purge",
+            "conditions": [{"type": "command", "pattern": "repo.purge"}],
+            "action": {"type": "deny", "reason": "Purging is disabled"}
+        }]
+    }))?;
+
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.add_external_command(ExternalCommand::new("repo", Some("purge".to_string()), script));
+    registry.set_policy_engine(policy);
+
+    // Act
+    let result = registry.run(args(&["cli", "repo", "purge", "core"]));
+
+    // Assert
+    assert!(matches!(result, Err(NounVerbError::PolicyDenied { .. })));
+    assert!(!log.exists(), "a denied plugin must not run");
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//! Tests for exporting the live command registry as a grammar
+//!
+//! `GrammarModel::from_registry` must describe every `#[verb]` in the binary -
+//! nested nouns, root verbs, argument types, defaults, env vars, groups,
+//! conflicts and hints - and the hidden `__grammar` entry point must print it.
+
+#![cfg(feature = "kernel")]
+
+use clap_noun_verb::cli::registry::{grammar_requested, CommandRegistry};
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb::kernel::grammar::{ArgumentType, GrammarModel};
+use clap_noun_verb_macros::verb;
+use std::path::PathBuf;
+
+/// Seal a secret into the vault
+///
+/// # Arguments
+/// * `name` - Secret name
+/// * `file` - Read the secret from a file [group: source] [value_hint: file_path]
+/// * `stdin` - Read the secret from stdin [group: source] [conflicts: ttl]
+/// * `ttl` - Lifetime in seconds [default: 3600] [env: VAULT_TTL]
+#[verb("seal", "vault")]
+fn seal_secret(
+    name: String,
+    file: Option<PathBuf>,
+    stdin: bool,
+    ttl: Option<u32>,
+) -> Result<String> {
+    let _ = (file, stdin, ttl);
+    Ok(name)
+}
+
+/// List vault keys
+#[verb("list", "vault.key")]
+fn list_keys() -> Result<Vec<String>> {
+    Ok(Vec::new())
+}
+
+/// Check the installation
+#[verb("doctor", "root")]
+fn doctor() -> Result<String> {
+    Ok("ok".to_string())
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+fn grammar() -> GrammarModel {
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.grammar(&args(&["/usr/local/bin/vaultctl"]))
+}
+
+#[test]
+fn test_grammar_describes_arguments() -> Result<()> {
+    // Arrange
+    let grammar = grammar();
+
+    // Act
+    let seal = grammar
+        .find_verb("vault", "seal")
+        .ok_or_else(|| NounVerbError::verb_not_found("vault", "seal"))?;
+    let arg = |name: &str| {
+        seal.arguments
+            .iter()
+            .find(|arg| arg.name == name)
+            .ok_or_else(|| NounVerbError::missing_argument(name))
+    };
+
+    // Assert
+    assert_eq!(grammar.app_name, "vaultctl");
+    assert_eq!(seal.help.as_deref(), Some("Seal a secret into the vault"));
+    let name = arg("name")?;
+    assert!(name.required);
+    assert_eq!(name.value_type.as_deref(), Some("String"));
+    let file = arg("file")?;
+    assert_eq!(file.value_type.as_deref(), Some("PathBuf"));
+    assert_eq!(file.value_hint.as_deref(), Some("file_path"));
+    assert_eq!(file.group.as_deref(), Some("source"));
+    let stdin = arg("stdin")?;
+    assert_eq!(stdin.arg_type, ArgumentType::Flag);
+    assert_eq!(stdin.conflicts_with, vec!["ttl".to_string()]);
+    let ttl = arg("ttl")?;
+    assert_eq!(ttl.default.as_deref(), Some("3600"));
+    assert_eq!(ttl.env.as_deref(), Some("VAULT_TTL"));
+    assert_eq!(ttl.value_type.as_deref(), Some("u32"));
+    assert_eq!(seal.groups.len(), 1);
+    assert_eq!(seal.groups[0].arguments, vec!["file".to_string(), "stdin".to_string()]);
+    Ok(())
+}
+
+#[test]
+fn test_grammar_includes_nested_nouns_root_verbs_and_globals() {
+    // Arrange
+    let grammar = grammar();
+
+    // Act
+    let vault = grammar.find_noun("vault");
+    let key = vault.and_then(|vault| vault.sub_nouns.iter().find(|noun| noun.name == "key"));
+
+    // Assert
+    assert!(key.is_some_and(|key| key.verbs.iter().any(|verb| verb.noun == "vault.key")));
+    assert!(grammar.root_verbs.iter().any(|verb| verb.name == "doctor" && verb.noun.is_empty()));
+    assert!(grammar
+        .global_arguments
+        .iter()
+        .any(|arg| arg.long.as_deref() == Some("format") && arg.global));
+}
+
+#[test]
+fn test_grammar_json_is_deterministic() -> std::result::Result<(), serde_json::Error> {
+    // Arrange
+    let first = serde_json::to_string(&grammar())?;
+
+    // Act
+    let second = serde_json::to_string(&grammar())?;
+    let value: serde_json::Value = serde_json::from_str(&first)?;
+
+    // Assert
+    assert_eq!(first, second);
+    assert_eq!(value["schema_version"], "1.0.0");
+    assert!(value["nouns"].as_array().is_some_and(|nouns| !nouns.is_empty()));
+    Ok(())
+}
+
+#[test]
+fn test_hidden_grammar_entry_point() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let result = registry.run(args(&["cli", "__grammar"]));
+
+    // Assert
+    assert!(grammar_requested(&args(&["cli", "--grammar"])));
+    assert!(!grammar_requested(&args(&["cli", "vault", "__grammar"])));
+    result
+}
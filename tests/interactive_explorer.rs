@@ -0,0 +1,133 @@
+//! Tests for the interactive explorer built from the command registry
+//!
+//! Answers are scripted through `RegistryExplorer::run_with`; the explorer
+//! prompts for each argument, shows the command line, then runs the verb.
+
+use clap_noun_verb::cli::examples::ExamplesRegistry;
+use clap_noun_verb::cli::interactive::RegistryExplorer;
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use std::sync::Mutex;
+
+/// Volumes the handlers were called with
+static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Resize a volume
+///
+/// # Arguments
+/// * `name` - Volume to resize
+/// * `size` - New size in GB
+/// * `force` - Resize while mounted
+#[verb(
+    "resize",
+    "volume",
+    example = "app volume resize --name data --size 20",
+    example = "app volume resize --name logs --size 5 --force"
+)]
+fn resize_volume(name: String, size: Option<u32>, force: bool) -> Result<String> {
+    CALLS.lock().unwrap_or_else(|e| e.into_inner()).push(format!("resize {}", name));
+    Ok(format!("{} resized to {}GB (force: {})", name, size.unwrap_or(10), force))
+}
+
+/// Wipe a volume
+///
+/// # Arguments
+/// * `name` - Volume to wipe
+#[verb("wipe", "volume", effect = "destructive")]
+fn wipe_volume(name: String) -> Result<String> {
+    CALLS.lock().unwrap_or_else(|e| e.into_inner()).push(format!("wipe {}", name));
+    Ok(format!("{} wiped", name))
+}
+
+/// Run the explorer over the global registry with scripted answers
+fn explore(answers: &str) -> Result<String> {
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    let mut output = Vec::new();
+    RegistryExplorer::new(&registry, "app").run_with(answers.as_bytes(), &mut output)?;
+    String::from_utf8(output).map_err(|e| NounVerbError::execution_error(e.to_string()))
+}
+
+fn calls() -> Vec<String> {
+    CALLS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[test]
+fn test_explorer_prompts_arguments_and_runs_verb() -> Result<()> {
+    // Arrange
+    // volume -> resize; name (required, asked twice), size, force; run
+    let answers = "1\n1\n\nscratch\n20\ny\n\nq\n";
+
+    // Act
+    let output = explore(answers)?;
+
+    // Assert
+    assert!(output.contains("app commands:"));
+    assert!(output.contains("$ app volume resize --name data --size 20"));
+    assert!(output.contains("New size in GB"));
+    assert!(output.contains("name is required"));
+    assert!(output.contains("$ app volume resize --force --name=scratch --size=20"));
+    assert!(output.contains("\"scratch resized to 20GB (force: true)\""));
+    assert!(calls().contains(&"resize scratch".to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_explorer_confirms_destructive_verbs() -> Result<()> {
+    // Arrange
+    // volume -> wipe "tmp", decline; wipe "cache", confirm and run
+    let answers = "1\n2\ntmp\nn\n2\ncache\ny\ny\nb\nq\n";
+
+    // Act
+    let output = explore(answers)?;
+
+    // Assert
+    assert!(output.contains("Cancelled"));
+    assert!(output.contains("$ app volume wipe --name=cache --yes"));
+    assert!(output.contains("\"cache wiped\""));
+    let calls = calls();
+    assert!(calls.contains(&"wipe cache".to_string()));
+    assert!(!calls.contains(&"wipe tmp".to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_explorer_stops_at_end_of_input() -> Result<()> {
+    // Arrange
+    let answers = "7\n1\n";
+
+    // Act
+    let output = explore(answers)?;
+
+    // Assert
+    assert!(output.contains("Unknown choice '7'"));
+    assert!(output.contains("app volume:"));
+    Ok(())
+}
+
+#[test]
+fn test_examples_are_collected_from_registry() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let examples = ExamplesRegistry::from_registry(&registry);
+    let command = registry.build_command();
+    let after_help = command
+        .find_subcommand("volume")
+        .and_then(|noun| noun.find_subcommand("resize"))
+        .and_then(|verb| verb.get_after_help())
+        .map(|help| help.to_string())
+        .unwrap_or_default();
+
+    // Assert
+    assert_eq!(examples.all().len(), 1);
+    let example = &examples.all()[0];
+    assert_eq!(example.title, "volume resize");
+    assert_eq!(example.command, "app volume resize --name data --size 20");
+    assert_eq!(example.variations.len(), 1);
+    assert_eq!(examples.by_tag("volume").len(), 1);
+    assert!(after_help.contains("$ app volume resize --name logs --size 5 --force"));
+}
@@ -0,0 +1,280 @@
+//! Tests for propagating the invocation context into handlers
+//!
+//! The runner builds an `InvocationContext` from `--agent`-style flags, the
+//! `CNV_*` environment variables and `CNV_DELEGATION` chains; `#[verb]`
+//! handlers receive it, and policy rules and receipts see the same identity.
+#![cfg(feature = "autonomic")]
+
+use clap_noun_verb::autonomic::*;
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+type TestResult = std::result::Result<(), Box<dyn std::error::Error>>;
+
+/// Context the last `job run` invocation saw
+static SEEN: Mutex<Option<InvocationContext>> = Mutex::new(None);
+
+/// Serializes tests that set `CNV_*` environment variables, taken after the registry lock
+static ENV: Mutex<()> = Mutex::new(());
+
+/// Run a job
+///
+/// # Arguments
+/// * `name` - Job to run
+#[verb("run", "job")]
+fn run_job(name: String, context: InvocationContext) -> Result<String> {
+    *SEEN.lock().unwrap_or_else(|e| e.into_inner()) = Some(context);
+    Ok(format!("ran {}", name))
+}
+
+/// Set `vars` for the duration of a test, holding the environment lock
+struct EnvGuard {
+    vars: Vec<&'static str>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl EnvGuard {
+    fn set(vars: &[(&'static str, &str)]) -> Self {
+        let lock = ENV.lock().unwrap_or_else(|e| e.into_inner());
+        for (name, value) in vars {
+            std::env::set_var(name, value);
+        }
+        Self { vars: vars.iter().map(|(name, _)| *name).collect(), _lock: lock }
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for name in &self.vars {
+            std::env::remove_var(name);
+        }
+    }
+}
+
+/// Global registry writing receipts to a fresh log in `dir`
+fn registry(dir: &tempfile::TempDir) -> (MutexGuard<'static, CommandRegistry>, ReceiptLog) {
+    let mut registry = CommandRegistry::get().lock().unwrap_or_else(|e| e.into_inner());
+    let log = ReceiptLog::new(dir.path().join("receipts.jsonl"));
+    registry.set_receipt_log(log.clone());
+    (registry, log)
+}
+
+fn seen() -> std::result::Result<InvocationContext, &'static str> {
+    SEEN.lock().unwrap_or_else(|e| e.into_inner()).take().ok_or("handler did not run")
+}
+
+fn argv(args: &[&str]) -> Vec<String> {
+    std::iter::once("app").chain(args.iter().copied()).map(String::from).collect()
+}
+
+fn principal(agent: &str) -> Principal {
+    Principal::new(AgentIdentity::new(agent, "agent"), TenantIdentity::new("acme"))
+}
+
+/// Unsigned token in which alice delegates `nouns` to bob for an hour
+fn token(nouns: &[&str]) -> DelegationToken {
+    let constraints = CapabilityConstraint {
+        allowed_nouns: Some(nouns.iter().map(|noun| noun.to_string()).collect::<HashSet<_>>()),
+        ..CapabilityConstraint::unrestricted()
+    };
+    DelegationToken::new(
+        principal("alice"),
+        principal("bob"),
+        constraints,
+        TemporalConstraint::valid_for(Duration::from_secs(3600)),
+    )
+}
+
+/// alice delegates `nouns` to bob for an hour, signed with `key`
+#[cfg(feature = "signing")]
+fn delegation(
+    key: &IssuerKey,
+    nouns: &[&str],
+) -> std::result::Result<String, Box<dyn std::error::Error>> {
+    let token = token(nouns).sign(key)?;
+    Ok(serde_json::to_string(&DelegationChain::with_delegation(token))?)
+}
+
+#[test]
+fn test_flags_override_environment_identity() -> TestResult {
+    // Arrange
+    let dir = tempfile::tempdir()?;
+    let (mut registry, _) = registry(&dir);
+    registry.set_invocation_flags(true);
+    let _env = EnvGuard::set(&[
+        ("CNV_AGENT_ID", "env-agent"),
+        ("CNV_AGENT_TYPE", "build-bot"),
+        ("CNV_TENANT_ID", "acme"),
+        ("CNV_CORRELATION_ID", "corr-1"),
+        ("CNV_PRIORITY", "high"),
+    ]);
+
+    // Act
+    registry.call_tool("job_run", &json!({ "name": "nightly" }))?;
+    let from_env = seen()?;
+    registry.run(argv(&[
+        "job",
+        "run",
+        "--name",
+        "nightly",
+        "--agent",
+        "cli-agent",
+        "--priority",
+        "critical",
+    ]))?;
+    let from_flags = seen()?;
+    let invalid = registry.run(argv(&["job", "run", "--name", "nightly", "--priority", "urgent"]));
+
+    // Assert
+    assert_eq!(from_env.agent, AgentIdentity::new("env-agent", "build-bot"));
+    assert_eq!(from_env.tenant.tenant_id, "acme");
+    assert_eq!(from_env.correlation_id, "corr-1");
+    assert_eq!(from_env.qos.priority, PriorityClass::High);
+    assert_eq!(from_flags.agent.agent_id, "cli-agent");
+    assert_eq!(from_flags.tenant.tenant_id, "acme");
+    assert_eq!(from_flags.qos.priority, PriorityClass::Critical);
+    assert!(matches!(invalid, Err(NounVerbError::ArgumentError { .. })));
+    Ok(())
+}
+
+#[test]
+fn test_policy_and_receipts_see_the_invoking_tenant() -> TestResult {
+    // Arrange
+    let dir = tempfile::tempdir()?;
+    let (mut registry, log) = registry(&dir);
+    let policy = json!({"rules": [{
+        "name": "suspended-tenant",
+        "conditions": [{"type": "tenant", "tenant_id": "suspended"}],
+        "action": {"type": "deny", "reason": "Tenant is suspended"}
+    }]});
+    registry.set_policy_engine(RuleBasedPolicyEngine::from_value(policy)?);
+
+    // Act
+    let allowed = {
+        let _env = EnvGuard::set(&[
+            ("CNV_AGENT_ID", "deployer"),
+            ("CNV_TENANT_ID", "acme"),
+            ("CNV_CORRELATION_ID", "corr-2"),
+        ]);
+        registry.call_tool("job_run", &json!({ "name": "deploy" }))
+    };
+    let denied = {
+        let _env = EnvGuard::set(&[("CNV_TENANT_ID", "suspended")]);
+        registry.call_tool("job_run", &json!({ "name": "deploy" }))
+    };
+    let records = log.records()?;
+
+    // Assert
+    assert!(allowed.is_ok());
+    assert!(matches!(denied, Err(NounVerbError::PolicyDenied { .. })));
+    let [allowed, denied] = &records[..] else {
+        return Err("expected two receipts".into());
+    };
+    assert_eq!(allowed.receipt.agent_id.as_deref(), Some("deployer"));
+    assert_eq!(allowed.receipt.tenant_id.as_deref(), Some("acme"));
+    assert_eq!(allowed.receipt.correlation_id, "corr-2");
+    assert!(!denied.receipt.success);
+    assert_eq!(denied.receipt.tenant_id.as_deref(), Some("suspended"));
+    Ok(())
+}
+
+#[cfg(feature = "signing")]
+#[test]
+fn test_delegated_calls_run_as_the_delegate() -> TestResult {
+    // Arrange
+    let alice = IssuerKey::generate("alice");
+    let granted = delegation(&alice, &["job"])?;
+    let other_noun = delegation(&alice, &["billing"])?;
+    let dir = tempfile::tempdir()?;
+    let (mut registry, log) = registry(&dir);
+    let run = |registry: &CommandRegistry, vars: &[(&'static str, &str)]| {
+        let _env = EnvGuard::set(vars);
+        registry.call_tool("job_run", &json!({ "name": "backup" }))
+    };
+
+    // Act
+    let without_trust_store = run(&registry, &[("CNV_DELEGATION", &granted)]);
+    registry.set_trust_store(TrustStore::new().with_key(alice.public_key()));
+    let trusted = run(&registry, &[("CNV_DELEGATION", &granted)]).map(|_| seen());
+    let wrong_agent = run(&registry, &[("CNV_DELEGATION", &granted), ("CNV_AGENT_ID", "mallory")]);
+    let not_allowed = run(&registry, &[("CNV_DELEGATION", &other_noun)]);
+    let malformed = run(&registry, &[("CNV_DELEGATION", "{")]);
+    registry.set_trust_store(TrustStore::new());
+    let untrusted = run(&registry, &[("CNV_DELEGATION", &granted)]);
+    let records = log.records()?;
+
+    // Assert
+    let context = trusted??;
+    assert_eq!(context.agent.agent_id, "bob");
+    assert_eq!(context.tenant.tenant_id, "acme");
+    assert_eq!(context.on_behalf_of().map(|agent| agent.agent_id.as_str()), Some("alice"));
+    let receipt = &records.get(1).ok_or("missing receipt")?.receipt;
+    assert_eq!(receipt.agent_id.as_deref(), Some("bob"));
+    assert_eq!(receipt.on_behalf_of.as_deref(), Some("alice"));
+    assert!(matches!(
+        wrong_agent,
+        Err(NounVerbError::PolicyDenied { ref reason, .. }) if reason.contains("'bob'")
+    ));
+    assert!(matches!(not_allowed, Err(NounVerbError::PolicyDenied { .. })));
+    assert!(matches!(malformed, Err(NounVerbError::ArgumentError { .. })));
+    assert!(matches!(
+        without_trust_store,
+        Err(NounVerbError::PolicyDenied { ref reason, .. }) if reason.contains("No trust store")
+    ));
+    assert!(matches!(
+        untrusted,
+        Err(NounVerbError::PolicyDenied { ref reason, .. }) if reason.contains("not trusted")
+    ));
+    Ok(())
+}
+
+#[cfg(not(feature = "signing"))]
+#[test]
+fn test_delegations_are_denied_without_signing() -> TestResult {
+    // Arrange
+    let chain = serde_json::to_string(&DelegationChain::with_delegation(token(&["job"])))?;
+    let dir = tempfile::tempdir()?;
+    let (registry, _log) = registry(&dir);
+
+    // Act
+    let result = {
+        let _env = EnvGuard::set(&[("CNV_DELEGATION", &chain)]);
+        registry.call_tool("job_run", &json!({ "name": "backup" }))
+    };
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(NounVerbError::PolicyDenied { ref reason, .. }) if reason.contains("No trust store")
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_spans_carry_the_invocation_identity() {
+    // Arrange
+    let context = InvocationContext::new(
+        AgentIdentity::new("agent-7", "monitor"),
+        TenantIdentity::new("acme").with_environment("staging"),
+    )
+    .with_correlation_id("corr-3")
+    .with_parent("parent-span")
+    .with_qos(QoSHints::best_effort());
+
+    // Act
+    let span = context.span("invoke");
+
+    // Assert
+    assert_eq!(span.trace_id, "corr-3");
+    assert_eq!(span.parent_id.as_deref(), Some("parent-span"));
+    assert_eq!(span.attributes.get("agent.id").map(String::as_str), Some("agent-7"));
+    assert_eq!(span.attributes.get("tenant.id").map(String::as_str), Some("acme"));
+    assert_eq!(span.attributes.get("tenant.environment").map(String::as_str), Some("staging"));
+    assert_eq!(span.attributes.get("priority").map(String::as_str), Some("best_effort"));
+    assert!(!span.attributes.contains_key("on_behalf_of"));
+}
@@ -0,0 +1,117 @@
+//! Tests for the structured JSON error envelope
+//!
+//! Agent callers opt in with `--format json`, `CNV_ERROR_FORMAT` or
+//! `CommandRegistry::set_error_format`; every failure then becomes one
+//! `OutputEnvelope` error with kind, suggestions and exit code.
+
+use clap_noun_verb::cli::registry::{json_output_requested, CommandRegistry};
+use clap_noun_verb::envelope::{ErrorFormat, OutputEnvelope, StructuredError};
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use std::process::ExitCode;
+
+/// List invoices
+#[verb("list", "invoice")]
+fn list_invoices() -> Result<Vec<String>> {
+    Ok(vec!["inv-1".to_string()])
+}
+
+/// Void an invoice
+///
+/// # Arguments
+/// * `id` - Invoice id
+#[verb("void", "invoice")]
+fn void_invoice(id: String) -> Result<String> {
+    Err(NounVerbError::validation_error("id", id, Some("invoice is already paid")))
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+fn parse_error(argv: &[&str]) -> StructuredError {
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    match registry.build_command().try_get_matches_from(args(argv)) {
+        Ok(_) => StructuredError::new("none", "parsed successfully"),
+        Err(e) => StructuredError::from_clap_error(&e),
+    }
+}
+
+#[test]
+fn test_json_output_request_is_detected_in_raw_args() {
+    // Arrange + Act + Assert
+    assert!(json_output_requested(&args(&["cli", "invoice", "list", "--format", "json"])));
+    assert!(json_output_requested(&args(&["cli", "--format=json-pretty", "invoice"])));
+    assert!(json_output_requested(&args(&["cli", "-o", "json", "invoice", "lst"])));
+    assert!(!json_output_requested(&args(&["cli", "invoice", "list", "--format", "yaml"])));
+    assert!(!json_output_requested(&args(&["cli", "invoice", "--", "--format", "json"])));
+}
+
+#[test]
+fn test_unknown_verb_envelope_has_suggestions() {
+    // Arrange + Act
+    let error = parse_error(&["cli", "invoice", "lst"]);
+
+    // Assert
+    assert_eq!(error.kind, "invalid_subcommand");
+    assert_eq!(error.suggestions, vec!["list".to_string()]);
+    assert_eq!(error.exit_code(), 2);
+}
+
+#[test]
+fn test_unknown_flag_envelope_has_suggestions() {
+    // Arrange + Act
+    let error = parse_error(&["cli", "invoice", "void", "--idd", "7"]);
+
+    // Assert
+    assert_eq!(error.kind, "unknown_argument");
+    assert!(error.suggestions.iter().any(|s| s.contains("--id")), "{:?}", error.suggestions);
+}
+
+#[test]
+fn test_handler_error_envelope_serializes_kind_and_exit_code() -> Result<()> {
+    // Arrange
+    let error = NounVerbError::ValidationFailed("invoice is already paid".to_string());
+
+    // Act
+    let envelope: OutputEnvelope<()> = OutputEnvelope::error(StructuredError::from(&error));
+    let value = serde_json::to_value(&envelope)
+        .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+
+    // Assert
+    assert_eq!(value["status"], "error");
+    assert_eq!(value["error"]["kind"], "validation_failed");
+    assert_eq!(value["error"]["exit_code"], 3);
+    Ok(())
+}
+
+#[test]
+fn test_json_error_mode_keeps_exit_codes() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_error_format(ErrorFormat::Json);
+
+    // Act
+    let usage = registry.run_to_exit_code(args(&["cli", "invoice", "lst"]));
+    let failure = registry.run_to_exit_code(args(&["cli", "invoice", "void", "--id", "7"]));
+    let success = registry.run_to_exit_code(args(&["cli", "invoice", "list"]));
+    registry.set_error_format(ErrorFormat::Text);
+
+    // Assert
+    assert_eq!(usage, ExitCode::from(2));
+    assert_eq!(failure, ExitCode::from(2));
+    assert_eq!(success, ExitCode::SUCCESS);
+}
+
+#[test]
+fn test_error_format_parses_from_str() {
+    // Arrange + Act + Assert
+    assert_eq!("json".parse::<ErrorFormat>(), Ok(ErrorFormat::Json));
+    assert_eq!("AUTO".parse::<ErrorFormat>(), Ok(ErrorFormat::Auto));
+    assert!("xml".parse::<ErrorFormat>().is_err());
+    assert!(ErrorFormat::Json.use_json(false));
+    assert!(ErrorFormat::Text.use_json(true));
+    assert!(!ErrorFormat::Text.use_json(false));
+}
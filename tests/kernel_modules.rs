@@ -0,0 +1,198 @@
+//! Tests for the kernel modules wired into the `kernel` feature
+//!
+//! Exercises the public kernel API end to end: capability contracts, the
+//! grammar DSL, session frames and the framed transport.
+
+#![cfg(feature = "kernel")]
+
+use clap_noun_verb::kernel::concurrent::{FrameQueue, SessionRegistry};
+use clap_noun_verb::kernel::session::SessionId;
+use clap_noun_verb::kernel::simd::{AlignedBuffer, FrameSerializer};
+use clap_noun_verb::kernel::transport::{
+    read_frame, write_frame, FrameFlags, FrameType, InvocationFrame,
+};
+use clap_noun_verb::kernel::typestate::{TypedSession, Unverified};
+use clap_noun_verb::kernel::{
+    CapabilityClass, CapabilityContext, CapabilityContract, Frame, FramePayload, ResourceBand,
+    SafetyProfile, SessionBuilder, StabilityProfile, StreamId,
+};
+use std::sync::Arc;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+#[test]
+fn test_capability_contract_serializes_in_snake_case() -> TestResult {
+    // Arrange
+    let contract = CapabilityContract::read_only();
+
+    // Act
+    let json = serde_json::to_value(&contract)?;
+    let back: CapabilityContract = serde_json::from_value(json.clone())?;
+
+    // Assert
+    assert_eq!(json["capability_class"], "read_only_fs");
+    assert_eq!(json["safety"], "agent_safe");
+    assert_eq!(back, contract);
+    Ok(())
+}
+
+#[test]
+fn test_capability_contract_risk_and_agent_safety() {
+    // Arrange
+    let mut dangerous = CapabilityContract::dangerous();
+    dangerous.safety = SafetyProfile::AgentSafe;
+
+    // Act & Assert
+    assert!(!dangerous.is_agent_safe(), "dangerous contracts are never agent-safe");
+    assert!(
+        CapabilityContract::pure().risk_score() < CapabilityContract::read_write().risk_score()
+    );
+    assert!(dangerous.risk_score() <= 100);
+    assert!(CapabilityContract::pure().is_compatible_with(&CapabilityContract::read_write()));
+    assert!(!CapabilityContract::network().is_compatible_with(&CapabilityContract::read_only()));
+    assert!(ResourceBand::Fast.max_runtime_ms() < ResourceBand::Medium.max_runtime_ms());
+}
+
+#[test]
+fn test_capability_context_permissions() {
+    // Arrange
+    let read_only = CapabilityContext::new(CapabilityContract::read_only());
+    let network = CapabilityContext::new(CapabilityContract::network());
+
+    // Act & Assert
+    assert!(read_only.can_read_fs());
+    assert!(!read_only.can_write_fs());
+    assert!(network.can_access_network());
+    assert!(!network.can_spawn_subprocess());
+}
+
+#[test]
+fn test_grammar_dsl_attaches_capabilities() -> TestResult {
+    // Arrange & Act
+    let grammar = clap_noun_verb::grammar_dsl! {
+        app "ops" version "1.0.0" {
+            noun "backup" help "Backups" {
+                verb "prune" {
+                    capability: ReadWriteFS,
+                    resource: Slow,
+                    safety: HumanReviewRequired,
+                    stability: Preview,
+                    help: "Delete old backups",
+                    args: [
+                        keep: u32 = "Backups to keep"
+                    ]
+                }
+            }
+        }
+    };
+
+    // Assert
+    let verb = grammar
+        .nouns
+        .iter()
+        .flat_map(|noun| noun.verbs.iter())
+        .find(|verb| verb.name == "prune")
+        .ok_or("prune verb missing")?;
+    let capability = verb.capability.as_ref().ok_or("capability missing")?;
+    assert_eq!(capability.capability_class, CapabilityClass::ReadWriteFS);
+    assert_eq!(capability.stability, StabilityProfile::Preview);
+    assert_eq!(verb.arguments.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_typed_session_escalation_policy() {
+    // Arrange
+    let session =
+        TypedSession::<Unverified>::with_name("deploy").verify::<()>(CapabilityContract::pure());
+
+    // Act
+    let denied = TypedSession::<Unverified>::with_name("deploy")
+        .verify::<()>(CapabilityContract::pure())
+        .escalate::<()>(CapabilityContract::dangerous(), "needs root");
+    let escalated = session.escalate::<()>(CapabilityContract::read_only(), "read config");
+
+    // Assert
+    assert!(denied.is_err());
+    assert!(escalated.is_ok_and(|s| s.capability().capability_class == CapabilityClass::ReadOnlyFS));
+}
+
+#[test]
+fn test_session_frames_round_trip_through_serializer() -> TestResult {
+    // Arrange
+    let session = SessionBuilder::new().capability(CapabilityContract::read_only()).build();
+    let frame = Frame {
+        session_id: session.id(),
+        stream_id: StreamId::Logs,
+        sequence: 7,
+        timestamp_ms: 1_700_000_000_000,
+        payload: FramePayload::Log { level: "info".to_string(), message: "ready".to_string() },
+    };
+    let mut serializer = FrameSerializer::new();
+    let mut buffer = AlignedBuffer::with_capacity(1024);
+
+    // Act
+    serializer.serialize(&frame, &mut buffer)?;
+    let decoded = serializer.deserialize(buffer.as_slice())?;
+
+    // Assert
+    assert_eq!(decoded.session_id, session.id());
+    assert_eq!(decoded.stream_id, StreamId::Logs);
+    assert_eq!(decoded.sequence, 7);
+    assert_eq!(decoded.payload, frame.payload);
+    Ok(())
+}
+
+#[test]
+fn test_session_registry_and_frame_queue() -> TestResult {
+    // Arrange
+    let registry = SessionRegistry::new(8);
+    let queue = FrameQueue::new(2);
+    let session = Arc::new(SessionBuilder::new().build());
+    let id = session.id();
+
+    // Act
+    registry.register(Arc::clone(&session));
+    queue
+        .enqueue(Frame {
+            session_id: id,
+            stream_id: StreamId::Stdout,
+            sequence: 0,
+            timestamp_ms: 0,
+            payload: FramePayload::Data { data: serde_json::json!({"ok": true}) },
+        })
+        .map_err(|_| "queue rejected frame")?;
+
+    // Assert
+    assert!(registry.get(&id).is_some());
+    assert!(registry.get(&SessionId::new()).is_none());
+    assert_eq!(queue.dequeue().map(|frame| frame.session_id), Some(id));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transport_frames_round_trip_over_duplex_stream() -> TestResult {
+    // Arrange
+    let (mut client, mut server) = tokio::io::duplex(4096);
+    let frame = InvocationFrame {
+        version: 1,
+        correlation_id: "req-1".to_string(),
+        tenant_id: "tenant".to_string(),
+        agent_id: "agent".to_string(),
+        frame_type: FrameType::Request,
+        payload: br#"{"verb":"status"}"#.to_vec(),
+        sequence_number: 3,
+        flags: FrameFlags { end_of_stream: true, ..FrameFlags::default() },
+    };
+
+    // Act
+    write_frame(&mut client, &frame).await?;
+    let received = read_frame(&mut server).await?;
+
+    // Assert
+    assert_eq!(received.correlation_id, "req-1");
+    assert_eq!(received.frame_type, FrameType::Request);
+    assert_eq!(received.payload, frame.payload);
+    assert!(received.flags.end_of_stream);
+    Ok(())
+}
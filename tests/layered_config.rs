@@ -0,0 +1,160 @@
+//! Tests for layered configuration feeding `#[verb]` arguments
+//!
+//! Values keyed `noun.verb.arg` come from system, user and project files,
+//! a `--profile` section and environment variables, and CLI flags win over all.
+#![cfg(feature = "config-formats")]
+
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::config::{Config, ConfigSource, LayeredConfig};
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use std::sync::Mutex;
+
+static DEPLOYS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Deploy the application
+///
+/// # Arguments
+/// * `region` - Target region
+/// * `replicas` - Number of replicas [default: 1]
+#[verb("app", "deploy")]
+fn deploy_app(region: String, replicas: u32) -> Result<String> {
+    let summary = format!("{} x{}", region, replicas);
+    DEPLOYS.lock().unwrap_or_else(|e| e.into_inner()).push(summary.clone());
+    Ok(summary)
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+fn config(json: &str) -> Result<Config> {
+    Config::from_json(json)
+}
+
+/// Layers used by every test: system sets a region, project raises replicas
+fn layered() -> Result<LayeredConfig> {
+    Ok(LayeredConfig::new("cnvcfgtest")
+        .with_config(ConfigSource::System, config(r#"{"deploy": {"app": {"region": "eu"}}}"#)?)
+        .with_config(
+            ConfigSource::Project,
+            config(
+                r#"{"deploy": {"app": {"replicas": 2}},
+                    "profiles": {"prod": {"deploy": {"app": {"replicas": 9}}}}}"#,
+            )?,
+        ))
+}
+
+/// Run `argv` with the layered configuration, returning the recorded deployment
+fn deploy_with(argv: &[&str], env: Option<(&str, &str)>) -> Result<String> {
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_config(layered()?);
+    if let Some((name, value)) = env {
+        std::env::set_var(name, value);
+    }
+    let result = registry.run(args(argv));
+    if let Some((name, _)) = env {
+        std::env::remove_var(name);
+    }
+    result?;
+    DEPLOYS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .pop()
+        .ok_or_else(|| NounVerbError::execution_error("handler did not run"))
+}
+
+#[test]
+fn test_config_files_satisfy_required_args_and_override_defaults() -> Result<()> {
+    // Act
+    let deployed = deploy_with(&["cli", "deploy", "app"], None)?;
+
+    // Assert
+    assert_eq!(deployed, "eu x2");
+    Ok(())
+}
+
+#[test]
+fn test_profile_env_and_cli_precedence() -> Result<()> {
+    // Act
+    let profiled = deploy_with(&["cli", "deploy", "app", "--profile", "prod"], None)?;
+    let from_env = deploy_with(
+        &["cli", "deploy", "app", "--profile=prod"],
+        Some(("CNVCFGTEST_DEPLOY_APP_REPLICAS", "4")),
+    )?;
+    let from_cli = deploy_with(
+        &["cli", "--profile", "prod", "deploy", "app", "--replicas", "7", "--region", "us"],
+        Some(("CNVCFGTEST_DEPLOY_APP_REPLICAS", "4")),
+    )?;
+
+    // Assert
+    assert_eq!(profiled, "eu x9");
+    assert_eq!(from_env, "eu x4");
+    assert_eq!(from_cli, "us x7");
+    Ok(())
+}
+
+#[test]
+fn test_effective_config_reports_origins() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_config(layered()?);
+
+    // Act
+    let plain = registry.effective_config(None);
+    let profiled = registry.effective_config(Some("prod"));
+    let shown = registry.run(args(&["cli", "config", "show", "--origin"]));
+
+    // Assert
+    shown?;
+    assert_eq!(plain["deploy.app.region"].origin, ConfigSource::System);
+    assert_eq!(plain["deploy.app.replicas"].origin, ConfigSource::Project);
+    assert_eq!(plain["deploy.app.replicas"].value, serde_json::json!(2));
+    assert_eq!(profiled["deploy.app.replicas"].origin, ConfigSource::Profile);
+    assert_eq!(profiled["deploy.app.replicas"].location.as_deref(), Some("prod"));
+    Ok(())
+}
+
+#[test]
+fn test_builtin_default_origin_without_config_values() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_config(LayeredConfig::new("cnvcfgtest-empty"));
+
+    // Act
+    let effective = registry.effective_config(None);
+
+    // Assert
+    assert_eq!(effective["deploy.app.replicas"].origin, ConfigSource::Default);
+    assert!(!effective.contains_key("deploy.app.region"));
+    Ok(())
+}
+
+#[test]
+fn test_discover_reads_user_config_from_xdg_dir() -> Result<()> {
+    // Arrange
+    let dir = tempfile::tempdir().map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let app_dir = dir.path().join("cnvcfgxdg");
+    std::fs::create_dir_all(&app_dir)
+        .and_then(|()| {
+            std::fs::write(app_dir.join("config.toml"), "[deploy.app]\nregion = \"ap\"\n")
+        })
+        .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let registry = CommandRegistry::get();
+    let _guard = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    std::env::set_var("XDG_CONFIG_HOME", dir.path());
+    let discovered = LayeredConfig::discover("cnvcfgxdg");
+    std::env::remove_var("XDG_CONFIG_HOME");
+    let region = discovered?.resolve("deploy.app.region", None);
+
+    // Assert
+    let region = region.ok_or_else(|| NounVerbError::execution_error("region not found"))?;
+    assert_eq!(region.value, serde_json::json!("ap"));
+    assert_eq!(region.origin, ConfigSource::User);
+    Ok(())
+}
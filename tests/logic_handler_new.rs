@@ -47,7 +47,7 @@ fn test_handler_input_creation() {
     let args = HashMap::new();
     let opts = HashMap::new();
 
-    let input = HandlerInput { args, opts, context };
+    let input = HandlerInput::new(context).with_args(args).with_opts(opts);
 
     assert_eq!(input.context.verb, "status");
     assert_eq!(input.context.noun, Some("services".to_string()));
@@ -100,7 +100,7 @@ fn test_handler_input_with_args_and_opts() {
 
     let context = HandlerContext::new("status");
 
-    let input = HandlerInput { args: args.clone(), opts: opts.clone(), context };
+    let input = HandlerInput::new(context).with_args(args.clone()).with_opts(opts.clone());
 
     assert_eq!(input.args.get("service"), Some(&"api".to_string()));
     assert_eq!(input.opts.get("verbose"), Some(&"true".to_string()));
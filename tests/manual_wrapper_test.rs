@@ -20,15 +20,9 @@ fn it_compiles() {
     let mut args = std::collections::HashMap::new();
     args.insert("opt".to_string(), "test".to_string());
 
-    let input = HandlerInput {
-        args,
-        opts: std::collections::HashMap::new(),
-        context: clap_noun_verb::logic::HandlerContext {
-            noun: Some("test".to_string()),
-            verb: "test".to_string(),
-            data: std::collections::HashMap::new(),
-        },
-    };
+    let input =
+        HandlerInput::new(clap_noun_verb::logic::HandlerContext::new("test").with_noun("test"))
+            .with_args(args);
 
     let _result = test_opt_wrapper(input);
 }
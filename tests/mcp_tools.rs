@@ -0,0 +1,187 @@
+//! Tests for exposing `#[verb]` commands as MCP tools
+//!
+//! `tools/list` describes every registered verb with a JSON Schema built from
+//! its arguments and `tools/call` runs the verb through the registry.
+
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb::mcp::McpServer;
+use clap_noun_verb_macros::verb;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+#[derive(Serialize)]
+struct Scaled {
+    name: String,
+    replicas: u32,
+    strategy: String,
+    force: bool,
+    tags: Vec<String>,
+}
+
+/// Scale a deployment
+///
+/// # Arguments
+/// * `name` - Deployment to scale
+/// * `replicas` - Desired replica count [default: 1]
+/// * `strategy` - Rollout strategy
+/// * `force` - Skip safety checks
+/// * `tags` - Labels to apply
+#[verb("scale", "deployment")]
+fn scale_deployment(
+    name: String,
+    replicas: u32,
+    #[arg(value_parser = clap::builder::PossibleValuesParser::new(["fast", "safe"]))]
+    strategy: Option<String>,
+    force: bool,
+    tags: Vec<String>,
+) -> Result<Scaled> {
+    let strategy = strategy.unwrap_or_else(|| "safe".to_string());
+    Ok(Scaled { name, replicas, strategy, force, tags })
+}
+
+/// Fail on purpose
+#[verb("fail", "deployment")]
+fn fail_deployment() -> Result<String> {
+    Err(NounVerbError::execution_error("deployment unavailable"))
+}
+
+fn scale_tool(tools: &Value) -> Option<&Value> {
+    tools.as_array()?.iter().find(|tool| tool["name"] == "deployment_scale")
+}
+
+/// Send newline-delimited requests through the server and collect responses
+fn exchange(requests: &[Value]) -> Result<Vec<Value>> {
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    let input: String = requests.iter().map(|r| format!("{}\n", r)).collect();
+    let mut output = Vec::new();
+    McpServer::new(&registry, "deployctl").serve(input.as_bytes(), &mut output)?;
+    String::from_utf8_lossy(&output)
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line).map_err(|e| NounVerbError::execution_error(e.to_string()))
+        })
+        .collect()
+}
+
+#[test]
+fn test_tools_list_builds_input_schema_from_arguments() -> Result<()> {
+    // Act
+    let responses = exchange(&[json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"})])?;
+
+    // Assert
+    let tool = scale_tool(&responses[0]["result"]["tools"])
+        .ok_or_else(|| NounVerbError::execution_error("deployment_scale not listed"))?;
+    let schema = &tool["inputSchema"];
+    assert_eq!(tool["description"], "Scale a deployment");
+    assert_eq!(schema["required"], json!(["name", "tags"]));
+    assert_eq!(schema["properties"]["name"]["type"], "string");
+    assert_eq!(schema["properties"]["name"]["description"], "Deployment to scale");
+    assert_eq!(schema["properties"]["replicas"]["type"], "integer");
+    assert_eq!(schema["properties"]["replicas"]["minimum"], 0);
+    assert_eq!(schema["properties"]["replicas"]["default"], 1);
+    assert_eq!(schema["properties"]["strategy"]["enum"], json!(["fast", "safe"]));
+    assert_eq!(schema["properties"]["force"]["type"], "boolean");
+    assert_eq!(schema["properties"]["tags"]["type"], "array");
+    assert_eq!(schema["properties"]["tags"]["items"]["type"], "string");
+    Ok(())
+}
+
+#[test]
+fn test_tools_call_dispatches_to_verb() -> Result<()> {
+    // Act
+    let responses = exchange(&[
+        json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {
+            "name": "deployment_scale",
+            "arguments": {"name": "-web", "replicas": 3, "strategy": "fast", "force": true,
+                          "tags": ["a", "b"]}
+        }}),
+    ])?;
+
+    // Assert
+    assert_eq!(responses.len(), 2, "notifications get no response");
+    assert_eq!(responses[0]["result"]["serverInfo"]["name"], "deployctl");
+    assert!(responses[0]["result"]["capabilities"]["tools"].is_object());
+    let result = &responses[1]["result"];
+    assert_eq!(result["isError"], false);
+    assert_eq!(
+        result["structuredContent"],
+        json!({"name": "-web", "replicas": 3, "strategy": "fast", "force": true, "tags": ["a", "b"]})
+    );
+    Ok(())
+}
+
+#[test]
+fn test_repeated_tools_calls_parse_each_call_independently() -> Result<()> {
+    // Arrange
+    let call = |id: u32, arguments: Value| {
+        json!({"jsonrpc": "2.0", "id": id, "method": "tools/call", "params": {
+            "name": "deployment_scale", "arguments": arguments
+        }})
+    };
+
+    // Act
+    let responses = exchange(&[
+        call(1, json!({"name": "web", "replicas": 3, "force": true, "tags": ["a"]})),
+        call(2, json!({"name": "api", "replicas": 1, "tags": ["b"]})),
+    ])?;
+
+    // Assert
+    assert_eq!(
+        responses[0]["result"]["structuredContent"],
+        json!({"name": "web", "replicas": 3, "strategy": "safe", "force": true, "tags": ["a"]})
+    );
+    assert_eq!(
+        responses[1]["result"]["structuredContent"],
+        json!({"name": "api", "replicas": 1, "strategy": "safe", "force": false, "tags": ["b"]})
+    );
+    Ok(())
+}
+
+#[test]
+fn test_tools_call_reports_errors() -> Result<()> {
+    // Act
+    let responses = exchange(&[
+        json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": {
+            "name": "deployment_scale", "arguments": {"replicas": 2}
+        }}),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {
+            "name": "deployment_scale", "arguments": {"name": "web", "strategy": "yolo"}
+        }}),
+        json!({"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {
+            "name": "deployment_fail"
+        }}),
+        json!({"jsonrpc": "2.0", "id": 4, "method": "resources/list"}),
+    ])?;
+
+    // Assert
+    assert_eq!(responses[0]["result"]["isError"], true);
+    assert_eq!(responses[1]["result"]["isError"], true);
+    assert_eq!(responses[2]["result"]["isError"], true);
+    assert!(responses[2]["result"]["content"][0]["text"]
+        .as_str()
+        .is_some_and(|text| text.contains("deployment unavailable")));
+    assert_eq!(responses[3]["error"]["code"], -32601);
+    Ok(())
+}
+
+#[test]
+fn test_serve_mcp_command_is_opt_in() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let without = registry.build_command();
+    registry.set_serve_mcp(true);
+    let with = registry.build_command();
+    registry.set_serve_mcp(false);
+
+    // Assert
+    assert!(without.find_subcommand("serve").is_none());
+    let serve = with.find_subcommand("serve");
+    assert!(serve.and_then(|serve| serve.find_subcommand("mcp")).is_some());
+}
@@ -0,0 +1,164 @@
+//! Tests for running the middleware pipeline around `#[verb]` handlers
+//!
+//! Middleware registered on the command registry must see every invocation:
+//! `before` with the noun/verb path and parsed args, `after` with the outcome,
+//! and `handle_error` with the chance to turn a failure into output.
+
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb::middleware::{
+    Middleware, MiddlewarePipeline, MiddlewareRequest, MiddlewareResponse,
+};
+use clap_noun_verb_macros::verb;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+static VERIFIES: AtomicUsize = AtomicUsize::new(0);
+
+/// Scan a target
+///
+/// # Arguments
+/// * `target` - What to scan
+#[verb("scan", "audit")]
+fn scan_target(target: String) -> Result<String> {
+    Ok(target)
+}
+
+/// Verify the audit trail
+#[verb("verify", "audit")]
+fn verify_trail() -> Result<bool> {
+    VERIFIES.fetch_add(1, Ordering::SeqCst);
+    Ok(true)
+}
+
+/// Purge the audit log
+#[verb("purge", "audit")]
+fn purge_log() -> Result<String> {
+    Err(NounVerbError::execution_error("audit log is locked"))
+}
+
+/// Records every hook call; optionally rejects requests or recovers errors
+#[derive(Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<String>>>,
+    reject: bool,
+    recover: bool,
+}
+
+impl Recorder {
+    fn events(&self) -> Arc<Mutex<Vec<String>>> {
+        Arc::clone(&self.events)
+    }
+
+    fn record(&self, event: String) {
+        self.events.lock().unwrap_or_else(|e| e.into_inner()).push(event);
+    }
+}
+
+impl Middleware for Recorder {
+    fn name(&self) -> &str {
+        "recorder"
+    }
+
+    fn before(&self, request: &MiddlewareRequest) -> Result<bool> {
+        self.record(format!(
+            "before {} noun={} verb={} args={}",
+            request.command(),
+            request.noun().unwrap_or_default(),
+            request.verb().unwrap_or_default(),
+            request.args().join(",")
+        ));
+        Ok(!self.reject)
+    }
+
+    fn after(&self, response: &MiddlewareResponse) -> Result<()> {
+        self.record(format!("after {} {}", response.is_success(), response.message()));
+        Ok(())
+    }
+
+    fn handle_error(&self, error: &NounVerbError) -> Result<Option<String>> {
+        self.record(format!("error {}", error));
+        Ok(self.recover.then(|| "recovered".to_string()))
+    }
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+/// Run `argv` with `recorder` as the only middleware, returning the result and events
+fn run_with(recorder: Recorder, argv: &[&str]) -> (Result<()>, Vec<String>) {
+    let events = recorder.events();
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_middleware(MiddlewarePipeline::new().add(Box::new(recorder)));
+    let result = registry.run(args(argv));
+    registry.set_middleware(MiddlewarePipeline::new());
+    let events = events.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    (result, events)
+}
+
+#[test]
+fn test_middleware_wraps_successful_verb() -> Result<()> {
+    // Arrange
+    let recorder = Recorder::default();
+
+    // Act
+    let (result, events) = run_with(recorder, &["cli", "audit", "scan", "--target", "db"]);
+
+    // Assert
+    result?;
+    assert_eq!(
+        events,
+        vec![
+            "before audit scan noun=audit verb=scan args=target=db".to_string(),
+            "after true \"db\"".to_string(),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rejecting_middleware_skips_handler() {
+    // Arrange
+    let recorder = Recorder { reject: true, ..Recorder::default() };
+
+    // Act
+    let (result, events) = run_with(recorder, &["cli", "audit", "verify"]);
+
+    // Assert
+    assert!(matches!(result, Err(NounVerbError::MiddlewareError(_))));
+    assert_eq!(events.len(), 1);
+    assert_eq!(VERIFIES.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_handle_error_recovers_failed_verb() -> Result<()> {
+    // Arrange
+    let recorder = Recorder { recover: true, ..Recorder::default() };
+
+    // Act
+    let (result, events) = run_with(recorder, &["cli", "audit", "purge"]);
+
+    // Assert
+    result?;
+    assert_eq!(events[1], "error Command execution failed: audit log is locked");
+    assert_eq!(events[2], "after true recovered");
+    Ok(())
+}
+
+#[test]
+fn test_unrecovered_error_reaches_after_and_caller() {
+    // Arrange
+    let recorder = Recorder::default();
+
+    // Act
+    let (result, events) = run_with(recorder, &["cli", "audit", "purge"]);
+
+    // Assert
+    assert!(matches!(result, Err(NounVerbError::ExecutionError { .. })));
+    assert_eq!(
+        events.last().map(String::as_str),
+        Some("after false Command execution failed: audit log is locked")
+    );
+}
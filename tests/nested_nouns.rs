@@ -0,0 +1,122 @@
+//! Tests for nested nouns (multi-level command trees) via `#[verb]`
+//!
+//! Dotted noun paths such as `cloud.vm.snapshot` must produce the command tree
+//! `cli cloud vm snapshot <verb>`, with implicit parents and full-path routing.
+
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use serde::Serialize;
+use std::sync::Mutex;
+
+static SNAPSHOTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+#[derive(Serialize, Debug)]
+struct Snapshot {
+    vm: String,
+}
+
+/// Create a VM snapshot
+#[verb("create", noun = "cloud.vm.snapshot")]
+fn create_snapshot(vm: String) -> Result<Snapshot> {
+    SNAPSHOTS.lock().unwrap_or_else(|e| e.into_inner()).push(vm.clone());
+    Ok(Snapshot { vm })
+}
+
+/// List VMs
+#[verb("list", "cloud.vm")]
+fn list_vms() -> Result<Vec<String>> {
+    Ok(vec!["vm-1".to_string()])
+}
+
+/// Show cloud status
+#[verb("status", "cloud")]
+fn cloud_status() -> Result<String> {
+    Ok("ok".to_string())
+}
+
+fn find_path<'a>(cmd: &'a clap::Command, path: &[&str]) -> Option<&'a clap::Command> {
+    path.iter().try_fold(cmd, |cmd, name| cmd.find_subcommand(name))
+}
+
+#[test]
+fn test_nested_nouns_build_full_tree() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let cmd = registry.build_command();
+
+    // Assert
+    assert!(find_path(&cmd, &["cloud", "status"]).is_some());
+    assert!(find_path(&cmd, &["cloud", "vm", "list"]).is_some());
+    assert!(find_path(&cmd, &["cloud", "vm", "snapshot", "create"]).is_some());
+    assert!(cmd.find_subcommand("cloud.vm").is_none(), "dotted paths must not leak into names");
+}
+
+#[test]
+fn test_implicit_parent_nouns_are_registered() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let top_level = registry.get_sub_nouns("");
+    let under_cloud = registry.get_sub_nouns("cloud");
+
+    // Assert
+    assert!(top_level.contains(&"cloud"));
+    assert!(!top_level.contains(&"cloud.vm"));
+    assert_eq!(under_cloud, vec!["cloud.vm"]);
+    assert_eq!(registry.get_sub_nouns("cloud.vm"), vec!["cloud.vm.snapshot"]);
+}
+
+#[test]
+fn test_run_routes_full_noun_path() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    let args = ["cli", "cloud", "vm", "snapshot", "create", "--vm", "web-1"];
+
+    // Act
+    registry.run(args.iter().map(|a| a.to_string()).collect())?;
+
+    // Assert
+    let snapshots = SNAPSHOTS.lock().unwrap_or_else(|e| e.into_inner());
+    assert_eq!(snapshots.as_slice(), ["web-1".to_string()]);
+    Ok(())
+}
+
+#[test]
+fn test_execute_verb_by_dotted_path() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    let input = clap_noun_verb::logic::HandlerInput::new(
+        clap_noun_verb::logic::HandlerContext::new("list").with_noun("cloud.vm"),
+    );
+
+    // Act
+    let output = registry.execute_verb("cloud.vm", "list", input)?;
+
+    // Assert
+    assert_eq!(output.data, serde_json::json!(["vm-1"]));
+    Ok(())
+}
+
+#[test]
+fn test_unknown_nested_verb_is_rejected() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let result = registry
+        .build_command()
+        .try_get_matches_from(["cli", "cloud", "vm", "snapshot", "delete"])
+        .map_err(|e| NounVerbError::argument_error(e.to_string()));
+
+    // Assert
+    assert!(result.is_err());
+}
@@ -0,0 +1,134 @@
+//! Tests for the framework-injected global `--format` option
+//!
+//! These tests verify that every `#[verb]` receives `--format`/`-o`, that the
+//! `CNV_FORMAT` environment variable acts as an override, and that the chosen
+//! formatter renders `HandlerOutput.data`.
+
+use clap_noun_verb::cli::registry::{
+    resolve_output_format, CommandRegistry, FORMAT_ARG_ID, FORMAT_ENV_VAR,
+};
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb::format::OutputFormat;
+use clap_noun_verb::logic::HandlerOutput;
+use clap_noun_verb_macros::verb;
+use serde::Serialize;
+use serial_test::serial;
+
+#[derive(Serialize, Debug)]
+struct Server {
+    name: String,
+    port: u16,
+}
+
+/// List servers
+#[verb("list", "servers")]
+fn list_servers() -> Result<Vec<Server>> {
+    Ok(vec![Server { name: "web".to_string(), port: 80 }])
+}
+
+/// Export servers in a verb-specific format
+#[verb("export", "servers")]
+fn export_servers(format: Option<String>) -> Result<String> {
+    Ok(format.unwrap_or_default())
+}
+
+fn parse(args: &[&str]) -> Result<clap::ArgMatches> {
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry
+        .build_command()
+        .try_get_matches_from(args)
+        .map_err(|e| NounVerbError::argument_error(e.to_string()))
+}
+
+fn verb_levels(matches: &clap::ArgMatches) -> Result<OutputFormat> {
+    let (_, noun_matches) =
+        matches.subcommand().ok_or_else(|| NounVerbError::invalid_structure("missing noun"))?;
+    let (_, verb_matches) = noun_matches
+        .subcommand()
+        .ok_or_else(|| NounVerbError::invalid_structure("missing verb"))?;
+    resolve_output_format(&[verb_matches, noun_matches, matches])
+}
+
+#[test]
+#[serial]
+fn test_format_flag_on_verb() -> Result<()> {
+    // Arrange + Act
+    let matches = parse(&["cli", "servers", "list", "-o", "table"])?;
+
+    // Assert
+    assert_eq!(verb_levels(&matches)?, OutputFormat::Table);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_format_flag_before_noun() -> Result<()> {
+    // Arrange + Act
+    let matches = parse(&["cli", "--format", "yaml", "servers", "list"])?;
+
+    // Assert
+    assert_eq!(verb_levels(&matches)?, OutputFormat::Yaml);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_default_format_is_compact_json() -> Result<()> {
+    // Arrange + Act
+    let matches = parse(&["cli", "servers", "list"])?;
+
+    // Assert
+    assert_eq!(verb_levels(&matches)?, OutputFormat::Json);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_env_var_overrides_default_but_not_flag() -> Result<()> {
+    // Arrange
+    std::env::set_var(FORMAT_ENV_VAR, "plain");
+
+    // Act
+    let from_env = verb_levels(&parse(&["cli", "servers", "list"])?);
+    let from_flag = verb_levels(&parse(&["cli", "servers", "list", "--format", "tsv"])?);
+    std::env::remove_var(FORMAT_ENV_VAR);
+
+    // Assert
+    assert_eq!(from_env?, OutputFormat::Plain);
+    assert_eq!(from_flag?, OutputFormat::Tsv);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_verb_owning_format_keeps_its_argument() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    let cmd = registry.build_command();
+
+    // Act
+    let export_cmd = cmd.find_subcommand("servers").and_then(|noun| noun.find_subcommand("export"));
+
+    // Assert
+    assert!(export_cmd.is_some(), "export verb should be registered");
+    let args: Vec<_> = export_cmd.into_iter().flat_map(|c| c.get_arguments()).collect();
+    assert!(args.iter().any(|a| a.get_id() == "format"));
+    assert!(!args.iter().any(|a| a.get_id() == FORMAT_ARG_ID));
+}
+
+#[test]
+fn test_handler_output_renders_selected_format() -> Result<()> {
+    // Arrange
+    let output = HandlerOutput::from_data(vec![Server { name: "web".to_string(), port: 80 }])?;
+
+    // Act
+    let json = output.render(OutputFormat::Json)?;
+    let table = output.render(OutputFormat::Table)?;
+
+    // Assert
+    assert_eq!(json, r#"[{"name":"web","port":80}]"#);
+    assert!(table.starts_with("name\tport\n"));
+    Ok(())
+}
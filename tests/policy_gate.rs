@@ -0,0 +1,170 @@
+//! Tests for evaluating a policy engine before every `#[verb]`
+//!
+//! Deny decisions fail with a structured error, rewrites change the arguments
+//! the handler sees and redirects run another verb.
+#![cfg(feature = "autonomic")]
+
+use clap_noun_verb::autonomic::RuleBasedPolicyEngine;
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::envelope::StructuredError;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use serde_json::json;
+
+/// Drop a table
+///
+/// # Arguments
+/// * `name` - Table to drop
+#[verb("drop", "table")]
+fn drop_table(name: String) -> Result<String> {
+    Ok(format!("dropped {}", name))
+}
+
+/// Archive a table
+///
+/// # Arguments
+/// * `name` - Table to archive
+/// * `days` - Days to keep the archive
+#[verb("archive", "table")]
+fn archive_table(name: String, days: Option<u32>) -> Result<String> {
+    Ok(format!("archived {} for {} days", name, days.unwrap_or(30)))
+}
+
+/// Vacuum a table
+///
+/// # Arguments
+/// * `name` - Table to vacuum
+/// * `workers` - Parallel workers
+#[verb("vacuum", "table")]
+fn vacuum_table(name: String, workers: Option<u32>) -> Result<String> {
+    Ok(format!("vacuumed {} with {} workers", name, workers.unwrap_or(1)))
+}
+
+/// Run a tool call with `policy` installed as the registry's engine
+fn call_with_policy(
+    policy: serde_json::Value,
+    tool: &str,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value> {
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_policy_engine(RuleBasedPolicyEngine::from_value(policy)?);
+    registry.call_tool(tool, &arguments).map(|output| output.data)
+}
+
+#[test]
+fn test_deny_produces_structured_error() -> Result<()> {
+    // Arrange
+    let policy = json!({"rules": [{
+        "name": "no-drop",
+        "conditions": [{"type": "command", "pattern": "table.drop"}],
+        "action": {"type": "deny", "reason": "Tables are append-only",
+                   "suggestion": "table archive"}
+    }]});
+
+    // Act
+    let result = call_with_policy(policy, "table_drop", json!({"name": "users"}));
+
+    // Assert
+    let Err(error) = result else {
+        return Err(NounVerbError::execution_error("expected a policy denial"));
+    };
+    assert!(matches!(
+        &error,
+        NounVerbError::PolicyDenied { command, reason, suggestion }
+            if command == "table drop"
+                && reason == "Tables are append-only"
+                && suggestion.as_deref() == Some("table archive")
+    ));
+    let structured = StructuredError::from(&error);
+    assert_eq!(structured.kind, "policy_denied");
+    assert_eq!(structured.exit_code(), 5);
+    assert_eq!(structured.suggestions, vec!["table archive"]);
+    Ok(())
+}
+
+#[test]
+fn test_rewrite_changes_handler_arguments() -> Result<()> {
+    // Arrange
+    let policy = json!({"rules": [{
+        "name": "cap-workers",
+        "conditions": [{"type": "command", "pattern": "table.vacuum"}],
+        "action": {"type": "rewrite", "args": {"workers": 2}}
+    }]});
+
+    // Act
+    let data = call_with_policy(policy, "table_vacuum", json!({"name": "users", "workers": 16}))?;
+
+    // Assert
+    assert_eq!(data, json!("vacuumed users with 2 workers"));
+    Ok(())
+}
+
+#[test]
+fn test_redirect_runs_other_verb_with_carried_arguments() -> Result<()> {
+    // Arrange
+    let policy = json!({"rules": [{
+        "name": "archive-instead",
+        "conditions": [{"type": "command", "pattern": "table.drop"}],
+        "action": {"type": "redirect", "noun": "table", "verb": "archive", "args": {"days": 7}}
+    }]});
+
+    // Act
+    let data = call_with_policy(policy, "table_drop", json!({"name": "users"}))?;
+
+    // Assert
+    assert_eq!(data, json!("archived users for 7 days"));
+    Ok(())
+}
+
+#[test]
+fn test_rules_match_environment() -> Result<()> {
+    // Arrange
+    let policy = json!({"rules": [{
+        "name": "prod-read-only",
+        "conditions": [{"type": "environment", "environment": "production"},
+                       {"type": "command", "pattern": "table.vacuum"}],
+        "action": {"type": "deny", "reason": "Read-only in production"}
+    }]});
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_policy_engine(RuleBasedPolicyEngine::from_value(policy)?);
+    let arguments = json!({"name": "users"});
+
+    // Act
+    let staging = registry.call_tool("table_vacuum", &arguments);
+    std::env::set_var("CNV_ENVIRONMENT", "production");
+    let production = registry.call_tool("table_vacuum", &arguments);
+    std::env::remove_var("CNV_ENVIRONMENT");
+
+    // Assert
+    assert!(staging.is_ok());
+    assert!(matches!(production, Err(NounVerbError::PolicyDenied { .. })));
+    Ok(())
+}
+
+#[cfg(feature = "config-formats")]
+#[test]
+fn test_policy_loaded_from_yaml_file() -> Result<()> {
+    // Arrange
+    let dir = tempfile::tempdir().map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let path = dir.path().join("policy.yaml");
+    std::fs::write(
+        &path,
+        "name: ops\nrules:\n  - name: no-drop\n    conditions:\n      - { type: command, pattern: table.drop }\n    action: { type: deny, reason: frozen }\n",
+    )
+    .map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let engine = RuleBasedPolicyEngine::from_file(&path)?;
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_policy_engine(engine);
+
+    // Act
+    let result = registry.run(vec!["app".into(), "table".into(), "drop".into(), "--name=x".into()]);
+
+    // Assert
+    assert!(
+        matches!(result, Err(NounVerbError::PolicyDenied { reason, .. }) if reason == "frozen")
+    );
+    Ok(())
+}
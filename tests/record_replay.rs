@@ -0,0 +1,195 @@
+//! Tests for recording real invocations and replaying them as golden tests
+//!
+//! A shell script stands in for the CLI binary: invocations of it are
+//! recorded as session log frames, the script is changed, and the replay
+//! reports the resulting `FrameDelta` and `TimingDrift`.
+
+#![cfg(all(feature = "kernel", unix))]
+
+use clap_noun_verb::cli::registry::{record_requested, replay_requested, RecordRequest};
+use clap_noun_verb::kernel::recording::{
+    read_recording, run_invocation, InvocationRecorder, RecordedInvocation, ReplayRunner,
+    ENV_ALLOWLIST,
+};
+use clap_noun_verb::kernel::session_log::{ExitCodeClass, ResultFrame};
+use clap_noun_verb::kernel::test_harness::assert_replay_matches;
+use std::path::{Path, PathBuf};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const SHELL: &str = "/bin/sh";
+
+/// Record `sh <script> <args>` with `stdin` into `recording`
+fn record(
+    recorder: &InvocationRecorder,
+    script: &Path,
+    verb: &str,
+    args: &[&str],
+    stdin: Option<&str>,
+) -> Result<(), String> {
+    let mut argv = vec!["sh".to_string(), script.display().to_string()];
+    argv.extend(args.iter().map(|arg| arg.to_string()));
+    let mut invocation = RecordedInvocation::new(argv).with_env("CNV_TENANT", "acme");
+    invocation.stdin = stdin.map(str::to_string);
+    let outcome = run_invocation(Path::new(SHELL), &invocation, ENV_ALLOWLIST, false)?;
+    recorder.append("script", verb, &invocation, &outcome)?;
+    Ok(())
+}
+
+/// Directory with `app.sh` containing `body` and an empty recording next to it
+fn workspace(body: &str) -> Result<(tempfile::TempDir, PathBuf, PathBuf), std::io::Error> {
+    let dir = tempfile::tempdir()?;
+    let script = dir.path().join("app.sh");
+    std::fs::write(&script, body)?;
+    let recording = dir.path().join("calls.jsonl");
+    Ok((dir, script, recording))
+}
+
+#[test]
+fn test_recorded_invocations_replay_unchanged() -> TestResult {
+    // Arrange
+    let (_dir, script, recording) = workspace(
+        "case \"$1\" in\n  greet) echo \"hello $CNV_TENANT\"; echo warming >&2 ;;\n  \
+         upload) cat; echo missing >&2; exit 4 ;;\nesac\n",
+    )?;
+    let recorder = InvocationRecorder::new(&recording);
+    record(&recorder, &script, "greet", &["greet"], None)?;
+    record(&recorder, &script, "upload", &["upload"], Some("payload\n"))?;
+
+    // Act
+    let frames = read_recording(&recording)?;
+    let report = assert_replay_matches(SHELL, &recording)?;
+
+    // Assert
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].capability_id, "script.greet");
+    assert_eq!(frames[0].env_vars.get("CNV_TENANT").map(String::as_str), Some("acme"));
+    assert_eq!(frames[0].exit_code_class, ExitCodeClass::Success);
+    assert!(matches!(
+        &frames[0].output_result,
+        ResultFrame::Success(output) if output["stdout"] == "hello acme\n"
+            && output["stderr"] == "warming\n"
+    ));
+    assert_eq!(frames[1].input_args["stdin"], "payload\n");
+    assert_eq!(frames[1].exit_code_class, ExitCodeClass::NotFound);
+    assert!(matches!(
+        &frames[1].output_result,
+        ResultFrame::Error(error) if error.error_message == "missing\n"
+            && error.error_details.as_ref().is_some_and(|d| d["stdout"] == "payload\n")
+    ));
+    assert_eq!(frames[1].metadata.session_id, frames[0].metadata.session_id);
+    assert_eq!(frames[1].metadata.sequence_number, 2);
+    assert_eq!(frames[1].metadata.parent_frame_hash.as_ref(), Some(&frames[0].content_hash));
+    assert_eq!(report.outcomes.len(), 2);
+    assert!(report.outcomes.iter().all(|outcome| !outcome.delta.outcome_changed));
+    assert!(report.outcomes.iter().all(|outcome| outcome.timing().is_some()));
+    Ok(())
+}
+
+#[test]
+fn test_replay_reports_changed_outcome() -> TestResult {
+    // Arrange
+    let (_dir, script, recording) = workspace("echo \"count: 3\"\n")?;
+    record(&InvocationRecorder::new(&recording), &script, "count", &[], None)?;
+    std::fs::write(&script, "echo \"count: 4\"\necho gone >&2\nexit 4\n")?;
+
+    // Act
+    let report = ReplayRunner::new(SHELL).replay_file(&recording)?;
+    let asserted = assert_replay_matches(SHELL, &recording);
+
+    // Assert
+    let outcome = &report.outcomes[0];
+    assert!(!report.passed());
+    assert!(outcome.delta.outcome_changed);
+    assert!(!outcome.result.outcome_match);
+    assert_eq!((outcome.original_exit_code, outcome.replayed_exit_code), (0, 4));
+    assert_eq!(
+        outcome.mismatches,
+        vec![
+            "exit code 0 -> 4".to_string(),
+            "stdout line 1: expected \"count: 3\", got \"count: 4\"".to_string(),
+            "stderr line 1: expected \"<end>\", got \"gone\"".to_string(),
+        ]
+    );
+    let Err(rendered) = asserted else {
+        return Err("expected the replay to fail".into());
+    };
+    assert!(rendered.contains("FAIL #1 sh"));
+    assert!(rendered.contains("1 invocations replayed, 1 failed"));
+    Ok(())
+}
+
+#[test]
+fn test_replay_reports_timing_drift() -> TestResult {
+    // Arrange
+    let (_dir, script, recording) = workspace("echo done\n")?;
+    record(&InvocationRecorder::new(&recording), &script, "run", &[], None)?;
+    std::fs::write(&script, "sleep 0.3\necho done\n")?;
+
+    // Act
+    let lenient = ReplayRunner::new(SHELL).replay_file(&recording)?;
+    let strict = ReplayRunner::new(SHELL).with_max_drift_percent(50.0).replay_file(&recording)?;
+
+    // Assert
+    assert!(lenient.passed());
+    let outcome = &strict.outcomes[0];
+    let timing = outcome.timing().ok_or("expected a timing drift")?;
+    assert!(timing.replayed_ms >= 300);
+    assert!(timing.drift_percent > 50.0);
+    assert!(outcome.delta.timing_delta_ms >= 250);
+    assert!(!outcome.delta.outcome_changed);
+    assert!(outcome.mismatches[0].contains("exceeds the 50.0% drift limit"));
+    assert_eq!(outcome.result.quota_check.exceeded_resources, vec!["runtime_ms".to_string()]);
+    assert!(!strict.passed());
+    Ok(())
+}
+
+#[test]
+fn test_record_and_replay_flags_are_detected() {
+    // Arrange
+    let args = |line: &str| line.split(' ').map(str::to_string).collect::<Vec<_>>();
+
+    // Act
+    let recorded = record_requested(&args("app --record calls.jsonl volume list --all"));
+    let inline = record_requested(&args("app --record=calls.jsonl volume list"));
+    let with_stdin = record_requested(&args("app --record calls.jsonl --record-stdin volume list"));
+    let replayed = replay_requested(&args("app --replay calls.jsonl"));
+
+    // Assert
+    let request = |argv: &str, record_stdin: bool| {
+        Some(RecordRequest {
+            recording: PathBuf::from("calls.jsonl"),
+            argv: args(argv),
+            record_stdin,
+        })
+    };
+    assert_eq!(recorded, request("app volume list --all", false));
+    assert_eq!(inline, request("app volume list", false));
+    assert_eq!(with_stdin, request("app volume list", true));
+    assert_eq!(replayed, Some(PathBuf::from("calls.jsonl")));
+    assert_eq!(record_requested(&args("app volume list --record x")), None);
+    assert_eq!(record_requested(&args("app --record")), None);
+}
+
+#[test]
+fn test_delegation_chain_is_not_recorded() -> TestResult {
+    // Arrange
+    let (_dir, script, recording) = workspace("echo \"hello $CNV_RECORDED_TENANT\"\n")?;
+    let argv = vec!["sh".to_string(), script.display().to_string()];
+    std::env::set_var("CNV_DELEGATION", "{\"chain\": []}");
+    std::env::set_var("CNV_RECORDED_TENANT", "acme");
+
+    // Act
+    let recorded =
+        InvocationRecorder::new(&recording).record(Path::new(SHELL), "script", "greet", argv, None);
+    std::env::remove_var("CNV_DELEGATION");
+    std::env::remove_var("CNV_RECORDED_TENANT");
+    recorded?;
+    let frames = read_recording(&recording)?;
+
+    // Assert
+    let env = &frames.first().ok_or("missing frame")?.env_vars;
+    assert_eq!(env.get("CNV_RECORDED_TENANT").map(String::as_str), Some("acme"));
+    assert!(!env.contains_key("CNV_DELEGATION"));
+    Ok(())
+}
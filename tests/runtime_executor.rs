@@ -4,7 +4,6 @@ use clap_noun_verb::error::{NounVerbError, Result};
 use clap_noun_verb::logic::{HandlerContext, HandlerInput, HandlerOutput};
 use clap_noun_verb::runtime::executor::Executor;
 use clap_noun_verb::runtime::interceptor::NoOpInterceptor;
-use std::collections::HashMap;
 
 #[test]
 fn test_executor_new() {
@@ -28,11 +27,7 @@ fn test_executor_execute_success() -> Result<()> {
         HandlerOutput::from_data(format!("Processed: {}", input.context.verb))
     };
 
-    let input = HandlerInput {
-        args: HashMap::new(),
-        opts: HashMap::new(),
-        context: HandlerContext::new("test"),
-    };
+    let input = HandlerInput::new(HandlerContext::new("test"));
 
     let output = executor.execute(handler, input)?;
 
@@ -51,11 +46,7 @@ fn test_executor_execute_with_error() -> Result<()> {
         Err(NounVerbError::execution_error("Test error"))
     };
 
-    let input = HandlerInput {
-        args: HashMap::new(),
-        opts: HashMap::new(),
-        context: HandlerContext::new("test"),
-    };
+    let input = HandlerInput::new(HandlerContext::new("test"));
 
     let result = executor.execute(handler, input);
     assert!(result.is_err());
@@ -71,11 +62,7 @@ fn test_executor_with_interceptors() -> Result<()> {
     let handler =
         |_input: HandlerInput| -> Result<HandlerOutput> { HandlerOutput::from_data("Success") };
 
-    let input = HandlerInput {
-        args: HashMap::new(),
-        opts: HashMap::new(),
-        context: HandlerContext::new("test"),
-    };
+    let input = HandlerInput::new(HandlerContext::new("test"));
 
     let output = executor.execute(handler, input)?;
 
@@ -96,11 +83,7 @@ fn test_executor_interceptor_chain() -> Result<()> {
         HandlerOutput::from_data(format!("Processed: {}", input.context.verb))
     };
 
-    let input = HandlerInput {
-        args: HashMap::new(),
-        opts: HashMap::new(),
-        context: HandlerContext::new("test"),
-    };
+    let input = HandlerInput::new(HandlerContext::new("test"));
 
     let output = executor.execute(handler, input)?;
 
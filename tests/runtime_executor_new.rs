@@ -4,7 +4,6 @@ use clap_noun_verb::error::{NounVerbError, Result};
 use clap_noun_verb::logic::{HandlerContext, HandlerInput, HandlerOutput};
 use clap_noun_verb::runtime::executor::Executor;
 use clap_noun_verb::runtime::interceptor::{Interceptor, NoOpInterceptor};
-use std::collections::HashMap;
 
 #[test]
 fn test_executor_new() {
@@ -28,11 +27,7 @@ fn test_executor_execute_success() -> Result<()> {
         HandlerOutput::from_data(format!("Processed: {}", input.context.verb))
     };
 
-    let input = HandlerInput {
-        args: HashMap::new(),
-        opts: HashMap::new(),
-        context: HandlerContext::new("test"),
-    };
+    let input = HandlerInput::new(HandlerContext::new("test"));
 
     let output = executor.execute(handler, input)?;
 
@@ -49,11 +44,7 @@ fn test_executor_execute_with_error() {
         Err(NounVerbError::execution_error("Handler failed"))
     };
 
-    let input = HandlerInput {
-        args: HashMap::new(),
-        opts: HashMap::new(),
-        context: HandlerContext::new("test"),
-    };
+    let input = HandlerInput::new(HandlerContext::new("test"));
 
     let result = executor.execute(handler, input);
 
@@ -77,11 +68,7 @@ fn test_executor_with_noop_interceptor() -> Result<()> {
         HandlerOutput::from_data("Success".to_string())
     };
 
-    let input = HandlerInput {
-        args: HashMap::new(),
-        opts: HashMap::new(),
-        context: HandlerContext::new("test"),
-    };
+    let input = HandlerInput::new(HandlerContext::new("test"));
 
     let output = executor.execute(handler, input)?;
 
@@ -101,11 +88,7 @@ fn test_executor_with_interceptors() -> Result<()> {
         HandlerOutput::from_data(format!("Processed: {}", input.context.verb))
     };
 
-    let input = HandlerInput {
-        args: HashMap::new(),
-        opts: HashMap::new(),
-        context: HandlerContext::new("test"),
-    };
+    let input = HandlerInput::new(HandlerContext::new("test"));
 
     let output = executor.execute(handler, input)?;
 
@@ -118,11 +101,7 @@ fn test_executor_with_interceptors() -> Result<()> {
 fn test_noop_interceptor_pre_execute() -> Result<()> {
     let interceptor = NoOpInterceptor;
 
-    let input = HandlerInput {
-        args: HashMap::new(),
-        opts: HashMap::new(),
-        context: HandlerContext::new("test"),
-    };
+    let input = HandlerInput::new(HandlerContext::new("test"));
 
     let result = interceptor.pre_execute(input.clone())?;
 
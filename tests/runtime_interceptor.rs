@@ -3,17 +3,12 @@
 use clap_noun_verb::error::Result;
 use clap_noun_verb::logic::{HandlerContext, HandlerInput, HandlerOutput};
 use clap_noun_verb::runtime::interceptor::{Interceptor, NoOpInterceptor};
-use std::collections::HashMap;
 
 #[test]
 fn test_noop_interceptor_pre_execute() -> Result<()> {
     let interceptor = NoOpInterceptor;
 
-    let input = HandlerInput {
-        args: HashMap::new(),
-        opts: HashMap::new(),
-        context: HandlerContext::new("test"),
-    };
+    let input = HandlerInput::new(HandlerContext::new("test"));
 
     let original_verb = input.context.verb.clone();
 
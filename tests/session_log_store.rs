@@ -0,0 +1,197 @@
+//! Tests for the durable, file-backed session log store
+//!
+//! Frames are written to append-only segment files, survive reopening the
+//! store, rotate into sealed segments, compact, and are audited by
+//! `verify_integrity`.
+
+#![cfg(feature = "kernel")]
+
+use clap_noun_verb::autonomic::tenancy::{AgentIdentity, InvocationContext, TenantIdentity};
+use clap_noun_verb::kernel::session_log::{
+    ErrorFrame, ExitCodeClass, FrameMetadata, LogicalClock, QuotaFootprint, ResultFrame,
+    SessionLogFrame, SessionLogStore,
+};
+use clap_noun_verb::kernel::session_log_store::{
+    verify_integrity, FileSessionLogStore, FileStoreConfig,
+};
+use clap_noun_verb::kernel::telemetry::TelemetryProfile;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Frame `seq` of `session` running `capability`, failing when `failed`
+fn frame(
+    session: &str,
+    seq: u64,
+    capability: &str,
+    failed: bool,
+) -> Result<SessionLogFrame, String> {
+    let context =
+        InvocationContext::new(AgentIdentity::anonymous(), TenantIdentity::default_tenant());
+    let output = if failed {
+        ResultFrame::Error(ErrorFrame {
+            error_code: "not_found".to_string(),
+            error_message: format!("{} failed", capability),
+            error_details: None,
+        })
+    } else {
+        ResultFrame::Success(json!({ "seq": seq }))
+    };
+    let footprint = QuotaFootprint { runtime_ms: seq * 10, ..QuotaFootprint::zero() };
+    SessionLogFrame::new(
+        capability.split('.').next().unwrap_or(capability).to_string(),
+        capability.rsplit('.').next().unwrap_or(capability).to_string(),
+        capability.to_string(),
+        1,
+        Arc::new(context),
+        None,
+        "standard".to_string(),
+        footprint,
+        json!({ "seq": seq }),
+        BTreeMap::new(),
+        LogicalClock::new(seq, 1_000_000_000 + seq * 1_000),
+        output,
+        if failed { ExitCodeClass::NotFound } else { ExitCodeClass::Success },
+        TelemetryProfile::default(),
+        FrameMetadata {
+            frame_id: format!("{}-{}", session, seq),
+            session_id: session.to_string(),
+            agent_id: "agent".to_string(),
+            sequence_number: seq,
+            parent_frame_hash: None,
+            tags: Vec::new(),
+        },
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[test]
+fn test_frames_survive_reopening_the_store() -> TestResult {
+    // Arrange
+    let dir = tempfile::tempdir()?;
+    let ok = frame("s1", 1, "bucket.list", false)?;
+    let failed = frame("s1", 2, "bucket.delete", true)?;
+    let store = FileSessionLogStore::open(dir.path())?;
+    store.append(ok.clone())?;
+    store.append(failed.clone())?;
+    store.append(frame("s2", 1, "bucket.list", false)?)?;
+    drop(store);
+
+    // Act
+    let store = FileSessionLogStore::open(dir.path())?;
+    let session = store.get_session_frames("s1")?;
+    let by_hash = store.get_by_hash(&failed.content_hash)?;
+    let lists = store.query(&|f| f.capability_id == "bucket.list")?;
+    let stale = store.append(frame("s1", 1, "bucket.list", false)?);
+
+    // Assert
+    assert_eq!(session.len(), 2);
+    assert_eq!(session[0].content_hash, ok.content_hash);
+    assert!(matches!(by_hash.map(|f| f.output_result), Some(ResultFrame::Error(_))));
+    assert_eq!(lists.len(), 2);
+    assert!(stale.is_err());
+    Ok(())
+}
+
+#[test]
+fn test_rotation_and_compaction() -> TestResult {
+    // Arrange
+    let dir = tempfile::tempdir()?;
+    let store = FileSessionLogStore::open_with(
+        dir.path(),
+        FileStoreConfig::new().with_max_segment_frames(2).without_sync(),
+    )?;
+    for seq in 1..=5 {
+        store.append(frame(
+            "s1",
+            seq,
+            if seq % 2 == 0 { "bucket.delete" } else { "bucket.list" },
+            false,
+        )?)?;
+    }
+    let rotated = store.segments();
+
+    // Act
+    let report = store.compact()?.ok_or("expected a compaction")?;
+    let compacted = store.segments();
+    let frames = store.get_session_frames("s1")?;
+    let compression = store.compute_compression(1, 5)?;
+
+    // Assert
+    assert_eq!(rotated.len(), 3);
+    assert!(rotated[0].sealed && rotated[1].sealed && !rotated[2].sealed);
+    assert_eq!(report.merged_segments, vec![rotated[0].id, rotated[1].id]);
+    assert_eq!((report.frames_before, report.frames_after), (4, 4));
+    assert_eq!(report.compression.invocation_histogram.get("bucket.list"), Some(&2));
+    assert_eq!(compacted.len(), 2);
+    assert_eq!(compacted[0].id, report.segment);
+    assert!(compacted[0].compression.is_some());
+    assert!(!rotated[0].path.exists());
+    assert_eq!(
+        frames.iter().map(|f| f.metadata.sequence_number).collect::<Vec<_>>(),
+        vec![1, 2, 3, 4, 5]
+    );
+    assert_eq!(compression.original_frame_count, 5);
+    assert!(verify_integrity(dir.path())?.is_intact());
+    Ok(())
+}
+
+#[test]
+fn test_open_recovers_from_interrupted_writes() -> TestResult {
+    // Arrange
+    let dir = tempfile::tempdir()?;
+    let store = FileSessionLogStore::open(dir.path())?;
+    store.append(frame("s1", 1, "bucket.list", false)?)?;
+    let active = store.segments()[0].path.clone();
+    drop(store);
+    let mut content = std::fs::read(&active)?;
+    content.extend_from_slice(b"{\"frame_schema_version\":1,\"noun");
+    std::fs::write(&active, content)?;
+    std::fs::write(dir.path().join("00000000000000000042.seg"), b"left over")?;
+    let torn = verify_integrity(dir.path())?;
+
+    // Act
+    let store = FileSessionLogStore::open(dir.path())?;
+    store.append(frame("s1", 2, "bucket.list", false)?)?;
+    let report = store.verify()?;
+
+    // Assert
+    assert_eq!(torn.problems.len(), 2);
+    assert!(torn.problems.iter().any(|p| p.message == "incomplete last line" && p.line == Some(2)));
+    assert!(report.is_intact());
+    assert_eq!(report.frames, 2);
+    assert!(!dir.path().join("00000000000000000042.seg").exists());
+    Ok(())
+}
+
+#[test]
+fn test_verify_integrity_reports_tampered_frames() -> TestResult {
+    // Arrange
+    let dir = tempfile::tempdir()?;
+    let store = FileSessionLogStore::open_with(
+        dir.path(),
+        FileStoreConfig::new().with_max_segment_frames(2),
+    )?;
+    for seq in 1..=3 {
+        store.append(frame("s1", seq, "bucket.list", false)?)?;
+    }
+    let sealed = store.segments()[0].path.clone();
+    drop(store);
+    let content = std::fs::read_to_string(&sealed)?;
+    std::fs::write(&sealed, content.replacen("\"seq\":2", "\"seq\":9", 1))?;
+
+    // Act
+    let report = verify_integrity(dir.path())?;
+    let reopened = FileSessionLogStore::open(dir.path())?;
+    let read = reopened.get_session_frames("s1");
+
+    // Assert
+    assert!(!report.is_intact());
+    assert_eq!(report.frames, 3);
+    assert_eq!(report.sessions, 1);
+    assert!(report.problems.iter().any(|p| p.line == Some(2) && p.message.contains("tampering")));
+    assert!(read.is_err());
+    Ok(())
+}
@@ -0,0 +1,193 @@
+//! Tests for Ed25519-signed delegation tokens and command certificates
+//!
+//! Tokens are signed by their delegator, sub-delegations chain the parent's
+//! signature, and chains and certificates are checked against a `TrustStore`.
+
+#![cfg(all(feature = "autonomic", feature = "signing"))]
+
+use clap_noun_verb::autonomic::certificates::Verified;
+use clap_noun_verb::autonomic::*;
+use std::collections::HashSet;
+use std::time::Duration;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn principal(agent: &str) -> Principal {
+    Principal::new(AgentIdentity::new(agent, "agent"), TenantIdentity::default_tenant())
+}
+
+fn constraints(nouns: &[&str]) -> CapabilityConstraint {
+    CapabilityConstraint {
+        allowed_nouns: Some(nouns.iter().map(|noun| noun.to_string()).collect::<HashSet<_>>()),
+        ..CapabilityConstraint::unrestricted()
+    }
+}
+
+fn hour() -> TemporalConstraint {
+    TemporalConstraint::valid_for(Duration::from_secs(3600))
+}
+
+/// alice -> bob -> carol, each hop signed by its delegator
+fn signed_chain(alice: &IssuerKey, bob: &IssuerKey) -> Result<DelegationChain, DelegationError> {
+    let granted = DelegationToken::new(
+        principal("alice"),
+        principal("bob"),
+        constraints(&["volume", "bucket", "queue"]),
+        hour(),
+    )
+    .sign(alice)?;
+    let sub = granted
+        .sub_delegate(principal("carol"), constraints(&["bucket", "volume"]), hour())?
+        .sign(bob)?;
+    Ok(DelegationChain::with_delegation(granted).add_delegation(sub))
+}
+
+#[test]
+fn test_signed_chain_verifies_after_round_trip() -> TestResult {
+    // Arrange
+    let alice = IssuerKey::generate("alice");
+    let bob = IssuerKey::generate("bob");
+    let trust = TrustStore::new().with_key(alice.public_key()).with_key(bob.public_key());
+    let chain = signed_chain(&alice, &bob)?;
+
+    // Act
+    let json = serde_json::to_string(&chain)?;
+    let decoded: DelegationChain = serde_json::from_str(&json)?;
+
+    // Assert
+    assert_eq!(chain.verify_against(&trust).map_err(|e| e.to_string()), Ok(()));
+    assert_eq!(decoded.verify_against(&trust).map_err(|e| e.to_string()), Ok(()));
+    let [granted, sub] = &decoded.tokens[..] else {
+        return Err("expected two tokens".into());
+    };
+    let granted_signature = granted.signature.as_ref().ok_or("unsigned grant")?;
+    assert_eq!(granted_signature.algorithm, SIGNATURE_ALGORITHM);
+    assert_eq!(granted_signature.key_id, alice.key_id());
+    assert_eq!(sub.parent_signature.as_ref(), Some(&granted_signature.signature));
+    Ok(())
+}
+
+#[test]
+fn test_chain_rejects_tampered_and_untrusted_hops() -> TestResult {
+    // Arrange
+    let alice = IssuerKey::generate("alice");
+    let bob = IssuerKey::generate("bob");
+    let trust = TrustStore::new().with_key(alice.public_key()).with_key(bob.public_key());
+    let mut widened = signed_chain(&alice, &bob)?;
+    widened.tokens[1].constraints = CapabilityConstraint::unrestricted();
+    let mut unsigned = signed_chain(&alice, &bob)?;
+    unsigned.tokens[1].signature = None;
+
+    // Act
+    let tampered = widened.verify_against(&trust);
+    let untrusted =
+        signed_chain(&alice, &bob)?.verify_against(&TrustStore::new().with_key(alice.public_key()));
+    let missing = unsigned.verify_against(&trust);
+    let forged =
+        DelegationToken::new(principal("alice"), principal("bob"), constraints(&[]), hour())
+            .sign(&bob);
+
+    // Assert
+    assert!(matches!(tampered, Err(DelegationError::Signature(SignatureError::Invalid))));
+    assert!(matches!(
+        untrusted,
+        Err(DelegationError::Signature(SignatureError::UntrustedKey(ref key))) if *key == bob.key_id()
+    ));
+    assert!(matches!(missing, Err(DelegationError::Signature(SignatureError::Missing))));
+    assert!(matches!(
+        forged,
+        Err(DelegationError::Signature(SignatureError::WrongSigner { ref expected, .. }))
+            if expected == "alice"
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_chain_rejects_hops_that_are_not_chained() -> TestResult {
+    // Arrange
+    let alice = IssuerKey::generate("alice");
+    let bob = IssuerKey::generate("bob");
+    let trust = TrustStore::new().with_key(alice.public_key()).with_key(bob.public_key());
+    let granted = DelegationToken::new(
+        principal("alice"),
+        principal("bob"),
+        constraints(&["volume"]),
+        hour(),
+    )
+    .sign(&alice)?;
+    let spliced = DelegationToken::new(
+        principal("bob"),
+        principal("carol"),
+        constraints(&["volume"]),
+        hour(),
+    )
+    .sign(&bob)?;
+    let unsigned_parent = DelegationToken::new(
+        principal("alice"),
+        principal("bob"),
+        constraints(&["volume"]),
+        hour(),
+    );
+    let early_sub = unsigned_parent
+        .sub_delegate(principal("carol"), constraints(&["volume"]), hour())?
+        .sign(&bob)?;
+    let late_parent = unsigned_parent.sign(&alice)?;
+
+    // Act
+    let independent = DelegationChain::with_delegation(granted).add_delegation(spliced);
+    let signed_too_late = DelegationChain::with_delegation(late_parent).add_delegation(early_sub);
+
+    // Assert
+    assert!(independent.verify().is_ok());
+    assert!(matches!(independent.verify_against(&trust), Err(DelegationError::BrokenChain)));
+    assert!(matches!(signed_too_late.verify_against(&trust), Err(DelegationError::BrokenChain)));
+    Ok(())
+}
+
+/// Verified certificate for `cmd.run`, issued to alice
+fn certificate() -> Result<Certificate<Verified>, CertificateError> {
+    let allow = PolicyResult {
+        decision: PolicyDecision::Allow,
+        evaluated_rules: vec!["allow-all".to_string()],
+        metadata: std::collections::HashMap::new(),
+    };
+    CertificateBuilder::new(
+        CapabilityId::from_path("cmd.run"),
+        "1.0.0",
+        InputSchema::default(),
+        OutputSchema::new(TypeSchema::primitive(PrimitiveType::String)),
+    )
+    .with_agent(AgentIdentity::new("alice", "agent"))
+    .build()
+    .with_policy_check("engine", &allow)?
+    .with_capability_check(&[CapabilityId::from_path("cmd.run")])?
+    .verify()
+}
+
+#[test]
+fn test_signed_certificates_import_against_trust_store() -> TestResult {
+    // Arrange
+    let issuer = IssuerKey::from_seed("runtime", [42; 32]);
+    let trust = TrustStore::new().with_key(issuer.public_key());
+    let exported = certificate()?.sign(&issuer)?.export()?;
+    let unsigned = certificate()?.export()?;
+
+    // Act
+    let imported = Certificate::<Verified>::import_signed(&exported, &trust);
+    let tampered = Certificate::<Verified>::import_signed(
+        &exported.replace("\"version\":\"1.0.0\"", "\"version\":\"2.0.0\""),
+        &trust,
+    );
+    let untrusted = Certificate::<Verified>::import_signed(&exported, &TrustStore::new());
+    let missing = Certificate::<Verified>::import_signed(&unsigned, &trust);
+
+    // Assert
+    let imported = imported?;
+    assert_eq!(imported.capability_id(), &CapabilityId::from_path("cmd.run"));
+    assert_eq!(imported.signature.as_ref().map(|s| s.key_id.clone()), Some(issuer.key_id()));
+    assert!(exported.contains("\"version\":\"1.0.0\""));
+    assert!(matches!(tampered, Err(CertificateError::Signature(SignatureError::Invalid))));
+    assert!(matches!(untrusted, Err(CertificateError::Signature(SignatureError::UntrustedKey(_)))));
+    assert!(matches!(missing, Err(CertificateError::Signature(SignatureError::Missing))));
+    Ok(())
+}
@@ -0,0 +1,251 @@
+//! Tests for typed handler input
+//!
+//! The registry must hand handlers structured values: vectors stay vectors
+//! (even when items contain commas), flags are booleans, counts are numbers
+//! and absent optional arguments stay absent.
+
+use clap_noun_verb::cli::registry::{ArgMetadata, CommandRegistry};
+use clap_noun_verb::error::Result;
+use clap_noun_verb::logic::{HandlerContext, HandlerInput, HandlerOutput};
+use clap_noun_verb_macros::verb;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct Deployment {
+    env: String,
+    labels: Vec<String>,
+    force: bool,
+    verbose: usize,
+    replicas: Option<u16>,
+}
+
+static DEPLOYMENTS: Mutex<Vec<Deployment>> = Mutex::new(Vec::new());
+
+/// Deploy an environment
+///
+/// # Arguments
+/// * `env` - Target environment
+/// * `labels` - Labels to apply
+/// * `force` - Skip safety checks
+/// * `verbose` - Verbosity level
+/// * `replicas` - Replica count
+#[verb("deploy", "typed")]
+fn deploy(
+    env: String,
+    labels: Vec<String>,
+    force: bool,
+    verbose: usize,
+    replicas: Option<u16>,
+) -> Result<Deployment> {
+    let deployment = Deployment { env, labels, force, verbose, replicas };
+    DEPLOYMENTS.lock().unwrap_or_else(|e| e.into_inner()).push(deployment.clone());
+    Ok(deployment)
+}
+
+fn run(args: &[&str]) -> Result<Deployment> {
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    DEPLOYMENTS.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    registry.run(args.iter().map(|a| a.to_string()).collect())?;
+    DEPLOYMENTS.lock().unwrap_or_else(|e| e.into_inner()).pop().ok_or_else(|| {
+        clap_noun_verb::error::NounVerbError::execution_error("handler was not called")
+    })
+}
+
+#[test]
+fn test_registry_preserves_argument_structure() -> Result<()> {
+    // Arrange
+    let args = [
+        "cli",
+        "typed",
+        "deploy",
+        "--env",
+        "prod",
+        "--labels",
+        "tier=web,critical",
+        "--labels",
+        "team=core",
+        "--force",
+        "--verbose",
+        "--verbose",
+        "--replicas",
+        "3",
+    ];
+
+    // Act
+    let deployment = run(&args)?;
+
+    // Assert
+    assert_eq!(
+        deployment,
+        Deployment {
+            env: "prod".to_string(),
+            labels: vec!["tier=web,critical".to_string(), "team=core".to_string()],
+            force: true,
+            verbose: 2,
+            replicas: Some(3),
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_registry_leaves_absent_arguments_empty() -> Result<()> {
+    // Arrange + Act
+    let deployment = run(&["cli", "typed", "deploy", "--env", "dev", "--labels", "solo"])?;
+
+    // Assert
+    assert_eq!(deployment.labels, vec!["solo".to_string()]);
+    assert!(!deployment.force);
+    assert_eq!(deployment.verbose, 0);
+    assert_eq!(deployment.replicas, None);
+    Ok(())
+}
+
+/// `--color` flag that clears `color` (`ArgAction::SetFalse`)
+fn set_false_flag(name: &str) -> ArgMetadata {
+    ArgMetadata {
+        name: name.to_string(),
+        required: false,
+        is_flag: true,
+        help: None,
+        min_value: None,
+        max_value: None,
+        min_length: None,
+        max_length: None,
+        short: None,
+        long: None,
+        default_value: None,
+        env: None,
+        multiple: false,
+        value_name: None,
+        aliases: vec![],
+        positional: None,
+        action: Some(clap::ArgAction::SetFalse),
+        group: None,
+        requires: vec![],
+        conflicts_with: vec![],
+        value_parser: None,
+        hide: false,
+        next_help_heading: None,
+        long_help: None,
+        next_line_help: false,
+        display_order: None,
+        exclusive: None,
+        trailing_vararg: false,
+        allow_negative_numbers: false,
+        value_hint: None,
+        global: false,
+        value_type: None,
+        complete: None,
+    }
+}
+
+/// `args` and `values` entries for `color` seen by the last `paint draw` run
+static PAINTED: Mutex<Vec<(Option<String>, Option<serde_json::Value>)>> = Mutex::new(Vec::new());
+
+#[test]
+fn test_set_false_flags_keep_their_string_arg() -> Result<()> {
+    // Arrange
+    CommandRegistry::register_noun("paint", "Paint things");
+    CommandRegistry::register_verb_with_args(
+        "paint",
+        "draw",
+        "Draw a picture",
+        vec![set_false_flag("color")],
+        |input: HandlerInput| {
+            let seen = (input.args.get("color").cloned(), input.values.get("color").cloned());
+            PAINTED.lock().unwrap_or_else(|e| e.into_inner()).push(seen);
+            HandlerOutput::from_data("drawn")
+        },
+    );
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    registry.run(vec!["cli".to_string(), "paint".to_string(), "draw".to_string()])?;
+    registry.run(["cli", "paint", "draw", "--color"].iter().map(|a| a.to_string()).collect())?;
+    let painted = PAINTED.lock().unwrap_or_else(|e| e.into_inner()).clone();
+
+    // Assert
+    let unset = (Some("false".to_string()), Some(json!(true)));
+    let passed = (Some("false".to_string()), Some(json!(false)));
+    assert_eq!(painted, vec![unset, passed]);
+    Ok(())
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct DeployArgs {
+    env: String,
+    labels: Vec<String>,
+    force: bool,
+    replicas: Option<u16>,
+}
+
+fn input_with_values(values: serde_json::Value) -> HandlerInput {
+    let values = match values {
+        serde_json::Value::Object(map) => map.into_iter().collect(),
+        _ => HashMap::new(),
+    };
+    HandlerInput::new(HandlerContext::new("deploy").with_noun("typed")).with_values(values)
+}
+
+#[test]
+fn test_handler_input_deserializes_typed_values() -> Result<()> {
+    // Arrange
+    let input = input_with_values(json!({
+        "env": "prod",
+        "labels": ["a", "b"],
+        "force": true,
+        "replicas": "5",
+    }));
+
+    // Act
+    let args: DeployArgs = input.deserialize()?;
+
+    // Assert
+    assert_eq!(
+        args,
+        DeployArgs {
+            env: "prod".to_string(),
+            labels: vec!["a".to_string(), "b".to_string()],
+            force: true,
+            replicas: Some(5),
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_handler_input_get_falls_back_to_string_args() -> Result<()> {
+    // Arrange
+    let mut input = input_with_values(json!({}));
+    input.args.insert("replicas".to_string(), "7".to_string());
+    input.opts.insert("force".to_string(), "true".to_string());
+
+    // Act
+    let replicas: Option<u16> = input.get("replicas")?;
+    let force: Option<bool> = input.get("force")?;
+    let missing: Option<String> = input.get("env")?;
+
+    // Assert
+    assert_eq!(replicas, Some(7));
+    assert_eq!(force, Some(true));
+    assert_eq!(missing, None);
+    Ok(())
+}
+
+#[test]
+fn test_handler_input_rejects_mistyped_values() {
+    // Arrange
+    let input = input_with_values(json!({ "replicas": "many" }));
+
+    // Act
+    let result = input.get::<u16>("replicas");
+
+    // Assert
+    assert!(result.is_err());
+}
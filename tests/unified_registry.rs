@@ -0,0 +1,269 @@
+//! Tests for merging builder nouns and agent commands into the command registry
+//!
+//! A builder noun shares the `cluster` noun with a `#[verb]` function, a
+//! builder registry adds `queue`, and an agent-built CLI adds `ops`. All of
+//! them run, show in help and are callable as tools through one tree.
+
+use clap_noun_verb::agent_cli::{
+    AgentCliBuilder, AgentResult, ArgumentSpec, CommandArgs, CommandHandler, CommandMetadata,
+};
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb::{noun, verb, NounCommand, VerbArgs, VerbCommand};
+use serde_json::json;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+type TestResult = std::result::Result<(), Box<dyn std::error::Error>>;
+
+/// Commands the builder verbs ran
+static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Show cluster status
+#[clap_noun_verb_macros::verb("status", "cluster")]
+fn cluster_status() -> Result<String> {
+    Ok("healthy".to_string())
+}
+
+fn record(call: String) {
+    CALLS.lock().unwrap_or_else(|e| e.into_inner()).push(call);
+}
+
+fn calls() -> Vec<String> {
+    CALLS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// `cluster` with a `scale` verb and a `node drain` sub-noun
+struct ClusterNoun;
+
+impl NounCommand for ClusterNoun {
+    fn name(&self) -> &'static str {
+        "cluster"
+    }
+
+    fn about(&self) -> &'static str {
+        "Manage clusters"
+    }
+
+    fn verbs(&self) -> Vec<Box<dyn VerbCommand>> {
+        vec![Box::new(verb!(
+            "scale",
+            "Scale the cluster",
+            |args: &VerbArgs| {
+                let replicas = args.get_one_opt::<String>("replicas").unwrap_or_default();
+                record(format!("scale {}", replicas));
+                Ok(())
+            },
+            args: [clap::Arg::new("replicas").long("replicas").help("Node count").required(true)]
+        ))]
+    }
+
+    fn sub_nouns(&self) -> Vec<Box<dyn NounCommand>> {
+        vec![Box::new(noun!(
+            "node",
+            "Manage nodes",
+            [verb!(
+                "drain",
+                "Drain a node",
+                |args: &VerbArgs| {
+                    let node = args.get_one_str("node")?;
+                    record(format!("drain {} force={}", node, args.is_flag_set("force")));
+                    Ok(())
+                },
+                args: [
+                    clap::Arg::new("node").required(true),
+                    clap::Arg::new("force").short('f').action(clap::ArgAction::SetTrue),
+                ]
+            )]
+        ))]
+    }
+}
+
+/// Agent command echoing its `message` argument
+struct EchoHandler;
+
+impl CommandHandler for EchoHandler {
+    fn execute(&self, args: &CommandArgs) -> AgentResult<serde_json::Value> {
+        Ok(json!({ "echo": args.get("message"), "positional": args.positional }))
+    }
+
+    fn metadata(&self) -> CommandMetadata {
+        CommandMetadata {
+            name: "echo".to_string(),
+            description: "Echo a message".to_string(),
+            arguments: vec![ArgumentSpec {
+                name: "message".to_string(),
+                description: "Text to echo".to_string(),
+                required: true,
+                default: None,
+            }],
+            requires_args: true,
+        }
+    }
+}
+
+/// Global registry with the builder nouns and agent commands added once
+fn registry() -> std::result::Result<MutexGuard<'static, CommandRegistry>, String> {
+    static SETUP: OnceLock<std::result::Result<(), String>> = OnceLock::new();
+    let mut registry = CommandRegistry::get().lock().unwrap_or_else(|e| e.into_inner());
+    SETUP
+        .get_or_init(|| {
+            registry.add_noun_command(Box::new(ClusterNoun)).map_err(|e| e.to_string())?;
+            let queues = clap_noun_verb::registry::CommandRegistry::new().register_noun(noun!(
+                "queue",
+                "Manage queues",
+                [verb!("purge", "Purge a queue", |_args: &VerbArgs| {
+                    record("purge".to_string());
+                    Ok(())
+                })]
+            ));
+            registry.add_registry(queues).map_err(|e| e.to_string())?;
+            let mut agent = AgentCliBuilder::new("ops", "Operations");
+            agent
+                .register_command("echo", "Echo a message", Arc::new(EchoHandler))
+                .map_err(|e| e.to_string())?;
+            let agent = agent.build().map_err(|e| e.to_string())?;
+            registry.add_agent_cli("ops", &agent).map_err(|e| e.to_string())
+        })
+        .clone()?;
+    Ok(registry)
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn test_builder_verbs_run_through_the_registry() -> TestResult {
+    // Arrange
+    let registry = registry()?;
+
+    // Act
+    registry.run(args(&["app", "cluster", "scale", "--replicas", "5"]))?;
+    registry.run(args(&["app", "cluster", "node", "drain", "n1", "-f"]))?;
+    registry.run(args(&["app", "queue", "purge"]))?;
+    registry.run(args(&["app", "cluster", "status"]))?;
+    let missing = registry.run(args(&["app", "cluster", "scale"]));
+
+    // Assert
+    let calls = calls();
+    assert!(calls.contains(&"scale 5".to_string()));
+    assert!(calls.contains(&"drain n1 force=true".to_string()));
+    assert!(calls.contains(&"purge".to_string()));
+    assert!(missing.is_err());
+    Ok(())
+}
+
+#[test]
+fn test_help_tree_merges_all_sources() -> TestResult {
+    // Arrange
+    let registry = registry()?;
+
+    // Act
+    let command = registry.build_command();
+    let cluster = command.find_subcommand("cluster").ok_or("missing cluster")?;
+    let scale = cluster.find_subcommand("scale").ok_or("missing scale")?;
+    let drain = cluster
+        .find_subcommand("node")
+        .and_then(|node| node.find_subcommand("drain"))
+        .ok_or("missing node drain")?;
+    let echo = command
+        .find_subcommand("ops")
+        .and_then(|ops| ops.find_subcommand("echo"))
+        .ok_or("missing ops echo")?;
+    let verbs = registry.get_verbs_with_metadata("cluster.node");
+
+    // Assert
+    assert_eq!(
+        cluster.get_about().map(|about| about.to_string()).as_deref(),
+        Some("Manage clusters")
+    );
+    assert!(cluster.find_subcommand("status").is_some());
+    assert!(scale.get_arguments().any(|arg| arg.get_long() == Some("replicas")));
+    assert!(scale.get_arguments().any(|arg| arg.get_long() == Some("format")));
+    assert!(drain.get_arguments().any(|arg| arg.get_short() == Some('f')));
+    assert!(echo.get_arguments().any(|arg| arg.get_id() == "message" && arg.is_positional()));
+    assert_eq!(registry.get_sub_nouns(""), vec!["cluster", "ops", "queue"]);
+    let (_, _, drain_args) = verbs.first().ok_or("missing drain metadata")?;
+    let node = drain_args.iter().find(|arg| arg.name == "node").ok_or("missing node arg")?;
+    let force = drain_args.iter().find(|arg| arg.name == "force").ok_or("missing force arg")?;
+    assert_eq!((node.positional, node.required), (Some(1), true));
+    assert!(force.is_flag && force.short == Some('f'));
+    Ok(())
+}
+
+#[test]
+fn test_agent_commands_are_callable_as_tools() -> TestResult {
+    // Arrange
+    let registry = registry()?;
+
+    // Act
+    let echoed = registry.call_tool("ops_echo", &json!({ "message": "hi" }))?;
+    let scaled = registry.call_tool("cluster_scale", &json!({ "replicas": "7" }))?;
+    let missing = registry.call_tool("ops_echo", &json!({}));
+
+    // Assert
+    assert_eq!(echoed.data, json!({ "echo": "hi", "positional": ["hi"] }));
+    assert_eq!(scaled.data, serde_json::Value::Null);
+    assert!(calls().contains(&"scale 7".to_string()));
+    assert!(missing.is_err());
+    Ok(())
+}
+
+#[test]
+fn test_conflicting_verbs_are_rejected() -> TestResult {
+    // Arrange
+    let mut registry = registry()?;
+    let conflicting = noun!(
+        "cluster",
+        "Other clusters",
+        [
+            verb!("restart", "Restart the cluster", |_args: &VerbArgs| Ok(())),
+            verb!("status", "Other status", |_args: &VerbArgs| Ok(())),
+        ]
+    );
+
+    // Act
+    let result = registry.add_noun_command(Box::new(conflicting));
+    let status = registry.call_tool("cluster_status", &json!({}))?;
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(NounVerbError::InvalidStructure { ref message }) if message.contains("'cluster status'")
+    ));
+    assert!(registry.verb_safety("cluster", "restart").is_none());
+    assert_eq!(status.data, json!("healthy"));
+    Ok(())
+}
+
+#[cfg(feature = "autonomic")]
+#[test]
+fn test_autonomic_introspection_covers_the_registry() -> TestResult {
+    use clap_noun_verb::autonomic::AutonomicCli;
+
+    // Arrange
+    let registry = registry()?;
+    let cli =
+        AutonomicCli::from_registry(clap_noun_verb::registry::CommandRegistry::new(), "1.0.0")
+            .with_command_registry(&registry);
+
+    // Act
+    let cluster = cli.introspect_noun_by_name("cluster").ok_or("missing cluster")?;
+    let scale = cli.introspect_verb("cluster", "scale").ok_or("missing scale")?;
+    let response = cli.introspect();
+
+    // Assert
+    let verbs: Vec<&str> = cluster.verbs.iter().map(|verb| verb.name.as_str()).collect();
+    assert_eq!(verbs, vec!["scale", "status"]);
+    assert_eq!(cluster.sub_nouns[0].name, "node");
+    let replicas = scale
+        .command
+        .arguments
+        .iter()
+        .find(|arg| arg.name == "replicas")
+        .ok_or("missing replicas")?;
+    assert_eq!(replicas.long.as_deref(), Some("--replicas"));
+    assert!(replicas.required);
+    assert_eq!(response.nouns.len(), 3);
+    Ok(())
+}
@@ -0,0 +1,146 @@
+//! Tests for latency, CPU and memory budgets declared with `#[verb(max_latency_ms = ...)]`
+//!
+//! The registry measures every handler run of a verb with a budget; an
+//! exceeded budget warns, fails with exit code 8, or cancels an async handler.
+
+use clap_noun_verb::cli::budget::{BudgetAction, VerbBudget};
+use clap_noun_verb::cli::registry::CommandRegistry;
+use clap_noun_verb::envelope::StructuredError;
+use clap_noun_verb::error::{NounVerbError, Result};
+use clap_noun_verb_macros::verb;
+use serde_json::json;
+use std::time::Duration;
+
+/// Rebuild the search index
+#[verb("rebuild", "index", max_latency_ms = 10, on_exceed = "fail")]
+fn rebuild_index() -> Result<String> {
+    std::thread::sleep(Duration::from_millis(40));
+    Ok("rebuilt".to_string())
+}
+
+/// Compact the search index
+#[verb("compact", "index", max_latency_ms = 10)]
+fn compact_index() -> Result<String> {
+    std::thread::sleep(Duration::from_millis(40));
+    Ok("compacted".to_string())
+}
+
+/// Count index entries
+#[verb("count", "index", max_latency_ms = 60000, max_memory_kb = 104857600)]
+fn count_index() -> Result<u32> {
+    Ok(42)
+}
+
+#[test]
+fn test_exceeded_budget_fails_with_distinct_exit_code() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let result = registry.call_tool("index_rebuild", &json!({}));
+
+    // Assert
+    let Err(error) = result else {
+        return Err(NounVerbError::execution_error("expected the budget to fail the invocation"));
+    };
+    assert!(
+        matches!(&error, NounVerbError::BudgetExceeded { command, .. } if command == "index rebuild")
+    );
+    assert_eq!(error.exit_code(), 8);
+    let structured = StructuredError::from(&error);
+    assert_eq!(structured.kind, "budget_exceeded");
+    let guard = structured.context.map(|context| context["guard"].clone()).unwrap_or_default();
+    assert_eq!(guard["budget"]["max_latency_ms"], json!(10));
+    assert!(guard["latency_ms"].as_u64().is_some_and(|ms| ms >= 40));
+    Ok(())
+}
+
+#[test]
+fn test_warn_budget_keeps_result() -> Result<()> {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let compacted = registry.call_tool("index_compact", &json!({}))?;
+    let counted = registry.call_tool("index_count", &json!({}))?;
+
+    // Assert
+    assert_eq!(compacted.data, json!("compacted"));
+    assert_eq!(counted.data, json!(42));
+    Ok(())
+}
+
+#[test]
+fn test_budget_is_registered() {
+    // Arrange
+    let registry = CommandRegistry::get();
+    let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Act
+    let budget = registry.verb_budget("index", "rebuild");
+
+    // Assert
+    assert_eq!(
+        budget,
+        Some(VerbBudget::new().with_max_latency_ms(10).on_exceed(BudgetAction::Fail))
+    );
+    assert_eq!(registry.verb_budget("index", "missing"), None);
+}
+
+#[cfg(feature = "async")]
+mod cancel {
+    use super::*;
+    use std::time::Instant;
+
+    /// Wait for a replica that never answers
+    #[verb("replicate", "index", max_latency_ms = 50, on_exceed = "cancel")]
+    async fn replicate_index() -> Result<String> {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        Ok("replicated".to_string())
+    }
+
+    #[test]
+    fn test_async_handler_is_cancelled_at_latency_budget() {
+        // Arrange
+        let registry = CommandRegistry::get();
+        let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+        let started = Instant::now();
+
+        // Act
+        let result = registry.call_tool("index_replicate", &json!({}));
+
+        // Assert
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(matches!(result, Err(NounVerbError::BudgetExceeded { .. })));
+    }
+}
+
+#[cfg(feature = "autonomic")]
+#[test]
+fn test_guard_result_is_recorded_in_receipt() -> Result<()> {
+    use clap_noun_verb::autonomic::{GuardStatus, ReceiptLog};
+
+    // Arrange
+    // Kept on disk: the log stays set for tests running after this one
+    let dir = tempfile::tempdir().map_err(|e| NounVerbError::execution_error(e.to_string()))?;
+    let log = ReceiptLog::new(dir.keep().join("receipts.jsonl"));
+    let registry = CommandRegistry::get();
+    let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+    registry.set_receipt_log(log.clone());
+
+    // Act
+    registry.call_tool("index_count", &json!({}))?;
+    let _ = registry.call_tool("index_rebuild", &json!({}));
+    let records: Vec<_> = log.records()?.into_iter().take(2).collect();
+
+    // Assert
+    let guards: Vec<_> = records.iter().map(|record| record.receipt.guard.clone()).collect();
+    assert_eq!(guards.len(), 2);
+    assert_eq!(guards[0].status, GuardStatus::WithinBudget);
+    assert_eq!(guards[0].max_memory_kb, Some(104857600));
+    assert_eq!(guards[1].status, GuardStatus::ExceededBudget);
+    assert!(!records[1].receipt.success);
+    Ok(())
+}